@@ -16,16 +16,18 @@
 // under the License.
 
 use anyhow::bail;
-use futures::AsyncBufReadExt;
 use indicatif::ProgressBar;
 use indicatif::ProgressStyle;
+use opendal::options::CopyToOptions;
 use opendal::ErrorKind;
 use opendal::Metadata;
+use opendal::Operator;
+use opendal::TransferProgress;
 use std::path::Path;
+use std::time::Duration;
 
 use anyhow::Context;
 use anyhow::Result;
-use futures::AsyncWriteExt;
 use futures::TryStreamExt;
 
 use crate::config::Config;
@@ -46,6 +48,12 @@ const PROGRESS_BAR_TEMPLATE: &str =
 
 const PROGRESS_CHARS: &str = "#>-";
 
+/// Interval to refresh the progress bar from the copied bytes.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Chunk size used for both reading the source and writing the destination.
+const COPY_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
 #[derive(Debug, clap::Parser)]
 #[command(name = "cp", about = "Copy object", disable_version_flag = true)]
 pub struct CopyCmd {
@@ -109,19 +117,10 @@ impl CopyCmd {
 
         if !self.recursive {
             // Non-recursive copy: Use the final_dst_path directly.
-            let mut dst_w = dst_op
-                .writer(&final_dst_path)
-                .await?
-                .into_futures_async_write();
             let src_meta = src_op.stat(&src_path).await?;
-            let reader = src_op.reader_with(&src_path).chunk(8 * 1024 * 1024).await?;
-            let buf_reader = reader
-                .into_futures_async_read(0..src_meta.content_length())
+            CopyProgress::new(&src_meta, src_path.clone())
+                .copy(&src_op, &dst_op, &src_path, &final_dst_path)
                 .await?;
-
-            let copy_progress = CopyProgress::new(&src_meta, src_path.clone());
-            copy_progress.copy(buf_reader, &mut dst_w).await?;
-            dst_w.close().await?;
             return Ok(());
         }
 
@@ -179,11 +178,6 @@ impl CopyCmd {
                 continue;
             }
 
-            // Explicitly stat the source file to get fresh metadata
-            let fresh_meta = src_op.stat(depath).await.with_context(|| {
-                format!("Failed to stat source file '{depath}' before recursive copy")
-            })?;
-
             // Use the Path object `current_dst_path_path` to check parent
             if let Some(parent_path) = current_dst_path_path.parent() {
                 if parent_path != dst_root {
@@ -195,19 +189,13 @@ impl CopyCmd {
                 }
             }
 
-            let reader = src_op.reader_with(depath).chunk(8 * 1024 * 1024).await?;
-            let buf_reader = reader
-                .into_futures_async_read(0..fresh_meta.content_length())
+            // Explicitly stat the source file to get fresh metadata
+            let fresh_meta = src_op.stat(depath).await.with_context(|| {
+                format!("Failed to stat source file '{depath}' before recursive copy")
+            })?;
+            CopyProgress::new(&fresh_meta, depath.to_string())
+                .copy(&src_op, &dst_op, depath, &current_dst_path)
                 .await?;
-
-            let copy_progress = CopyProgress::new(&fresh_meta, depath.to_string());
-            let mut writer = dst_op
-                .writer(&current_dst_path)
-                .await?
-                .into_futures_async_write();
-
-            copy_progress.copy(buf_reader, &mut writer).await?;
-            writer.close().await?;
         }
         Ok(())
     }
@@ -234,25 +222,34 @@ impl CopyProgress {
         }
     }
 
-    async fn copy<R, W>(&self, mut reader: R, writer: &mut W) -> std::io::Result<u64>
-    where
-        R: futures::AsyncBufRead + Unpin,
-        W: futures::AsyncWrite + Unpin + ?Sized,
-    {
-        let mut written = 0;
-        loop {
-            let buf = reader.fill_buf().await?;
-            if buf.is_empty() {
-                break;
+    async fn copy(
+        &self,
+        src_op: &Operator,
+        dst_op: &Operator,
+        from: &str,
+        to: &str,
+    ) -> Result<Metadata> {
+        let progress = TransferProgress::default();
+        let mut copy = std::pin::pin!(src_op.copy_to_options(
+            dst_op,
+            from,
+            to,
+            CopyToOptions {
+                chunk: Some(COPY_CHUNK_SIZE),
+                progress: Some(progress.clone()),
+                ..Default::default()
+            },
+        ));
+        let mut ticker = tokio::time::interval(PROGRESS_INTERVAL);
+        let meta = loop {
+            tokio::select! {
+                res = &mut copy => break res?,
+                _ = ticker.tick() => self.progress_bar.set_position(progress.bytes()),
             }
-            writer.write_all(buf).await?;
-            let len = buf.len();
-            reader.consume_unpin(len);
-            written += len as u64;
-            self.progress_bar.inc(len as u64);
-        }
+        };
+        self.progress_bar.set_position(progress.bytes());
         self.progress_bar.finish_and_clear();
         println!("Finish {}", self.path);
-        Ok(written)
+        Ok(meta)
     }
 }
@@ -0,0 +1,646 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::sync::Arc;
+
+use crate::raw::*;
+use crate::*;
+
+/// Add path based access control for the underlying services.
+///
+/// # Rules
+///
+/// `AccessControlLayer` evaluates allow and deny rules before delegating the
+/// calls. Every rule is a glob on path plus a set of [`Operation`]s:
+///
+/// - `**` matches any characters including `/`.
+/// - `*` matches any characters except `/`.
+/// - `?` matches one character except `/`.
+/// - `/` only matches the root.
+///
+/// Leading `/` of patterns will be trimmed, so `/raw/**` is the same as
+/// `raw/**`.
+///
+/// A call is permitted if no deny rule matches it, and either an allow rule
+/// matches it or the layer allows by default. Denied calls will fail with
+/// [`ErrorKind::PermissionDenied`].
+///
+/// Operations are checked against paths as follows:
+///
+/// - `copy` checks [`Operation::Read`] on the source and [`Operation::Copy`]
+///   on the target.
+/// - `rename` checks [`Operation::Rename`] on both the source and the target,
+///   [`Operation::Delete`] on the source and [`Operation::Write`] on the target.
+/// - `presign` checks [`Operation::Presign`] and the presigned operation on
+///   the path.
+/// - `list` checks [`Operation::List`] on the path, and drops entries that
+///   [`Operation::List`] or [`Operation::Stat`] is denied on.
+/// - Other calls check their own operation on the path.
+///
+/// # Capability
+///
+/// Operations that are denied for all paths will be removed from the full
+/// capability of the layered operator, so that callers could introspect
+/// them via [`OperatorInfo`]. Other layered operators sharing the same
+/// services will not be affected.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::AccessControlLayer;
+/// # use opendal::raw::Operation;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(
+///         AccessControlLayer::new()
+///             .read_only("/raw/**")
+///             .deny("/audit/**", [Operation::Delete])
+///             .deny("/", [Operation::List]),
+///     )
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AccessControlLayer {
+    rules: Vec<AccessRule>,
+    allow_by_default: bool,
+}
+
+impl Default for AccessControlLayer {
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            allow_by_default: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AccessRule {
+    allow: bool,
+    pattern: String,
+    operations: Vec<Operation>,
+}
+
+impl AccessRule {
+    fn matches(&self, op: Operation, path: &str) -> bool {
+        self.operations.contains(&op) && glob_match(self.pattern.as_bytes(), path.as_bytes())
+    }
+}
+
+/// Operations that will modify the services.
+const WRITE_OPERATIONS: [Operation; 9] = [
+    Operation::CreateDir,
+    Operation::Write,
+    Operation::Copy,
+    Operation::Rename,
+    Operation::Delete,
+    Operation::AbortUpload,
+    Operation::Lock,
+    Operation::Concat,
+    Operation::UpdateMetadata,
+];
+
+impl AccessControlLayer {
+    /// Create a new `AccessControlLayer` which allows everything by default.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Deny everything that isn't allowed by rules.
+    pub fn deny_by_default(mut self) -> Self {
+        self.allow_by_default = false;
+        self
+    }
+
+    /// Allow given operations on paths matching given pattern.
+    pub fn allow(mut self, pattern: &str, ops: impl IntoIterator<Item = Operation>) -> Self {
+        self.rules.push(AccessRule {
+            allow: true,
+            pattern: normalize_pattern(pattern),
+            operations: ops.into_iter().collect(),
+        });
+        self
+    }
+
+    /// Deny given operations on paths matching given pattern.
+    pub fn deny(mut self, pattern: &str, ops: impl IntoIterator<Item = Operation>) -> Self {
+        self.rules.push(AccessRule {
+            allow: false,
+            pattern: normalize_pattern(pattern),
+            operations: ops.into_iter().collect(),
+        });
+        self
+    }
+
+    /// Deny all operations that modify paths matching given pattern.
+    pub fn read_only(self, pattern: &str) -> Self {
+        self.deny(pattern, WRITE_OPERATIONS)
+    }
+
+    /// Check if given operation on given path is permitted.
+    fn check(&self, op: Operation, path: &str) -> Result<()> {
+        let mut allowed = self.allow_by_default;
+        for rule in &self.rules {
+            if !rule.matches(op, path) {
+                continue;
+            }
+            if !rule.allow {
+                allowed = false;
+                break;
+            }
+            allowed = true;
+        }
+
+        if allowed {
+            return Ok(());
+        }
+        Err(Error::new(
+            ErrorKind::PermissionDenied,
+            "operation is denied by access control",
+        )
+        .with_operation(op)
+        .with_context("path", path))
+    }
+
+    /// Check if given operation is denied for all paths.
+    fn denied_everywhere(&self, op: Operation) -> bool {
+        let mut allowed = self.allow_by_default;
+        for rule in self.rules.iter().filter(|v| v.operations.contains(&op)) {
+            if !rule.allow && rule.pattern == "**" {
+                return true;
+            }
+            if rule.allow {
+                allowed = true;
+            }
+        }
+        !allowed
+    }
+
+    fn capability(&self, mut cap: Capability) -> Capability {
+        if self.denied_everywhere(Operation::CreateDir) {
+            cap.create_dir = false;
+        }
+        if self.denied_everywhere(Operation::Read) {
+            cap.read = false;
+            cap.presign_read = false;
+        }
+        if self.denied_everywhere(Operation::Write) {
+            cap.write = false;
+            cap.presign_write = false;
+            cap.presign_post = false;
+            cap.presign_multipart = false;
+        }
+        if self.denied_everywhere(Operation::Copy) {
+            cap.copy = false;
+        }
+        if self.denied_everywhere(Operation::Rename)
+            || self.denied_everywhere(Operation::Delete)
+            || self.denied_everywhere(Operation::Write)
+        {
+            cap.rename = false;
+        }
+        if self.denied_everywhere(Operation::Stat) {
+            cap.stat = false;
+            cap.stat_many = false;
+            cap.presign_stat = false;
+        }
+        if self.denied_everywhere(Operation::Delete) {
+            cap.delete = false;
+            cap.presign_delete = false;
+        }
+        if self.denied_everywhere(Operation::List) {
+            cap.list = false;
+        }
+        if self.denied_everywhere(Operation::Presign) {
+            cap.presign = false;
+            cap.presign_read = false;
+            cap.presign_stat = false;
+            cap.presign_write = false;
+            cap.presign_delete = false;
+            cap.presign_post = false;
+            cap.presign_multipart = false;
+        }
+        if self.denied_everywhere(Operation::ListUploads) {
+            cap.list_uploads = false;
+        }
+        if self.denied_everywhere(Operation::AbortUpload) {
+            cap.abort_upload = false;
+        }
+        if self.denied_everywhere(Operation::Lock) {
+            cap.lock = false;
+        }
+        if self.denied_everywhere(Operation::Concat) {
+            cap.concat = false;
+        }
+        if self.denied_everywhere(Operation::UpdateMetadata) {
+            cap.update_metadata = false;
+        }
+        cap
+    }
+}
+
+impl<A: Access> Layer<A> for AccessControlLayer {
+    type LayeredAccess = AccessControlAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        // Derive a new info instead of updating the inner one, so that
+        // operators with different policies could share the same services.
+        let info = AccessorInfo::derive(inner.info());
+        info.update_full_capability(|cap| self.capability(cap));
+
+        AccessControlAccessor {
+            inner,
+            info: Arc::new(info),
+            layer: Arc::new(self.clone()),
+        }
+    }
+}
+
+fn normalize_pattern(pattern: &str) -> String {
+    let pattern = pattern.trim();
+    if pattern == "/" {
+        return pattern.to_string();
+    }
+    pattern.trim_start_matches('/').to_string()
+}
+
+/// Match path against the glob pattern.
+fn glob_match(pattern: &[u8], path: &[u8]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        [b'*', b'*', rest @ ..] => (0..=path.len()).any(|i| glob_match(rest, &path[i..])),
+        [b'*', rest @ ..] => {
+            let end = path.iter().position(|v| *v == b'/').unwrap_or(path.len());
+            (0..=end).any(|i| glob_match(rest, &path[i..]))
+        }
+        [b'?', rest @ ..] => match path {
+            [c, remain @ ..] if *c != b'/' => glob_match(rest, remain),
+            _ => false,
+        },
+        [p, rest @ ..] => match path {
+            [c, remain @ ..] if c == p => glob_match(rest, remain),
+            _ => false,
+        },
+    }
+}
+
+#[derive(Debug)]
+pub struct AccessControlAccessor<A: Access> {
+    inner: A,
+    info: Arc<AccessorInfo>,
+    layer: Arc<AccessControlLayer>,
+}
+
+impl<A: Access> LayeredAccess for AccessControlAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type Writer = A::Writer;
+    type Lister = AccessControlLister<A::Lister>;
+    type Deleter = AccessControlDeleter<A::Deleter>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn info(&self) -> Arc<AccessorInfo> {
+        self.info.clone()
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.layer.check(Operation::CreateDir, path)?;
+        self.inner.create_dir(path, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.layer.check(Operation::Read, path)?;
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.layer.check(Operation::Write, path)?;
+        self.inner.write(path, args).await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.layer.check(Operation::Read, from)?;
+        self.layer.check(Operation::Copy, to)?;
+        self.inner.copy(from, to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        // Rename removes the source and creates the target, so it must not
+        // bypass the rules of delete and write.
+        self.layer.check(Operation::Rename, from)?;
+        self.layer.check(Operation::Delete, from)?;
+        self.layer.check(Operation::Rename, to)?;
+        self.layer.check(Operation::Write, to)?;
+        self.inner.rename(from, to, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.layer.check(Operation::Stat, path)?;
+        self.inner.stat(path, args).await
+    }
+
+    async fn stat_many(&self, args: Vec<(String, OpStat)>) -> Result<RpStatMany> {
+        let mut results: Vec<Option<Result<Metadata>>> = Vec::with_capacity(args.len());
+        let mut permitted = Vec::with_capacity(args.len());
+        for (path, op) in args {
+            match self.layer.check(Operation::Stat, &path) {
+                Ok(()) => {
+                    results.push(None);
+                    permitted.push((path, op));
+                }
+                Err(err) => results.push(Some(Err(err))),
+            }
+        }
+
+        let mut inner_results = if permitted.is_empty() {
+            Vec::new()
+        } else {
+            self.inner.stat_many(permitted).await?.into_results()
+        }
+        .into_iter();
+
+        let results = results
+            .into_iter()
+            .map(|v| {
+                v.or_else(|| inner_results.next()).unwrap_or_else(|| {
+                    Err(Error::new(
+                        ErrorKind::Unexpected,
+                        "stat_many returned less results than expected",
+                    ))
+                })
+            })
+            .collect();
+        Ok(RpStatMany::new(results))
+    }
+
+    async fn list_uploads(&self, path: &str, args: OpListUploads) -> Result<RpListUploads> {
+        self.layer.check(Operation::ListUploads, path)?;
+        self.inner.list_uploads(path, args).await
+    }
+
+    async fn abort_upload(&self, path: &str, args: OpAbortUpload) -> Result<RpAbortUpload> {
+        self.layer.check(Operation::AbortUpload, path)?;
+        self.inner.abort_upload(path, args).await
+    }
+
+    async fn lock(&self, path: &str, args: OpLock) -> Result<RpLock> {
+        self.layer.check(Operation::Lock, path)?;
+        self.inner.lock(path, args).await
+    }
+
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        for source in args.sources() {
+            self.layer.check(Operation::Read, source)?;
+        }
+        self.layer.check(Operation::Concat, path)?;
+        self.inner.concat(path, args).await
+    }
+
+    async fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> Result<RpUpdateMetadata> {
+        self.layer.check(Operation::UpdateMetadata, path)?;
+        self.inner.update_metadata(path, args).await
+    }
+
+    async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        self.inner
+            .delete()
+            .await
+            .map(|(rp, d)| (rp, AccessControlDeleter::new(d, self.layer.clone())))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.layer.check(Operation::List, path)?;
+        self.inner
+            .list(path, args)
+            .await
+            .map(|(rp, l)| (rp, AccessControlLister::new(l, self.layer.clone())))
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.layer.check(Operation::Presign, path)?;
+        let op = match args.operation() {
+            PresignOperation::Stat(_) => Operation::Stat,
+            PresignOperation::Read(_) => Operation::Read,
+            PresignOperation::Write(_)
+            | PresignOperation::Post(_)
+            | PresignOperation::CreateMultipart(_)
+            | PresignOperation::WritePart(_)
+            | PresignOperation::CompleteMultipart(_) => Operation::Write,
+            PresignOperation::Delete(_) => Operation::Delete,
+        };
+        self.layer.check(op, path)?;
+        self.inner.presign(path, args).await
+    }
+}
+
+/// AccessControlLister drops entries that are not permitted to be listed or stat.
+///
+/// Entries carry their metadata, so both [`Operation::List`] and [`Operation::Stat`]
+/// must be permitted on the entry path, otherwise recursive listing on a permitted
+/// prefix would reveal the paths under denied ones.
+pub struct AccessControlLister<L> {
+    inner: L,
+    layer: Arc<AccessControlLayer>,
+}
+
+impl<L> AccessControlLister<L> {
+    fn new(inner: L, layer: Arc<AccessControlLayer>) -> Self {
+        Self { inner, layer }
+    }
+}
+
+impl<L: oio::List> oio::List for AccessControlLister<L> {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        while let Some(entry) = self.inner.next().await? {
+            let path = entry.path();
+            if self.layer.check(Operation::List, path).is_ok()
+                && self.layer.check(Operation::Stat, path).is_ok()
+            {
+                return Ok(Some(entry));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// AccessControlDeleter checks every path before deleting.
+pub struct AccessControlDeleter<D> {
+    inner: D,
+    layer: Arc<AccessControlLayer>,
+}
+
+impl<D> AccessControlDeleter<D> {
+    fn new(inner: D, layer: Arc<AccessControlLayer>) -> Self {
+        Self { inner, layer }
+    }
+}
+
+impl<D: oio::Delete> oio::Delete for AccessControlDeleter<D> {
+    fn delete(&mut self, path: &str, args: OpDelete) -> Result<()> {
+        self.layer.check(Operation::Delete, path)?;
+        self.inner.delete(path, args)
+    }
+
+    async fn flush(&mut self) -> Result<usize> {
+        self.inner.flush().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[test]
+    fn test_glob_match() {
+        let cases = vec![
+            ("raw/**", "raw/", true),
+            ("raw/**", "raw/a/b/c", true),
+            ("raw/**", "rawx/a", false),
+            ("raw/*", "raw/a", true),
+            ("raw/*", "raw/a/b", false),
+            ("raw/*.csv", "raw/a.csv", true),
+            ("raw/*.csv", "raw/a.json", false),
+            ("**/*.csv", "a/b/c.csv", true),
+            ("**/*.csv", "c.csv", false),
+            ("raw/?", "raw/a", true),
+            ("raw/?", "raw/ab", false),
+            ("/", "/", true),
+            ("/", "a", false),
+            ("**", "/", true),
+        ];
+
+        for (pattern, path, expected) in cases {
+            assert_eq!(
+                glob_match(pattern.as_bytes(), path.as_bytes()),
+                expected,
+                "{pattern} {path}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_access_control() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        op.write("raw/a", "raw").await?;
+        op.write("audit/a", "audit").await?;
+
+        let acl = op.clone().layer(
+            AccessControlLayer::new()
+                .read_only("/raw/**")
+                .deny("/audit/**", [Operation::Delete])
+                .deny("/", [Operation::List]),
+        );
+
+        assert_eq!(acl.read("raw/a").await?.to_vec(), b"raw");
+        let err = acl.write("raw/b", "raw").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = acl.delete("audit/a").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = acl.list("/").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        let err = acl.rename("audit/a", "tmp/a").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        assert!(acl.exists("audit/a").await?);
+
+        acl.write("audit/b", "audit").await?;
+        assert_eq!(acl.list("audit/").await?.len(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_access_control_capability() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+
+        let acl = op
+            .clone()
+            .layer(AccessControlLayer::new().deny("**", [Operation::Delete]));
+        assert!(!acl.info().full_capability().delete);
+        assert!(acl.info().full_capability().write);
+        assert!(op.info().full_capability().delete);
+
+        // Updates from outer layers must reach the shared info of the service.
+        acl.into_inner().info().set_name("acl");
+        assert_eq!(op.info().name(), "acl");
+
+        let acl = op.clone().layer(
+            AccessControlLayer::new()
+                .deny_by_default()
+                .allow("public/**", [Operation::Read, Operation::Stat]),
+        );
+        let cap = acl.info().full_capability();
+        assert!(cap.read);
+        assert!(cap.stat);
+        assert!(!cap.write);
+        assert!(!cap.list);
+
+        op.write("public/a", "public").await?;
+        op.write("private/a", "private").await?;
+        assert_eq!(acl.read("public/a").await?.to_vec(), b"public");
+        let err = acl.read("private/a").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_access_control_list() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        op.write("data/public/a", "a").await?;
+        op.write("data/secret/b", "b").await?;
+        op.write("data/c", "c").await?;
+
+        let acl = op.clone().layer(
+            AccessControlLayer::new()
+                .deny("data/secret/**", [Operation::Stat])
+                .deny("data/c", [Operation::List]),
+        );
+
+        let mut paths: Vec<_> = acl
+            .list_with("data/")
+            .recursive(true)
+            .await?
+            .into_iter()
+            .map(|v| v.path().to_string())
+            .collect();
+        paths.sort();
+        assert_eq!(paths, ["data/public/a"]);
+
+        let paths: Vec<_> = acl
+            .list("data/")
+            .await?
+            .into_iter()
+            .map(|v| v.path().to_string())
+            .collect();
+        assert!(paths.contains(&"data/public/".to_string()), "{paths:?}");
+        assert!(!paths.contains(&"data/secret/".to_string()), "{paths:?}");
+        assert!(!paths.contains(&"data/c".to_string()), "{paths:?}");
+        Ok(())
+    }
+}
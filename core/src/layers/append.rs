@@ -0,0 +1,1116 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use log::warn;
+use serde::Deserialize;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::layers::TypeEraseLayer;
+use crate::raw::*;
+use crate::*;
+
+/// The suffix of the dir that holds pending segments and the index of an object.
+const APPEND_SUFFIX: &str = ".appends/";
+/// The name of the index file under the append dir.
+const INDEX_NAME: &str = "index.json";
+
+/// Emulate append writes for services that can't append natively.
+///
+/// `AppendLayer` is opt-in and picks a strategy based on the capability of the
+/// underlying service:
+///
+/// - Services that support [`Capability::write_can_append`] are left untouched.
+/// - Services that support [`Capability::concat`] (like gcs compose and s3 multipart
+///   copy) write every append into a segment first, then concat the object with the
+///   segment in place.
+/// - Services that support both [`Capability::write_with_if_match`] and
+///   [`Capability::write_with_if_not_exists`] write every append into a segment and
+///   record it in an index next to the object. Reads and stats of the object will
+///   stitch the object and its segments together.
+///
+/// Commits of both emulated strategies are guarded by `if_match` and retried on
+/// [`ErrorKind::ConditionNotMatch`], so concurrent appenders won't lose data.
+///
+/// # Notes
+///
+/// - Segments and the index live under `<path>.appends/`, which is hidden from list
+///   if `<path>` exists or has an index.
+/// - For the index strategy, every read and stat costs an extra request to check the
+///   index, and the size returned by list doesn't include pending segments.
+/// - For the index strategy, `copy`, `rename` and `presign` of objects with pending
+///   segments will be rejected with [`ErrorKind::Unsupported`], overwrite the object to
+///   compact it first. Targets of `copy` and `rename` will drop their segments.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::AppendLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # async fn test() -> Result<()> {
+/// let op = Operator::new(services::Gcs::default().bucket("test"))?
+///     .layer(AppendLayer::new())
+///     .finish();
+///
+/// op.write_with("log", "hello, ").append(true).await?;
+/// op.write_with("log", "world").append(true).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct AppendLayer {}
+
+impl AppendLayer {
+    /// Create a new `AppendLayer`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<A: Access> Layer<A> for AppendLayer {
+    type LayeredAccess = AppendAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        let info = inner.info();
+        let cap = info.full_capability();
+        let strategy = if cap.write_can_append {
+            None
+        } else if cap.concat && cap.stat && cap.delete {
+            Some(AppendStrategy::Compose)
+        } else if cap.write_with_if_match
+            && cap.write_with_if_not_exists
+            && cap.stat
+            && cap.read
+            && cap.delete
+        {
+            Some(AppendStrategy::Index)
+        } else {
+            None
+        };
+
+        // Derive a new info instead of updating the inner one, so that operators
+        // without this layer won't append to the same services by overwriting.
+        let info = AccessorInfo::derive(info);
+        if strategy.is_some() {
+            info.update_full_capability(|mut cap| {
+                cap.write_can_append = true;
+                cap
+            });
+        }
+
+        let inner = Arc::new(inner);
+        AppendAccessor {
+            op: Operator::from_inner(Arc::new(TypeEraseLayer.layer(inner.clone()))),
+            inner,
+            info: Arc::new(info),
+            strategy,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AppendStrategy {
+    Compose,
+    Index,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct AppendIndex {
+    /// The size of the object when the index is created.
+    base: u64,
+    segments: Vec<AppendSegment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct AppendSegment {
+    name: String,
+    size: u64,
+}
+
+impl AppendIndex {
+    fn size(&self) -> u64 {
+        self.base + self.segments.iter().map(|v| v.size).sum::<u64>()
+    }
+
+    /// Map the range of the logical object into ranges of the object and its segments.
+    fn plan(&self, path: &str, range: BytesRange) -> Vec<(String, BytesRange)> {
+        let pieces = std::iter::once((path.to_string(), self.base)).chain(
+            self.segments
+                .iter()
+                .map(|v| (format!("{path}{APPEND_SUFFIX}{}", v.name), v.size)),
+        );
+
+        let start = range.offset();
+        let end = range
+            .size()
+            .map_or(u64::MAX, |size| start.saturating_add(size));
+
+        let mut plan = vec![];
+        let mut offset = 0;
+        for (piece, size) in pieces {
+            let (piece_start, piece_end) = (offset, offset + size);
+            offset = piece_end;
+
+            let (lo, hi) = (start.max(piece_start), end.min(piece_end));
+            if lo < hi {
+                plan.push((piece, BytesRange::new(lo - piece_start, Some(hi - lo))));
+            }
+        }
+        plan
+    }
+}
+
+fn append_dir(path: &str) -> String {
+    format!("{path}{APPEND_SUFFIX}")
+}
+
+fn index_path(path: &str) -> String {
+    format!("{path}{APPEND_SUFFIX}{INDEX_NAME}")
+}
+
+/// Load the index of given path, return `None` if the object has no index.
+async fn load_index(op: &Operator, path: &str) -> Result<Option<AppendIndex>> {
+    match op.read(&index_path(path)).await {
+        Ok(bs) => Ok(Some(parse_index(bs)?)),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+fn parse_index(bs: Buffer) -> Result<AppendIndex> {
+    serde_json::from_reader(bs).map_err(|err| {
+        Error::new(ErrorKind::Unexpected, "append index is malformed").set_source(err)
+    })
+}
+
+/// Remove the index and all segments of given path.
+async fn remove_index(op: &Operator, path: &str) -> Result<()> {
+    let Some(index) = load_index(op, path).await? else {
+        return Ok(());
+    };
+
+    let dir = append_dir(path);
+    let mut paths: Vec<_> = index
+        .segments
+        .iter()
+        .map(|v| format!("{dir}{}", v.name))
+        .collect();
+    // Remove the index last so that a failed removal could be retried.
+    paths.push(index_path(path));
+    for p in paths {
+        op.delete(&p).await?;
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct AppendAccessor<A: Access> {
+    inner: Arc<A>,
+    op: Operator,
+    info: Arc<AccessorInfo>,
+    strategy: Option<AppendStrategy>,
+}
+
+impl<A: Access> AppendAccessor<A> {
+    fn is_indexed(&self, path: &str) -> bool {
+        self.strategy == Some(AppendStrategy::Index) && !path.ends_with('/')
+    }
+
+    /// Reject the operation if the object has pending segments, since the
+    /// underlying service only sees the object without them.
+    async fn check_no_index(&self, op: Operation, path: &str) -> Result<()> {
+        if !self.is_indexed(path) || load_index(&self.op, path).await?.is_none() {
+            return Ok(());
+        }
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "object has pending append segments, overwrite it to compact first",
+        )
+        .with_operation(op)
+        .with_context("path", path))
+    }
+}
+
+impl<A: Access> LayeredAccess for AppendAccessor<A> {
+    type Inner = Arc<A>;
+    type Reader = TwoWays<A::Reader, AppendReader<A>>;
+    type Writer = TwoWays<A::Writer, AppendWriter<A>>;
+    type Lister = AppendLister<A::Lister>;
+    type Deleter = AppendDeleter<A::Deleter>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn info(&self) -> Arc<AccessorInfo> {
+        self.info.clone()
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        if self.is_indexed(path) {
+            if let Some(index) = load_index(&self.op, path).await? {
+                let r = AppendReader {
+                    inner: self.inner.clone(),
+                    pieces: index.plan(path, args.range()).into(),
+                    reader: None,
+                };
+                return Ok((RpRead::default(), TwoWays::Two(r)));
+            }
+        }
+
+        let (rp, r) = self.inner.read(path, args).await?;
+        Ok((rp, TwoWays::One(r)))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let kind = match (self.strategy, args.append()) {
+            (Some(AppendStrategy::Compose), true) => WriteKind::Compose,
+            (Some(AppendStrategy::Index), true) => WriteKind::Index,
+            (Some(AppendStrategy::Index), false) => WriteKind::Overwrite,
+            _ => {
+                let (rp, w) = self.inner.write(path, args).await?;
+                return Ok((rp, TwoWays::One(w)));
+            }
+        };
+
+        let (target, name) = match kind {
+            WriteKind::Overwrite => (path.to_string(), String::new()),
+            _ => {
+                let name = Uuid::new_v4().to_string();
+                (format!("{}{name}", append_dir(path)), name)
+            }
+        };
+        let (op, write_args) = if kind == WriteKind::Overwrite {
+            (args, OpWrite::default())
+        } else {
+            (
+                OpWrite::new().with_concurrent(args.concurrent()),
+                args.with_append(false),
+            )
+        };
+        let (rp, w) = self.inner.write(&target, op).await?;
+
+        let w = AppendWriter {
+            inner: self.inner.clone(),
+            op: self.op.clone(),
+            kind,
+            path: path.to_string(),
+            name,
+            size: 0,
+            write_args,
+            writer: w,
+        };
+        Ok((rp, TwoWays::Two(w)))
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if !self.is_indexed(path) {
+            return self.inner.stat(path, args).await;
+        }
+        let Some(index) = load_index(&self.op, path).await? else {
+            return self.inner.stat(path, args).await;
+        };
+
+        // The object could be absent if it's created by append.
+        let meta = match self.inner.stat(path, args).await {
+            Ok(rp) => rp.into_metadata(),
+            Err(err) if err.kind() == ErrorKind::NotFound => self
+                .inner
+                .stat(&index_path(path), OpStat::new())
+                .await?
+                .into_metadata(),
+            Err(err) => return Err(err),
+        };
+        Ok(RpStat::new(meta.with_content_length(index.size())))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.check_no_index(Operation::Copy, from).await?;
+        let rp = self.inner.copy(from, to, args).await?;
+        // The copied object replaces the target, drop its segments like overwrite does.
+        if self.is_indexed(to) {
+            remove_index(&self.op, to).await?;
+        }
+        Ok(rp)
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.check_no_index(Operation::Rename, from).await?;
+        let rp = self.inner.rename(from, to, args).await?;
+        if self.is_indexed(to) {
+            remove_index(&self.op, to).await?;
+        }
+        Ok(rp)
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.check_no_index(Operation::Presign, path).await?;
+        self.inner.presign(path, args).await
+    }
+
+    async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        let (rp, d) = self.inner.delete().await?;
+        let d = AppendDeleter {
+            op: self.op.clone(),
+            indexed: self.strategy == Some(AppendStrategy::Index),
+            inner: d,
+            deleted: vec![],
+        };
+        Ok((rp, d))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let (rp, l) = self.inner.list(path, args).await?;
+        let l = AppendLister {
+            op: self.op.clone(),
+            inner: l,
+            owners: HashMap::new(),
+        };
+        Ok((rp, l))
+    }
+}
+
+pub struct AppendReader<A: Access> {
+    inner: Arc<A>,
+    pieces: VecDeque<(String, BytesRange)>,
+    reader: Option<A::Reader>,
+}
+
+impl<A: Access> oio::Read for AppendReader<A> {
+    async fn read(&mut self) -> Result<Buffer> {
+        loop {
+            let reader = match &mut self.reader {
+                Some(r) => r,
+                None => {
+                    let Some((path, range)) = self.pieces.pop_front() else {
+                        return Ok(Buffer::new());
+                    };
+                    let (_, r) = self
+                        .inner
+                        .read(&path, OpRead::new().with_range(range))
+                        .await?;
+                    self.reader.insert(r)
+                }
+            };
+
+            let bs = reader.read().await?;
+            if !bs.is_empty() {
+                return Ok(bs);
+            }
+            self.reader = None;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WriteKind {
+    /// Append by concat the object with the segment.
+    Compose,
+    /// Append by recording the segment in the index.
+    Index,
+    /// Overwrite the object and drop its index.
+    Overwrite,
+}
+
+pub struct AppendWriter<A: Access> {
+    inner: Arc<A>,
+    op: Operator,
+    kind: WriteKind,
+    path: String,
+    name: String,
+    size: u64,
+    /// The args of the append, used as the metadata of the object created by compose.
+    write_args: OpWrite,
+
+    writer: A::Writer,
+}
+
+impl<A: Access> AppendWriter<A> {
+    fn segment_path(&self) -> String {
+        format!("{}{}", append_dir(&self.path), self.name)
+    }
+
+    async fn commit_compose(&self) -> Result<()> {
+        let segment = self.segment_path();
+        loop {
+            let args = match self.inner.stat(&self.path, OpStat::new()).await {
+                Ok(rp) => {
+                    let meta = rp.into_metadata();
+                    let etag = meta.etag().ok_or_else(|| {
+                        Error::new(
+                            ErrorKind::Unsupported,
+                            "append via concat requires etag of the object",
+                        )
+                    })?;
+                    // Compose creates a new object, carry the metadata over.
+                    OpConcat::new(vec![self.path.clone(), segment.clone()])
+                        .with_if_match(etag)
+                        .with_write_args(OpUpdateMetadata::new().apply(&meta))
+                }
+                Err(err) if err.kind() == ErrorKind::NotFound => {
+                    OpConcat::new(vec![segment.clone()])
+                        .with_if_not_exists(true)
+                        .with_write_args(self.write_args.clone())
+                }
+                Err(err) => return Err(err),
+            };
+
+            match self.inner.concat(&self.path, args).await {
+                Ok(_) => break,
+                Err(err) if err.kind() == ErrorKind::ConditionNotMatch => continue,
+                Err(err) => return Err(err),
+            }
+        }
+
+        // The segment has been concatenated, failing to remove it only leaves garbage.
+        if let Err(err) = self.op.delete(&segment).await {
+            warn!("failed to remove append segment {segment}: {err}");
+        }
+        Ok(())
+    }
+
+    async fn commit_index(&self) -> Result<()> {
+        let index_path = index_path(&self.path);
+        let segment = AppendSegment {
+            name: self.name.clone(),
+            size: self.size,
+        };
+
+        loop {
+            let ret = match self.op.stat(&index_path).await {
+                Ok(meta) => {
+                    let etag = meta.etag().ok_or_else(|| {
+                        Error::new(
+                            ErrorKind::Unsupported,
+                            "append via index requires etag of the index",
+                        )
+                    })?;
+                    let bs = match self.op.read_with(&index_path).if_match(etag).await {
+                        Ok(bs) => bs,
+                        Err(err) if err.kind() == ErrorKind::ConditionNotMatch => continue,
+                        Err(err) => return Err(err),
+                    };
+                    let mut index = parse_index(bs)?;
+                    index.segments.push(segment.clone());
+                    self.op
+                        .write_with(&index_path, to_json(&index)?)
+                        .if_match(etag)
+                        .await
+                }
+                Err(err) if err.kind() == ErrorKind::NotFound => {
+                    let base = match self.op.stat(&self.path).await {
+                        Ok(meta) => meta.content_length(),
+                        Err(err) if err.kind() == ErrorKind::NotFound => 0,
+                        Err(err) => return Err(err),
+                    };
+                    let index = AppendIndex {
+                        base,
+                        segments: vec![segment.clone()],
+                    };
+                    self.op
+                        .write_with(&index_path, to_json(&index)?)
+                        .if_not_exists(true)
+                        .await
+                }
+                Err(err) => return Err(err),
+            };
+
+            match ret {
+                Ok(_) => return Ok(()),
+                Err(err) if err.kind() == ErrorKind::ConditionNotMatch => continue,
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+fn to_json(index: &AppendIndex) -> Result<Vec<u8>> {
+    serde_json::to_vec(index).map_err(|err| {
+        Error::new(ErrorKind::Unexpected, "failed to serialize append index").set_source(err)
+    })
+}
+
+impl<A: Access> oio::Write for AppendWriter<A> {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        self.size += bs.len() as u64;
+        self.writer.write(bs).await
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        let meta = self.writer.close().await?;
+        match self.kind {
+            WriteKind::Overwrite => {
+                remove_index(&self.op, &self.path).await?;
+                Ok(meta)
+            }
+            WriteKind::Compose => {
+                self.commit_compose().await?;
+                Ok(Metadata::new(EntryMode::FILE))
+            }
+            WriteKind::Index => {
+                self.commit_index().await?;
+                Ok(Metadata::new(EntryMode::FILE))
+            }
+        }
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.writer.abort().await
+    }
+}
+
+pub struct AppendLister<L> {
+    op: Operator,
+    inner: L,
+    /// Whether the path before `.appends/` is an object that owns the append dir.
+    owners: HashMap<String, bool>,
+}
+
+impl<L> AppendLister<L> {
+    /// Check if the entry lives in the append dir of an object.
+    ///
+    /// User files like `x.appends/y` are kept unless `x` exists or has an index.
+    async fn is_append_entry(&mut self, path: &str) -> Result<bool> {
+        for (idx, _) in path.match_indices(APPEND_SUFFIX) {
+            let owner = &path[..idx];
+            let owned = match self.owners.get(owner) {
+                Some(v) => *v,
+                None => {
+                    let v =
+                        self.op.exists(owner).await? || self.op.exists(&index_path(owner)).await?;
+                    self.owners.insert(owner.to_string(), v);
+                    v
+                }
+            };
+            if owned {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+impl<L: oio::List> oio::List for AppendLister<L> {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        while let Some(entry) = self.inner.next().await? {
+            if !self.is_append_entry(entry.path()).await? {
+                return Ok(Some(entry));
+            }
+        }
+        Ok(None)
+    }
+}
+
+pub struct AppendDeleter<D> {
+    op: Operator,
+    indexed: bool,
+    inner: D,
+    deleted: Vec<String>,
+}
+
+impl<D: oio::Delete> oio::Delete for AppendDeleter<D> {
+    fn delete(&mut self, path: &str, args: OpDelete) -> Result<()> {
+        self.inner.delete(path, args)?;
+        if self.indexed && !path.ends_with('/') {
+            self.deleted.push(path.to_string());
+        }
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<usize> {
+        let n = self.inner.flush().await?;
+        for path in std::mem::take(&mut self.deleted) {
+            remove_index(&self.op, &path).await?;
+        }
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+    use std::sync::OnceLock;
+
+    use super::*;
+    use crate::services::Memory;
+
+    #[derive(Debug, Clone)]
+    struct MockObject {
+        content: Vec<u8>,
+        etag: String,
+        content_type: Option<String>,
+    }
+
+    #[derive(Debug, Default)]
+    struct MockStore {
+        objects: HashMap<String, MockObject>,
+        generation: u64,
+        /// The number of writes and concats rejected by conditions.
+        conflicts: usize,
+    }
+
+    impl MockStore {
+        fn check(&mut self, path: &str, if_match: Option<&str>, if_not_exists: bool) -> Result<()> {
+            let current = self.objects.get(path).map(|v| v.etag.as_str());
+            let matched = match (if_match, current) {
+                (Some(etag), Some(current)) => etag == current,
+                (Some(_), None) => false,
+                (None, current) => !(if_not_exists && current.is_some()),
+            };
+            if matched {
+                return Ok(());
+            }
+            self.conflicts += 1;
+            Err(Error::new(
+                ErrorKind::ConditionNotMatch,
+                "condition not match",
+            ))
+        }
+
+        fn put(&mut self, path: &str, content: Vec<u8>, args: &OpWrite) {
+            self.generation += 1;
+            let object = MockObject {
+                content,
+                etag: format!("\"{}\"", self.generation),
+                content_type: args.content_type().map(|v| v.to_string()),
+            };
+            self.objects.insert(path.to_string(), object);
+        }
+    }
+
+    /// A service with conditional writes and concat, which can't append natively.
+    #[derive(Debug, Clone, Default)]
+    struct MockService {
+        concat: bool,
+        store: Arc<Mutex<MockStore>>,
+        info: Arc<OnceLock<Arc<AccessorInfo>>>,
+    }
+
+    impl MockService {
+        fn operator(&self) -> Operator {
+            OperatorBuilder::new(self.clone())
+                .layer(AppendLayer::new())
+                .finish()
+        }
+
+        fn paths(&self) -> Vec<String> {
+            let mut paths: Vec<_> = self.store.lock().unwrap().objects.keys().cloned().collect();
+            paths.sort();
+            paths
+        }
+
+        fn conflicts(&self) -> usize {
+            self.store.lock().unwrap().conflicts
+        }
+    }
+
+    impl Access for MockService {
+        type Reader = Buffer;
+        type Writer = MockWriter;
+        type Lister = MockLister;
+        type Deleter = oio::OneShotDeleter<MockService>;
+
+        fn info(&self) -> Arc<AccessorInfo> {
+            self.info
+                .get_or_init(|| {
+                    let am = AccessorInfo::default();
+                    am.set_scheme("mock").set_native_capability(Capability {
+                        read: true,
+                        read_with_if_match: true,
+                        write: true,
+                        write_with_content_type: true,
+                        write_with_if_match: !self.concat,
+                        write_with_if_not_exists: !self.concat,
+                        concat: self.concat,
+                        copy: true,
+                        rename: true,
+                        delete: true,
+                        stat: true,
+                        list: true,
+                        list_with_recursive: true,
+                        ..Default::default()
+                    });
+                    am.into()
+                })
+                .clone()
+        }
+
+        async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+            // Let concurrent appenders interleave between stat and commit.
+            tokio::task::yield_now().await;
+
+            let store = self.store.lock().unwrap();
+            let Some(object) = store.objects.get(path) else {
+                return Err(Error::new(ErrorKind::NotFound, "not found"));
+            };
+            let mut meta = Metadata::new(EntryMode::FILE)
+                .with_content_length(object.content.len() as u64)
+                .with_etag(object.etag.clone());
+            if let Some(v) = &object.content_type {
+                meta = meta.with_content_type(v.clone());
+            }
+            Ok(RpStat::new(meta))
+        }
+
+        async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            let store = self.store.lock().unwrap();
+            let Some(object) = store.objects.get(path) else {
+                return Err(Error::new(ErrorKind::NotFound, "not found"));
+            };
+            if args.if_match().is_some_and(|v| v != object.etag) {
+                return Err(Error::new(ErrorKind::ConditionNotMatch, "etag not match"));
+            }
+
+            let bs = Buffer::from(object.content.clone());
+            let range = args.range();
+            let start = (range.offset() as usize).min(bs.len());
+            let end = range
+                .size()
+                .map_or(bs.len(), |v| (start + v as usize).min(bs.len()));
+            Ok((RpRead::new(), bs.slice(start..end)))
+        }
+
+        async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+            let w = MockWriter {
+                store: self.store.clone(),
+                path: path.to_string(),
+                args,
+                content: vec![],
+            };
+            Ok((RpWrite::new(), w))
+        }
+
+        async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+            Ok((RpDelete::default(), oio::OneShotDeleter::new(self.clone())))
+        }
+
+        async fn list(&self, path: &str, _: OpList) -> Result<(RpList, Self::Lister)> {
+            let prefix = path.trim_start_matches('/');
+            let store = self.store.lock().unwrap();
+            let entries = store
+                .objects
+                .iter()
+                .filter(|(k, _)| k.starts_with(prefix))
+                .map(|(k, v)| {
+                    let meta =
+                        Metadata::new(EntryMode::FILE).with_content_length(v.content.len() as u64);
+                    oio::Entry::new(k, meta)
+                })
+                .collect();
+            Ok((RpList::default(), MockLister { entries }))
+        }
+
+        async fn copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
+            let mut store = self.store.lock().unwrap();
+            let Some(object) = store.objects.get(from).cloned() else {
+                return Err(Error::new(ErrorKind::NotFound, "not found"));
+            };
+            store.objects.insert(to.to_string(), object);
+            Ok(RpCopy::default())
+        }
+
+        async fn rename(&self, from: &str, to: &str, _: OpRename) -> Result<RpRename> {
+            let mut store = self.store.lock().unwrap();
+            let Some(object) = store.objects.remove(from) else {
+                return Err(Error::new(ErrorKind::NotFound, "not found"));
+            };
+            store.objects.insert(to.to_string(), object);
+            Ok(RpRename::default())
+        }
+
+        async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+            tokio::task::yield_now().await;
+
+            let mut store = self.store.lock().unwrap();
+            store.check(path, args.if_match(), args.if_not_exists())?;
+            let mut content = vec![];
+            for source in args.sources() {
+                let Some(object) = store.objects.get(source) else {
+                    return Err(Error::new(ErrorKind::NotFound, "source not found"));
+                };
+                content.extend_from_slice(&object.content);
+            }
+            store.put(path, content, args.write_args());
+            Ok(RpConcat::default())
+        }
+    }
+
+    impl oio::OneShotDelete for MockService {
+        async fn delete_once(&self, path: String, _: OpDelete) -> Result<()> {
+            self.store.lock().unwrap().objects.remove(&path);
+            Ok(())
+        }
+    }
+
+    struct MockWriter {
+        store: Arc<Mutex<MockStore>>,
+        path: String,
+        args: OpWrite,
+        content: Vec<u8>,
+    }
+
+    impl oio::Write for MockWriter {
+        async fn write(&mut self, bs: Buffer) -> Result<()> {
+            self.content.extend(bs.to_vec());
+            Ok(())
+        }
+
+        async fn close(&mut self) -> Result<Metadata> {
+            let mut store = self.store.lock().unwrap();
+            store.check(&self.path, self.args.if_match(), self.args.if_not_exists())?;
+            store.put(&self.path, std::mem::take(&mut self.content), &self.args);
+            Ok(Metadata::new(EntryMode::FILE))
+        }
+
+        async fn abort(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    struct MockLister {
+        entries: VecDeque<oio::Entry>,
+    }
+
+    impl oio::List for MockLister {
+        async fn next(&mut self) -> Result<Option<oio::Entry>> {
+            Ok(self.entries.pop_front())
+        }
+    }
+
+    async fn list_files(op: &Operator) -> Vec<String> {
+        let mut paths: Vec<_> = op
+            .list_with("")
+            .recursive(true)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|v| v.path().to_string())
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn test_append_index_plan() {
+        let index = AppendIndex {
+            base: 10,
+            segments: vec![
+                AppendSegment {
+                    name: "a".to_string(),
+                    size: 5,
+                },
+                AppendSegment {
+                    name: "b".to_string(),
+                    size: 5,
+                },
+            ],
+        };
+        assert_eq!(index.size(), 20);
+
+        let cases = vec![
+            (
+                BytesRange::default(),
+                vec![
+                    ("log", BytesRange::new(0, Some(10))),
+                    ("log.appends/a", BytesRange::new(0, Some(5))),
+                    ("log.appends/b", BytesRange::new(0, Some(5))),
+                ],
+            ),
+            (
+                BytesRange::new(8, Some(4)),
+                vec![
+                    ("log", BytesRange::new(8, Some(2))),
+                    ("log.appends/a", BytesRange::new(0, Some(2))),
+                ],
+            ),
+            (
+                BytesRange::new(17, None),
+                vec![("log.appends/b", BytesRange::new(2, Some(3)))],
+            ),
+            (BytesRange::new(30, None), vec![]),
+        ];
+
+        for (range, expected) in cases {
+            let expected: Vec<_> = expected
+                .into_iter()
+                .map(|(p, r)| (p.to_string(), r))
+                .collect();
+            assert_eq!(index.plan("log", range), expected, "{range}");
+        }
+    }
+
+    #[test]
+    fn test_append_index_json() {
+        let index = AppendIndex {
+            base: 3,
+            segments: vec![AppendSegment {
+                name: "a".to_string(),
+                size: 2,
+            }],
+        };
+        let bs = to_json(&index).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&bs),
+            r#"{"base":3,"segments":[{"name":"a","size":2}]}"#
+        );
+        assert_eq!(parse_index(Buffer::from(bs)).unwrap(), index);
+    }
+
+    #[tokio::test]
+    async fn test_append_via_compose() -> Result<()> {
+        let srv = MockService {
+            concat: true,
+            ..Default::default()
+        };
+        let op = srv.operator();
+        assert!(op.info().full_capability().write_can_append);
+
+        op.write_with("log", "hello, ")
+            .append(true)
+            .content_type("text/plain")
+            .await?;
+        op.write_with("log", "world").append(true).await?;
+        assert_eq!(op.read("log").await?.to_vec(), b"hello, world");
+
+        // Metadata of the object must be carried over by compose.
+        let meta = op.stat("log").await?;
+        assert_eq!(meta.content_length(), 12);
+        assert_eq!(meta.content_type(), Some("text/plain"));
+
+        // Segments must be removed after being concatenated.
+        assert_eq!(srv.paths(), vec!["log"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_append_via_index() -> Result<()> {
+        let srv = MockService::default();
+        let op = srv.operator();
+        assert!(op.info().full_capability().write_can_append);
+
+        op.write("log", "hello").await?;
+        op.write_with("log", ", ").append(true).await?;
+        op.write_with("log", "world").append(true).await?;
+        assert_eq!(op.read("log").await?.to_vec(), b"hello, world");
+        assert_eq!(op.read_with("log").range(3..9).await?.to_vec(), b"lo, wo");
+        assert_eq!(op.stat("log").await?.content_length(), 12);
+
+        // The append dir is hidden, while user files with the same suffix are kept.
+        op.write("x.appends/y", "user").await?;
+        assert_eq!(list_files(&op).await, vec!["log", "x.appends/y"]);
+
+        // Overwrite drops the index and segments.
+        op.write("log", "fresh").await?;
+        assert_eq!(op.read("log").await?.to_vec(), b"fresh");
+        assert_eq!(srv.paths(), vec!["log", "x.appends/y"]);
+
+        op.write_with("log", "!").append(true).await?;
+        op.delete("log").await?;
+        assert_eq!(srv.paths(), vec!["x.appends/y"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_append_via_index_copy_and_rename() -> Result<()> {
+        let srv = MockService::default();
+        let op = srv.operator();
+
+        op.write("log", "hello").await?;
+        op.write_with("log", ", world").append(true).await?;
+        op.write("other", "other").await?;
+
+        // Objects with pending segments can't be copied or renamed as a whole.
+        for err in [
+            op.copy("log", "copied").await.unwrap_err(),
+            op.rename("log", "renamed").await.unwrap_err(),
+        ] {
+            assert_eq!(err.kind(), ErrorKind::Unsupported);
+        }
+        assert_eq!(op.read("log").await?.to_vec(), b"hello, world");
+
+        // Overwriting the target drops its segments.
+        op.copy("other", "log").await?;
+        assert_eq!(op.read("log").await?.to_vec(), b"other");
+        assert_eq!(srv.paths(), vec!["log", "other"]);
+
+        op.rename("log", "renamed").await?;
+        assert_eq!(op.read("renamed").await?.to_vec(), b"other");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_append_layer_keeps_inner_capability() -> Result<()> {
+        let srv = MockService::default();
+        let base = OperatorBuilder::new(srv.clone()).finish();
+        let op = srv.operator();
+        assert!(op.info().full_capability().write_can_append);
+
+        // Operators without this layer must still reject append.
+        assert!(!base.info().full_capability().write_can_append);
+        let err = base
+            .write_with("log", "hello")
+            .append(true)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_append_concurrently() -> Result<()> {
+        for concat in [true, false] {
+            let srv = MockService {
+                concat,
+                ..Default::default()
+            };
+            let op = srv.operator();
+            op.write("log", "0").await?;
+
+            let (a, b, c) = futures::join!(
+                async { op.write_with("log", "a").append(true).await },
+                async { op.write_with("log", "b").append(true).await },
+                async { op.write_with("log", "c").append(true).await },
+            );
+            a?;
+            b?;
+            c?;
+            assert!(srv.conflicts() > 0, "appenders must conflict and retry");
+
+            let mut content = op.read("log").await?.to_vec();
+            assert_eq!(content.len(), 4, "concat: {concat}");
+            assert_eq!(content.remove(0), b'0');
+            content.sort();
+            assert_eq!(content, b"abc", "concat: {concat}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_append_layer_requires_capability() {
+        let op = Operator::new(Memory::default())
+            .unwrap()
+            .layer(AppendLayer::new())
+            .finish();
+        assert!(!op.info().full_capability().write_can_append);
+    }
+}
@@ -0,0 +1,1093 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::future::Future;
+use std::mem;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use chrono::SecondsFormat;
+use chrono::Utc;
+use futures::channel::mpsc::UnboundedSender;
+use log::warn;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::abort_upload_args;
+use super::concat_args;
+use super::copy_args;
+use super::delete_args;
+use super::list_args;
+use super::lock_args;
+use super::read_args;
+use super::rename_args;
+use super::stat_args;
+use super::write_args;
+use crate::raw::*;
+use crate::*;
+
+/// Add structured audit log for the underlying services.
+///
+/// # Records
+///
+/// Every call will be described by an [`AuditRecord`] which contains the
+/// timestamp, operation, path, options, result, transferred bytes, latency
+/// and the principal configured by [`AuditLayer::with_principal`].
+///
+/// Records of readers are emitted after reaching the end, failing or being
+/// dropped. Records of writers are emitted after closed or aborted. Records
+/// of deleters are emitted after flushed.
+///
+/// # Sink
+///
+/// Records are written into an [`AuditSink`] in batches. Available sinks
+/// are:
+///
+/// - [`OperatorAuditSink`]: write records as JSON lines into rolling files
+///   of another [`Operator`].
+/// - [`UnboundedSender`]: send records into a channel.
+///
+/// By default, records are buffered and calls never wait for the sink. Full
+/// batches are written by a background task spawned on the [`Executor`] of the
+/// service, so an executor must be available. Failed batches are kept in the
+/// buffer and retried with the next batch. Call [`AuditLayer::flush`] to write
+/// the buffered records and get the error of the sink.
+///
+/// The buffer holds at most [`AuditLayer::with_max_buffered`] records. Once it's
+/// full, the oldest records are shed and counted by
+/// [`AuditLayer::dropped_records`], with a warning logged.
+///
+/// # Strict Mode
+///
+/// With [`AuditLayer::with_strict`] enabled, mutations (`create_dir`,
+/// `write`, `copy`, `rename`, `delete`, `abort_upload`, `lock`, `concat` and
+/// `update_metadata`) will be written into the sink with all buffered records
+/// before returning. Mutations will fail if their records can't be written,
+/// even if the mutation itself succeeded. Failed records are kept in the buffer,
+/// and mutations will be rejected before running once the buffer is full.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::AuditLayer;
+/// # use opendal::layers::OperatorAuditSink;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let audit = Operator::new(services::Memory::default())?.finish();
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(
+///         AuditLayer::new(OperatorAuditSink::new(audit, "audit/"))
+///             .with_principal("alice")
+///             .with_strict(true),
+///     )
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AuditLayer<S: AuditSink> {
+    sink: Arc<S>,
+    buffer: Arc<AuditBuffer>,
+    principal: Option<String>,
+    batch_size: usize,
+    max_buffered: usize,
+    strict: bool,
+}
+
+impl<S: AuditSink> Clone for AuditLayer<S> {
+    fn clone(&self) -> Self {
+        Self {
+            sink: self.sink.clone(),
+            buffer: self.buffer.clone(),
+            principal: self.principal.clone(),
+            batch_size: self.batch_size,
+            max_buffered: self.max_buffered,
+            strict: self.strict,
+        }
+    }
+}
+
+impl<S: AuditSink> AuditLayer<S> {
+    /// Create a new `AuditLayer` that writes records into the given sink.
+    pub fn new(sink: S) -> Self {
+        Self {
+            sink: Arc::new(sink),
+            buffer: Arc::default(),
+            principal: None,
+            batch_size: 64,
+            max_buffered: 64 * 1024,
+            strict: false,
+        }
+    }
+
+    /// Set the principal that will be recorded in every record.
+    pub fn with_principal(mut self, principal: impl Into<String>) -> Self {
+        self.principal = Some(principal.into());
+        self
+    }
+
+    /// Set the number of records written into the sink in one batch.
+    ///
+    /// Default to 64.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Set the max number of records held in the buffer while the sink is
+    /// unavailable.
+    ///
+    /// Default to 65536.
+    pub fn with_max_buffered(mut self, max_buffered: usize) -> Self {
+        self.max_buffered = max_buffered.max(1);
+        self
+    }
+
+    /// Enable strict mode or not.
+    ///
+    /// Default to `false`.
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Write all buffered records into the sink.
+    ///
+    /// Records will be kept in the buffer if the sink returns an error.
+    pub async fn flush(&self) -> Result<()> {
+        let batch = self.buffer.take(0);
+        if batch.is_empty() {
+            return Ok(());
+        }
+        if let Err(err) = self.sink.write(&batch).await {
+            self.buffer.put_back(batch, self.max_buffered);
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// The number of records that have been shed because the buffer is full.
+    pub fn dropped_records(&self) -> u64 {
+        self.buffer.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl<A: Access, S: AuditSink> Layer<A> for AuditLayer<S> {
+    type LayeredAccess = AuditAccessor<A, S>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        AuditAccessor {
+            core: Arc::new(AuditCore {
+                sink: self.sink.clone(),
+                buffer: self.buffer.clone(),
+                info: inner.info(),
+                principal: self.principal.clone(),
+                batch_size: self.batch_size,
+                max_buffered: self.max_buffered,
+                strict: self.strict,
+            }),
+            inner,
+        }
+    }
+}
+
+/// The record of a call emitted by [`AuditLayer`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// The time when the call started in RFC 3339 format.
+    pub timestamp: String,
+    /// The principal configured by [`AuditLayer::with_principal`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub principal: Option<String>,
+    /// The operation of the call, for example `write`.
+    pub operation: String,
+    /// The path of the call.
+    pub path: String,
+    /// The options of the call.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub options: BTreeMap<String, String>,
+    /// The result of the call: `Ok`, `Aborted` or the [`ErrorKind`] of the
+    /// returned error.
+    pub result: String,
+    /// The bytes read or written by the call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytes: Option<u64>,
+    /// The latency of the call in microseconds.
+    pub latency_us: u64,
+}
+
+/// AuditSink receives the records of [`AuditLayer`] in batches.
+pub trait AuditSink: Debug + Send + Sync + Unpin + 'static {
+    /// Write a batch of records.
+    fn write(&self, records: &[AuditRecord]) -> impl Future<Output = Result<()>> + MaybeSend;
+}
+
+impl AuditSink for UnboundedSender<AuditRecord> {
+    async fn write(&self, records: &[AuditRecord]) -> Result<()> {
+        for record in records {
+            self.unbounded_send(record.clone()).map_err(|err| {
+                Error::new(ErrorKind::Unexpected, "audit channel has been closed").set_source(err)
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// OperatorAuditSink writes records as JSON lines into rolling files under
+/// a dir of the given operator.
+///
+/// If the operator supports append, records will be appended into the
+/// current file until it reaches the max file size. Otherwise, every batch
+/// will be written into a new file.
+///
+/// Files are named like `20250101T000000.000Z-0.jsonl`.
+#[derive(Debug)]
+pub struct OperatorAuditSink {
+    op: Operator,
+    dir: String,
+    max_file_size: u64,
+    state: tokio::sync::Mutex<OperatorAuditState>,
+}
+
+#[derive(Debug, Default)]
+struct OperatorAuditState {
+    path: Option<String>,
+    size: u64,
+    seq: u64,
+}
+
+impl OperatorAuditSink {
+    /// Create a new `OperatorAuditSink` that writes files under `dir` of `op`.
+    pub fn new(op: Operator, dir: &str) -> Self {
+        Self {
+            op,
+            dir: normalize_root(dir).trim_start_matches('/').to_string(),
+            max_file_size: 64 * 1024 * 1024,
+            state: tokio::sync::Mutex::default(),
+        }
+    }
+
+    /// Set the max size of a file before rolling to a new one.
+    ///
+    /// Default to 64 MiB.
+    pub fn with_max_file_size(mut self, size: u64) -> Self {
+        self.max_file_size = size;
+        self
+    }
+}
+
+impl AuditSink for OperatorAuditSink {
+    async fn write(&self, records: &[AuditRecord]) -> Result<()> {
+        let mut buf = Vec::new();
+        for record in records {
+            serde_json::to_writer(&mut buf, record).map_err(new_json_serialize_error)?;
+            buf.push(b'\n');
+        }
+
+        let append = self.op.info().full_capability().write_can_append;
+        let mut state = self.state.lock().await;
+        let path = match &state.path {
+            Some(path) if append && state.size + buf.len() as u64 <= self.max_file_size => {
+                path.clone()
+            }
+            _ => {
+                let path = format!(
+                    "{}{}-{}.jsonl",
+                    self.dir,
+                    Utc::now().format("%Y%m%dT%H%M%S%.3fZ"),
+                    state.seq
+                );
+                state.seq += 1;
+                state.size = 0;
+                state.path = Some(path.clone());
+                path
+            }
+        };
+
+        let size = buf.len() as u64;
+        self.op.write_with(&path, buf).append(append).await?;
+        state.size += size;
+        Ok(())
+    }
+}
+
+/// AuditBuffer holds the records that haven't been written into the sink.
+#[derive(Debug, Default)]
+struct AuditBuffer {
+    records: Mutex<Vec<AuditRecord>>,
+    /// Whether a background task is writing batches.
+    writing: AtomicBool,
+    /// The number of records shed because the buffer is full.
+    dropped: AtomicU64,
+}
+
+impl AuditBuffer {
+    fn len(&self) -> usize {
+        self.records.lock().expect("lock must succeed").len()
+    }
+
+    /// Push records and shed the oldest ones beyond `max`, returns the buffered size.
+    fn push(&self, records: impl IntoIterator<Item = AuditRecord>, max: usize) -> usize {
+        let mut buffer = self.records.lock().expect("lock must succeed");
+        buffer.extend(records);
+        self.shed(&mut buffer, max);
+        buffer.len()
+    }
+
+    /// Put the failed batch back to the front so that it could be retried.
+    fn put_back(&self, batch: Vec<AuditRecord>, max: usize) {
+        let mut buffer = self.records.lock().expect("lock must succeed");
+        buffer.splice(0..0, batch);
+        self.shed(&mut buffer, max);
+    }
+
+    /// Take all records if there are at least `min` records.
+    fn take(&self, min: usize) -> Vec<AuditRecord> {
+        let mut buffer = self.records.lock().expect("lock must succeed");
+        if buffer.is_empty() || buffer.len() < min {
+            return Vec::new();
+        }
+        mem::take(&mut *buffer)
+    }
+
+    fn shed(&self, buffer: &mut Vec<AuditRecord>, max: usize) {
+        if buffer.len() <= max {
+            return;
+        }
+        let n = buffer.len() - max;
+        buffer.drain(..n);
+        self.dropped.fetch_add(n as u64, Ordering::Relaxed);
+        warn!(
+            target: "opendal::layers::audit",
+            "audit buffer is full, {n} oldest records have been dropped"
+        );
+    }
+}
+
+#[derive(Debug)]
+struct AuditCore<S> {
+    sink: Arc<S>,
+    buffer: Arc<AuditBuffer>,
+    info: Arc<AccessorInfo>,
+    principal: Option<String>,
+    batch_size: usize,
+    max_buffered: usize,
+    strict: bool,
+}
+
+impl<S: AuditSink> AuditCore<S> {
+    /// Buffer the records without writing, it's safe to be called in drop.
+    fn buffer(&self, records: Vec<AuditRecord>) {
+        self.buffer.push(records, self.max_buffered);
+    }
+
+    /// Buffer the records and hand full batches to a background task, so that
+    /// calls never wait for the sink.
+    fn emit(self: &Arc<Self>, records: Vec<AuditRecord>) {
+        if self.buffer.push(records, self.max_buffered) < self.batch_size {
+            return;
+        }
+        if self.buffer.writing.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let core = self.clone();
+        self.info
+            .executor()
+            .into_inner()
+            .execute(Box::pin(async move { core.write_batches().await }));
+    }
+
+    /// Write full batches until the buffer is drained or the sink fails.
+    async fn write_batches(&self) {
+        loop {
+            let batch = self.buffer.take(self.batch_size);
+            if batch.is_empty() {
+                self.buffer.writing.store(false, Ordering::Release);
+                // Records pushed before the flag is reset won't start a new task.
+                if self.buffer.len() >= self.batch_size
+                    && !self.buffer.writing.swap(true, Ordering::AcqRel)
+                {
+                    continue;
+                }
+                return;
+            }
+
+            if let Err(err) = self.sink.write(&batch).await {
+                warn!(
+                    target: "opendal::layers::audit",
+                    "failed to write {} audit records, will retry with the next batch: {err}",
+                    batch.len()
+                );
+                self.buffer.put_back(batch, self.max_buffered);
+                self.buffer.writing.store(false, Ordering::Release);
+                return;
+            }
+        }
+    }
+
+    /// Reject mutations in strict mode if the buffer is full, since their
+    /// records can't be kept.
+    fn admit(&self, op: Operation) -> Result<()> {
+        if !self.strict {
+            return Ok(());
+        }
+        let buffered = self.buffer.len();
+        if buffered < self.max_buffered {
+            return Ok(());
+        }
+        Err(Error::new(
+            ErrorKind::Unexpected,
+            "audit buffer is full, the sink may be unavailable",
+        )
+        .with_operation(op)
+        .with_context("buffered", buffered)
+        .set_temporary())
+    }
+
+    /// Write the records of mutations.
+    ///
+    /// In strict mode, the records and all buffered records will be written
+    /// into sink before returning.
+    async fn commit(self: &Arc<Self>, records: Vec<AuditRecord>) -> Result<()> {
+        if !self.strict {
+            self.emit(records);
+            return Ok(());
+        }
+
+        self.buffer.push(records, self.max_buffered);
+        let batch = self.buffer.take(0);
+        if batch.is_empty() {
+            return Ok(());
+        }
+        if let Err(err) = self.sink.write(&batch).await {
+            // Put the records back so that they could be written later.
+            self.buffer.put_back(batch, self.max_buffered);
+            return Err(err);
+        }
+        Ok(())
+    }
+}
+
+/// AuditContext keeps the start state of a call.
+struct AuditContext {
+    operation: Operation,
+    path: String,
+    options: BTreeMap<String, String>,
+    timestamp: String,
+    start: Instant,
+}
+
+impl AuditContext {
+    fn new(operation: Operation, path: &str, options: BTreeMap<String, String>) -> Self {
+        Self {
+            operation,
+            path: path.to_string(),
+            options,
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            start: Instant::now(),
+        }
+    }
+
+    fn finish<S>(&self, core: &AuditCore<S>, result: &str, bytes: Option<u64>) -> AuditRecord {
+        AuditRecord {
+            timestamp: self.timestamp.clone(),
+            principal: core.principal.clone(),
+            operation: self.operation.into_static().to_string(),
+            path: self.path.clone(),
+            options: self.options.clone(),
+            result: result.to_string(),
+            bytes,
+            latency_us: self.start.elapsed().as_micros() as u64,
+        }
+    }
+}
+
+fn audit_result<T>(res: &Result<T>) -> &'static str {
+    match res {
+        Ok(_) => "Ok",
+        Err(err) => err.kind().into_static(),
+    }
+}
+
+#[derive(Debug)]
+pub struct AuditAccessor<A: Access, S: AuditSink> {
+    inner: A,
+    core: Arc<AuditCore<S>>,
+}
+
+impl<A: Access, S: AuditSink> LayeredAccess for AuditAccessor<A, S> {
+    type Inner = A;
+    type Reader = AuditReader<A::Reader, S>;
+    type Writer = AuditWriter<A::Writer, S>;
+    type Lister = A::Lister;
+    type Deleter = AuditDeleter<A::Deleter, S>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.core.admit(Operation::CreateDir)?;
+        let ctx = AuditContext::new(Operation::CreateDir, path, BTreeMap::new());
+        let res = self.inner.create_dir(path, args).await;
+        self.core
+            .commit(vec![ctx.finish(&self.core, audit_result(&res), None)])
+            .await?;
+        res
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let ctx = AuditContext::new(Operation::Read, path, read_args(&args));
+        match self.inner.read(path, args).await {
+            Ok((rp, r)) => Ok((rp, AuditReader::new(r, self.core.clone(), ctx))),
+            Err(err) => {
+                self.core
+                    .emit(vec![ctx.finish(&self.core, err.kind().into_static(), None)]);
+                Err(err)
+            }
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.core.admit(Operation::Write)?;
+        let ctx = AuditContext::new(Operation::Write, path, write_args(&args));
+        match self.inner.write(path, args).await {
+            Ok((rp, w)) => Ok((rp, AuditWriter::new(w, self.core.clone(), ctx))),
+            Err(err) => {
+                self.core
+                    .commit(vec![ctx.finish(&self.core, err.kind().into_static(), None)])
+                    .await?;
+                Err(err)
+            }
+        }
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.core.admit(Operation::Copy)?;
+        let ctx = AuditContext::new(Operation::Copy, from, copy_args(to, &args));
+        let res = self.inner.copy(from, to, args).await;
+        self.core
+            .commit(vec![ctx.finish(&self.core, audit_result(&res), None)])
+            .await?;
+        res
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.core.admit(Operation::Rename)?;
+        let ctx = AuditContext::new(Operation::Rename, from, rename_args(to, &args));
+        let res = self.inner.rename(from, to, args).await;
+        self.core
+            .commit(vec![ctx.finish(&self.core, audit_result(&res), None)])
+            .await?;
+        res
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let ctx = AuditContext::new(Operation::Stat, path, stat_args(&args));
+        let res = self.inner.stat(path, args).await;
+        self.core
+            .emit(vec![ctx.finish(&self.core, audit_result(&res), None)]);
+        res
+    }
+
+    async fn stat_many(&self, args: Vec<(String, OpStat)>) -> Result<RpStatMany> {
+        let ctxs: Vec<_> = args
+            .iter()
+            .map(|(path, op)| AuditContext::new(Operation::Stat, path, stat_args(op)))
+            .collect();
+        let res = self.inner.stat_many(args).await;
+        let records = match &res {
+            Ok(rp) => ctxs
+                .iter()
+                .zip(rp.results())
+                .map(|(ctx, res)| ctx.finish(&self.core, audit_result(res), None))
+                .collect(),
+            Err(err) => ctxs
+                .iter()
+                .map(|ctx| ctx.finish(&self.core, err.kind().into_static(), None))
+                .collect(),
+        };
+        self.core.emit(records);
+        res
+    }
+
+    async fn list_uploads(&self, path: &str, args: OpListUploads) -> Result<RpListUploads> {
+        let ctx = AuditContext::new(Operation::ListUploads, path, BTreeMap::new());
+        let res = self.inner.list_uploads(path, args).await;
+        self.core
+            .emit(vec![ctx.finish(&self.core, audit_result(&res), None)]);
+        res
+    }
+
+    async fn abort_upload(&self, path: &str, args: OpAbortUpload) -> Result<RpAbortUpload> {
+        self.core.admit(Operation::AbortUpload)?;
+        let ctx = AuditContext::new(Operation::AbortUpload, path, abort_upload_args(&args));
+        let res = self.inner.abort_upload(path, args).await;
+        self.core
+            .commit(vec![ctx.finish(&self.core, audit_result(&res), None)])
+            .await?;
+        res
+    }
+
+    async fn lock(&self, path: &str, args: OpLock) -> Result<RpLock> {
+        self.core.admit(Operation::Lock)?;
+        let ctx = AuditContext::new(Operation::Lock, path, lock_args(&args));
+        let res = self.inner.lock(path, args).await;
+        self.core
+            .commit(vec![ctx.finish(&self.core, audit_result(&res), None)])
+            .await?;
+        res
+    }
+
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        self.core.admit(Operation::Concat)?;
+        let ctx = AuditContext::new(Operation::Concat, path, concat_args(&args));
+        let res = self.inner.concat(path, args).await;
+        self.core
+            .commit(vec![ctx.finish(&self.core, audit_result(&res), None)])
+            .await?;
+        res
+    }
+
+    async fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> Result<RpUpdateMetadata> {
+        let mut options = BTreeMap::new();
+        if let Some(v) = args.content_type() {
+            options.insert("content_type".to_string(), v.to_string());
+        }
+        if let Some(v) = args.cache_control() {
+            options.insert("cache_control".to_string(), v.to_string());
+        }
+        if let Some(v) = args.content_disposition() {
+            options.insert("content_disposition".to_string(), v.to_string());
+        }
+        if !args.user_metadata().is_empty() {
+            let mut keys: Vec<_> = args.user_metadata().keys().map(|v| v.as_str()).collect();
+            keys.sort_unstable();
+            options.insert("user_metadata".to_string(), keys.join(","));
+        }
+        if let Some(tags) = args.tags() {
+            let mut keys: Vec<_> = tags.keys().map(|v| v.as_str()).collect();
+            keys.sort_unstable();
+            options.insert("tags".to_string(), keys.join(","));
+        }
+        self.core.admit(Operation::UpdateMetadata)?;
+        let ctx = AuditContext::new(Operation::UpdateMetadata, path, options);
+        let res = self.inner.update_metadata(path, args).await;
+        self.core
+            .commit(vec![ctx.finish(&self.core, audit_result(&res), None)])
+            .await?;
+        res
+    }
+
+    async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        let (rp, d) = self.inner.delete().await?;
+        Ok((rp, AuditDeleter::new(d, self.core.clone())))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let ctx = AuditContext::new(Operation::List, path, list_args(&args));
+        let res = self.inner.list(path, args).await;
+        self.core
+            .emit(vec![ctx.finish(&self.core, audit_result(&res), None)]);
+        res
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        let mut options = BTreeMap::new();
+        let op = match args.operation() {
+            PresignOperation::Stat(_) => Operation::Stat,
+            PresignOperation::Read(_) => Operation::Read,
+            PresignOperation::Write(_)
+            | PresignOperation::Post(_)
+            | PresignOperation::CreateMultipart(_)
+            | PresignOperation::WritePart(_)
+            | PresignOperation::CompleteMultipart(_) => Operation::Write,
+            PresignOperation::Delete(_) => Operation::Delete,
+        };
+        options.insert("operation".to_string(), op.into_static().to_string());
+        options.insert(
+            "expire".to_string(),
+            format!("{}s", args.expire().as_secs()),
+        );
+        let ctx = AuditContext::new(Operation::Presign, path, options);
+        let res = self.inner.presign(path, args).await;
+        self.core
+            .emit(vec![ctx.finish(&self.core, audit_result(&res), None)]);
+        res
+    }
+}
+
+/// AuditReader emits the record after reaching the end, failing or being
+/// dropped.
+pub struct AuditReader<R, S: AuditSink> {
+    inner: R,
+    core: Arc<AuditCore<S>>,
+    ctx: Option<AuditContext>,
+    read: u64,
+}
+
+impl<R, S: AuditSink> AuditReader<R, S> {
+    fn new(inner: R, core: Arc<AuditCore<S>>, ctx: AuditContext) -> Self {
+        Self {
+            inner,
+            core,
+            ctx: Some(ctx),
+            read: 0,
+        }
+    }
+
+    fn take_record(&mut self, result: &str) -> Option<AuditRecord> {
+        self.ctx
+            .take()
+            .map(|ctx| ctx.finish(&self.core, result, Some(self.read)))
+    }
+}
+
+impl<R, S: AuditSink> Drop for AuditReader<R, S> {
+    fn drop(&mut self) {
+        // Drop could happen outside of a runtime, leave the record to the next batch.
+        if let Some(record) = self.take_record("Ok") {
+            self.core.buffer(vec![record]);
+        }
+    }
+}
+
+impl<R: oio::Read, S: AuditSink> oio::Read for AuditReader<R, S> {
+    async fn read(&mut self) -> Result<Buffer> {
+        let res = self.inner.read().await;
+        let record = match &res {
+            Ok(bs) => {
+                self.read += bs.len() as u64;
+                if bs.is_empty() {
+                    self.take_record("Ok")
+                } else {
+                    None
+                }
+            }
+            Err(err) => self.take_record(err.kind().into_static()),
+        };
+        if let Some(record) = record {
+            self.core.emit(vec![record]);
+        }
+        res
+    }
+}
+
+/// AuditWriter emits the record after closed or aborted.
+pub struct AuditWriter<W, S: AuditSink> {
+    inner: W,
+    core: Arc<AuditCore<S>>,
+    ctx: AuditContext,
+    written: u64,
+}
+
+impl<W, S: AuditSink> AuditWriter<W, S> {
+    fn new(inner: W, core: Arc<AuditCore<S>>, ctx: AuditContext) -> Self {
+        Self {
+            inner,
+            core,
+            ctx,
+            written: 0,
+        }
+    }
+}
+
+impl<W: oio::Write, S: AuditSink> oio::Write for AuditWriter<W, S> {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        let size = bs.len() as u64;
+        self.inner.write(bs).await?;
+        self.written += size;
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        let res = self.inner.close().await;
+        let record = self
+            .ctx
+            .finish(&self.core, audit_result(&res), Some(self.written));
+        self.core.commit(vec![record]).await?;
+        res
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        let res = self.inner.abort().await;
+        let result = match &res {
+            Ok(()) => "Aborted",
+            Err(err) => err.kind().into_static(),
+        };
+        let record = self.ctx.finish(&self.core, result, Some(self.written));
+        self.core.commit(vec![record]).await?;
+        res
+    }
+}
+
+/// AuditDeleter emits records of the queued paths after flushed.
+pub struct AuditDeleter<D, S: AuditSink> {
+    inner: D,
+    core: Arc<AuditCore<S>>,
+    queue: VecDeque<AuditContext>,
+}
+
+impl<D, S: AuditSink> AuditDeleter<D, S> {
+    fn new(inner: D, core: Arc<AuditCore<S>>) -> Self {
+        Self {
+            inner,
+            core,
+            queue: VecDeque::new(),
+        }
+    }
+}
+
+impl<D: oio::Delete, S: AuditSink> oio::Delete for AuditDeleter<D, S> {
+    fn delete(&mut self, path: &str, args: OpDelete) -> Result<()> {
+        self.core.admit(Operation::Delete)?;
+        let ctx = AuditContext::new(Operation::Delete, path, delete_args(&args));
+        match self.inner.delete(path, args) {
+            Ok(()) => {
+                self.queue.push_back(ctx);
+                Ok(())
+            }
+            Err(err) => {
+                self.core
+                    .buffer(vec![ctx.finish(&self.core, err.kind().into_static(), None)]);
+                Err(err)
+            }
+        }
+    }
+
+    async fn flush(&mut self) -> Result<usize> {
+        let res = self.inner.flush().await;
+        let records = match &res {
+            Ok(n) => self
+                .queue
+                .drain(..(*n).min(self.queue.len()))
+                .map(|ctx| ctx.finish(&self.core, "Ok", None))
+                .collect(),
+            // Failed paths are kept in queue since they could be flushed again.
+            Err(err) => self
+                .queue
+                .iter()
+                .map(|ctx| ctx.finish(&self.core, err.kind().into_static(), None))
+                .collect(),
+        };
+        self.core.commit(records).await?;
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::channel::mpsc;
+    use futures::StreamExt;
+
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_audit_channel() -> Result<()> {
+        let (tx, mut rx) = mpsc::unbounded();
+        let layer = AuditLayer::new(tx)
+            .with_principal("alice")
+            .with_strict(true);
+        let op = Operator::new(Memory::default())?
+            .layer(layer.clone())
+            .finish();
+
+        let _ = op.read("not_exist").await;
+        op.write("a", "hello").await?;
+        let mut records = Vec::new();
+        while let Ok(Some(record)) = rx.try_next() {
+            records.push(record);
+        }
+        // Records of mutations are written with buffered ones in strict mode.
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].path, "not_exist");
+        assert_eq!(records[0].result, "NotFound");
+        assert_eq!(records[1].operation, "write");
+        assert_eq!(records[1].path, "a");
+        assert_eq!(records[1].result, "Ok");
+        assert_eq!(records[1].bytes, Some(5));
+        assert_eq!(records[1].principal.as_deref(), Some("alice"));
+
+        assert_eq!(op.read("a").await?.to_vec(), b"hello");
+        op.delete("a").await?;
+        layer.flush().await?;
+        drop(op);
+        drop(layer);
+
+        let records: Vec<_> = rx.collect().await;
+        let read = records.iter().find(|r| r.operation == "read").unwrap();
+        assert_eq!(read.bytes, Some(5));
+        assert!(records
+            .iter()
+            .any(|r| r.operation == "delete" && r.path == "a" && r.result == "Ok"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_audit_reader_drop_outside_runtime() -> Result<()> {
+        let (tx, mut rx) = mpsc::unbounded();
+        // Every record fills a batch, so the drop would write if it's allowed to.
+        let layer = AuditLayer::new(tx).with_batch_size(1);
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .expect("runtime must be built");
+
+        let op = Operator::new(Memory::default())?
+            .layer(layer.clone())
+            .finish();
+        let (_, r) = rt.block_on(async {
+            op.write("a", "hello").await?;
+            op.into_inner().read("a", OpRead::new()).await
+        })?;
+        // Dropping the reader outside of runtime must not panic.
+        drop(r);
+
+        rt.block_on(layer.flush())?;
+        let mut records = Vec::new();
+        while let Ok(Some(record)) = rx.try_next() {
+            records.push(record);
+        }
+        assert!(records.iter().any(|r| r.operation == "read"));
+        Ok(())
+    }
+
+    /// FlakySink keeps the written records and fails while `down` is set.
+    #[derive(Debug, Default)]
+    struct FlakySink {
+        down: AtomicBool,
+        records: Mutex<Vec<AuditRecord>>,
+    }
+
+    impl AuditSink for Arc<FlakySink> {
+        async fn write(&self, records: &[AuditRecord]) -> Result<()> {
+            if self.down.load(Ordering::Relaxed) {
+                return Err(Error::new(ErrorKind::Unexpected, "sink is down"));
+            }
+            self.records.lock().unwrap().extend_from_slice(records);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_audit_retry_failed_batches() -> Result<()> {
+        let sink = Arc::new(FlakySink::default());
+        sink.down.store(true, Ordering::Relaxed);
+        let layer = AuditLayer::new(sink.clone())
+            .with_batch_size(2)
+            .with_max_buffered(3);
+        let op = Operator::new(Memory::default())?
+            .layer(layer.clone())
+            .finish();
+
+        for _ in 0..4 {
+            let _ = op.stat("a").await;
+            tokio::task::yield_now().await;
+        }
+        // Failed batches are kept, the oldest records are shed beyond the limit.
+        assert!(layer.flush().await.is_err());
+        assert_eq!(layer.dropped_records(), 1);
+
+        sink.down.store(false, Ordering::Relaxed);
+        layer.flush().await?;
+        assert_eq!(sink.records.lock().unwrap().len(), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_audit_strict_rejects_when_full() -> Result<()> {
+        let sink = Arc::new(FlakySink::default());
+        sink.down.store(true, Ordering::Relaxed);
+        let layer = AuditLayer::new(sink.clone())
+            .with_strict(true)
+            .with_max_buffered(1);
+        let op = Operator::new(Memory::default())?
+            .layer(layer.clone())
+            .finish();
+
+        // The mutation runs but its record can't be written.
+        assert!(op.write("a", "hello").await.is_err());
+        assert!(op.exists("a").await?);
+        // The buffer is full, mutations are rejected before running.
+        let err = op.write("b", "hello").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(!op.exists("b").await?);
+
+        sink.down.store(false, Ordering::Relaxed);
+        layer.flush().await?;
+        op.write("b", "hello").await?;
+        assert!(sink
+            .records
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|r| r.operation == "write" && r.path == "b"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_audit_operator_sink() -> Result<()> {
+        let audit = Operator::new(Memory::default())?.finish();
+        let layer = AuditLayer::new(OperatorAuditSink::new(audit.clone(), "audit"));
+        let op = Operator::new(Memory::default())?
+            .layer(layer.clone())
+            .finish();
+
+        op.write("a", "hello").await?;
+        op.stat("a").await?;
+        let _ = op
+            .into_inner()
+            .stat_many(vec![("a".to_string(), OpStat::new())])
+            .await;
+        layer.flush().await?;
+
+        let entries = audit.list("audit/").await?;
+        assert_eq!(entries.len(), 1);
+        let bs = audit.read(entries[0].path()).await?.to_bytes();
+        let records = bs
+            .split(|b| *b == b'\n')
+            .filter(|v| !v.is_empty())
+            .map(|v| serde_json::from_slice::<AuditRecord>(v).unwrap())
+            .collect::<Vec<_>>();
+        assert!(records
+            .iter()
+            .any(|r| r.operation == "write" && r.bytes == Some(5)));
+        // Batch stats are recorded per path.
+        assert_eq!(
+            records
+                .iter()
+                .filter(|r| r.operation == "stat" && r.path == "a")
+                .count(),
+            2
+        );
+        Ok(())
+    }
+}
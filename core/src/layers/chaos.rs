@@ -17,6 +17,7 @@
 
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::Duration;
 
 use rand::prelude::*;
 use rand::rngs::StdRng;
@@ -36,23 +37,59 @@ use crate::*;
 /// For example: If we specify an error rate of 0.5, there is a 50% chance
 /// of an EOF error for every read operation.
 ///
-/// # Note
+/// # Fault Plan
 ///
-/// For now, ChaosLayer only injects read operations. More operations may
-/// be added in the future.
+/// Besides the EOF errors of read, more faults could be added for every
+/// operation via [`ChaosLayer::with_fault`], see [`ChaosFault`] for all
+/// supported faults.
+///
+/// Faults of an operation will be injected into both the call that starts
+/// the operation and the calls on the returned reader, writer, lister or
+/// deleter. For example, faults of [`Operation::List`] will be injected into
+/// `list` and every `next` of the lister.
+///
+/// # Reproducibility
+///
+/// All randomness comes from a single rng shared by the whole layer. Set a
+/// seed via [`ChaosLayer::with_seed`] to reproduce the same faults in the
+/// same order for the same sequence of calls.
 ///
 /// # Examples
 ///
 /// ```no_run
+/// # use std::time::Duration;
+/// # use opendal::layers::ChaosFault;
+/// # use opendal::layers::ChaosLatency;
 /// # use opendal::layers::ChaosLayer;
+/// # use opendal::raw::Operation;
 /// # use opendal::services;
+/// # use opendal::ErrorKind;
 /// # use opendal::Operator;
 /// # use opendal::Result;
 /// # use opendal::Scheme;
 ///
 /// # fn main() -> Result<()> {
 /// let _ = Operator::new(services::Memory::default())?
-///     .layer(ChaosLayer::new(0.1))
+///     .layer(
+///         ChaosLayer::new(0.1)
+///             .with_seed(42)
+///             .with_fault(
+///                 Operation::Stat,
+///                 ChaosFault::Error {
+///                     kind: ErrorKind::RateLimited,
+///                     ratio: 0.2,
+///                 },
+///             )
+///             .with_fault(
+///                 Operation::Read,
+///                 ChaosFault::Latency(ChaosLatency::Uniform {
+///                     min: Duration::from_millis(10),
+///                     max: Duration::from_millis(100),
+///                 }),
+///             )
+///             .with_fault(Operation::List, ChaosFault::DuplicateListEntry { ratio: 0.1 })
+///             .with_fault(Operation::Write, ChaosFault::PartialWrite { after: 2, ratio: 0.5 }),
+///     )
 ///     .finish();
 /// Ok(())
 /// # }
@@ -60,6 +97,8 @@ use crate::*;
 #[derive(Debug, Clone)]
 pub struct ChaosLayer {
     error_ratio: f64,
+    seed: Option<u64>,
+    faults: Vec<(Operation, ChaosFault)>,
 }
 
 impl ChaosLayer {
@@ -69,87 +108,306 @@ impl ChaosLayer {
     ///
     /// Input error_ratio must in [0.0..=1.0]
     pub fn new(error_ratio: f64) -> Self {
-        assert!(
-            (0.0..=1.0).contains(&error_ratio),
-            "error_ratio must between 0.0 and 1.0"
-        );
-        Self { error_ratio }
+        check_ratio(error_ratio);
+        Self {
+            error_ratio,
+            seed: None,
+            faults: Vec::new(),
+        }
+    }
+
+    /// Set the seed of the rng to make faults reproducible.
+    ///
+    /// The rng will be seeded from entropy if not set.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Add a fault for the given operation.
+    ///
+    /// Multiple faults could be added for the same operation, they will be
+    /// checked in the order of being added.
+    ///
+    /// # Panics
+    ///
+    /// Ratios in fault must in [0.0..=1.0]
+    pub fn with_fault(mut self, op: Operation, fault: ChaosFault) -> Self {
+        match &fault {
+            ChaosFault::Error { ratio, .. }
+            | ChaosFault::TruncateList { ratio }
+            | ChaosFault::DuplicateListEntry { ratio }
+            | ChaosFault::PartialWrite { ratio, .. } => check_ratio(*ratio),
+            ChaosFault::Latency(_) => {}
+        }
+        self.faults.push((op, fault));
+        self
     }
 }
 
+fn check_ratio(ratio: f64) {
+    assert!(
+        (0.0..=1.0).contains(&ratio),
+        "error_ratio must between 0.0 and 1.0"
+    );
+}
+
+/// Fault that could be injected by [`ChaosLayer`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ChaosFault {
+    /// Return an error of given kind at given ratio.
+    ///
+    /// Errors of [`ErrorKind::Unexpected`] and [`ErrorKind::RateLimited`] will
+    /// be marked as temporary so that they could be retried.
+    Error {
+        /// The kind of returned error.
+        kind: ErrorKind,
+        /// The ratio of returning error.
+        ratio: f64,
+    },
+    /// Add latency before the call.
+    Latency(ChaosLatency),
+    /// End the list early as if the service returned a truncated page.
+    ///
+    /// Only works for [`Operation::List`].
+    TruncateList {
+        /// The ratio of ending the list on every `next`.
+        ratio: f64,
+    },
+    /// Return the previous entry again as if pages overlapped.
+    ///
+    /// Only works for [`Operation::List`].
+    DuplicateListEntry {
+        /// The ratio of duplicating the previous entry on every `next`.
+        ratio: f64,
+    },
+    /// Fail writes partway through an upload.
+    ///
+    /// After `after` writes succeeded on a writer, every following `write`
+    /// and `close` will return a temporary error at given ratio.
+    ///
+    /// Only works for [`Operation::Write`].
+    PartialWrite {
+        /// The number of writes that will always succeed.
+        after: usize,
+        /// The ratio of returning error after that.
+        ratio: f64,
+    },
+}
+
+/// Latency distribution used by [`ChaosFault::Latency`].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum ChaosLatency {
+    /// Always add the same latency.
+    Fixed(Duration),
+    /// Add latency uniformly distributed in `[min, max]`.
+    Uniform {
+        /// The min latency.
+        min: Duration,
+        /// The max latency.
+        max: Duration,
+    },
+    /// Add latency exponentially distributed with given mean, which is useful
+    /// to simulate long tail latency.
+    Exponential {
+        /// The mean of latency.
+        mean: Duration,
+    },
+}
+
 impl<A: Access> Layer<A> for ChaosLayer {
     type LayeredAccess = ChaosAccessor<A>;
 
     fn layer(&self, inner: A) -> Self::LayeredAccess {
+        let rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
         ChaosAccessor {
             inner,
-            rng: StdRng::from_entropy(),
-            error_ratio: self.error_ratio,
+            plan: Arc::new(ChaosPlan {
+                rng: Mutex::new(rng),
+                error_ratio: self.error_ratio,
+                faults: self.faults.clone(),
+            }),
         }
     }
 }
 
+/// ChaosPlan decides which faults to inject.
 #[derive(Debug)]
-pub struct ChaosAccessor<A> {
-    inner: A,
-    rng: StdRng,
+struct ChaosPlan {
+    rng: Mutex<StdRng>,
 
     error_ratio: f64,
+    faults: Vec<(Operation, ChaosFault)>,
+}
+
+impl ChaosPlan {
+    /// Returns true at given ratio.
+    fn hit(&self, ratio: f64) -> bool {
+        if ratio <= 0.0 {
+            return false;
+        }
+        self.rng.lock().unwrap().gen_bool(ratio)
+    }
+
+    /// If I feel lucky, we can return the correct response. Otherwise,
+    /// we need to generate an error.
+    fn i_feel_lucky(&self) -> bool {
+        let point = self.rng.lock().unwrap().gen_range(0..=100);
+        point >= (self.error_ratio * 100.0) as i32
+    }
+
+    fn faults(&self, op: Operation) -> impl Iterator<Item = &ChaosFault> {
+        self.faults
+            .iter()
+            .filter(move |(v, _)| *v == op)
+            .map(|(_, f)| f)
+    }
+
+    fn latency(&self, latency: &ChaosLatency) -> Duration {
+        let mut rng = self.rng.lock().unwrap();
+        match *latency {
+            ChaosLatency::Fixed(d) => d,
+            ChaosLatency::Uniform { min, max } if min < max => rng.gen_range(min..=max),
+            ChaosLatency::Uniform { min, .. } => min,
+            ChaosLatency::Exponential { mean } => {
+                let u: f64 = rng.gen();
+                mean.mul_f64(-(1.0 - u).ln())
+            }
+        }
+    }
+
+    /// Inject latency and errors for given operation.
+    async fn inject(&self, op: Operation) -> Result<()> {
+        for fault in self.faults(op) {
+            match fault {
+                ChaosFault::Latency(latency) => {
+                    let dur = self.latency(latency);
+                    if !dur.is_zero() {
+                        tokio::time::sleep(dur).await;
+                    }
+                }
+                ChaosFault::Error { kind, ratio } if self.hit(*ratio) => {
+                    return Err(chaos_error(*kind, op));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Inject errors for given operation without latency.
+    fn inject_error(&self, op: Operation) -> Result<()> {
+        for fault in self.faults(op) {
+            if let ChaosFault::Error { kind, ratio } = fault {
+                if self.hit(*ratio) {
+                    return Err(chaos_error(*kind, op));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn chaos_error(kind: ErrorKind, op: Operation) -> Error {
+    let err = Error::new(kind, "I am your chaos!")
+        .with_operation("chaos")
+        .with_context("operation", op.into_static());
+    match kind {
+        ErrorKind::Unexpected | ErrorKind::RateLimited => err.set_temporary(),
+        _ => err,
+    }
+}
+
+#[derive(Debug)]
+pub struct ChaosAccessor<A> {
+    inner: A,
+    plan: Arc<ChaosPlan>,
 }
 
 impl<A: Access> LayeredAccess for ChaosAccessor<A> {
     type Inner = A;
     type Reader = ChaosReader<A::Reader>;
-    type Writer = A::Writer;
-    type Lister = A::Lister;
-    type Deleter = A::Deleter;
+    type Writer = ChaosWriter<A::Writer>;
+    type Lister = ChaosLister<A::Lister>;
+    type Deleter = ChaosDeleter<A::Deleter>;
 
     fn inner(&self) -> &Self::Inner {
         &self.inner
     }
 
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.plan.inject(Operation::CreateDir).await?;
+        self.inner.create_dir(path, args).await
+    }
+
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.plan.inject(Operation::Read).await?;
         self.inner
             .read(path, args)
             .await
-            .map(|(rp, r)| (rp, ChaosReader::new(r, self.rng.clone(), self.error_ratio)))
+            .map(|(rp, r)| (rp, ChaosReader::new(r, self.plan.clone())))
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        self.inner.write(path, args).await
+        self.plan.inject(Operation::Write).await?;
+        self.inner
+            .write(path, args)
+            .await
+            .map(|(rp, w)| (rp, ChaosWriter::new(w, self.plan.clone())))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.plan.inject(Operation::Copy).await?;
+        self.inner.copy(from, to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.plan.inject(Operation::Rename).await?;
+        self.inner.rename(from, to, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.plan.inject(Operation::Stat).await?;
+        self.inner.stat(path, args).await
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
-        self.inner.list(path, args).await
+        self.plan.inject(Operation::List).await?;
+        self.inner
+            .list(path, args)
+            .await
+            .map(|(rp, l)| (rp, ChaosLister::new(l, self.plan.clone())))
     }
 
     async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
-        self.inner.delete().await
+        self.plan.inject(Operation::Delete).await?;
+        self.inner
+            .delete()
+            .await
+            .map(|(rp, d)| (rp, ChaosDeleter::new(d, self.plan.clone())))
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.plan.inject(Operation::Presign).await?;
+        self.inner.presign(path, args).await
     }
 }
 
 /// ChaosReader will inject error into read operations.
 pub struct ChaosReader<R> {
     inner: R,
-    rng: Arc<Mutex<StdRng>>,
-
-    error_ratio: f64,
+    plan: Arc<ChaosPlan>,
 }
 
 impl<R> ChaosReader<R> {
-    fn new(inner: R, rng: StdRng, error_ratio: f64) -> Self {
-        Self {
-            inner,
-            rng: Arc::new(Mutex::new(rng)),
-            error_ratio,
-        }
-    }
-
-    /// If I feel lucky, we can return the correct response. Otherwise,
-    /// we need to generate an error.
-    fn i_feel_lucky(&self) -> bool {
-        let point = self.rng.lock().unwrap().gen_range(0..=100);
-        point >= (self.error_ratio * 100.0) as i32
+    fn new(inner: R, plan: Arc<ChaosPlan>) -> Self {
+        Self { inner, plan }
     }
 
     fn unexpected_eof() -> Error {
@@ -161,10 +419,226 @@ impl<R> ChaosReader<R> {
 
 impl<R: oio::Read> oio::Read for ChaosReader<R> {
     async fn read(&mut self) -> Result<Buffer> {
-        if self.i_feel_lucky() {
-            self.inner.read().await
-        } else {
-            Err(Self::unexpected_eof())
+        if !self.plan.i_feel_lucky() {
+            return Err(Self::unexpected_eof());
         }
+        self.plan.inject(Operation::Read).await?;
+        self.inner.read().await
+    }
+}
+
+/// ChaosWriter will inject error into write operations.
+pub struct ChaosWriter<W> {
+    inner: W,
+    plan: Arc<ChaosPlan>,
+
+    written: usize,
+}
+
+impl<W> ChaosWriter<W> {
+    fn new(inner: W, plan: Arc<ChaosPlan>) -> Self {
+        Self {
+            inner,
+            plan,
+            written: 0,
+        }
+    }
+
+    /// Inject failures partway through the upload.
+    fn inject_partial_write(&self) -> Result<()> {
+        for fault in self.plan.faults(Operation::Write) {
+            if let ChaosFault::PartialWrite { after, ratio } = fault {
+                if self.written >= *after && self.plan.hit(*ratio) {
+                    return Err(chaos_error(ErrorKind::Unexpected, Operation::Write)
+                        .with_context("written", self.written.to_string()));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<W: oio::Write> oio::Write for ChaosWriter<W> {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        self.plan.inject(Operation::Write).await?;
+        self.inject_partial_write()?;
+        self.inner.write(bs).await?;
+        self.written += 1;
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        self.plan.inject(Operation::Write).await?;
+        self.inject_partial_write()?;
+        self.inner.close().await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+}
+
+/// ChaosLister will inject error into list operations.
+pub struct ChaosLister<L> {
+    inner: L,
+    plan: Arc<ChaosPlan>,
+
+    previous: Option<oio::Entry>,
+    truncated: bool,
+}
+
+impl<L> ChaosLister<L> {
+    fn new(inner: L, plan: Arc<ChaosPlan>) -> Self {
+        Self {
+            inner,
+            plan,
+            previous: None,
+            truncated: false,
+        }
+    }
+}
+
+impl<L: oio::List> oio::List for ChaosLister<L> {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        if self.truncated {
+            return Ok(None);
+        }
+        self.plan.inject(Operation::List).await?;
+
+        for fault in self.plan.faults(Operation::List) {
+            match fault {
+                ChaosFault::TruncateList { ratio } if self.plan.hit(*ratio) => {
+                    self.truncated = true;
+                    return Ok(None);
+                }
+                ChaosFault::DuplicateListEntry { ratio } => {
+                    if let Some(entry) = &self.previous {
+                        if self.plan.hit(*ratio) {
+                            return Ok(Some(entry.clone()));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let entry = self.inner.next().await?;
+        self.previous.clone_from(&entry);
+        Ok(entry)
+    }
+}
+
+/// ChaosDeleter will inject error into delete operations.
+pub struct ChaosDeleter<D> {
+    inner: D,
+    plan: Arc<ChaosPlan>,
+}
+
+impl<D> ChaosDeleter<D> {
+    fn new(inner: D, plan: Arc<ChaosPlan>) -> Self {
+        Self { inner, plan }
+    }
+}
+
+impl<D: oio::Delete> oio::Delete for ChaosDeleter<D> {
+    fn delete(&mut self, path: &str, args: OpDelete) -> Result<()> {
+        self.plan.inject_error(Operation::Delete)?;
+        self.inner.delete(path, args)
+    }
+
+    async fn flush(&mut self) -> Result<usize> {
+        self.plan.inject(Operation::Delete).await?;
+        self.inner.flush().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::services::Memory;
+
+    async fn stat_results(seed: u64) -> Vec<bool> {
+        let op = Operator::new(Memory::default())
+            .unwrap()
+            .layer(ChaosLayer::new(0.0).with_seed(seed).with_fault(
+                Operation::Stat,
+                ChaosFault::Error {
+                    kind: ErrorKind::RateLimited,
+                    ratio: 0.5,
+                },
+            ))
+            .finish();
+        op.write("file", "hello").await.unwrap();
+
+        let mut results = Vec::new();
+        for _ in 0..32 {
+            match op.stat("file").await {
+                Ok(_) => results.push(true),
+                Err(err) => {
+                    assert_eq!(err.kind(), ErrorKind::RateLimited);
+                    assert!(err.is_temporary());
+                    results.push(false);
+                }
+            }
+        }
+        results
+    }
+
+    #[tokio::test]
+    async fn test_reproducible_with_seed() {
+        let results = stat_results(42).await;
+        assert!(results.contains(&true));
+        assert!(results.contains(&false));
+        assert_eq!(results, stat_results(42).await);
+    }
+
+    #[tokio::test]
+    async fn test_list_faults() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        for i in 0..16 {
+            op.write(&format!("dir/{i:02}"), "hello").await?;
+        }
+
+        let chaos = op
+            .clone()
+            .layer(ChaosLayer::new(0.0).with_seed(7).with_fault(
+                Operation::List,
+                ChaosFault::DuplicateListEntry { ratio: 1.0 },
+            ));
+        let entries: Vec<_> = chaos.lister("dir/").await?.take(4).try_collect().await?;
+        assert_eq!(entries[0].path(), entries[1].path());
+
+        let chaos = op.layer(
+            ChaosLayer::new(0.0)
+                .with_seed(7)
+                .with_fault(Operation::List, ChaosFault::TruncateList { ratio: 1.0 }),
+        );
+        assert!(chaos.list("dir/").await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_partial_write() -> Result<()> {
+        let op = Operator::new(Memory::default())?
+            .layer(ChaosLayer::new(0.0).with_fault(
+                Operation::Write,
+                ChaosFault::PartialWrite {
+                    after: 2,
+                    ratio: 1.0,
+                },
+            ))
+            .finish();
+
+        let mut w = op.writer_with("file").chunk(4).await?;
+        w.write("aaaa").await?;
+        w.write("bbbb").await?;
+        w.write("cccc").await?;
+        let err = w.write("dddd").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.is_temporary());
+        Ok(())
     }
 }
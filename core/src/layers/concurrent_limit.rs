@@ -240,6 +240,70 @@ impl<A: Access> LayeredAccess for ConcurrentLimitAccessor<A> {
         self.inner.stat(path, args).await
     }
 
+    async fn stat_many(&self, args: Vec<(String, OpStat)>) -> Result<RpStatMany> {
+        let _permit = self
+            .semaphore
+            .acquire()
+            .await
+            .expect("semaphore must be valid");
+
+        self.inner.stat_many(args).await
+    }
+
+    async fn list_uploads(&self, path: &str, args: OpListUploads) -> Result<RpListUploads> {
+        let _permit = self
+            .semaphore
+            .acquire()
+            .await
+            .expect("semaphore must be valid");
+
+        self.inner.list_uploads(path, args).await
+    }
+
+    async fn abort_upload(&self, path: &str, args: OpAbortUpload) -> Result<RpAbortUpload> {
+        let _permit = self
+            .semaphore
+            .acquire()
+            .await
+            .expect("semaphore must be valid");
+
+        self.inner.abort_upload(path, args).await
+    }
+
+    async fn lock(&self, path: &str, args: OpLock) -> Result<RpLock> {
+        let _permit = self
+            .semaphore
+            .acquire()
+            .await
+            .expect("semaphore must be valid");
+
+        self.inner.lock(path, args).await
+    }
+
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        let _permit = self
+            .semaphore
+            .acquire()
+            .await
+            .expect("semaphore must be valid");
+
+        self.inner.concat(path, args).await
+    }
+
+    async fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> Result<RpUpdateMetadata> {
+        let _permit = self
+            .semaphore
+            .acquire()
+            .await
+            .expect("semaphore must be valid");
+
+        self.inner.update_metadata(path, args).await
+    }
+
     async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
         let permit = self
             .semaphore
@@ -147,6 +147,13 @@ impl<A: Access> LayeredAccess for CorrectnessAccessor<A> {
                 "if_not_exists",
             ));
         }
+        if args.resume().is_some() && !capability.write_with_resume {
+            return Err(new_unsupported_error(
+                &self.info,
+                Operation::Write,
+                "resume",
+            ));
+        }
         if let Some(if_none_match) = args.if_none_match() {
             if !capability.write_with_if_none_match {
                 let mut err =
@@ -203,6 +210,109 @@ impl<A: Access> LayeredAccess for CorrectnessAccessor<A> {
         self.inner.stat(path, args).await
     }
 
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let capability = self.info.full_capability();
+        if !capability.copy_with_if_match && args.if_match().is_some() {
+            return Err(new_unsupported_error(
+                self.info.as_ref(),
+                Operation::Copy,
+                "if_match",
+            ));
+        }
+        if !capability.copy_with_source_if_match && args.source_if_match().is_some() {
+            return Err(new_unsupported_error(
+                self.info.as_ref(),
+                Operation::Copy,
+                "source_if_match",
+            ));
+        }
+        if !capability.copy_with_source_if_none_match && args.source_if_none_match().is_some() {
+            return Err(new_unsupported_error(
+                self.info.as_ref(),
+                Operation::Copy,
+                "source_if_none_match",
+            ));
+        }
+        if !capability.copy_with_source_if_unmodified_since
+            && args.source_if_unmodified_since().is_some()
+        {
+            return Err(new_unsupported_error(
+                self.info.as_ref(),
+                Operation::Copy,
+                "source_if_unmodified_since",
+            ));
+        }
+        if !capability.copy_with_source_version && args.source_version().is_some() {
+            return Err(new_unsupported_error(
+                self.info.as_ref(),
+                Operation::Copy,
+                "source_version",
+            ));
+        }
+
+        self.inner.copy(from, to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let capability = self.info.full_capability();
+        if !capability.rename_with_if_not_exists && args.if_not_exists() {
+            return Err(new_unsupported_error(
+                self.info.as_ref(),
+                Operation::Rename,
+                "if_not_exists",
+            ));
+        }
+        if !capability.rename_with_if_match && args.if_match().is_some() {
+            return Err(new_unsupported_error(
+                self.info.as_ref(),
+                Operation::Rename,
+                "if_match",
+            ));
+        }
+        if !capability.rename_with_source_if_match && args.source_if_match().is_some() {
+            return Err(new_unsupported_error(
+                self.info.as_ref(),
+                Operation::Rename,
+                "source_if_match",
+            ));
+        }
+        if !capability.rename_with_source_if_none_match && args.source_if_none_match().is_some() {
+            return Err(new_unsupported_error(
+                self.info.as_ref(),
+                Operation::Rename,
+                "source_if_none_match",
+            ));
+        }
+        if !capability.rename_with_source_if_unmodified_since
+            && args.source_if_unmodified_since().is_some()
+        {
+            return Err(new_unsupported_error(
+                self.info.as_ref(),
+                Operation::Rename,
+                "source_if_unmodified_since",
+            ));
+        }
+
+        self.inner.rename(from, to, args).await
+    }
+
+    async fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> Result<RpUpdateMetadata> {
+        let capability = self.info.full_capability();
+        if !capability.update_metadata_with_tags && args.tags().is_some() {
+            return Err(new_unsupported_error(
+                self.info.as_ref(),
+                Operation::UpdateMetadata,
+                "tags",
+            ));
+        }
+
+        self.inner.update_metadata(path, args).await
+    }
+
     async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
         self.inner.delete().await.map(|(rp, deleter)| {
             let deleter = CheckWrapper::new(deleter, self.info.clone());
@@ -226,13 +336,35 @@ impl<T> CheckWrapper<T> {
     }
 
     fn check_delete(&self, args: &OpDelete) -> Result<()> {
-        if args.version().is_some() && !self.info.full_capability().delete_with_version {
+        let capability = self.info.full_capability();
+        if args.version().is_some() && !capability.delete_with_version {
             return Err(new_unsupported_error(
                 &self.info,
                 Operation::Delete,
                 "version",
             ));
         }
+        if args.if_match().is_some() && !capability.delete_with_if_match {
+            return Err(new_unsupported_error(
+                &self.info,
+                Operation::Delete,
+                "if_match",
+            ));
+        }
+        if args.if_none_match().is_some() && !capability.delete_with_if_none_match {
+            return Err(new_unsupported_error(
+                &self.info,
+                Operation::Delete,
+                "if_none_match",
+            ));
+        }
+        if args.if_unmodified_since().is_some() && !capability.delete_with_if_unmodified_since {
+            return Err(new_unsupported_error(
+                &self.info,
+                Operation::Delete,
+                "if_unmodified_since",
+            ));
+        }
 
         Ok(())
     }
@@ -435,6 +567,18 @@ mod tests {
             ..Default::default()
         });
         let res = op.delete_with("path").version("version").await;
+        assert!(res.is_ok());
+
+        let res = op.delete_with("path").if_match("etag").await;
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+
+        let op = new_test_operator(Capability {
+            delete: true,
+            delete_with_if_match: true,
+            ..Default::default()
+        });
+        let res = op.delete_with("path").if_match("etag").await;
         assert!(res.is_ok())
     }
 }
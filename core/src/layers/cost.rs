@@ -0,0 +1,782 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use futures::future::poll_fn;
+use http::Method;
+use http::Request;
+use http::Response;
+
+use crate::layers::observe::MetricPathLabel;
+use crate::raw::*;
+use crate::*;
+
+/// Add request cost estimation for the underlying services.
+///
+/// # Cost
+///
+/// `CostLayer` classifies every HTTP request sent via [`HttpClient`],
+/// including multipart parts and list pages, into a [`CostClass`] by its
+/// method (the operation of the request only breaks ties), and
+/// estimates its cost by the given [`PriceTable`]. Estimated cost will be
+/// accumulated for the operator, per operation and per prefix, which could
+/// be fetched by [`CostLayer::snapshot`].
+///
+/// Requests are attributed to the operator call that sends them, so requests
+/// sent by a `write` call (like `stat` requests sent by other layers) will be
+/// accounted as `write`. Requests sent outside of operator calls will be
+/// accounted by the operation of the request.
+///
+/// Prefixes are mapped from paths by [`MetricPathLabel`] set via
+/// [`CostLayer::with_path_label`], and won't be accounted by default.
+///
+/// # Metrics
+///
+/// The estimated cost will also be attached to the response as
+/// [`RequestCost`]. Metrics layers like `PrometheusLayer` will export it as
+/// `opendal_http_request_cost_total` if they are added after this layer.
+///
+/// # Notes
+///
+/// Services that don't send requests via [`HttpClient`] won't be accounted.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::CostLayer;
+/// # use opendal::layers::PriceTable;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<()> {
+/// let cost = CostLayer::new(PriceTable::s3_standard());
+/// let op = Operator::new(services::Memory::default())?
+///     .layer(cost.clone())
+///     .finish();
+///
+/// op.write("test", "Hello, World!").await?;
+/// let snapshot = cost.snapshot();
+/// println!("estimated cost: {}", snapshot.total.cost);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CostLayer {
+    prices: PriceTable,
+    path_label: Option<MetricPathLabel>,
+    snapshot: Arc<Mutex<CostSnapshot>>,
+}
+
+impl CostLayer {
+    /// Create a new `CostLayer` with given price table.
+    pub fn new(prices: PriceTable) -> Self {
+        Self {
+            prices,
+            path_label: None,
+            snapshot: Arc::default(),
+        }
+    }
+
+    /// Account cost per prefix mapped by the given [`MetricPathLabel`].
+    pub fn with_path_label(mut self, path_label: MetricPathLabel) -> Self {
+        self.path_label = Some(path_label);
+        self
+    }
+
+    /// Return the snapshot of the accumulated cost.
+    pub fn snapshot(&self) -> CostSnapshot {
+        self.snapshot.lock().expect("lock must succeed").clone()
+    }
+
+    /// Reset the accumulated cost.
+    pub fn reset(&self) {
+        *self.snapshot.lock().expect("lock must succeed") = CostSnapshot::default();
+    }
+}
+
+impl<A: Access> Layer<A> for CostLayer {
+    type LayeredAccess = CostAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        let info = inner.info();
+        let core = Arc::new(CostCore {
+            prices: self.prices.clone(),
+            snapshot: self.snapshot.clone(),
+        });
+
+        info.update_http_client(|client| {
+            HttpClient::with(CostHttpFetcher {
+                inner: client.into_inner(),
+                core: core.clone(),
+            })
+        });
+        // Requests sent by background tasks should be accounted by the call
+        // that spawns them.
+        info.update_executor(|executor| {
+            Executor::with(CostExecutor {
+                inner: executor.into_inner(),
+            })
+        });
+
+        CostAccessor {
+            inner,
+            path_label: self.path_label.clone(),
+        }
+    }
+}
+
+/// The cost class of requests.
+///
+/// Most providers charge requests by classes, for example, `PUT`, `COPY`,
+/// `POST` and `LIST` requests are charged more than `GET` and `HEAD`
+/// requests on s3.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CostClass {
+    /// Requests that read data, like `GET` object.
+    Read,
+    /// Requests that read metadata, like `HEAD` object.
+    Metadata,
+    /// Requests that write data, like `PUT` object, multipart upload and copy.
+    Write,
+    /// Requests that list objects or uploads.
+    List,
+    /// Requests that delete objects or abort uploads.
+    Delete,
+    /// Requests that can't be classified.
+    Other,
+}
+
+impl CostClass {
+    /// Classify the request by its method, the operation of the request is only used
+    /// to tell apart requests that share the same method.
+    ///
+    /// For example, `GET` requests sent by `write` (like reading parts to merge) are
+    /// billed as reads, while `GET` requests listing objects are billed as lists.
+    fn classify(req: &Request<Buffer>) -> Self {
+        let op = req.extensions().get::<Operation>().copied();
+        match *req.method() {
+            Method::GET => match op {
+                Some(Operation::List | Operation::ListUploads) => CostClass::List,
+                Some(Operation::Stat) => CostClass::Metadata,
+                _ => CostClass::Read,
+            },
+            Method::HEAD => CostClass::Metadata,
+            Method::DELETE => CostClass::Delete,
+            // Some services delete, stat or list in batch via `POST`.
+            Method::PUT | Method::POST | Method::PATCH => match op {
+                Some(Operation::Delete | Operation::AbortUpload) => CostClass::Delete,
+                Some(Operation::Stat) if req.method() == Method::POST => CostClass::Metadata,
+                Some(Operation::List) if req.method() == Method::POST => CostClass::List,
+                _ => CostClass::Write,
+            },
+            _ => match op {
+                Some(Operation::Read) => CostClass::Read,
+                Some(Operation::Stat) => CostClass::Metadata,
+                Some(Operation::List | Operation::ListUploads) => CostClass::List,
+                Some(Operation::Delete | Operation::AbortUpload) => CostClass::Delete,
+                Some(
+                    Operation::Write
+                    | Operation::Copy
+                    | Operation::Rename
+                    | Operation::CreateDir
+                    | Operation::Concat
+                    | Operation::UpdateMetadata,
+                ) => CostClass::Write,
+                _ => CostClass::Other,
+            },
+        }
+    }
+}
+
+/// PriceTable is the price per request of every [`CostClass`].
+///
+/// The built-in tables are the list prices of the standard tier at the time
+/// of writing, please build your own table to match your bills.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PriceTable {
+    prices: BTreeMap<CostClass, f64>,
+}
+
+impl PriceTable {
+    /// Create an empty price table, all requests are free.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Price table of s3 standard storage in USD.
+    pub fn s3_standard() -> Self {
+        Self::new()
+            .with_price(CostClass::Write, 0.005 / 1000.0)
+            .with_price(CostClass::List, 0.005 / 1000.0)
+            .with_price(CostClass::Read, 0.0004 / 1000.0)
+            .with_price(CostClass::Metadata, 0.0004 / 1000.0)
+            .with_price(CostClass::Other, 0.0004 / 1000.0)
+    }
+
+    /// Price table of gcs standard storage in USD.
+    pub fn gcs_standard() -> Self {
+        Self::new()
+            .with_price(CostClass::Write, 0.005 / 1000.0)
+            .with_price(CostClass::List, 0.005 / 1000.0)
+            .with_price(CostClass::Read, 0.0004 / 1000.0)
+            .with_price(CostClass::Metadata, 0.0004 / 1000.0)
+            .with_price(CostClass::Other, 0.0004 / 1000.0)
+    }
+
+    /// Price table of azblob hot tier in USD.
+    pub fn azblob_hot() -> Self {
+        Self::new()
+            .with_price(CostClass::Write, 0.055 / 10000.0)
+            .with_price(CostClass::List, 0.055 / 10000.0)
+            .with_price(CostClass::Read, 0.0044 / 10000.0)
+            .with_price(CostClass::Metadata, 0.0044 / 10000.0)
+            .with_price(CostClass::Other, 0.0044 / 10000.0)
+    }
+
+    /// Set the price per request of the given class.
+    pub fn with_price(mut self, class: CostClass, price: f64) -> Self {
+        self.prices.insert(class, price);
+        self
+    }
+
+    /// Get the price per request of the given class.
+    pub fn price(&self, class: CostClass) -> f64 {
+        self.prices.get(&class).copied().unwrap_or_default()
+    }
+}
+
+/// The estimated cost of a request, attached to the response extensions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RequestCost {
+    /// The class of the request.
+    pub class: CostClass,
+    /// The estimated cost of the request.
+    pub cost: f64,
+}
+
+/// The accumulated requests and cost.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CostUsage {
+    /// The number of requests.
+    pub requests: u64,
+    /// The estimated cost of requests.
+    pub cost: f64,
+}
+
+impl CostUsage {
+    fn add(&mut self, cost: f64) {
+        self.requests += 1;
+        self.cost += cost;
+    }
+}
+
+/// The snapshot of cost accumulated by [`CostLayer`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CostSnapshot {
+    /// The cost of all requests.
+    pub total: CostUsage,
+    /// The cost per [`CostClass`].
+    pub classes: BTreeMap<CostClass, CostUsage>,
+    /// The cost per operation of calls like `write`.
+    pub operations: BTreeMap<&'static str, CostUsage>,
+    /// The cost per prefix mapped by [`CostLayer::with_path_label`].
+    pub prefixes: BTreeMap<String, CostUsage>,
+}
+
+#[derive(Debug)]
+struct CostCore {
+    prices: PriceTable,
+    snapshot: Arc<Mutex<CostSnapshot>>,
+}
+
+impl CostCore {
+    fn record(&self, req: &Request<Buffer>, scope: Option<&CostScope>) -> RequestCost {
+        let class = CostClass::classify(req);
+        let cost = self.prices.price(class);
+
+        let operation = scope
+            .map(|v| v.operation)
+            .or_else(|| req.extensions().get::<Operation>().copied())
+            .map(Operation::into_static)
+            .unwrap_or("unknown");
+
+        let mut snapshot = self.snapshot.lock().expect("lock must succeed");
+        snapshot.total.add(cost);
+        snapshot.classes.entry(class).or_default().add(cost);
+        snapshot.operations.entry(operation).or_default().add(cost);
+        if let Some(prefix) = scope.and_then(|v| v.prefix.as_ref()) {
+            snapshot
+                .prefixes
+                .entry(prefix.to_string())
+                .or_default()
+                .add(cost);
+        }
+
+        RequestCost { class, cost }
+    }
+}
+
+/// CostScope is the operator call that requests are attributed to.
+#[derive(Debug)]
+struct CostScope {
+    operation: Operation,
+    prefix: Option<Arc<str>>,
+}
+
+thread_local! {
+    static CURRENT_SCOPE: RefCell<Option<Arc<CostScope>>> = const { RefCell::new(None) };
+}
+
+fn current_scope() -> Option<Arc<CostScope>> {
+    CURRENT_SCOPE.with(|v| v.borrow().clone())
+}
+
+/// Poll the future with the scope entered, so that requests sent while
+/// polling will be attributed to the scope.
+async fn in_scope<F: Future>(scope: &Arc<CostScope>, fut: F) -> F::Output {
+    let mut fut = pin!(fut);
+    poll_fn(|cx| {
+        let prev = CURRENT_SCOPE.with(|v| v.replace(Some(scope.clone())));
+        let poll = fut.as_mut().poll(cx);
+        CURRENT_SCOPE.with(|v| *v.borrow_mut() = prev);
+        poll
+    })
+    .await
+}
+
+pub struct CostHttpFetcher {
+    inner: HttpFetcher,
+    core: Arc<CostCore>,
+}
+
+impl HttpFetch for CostHttpFetcher {
+    async fn fetch(&self, req: Request<Buffer>) -> Result<Response<HttpBody>> {
+        let scope = current_scope();
+        let cost = self.core.record(&req, scope.as_deref());
+
+        let mut resp = self.inner.fetch(req).await?;
+        resp.extensions_mut().insert(cost);
+        Ok(resp)
+    }
+}
+
+/// CostExecutor spawns tasks within the scope of the caller.
+struct CostExecutor {
+    inner: Arc<dyn Execute>,
+}
+
+impl Execute for CostExecutor {
+    fn execute(&self, f: BoxedStaticFuture<()>) {
+        match current_scope() {
+            Some(scope) => self
+                .inner
+                .execute(Box::pin(async move { in_scope(&scope, f).await })),
+            None => self.inner.execute(f),
+        }
+    }
+
+    fn timeout(&self) -> Option<BoxedStaticFuture<()>> {
+        self.inner.timeout()
+    }
+}
+
+#[derive(Debug)]
+pub struct CostAccessor<A: Access> {
+    inner: A,
+    path_label: Option<MetricPathLabel>,
+}
+
+impl<A: Access> CostAccessor<A> {
+    fn scope(&self, operation: Operation, path: Option<&str>) -> Arc<CostScope> {
+        let prefix = match (&self.path_label, path) {
+            (Some(path_label), Some(path)) => Some(path_label.label(path)),
+            _ => None,
+        };
+        Arc::new(CostScope { operation, prefix })
+    }
+}
+
+impl<A: Access> LayeredAccess for CostAccessor<A> {
+    type Inner = A;
+    type Reader = CostWrapper<A::Reader>;
+    type Writer = CostWrapper<A::Writer>;
+    type Lister = CostWrapper<A::Lister>;
+    type Deleter = CostWrapper<A::Deleter>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let scope = self.scope(Operation::CreateDir, Some(path));
+        in_scope(&scope, self.inner.create_dir(path, args)).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let scope = self.scope(Operation::Read, Some(path));
+        let (rp, r) = in_scope(&scope, self.inner.read(path, args)).await?;
+        Ok((rp, CostWrapper::new(r, scope)))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let scope = self.scope(Operation::Write, Some(path));
+        let (rp, w) = in_scope(&scope, self.inner.write(path, args)).await?;
+        Ok((rp, CostWrapper::new(w, scope)))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let scope = self.scope(Operation::Copy, Some(to));
+        in_scope(&scope, self.inner.copy(from, to, args)).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let scope = self.scope(Operation::Rename, Some(to));
+        in_scope(&scope, self.inner.rename(from, to, args)).await
+    }
+
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        let scope = self.scope(Operation::Concat, Some(path));
+        in_scope(&scope, self.inner.concat(path, args)).await
+    }
+
+    async fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> Result<RpUpdateMetadata> {
+        let scope = self.scope(Operation::UpdateMetadata, Some(path));
+        in_scope(&scope, self.inner.update_metadata(path, args)).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let scope = self.scope(Operation::Stat, Some(path));
+        in_scope(&scope, self.inner.stat(path, args)).await
+    }
+
+    async fn stat_many(&self, args: Vec<(String, OpStat)>) -> Result<RpStatMany> {
+        let scope = self.scope(Operation::Stat, None);
+        in_scope(&scope, self.inner.stat_many(args)).await
+    }
+
+    async fn list_uploads(&self, path: &str, args: OpListUploads) -> Result<RpListUploads> {
+        let scope = self.scope(Operation::ListUploads, Some(path));
+        in_scope(&scope, self.inner.list_uploads(path, args)).await
+    }
+
+    async fn abort_upload(&self, path: &str, args: OpAbortUpload) -> Result<RpAbortUpload> {
+        let scope = self.scope(Operation::AbortUpload, Some(path));
+        in_scope(&scope, self.inner.abort_upload(path, args)).await
+    }
+
+    async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        let scope = self.scope(Operation::Delete, None);
+        let (rp, d) = in_scope(&scope, self.inner.delete()).await?;
+        Ok((rp, CostWrapper::new(d, scope)))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let scope = self.scope(Operation::List, Some(path));
+        let (rp, l) = in_scope(&scope, self.inner.list(path, args)).await?;
+        Ok((rp, CostWrapper::new(l, scope)))
+    }
+}
+
+/// CostWrapper polls the inner reader, writer, lister and deleter within
+/// the scope of their call.
+pub struct CostWrapper<R> {
+    inner: R,
+    scope: Arc<CostScope>,
+}
+
+impl<R> CostWrapper<R> {
+    fn new(inner: R, scope: Arc<CostScope>) -> Self {
+        Self { inner, scope }
+    }
+}
+
+impl<R: oio::Read> oio::Read for CostWrapper<R> {
+    async fn read(&mut self) -> Result<Buffer> {
+        in_scope(&self.scope, self.inner.read()).await
+    }
+}
+
+impl<R: oio::Write> oio::Write for CostWrapper<R> {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        in_scope(&self.scope, self.inner.write(bs)).await
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        in_scope(&self.scope, self.inner.close()).await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        in_scope(&self.scope, self.inner.abort()).await
+    }
+}
+
+impl<R: oio::List> oio::List for CostWrapper<R> {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        in_scope(&self.scope, self.inner.next()).await
+    }
+}
+
+impl<R: oio::Delete> oio::Delete for CostWrapper<R> {
+    fn delete(&mut self, path: &str, args: OpDelete) -> Result<()> {
+        self.inner.delete(path, args)
+    }
+
+    async fn flush(&mut self) -> Result<usize> {
+        in_scope(&self.scope, self.inner.flush()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+
+    use super::*;
+    use crate::layers::HttpClientLayer;
+    use crate::services::Memory;
+
+    struct MockFetch;
+
+    impl HttpFetch for MockFetch {
+        async fn fetch(&self, _: Request<Buffer>) -> Result<Response<HttpBody>> {
+            Ok(Response::builder()
+                .status(StatusCode::OK)
+                .body(HttpBody::new(futures::stream::empty(), Some(0)))
+                .unwrap())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cost() -> Result<()> {
+        let prices = PriceTable::new()
+            .with_price(CostClass::Write, 5.0)
+            .with_price(CostClass::Read, 1.0);
+        let cost = CostLayer::new(prices)
+            .with_path_label(MetricPathLabel::segments(1).with_max_cardinality(10));
+        let op = Operator::new(Memory::default())?
+            .layer(HttpClientLayer::new(HttpClient::with(MockFetch)))
+            .layer(cost.clone())
+            .finish();
+        let client = op.inner().info().http_client();
+
+        // Requests outside of calls are accounted by their operation.
+        let mut req = Request::put("http://example.com/a")
+            .body(Buffer::new())
+            .unwrap();
+        req.extensions_mut().insert(Operation::Write);
+        let resp = client.fetch(req).await?;
+        assert_eq!(
+            resp.extensions().get::<RequestCost>(),
+            Some(&RequestCost {
+                class: CostClass::Write,
+                cost: 5.0
+            })
+        );
+
+        // Requests inside of calls are accounted by the call, including
+        // requests sent by spawned tasks.
+        let scope = Arc::new(CostScope {
+            operation: Operation::Read,
+            prefix: Some("dir/".into()),
+        });
+        let executor = op.executor();
+        in_scope(&scope, async {
+            let req = Request::get("http://example.com/dir/a")
+                .body(Buffer::new())
+                .unwrap();
+            let task = executor.execute(async move { client.fetch(req).await.map(|_| ()) });
+            task.await
+        })
+        .await?;
+
+        let snapshot = cost.snapshot();
+        assert_eq!(
+            snapshot.total,
+            CostUsage {
+                requests: 2,
+                cost: 6.0
+            }
+        );
+        assert_eq!(snapshot.operations["write"].cost, 5.0);
+        assert_eq!(snapshot.operations["read"].cost, 1.0);
+        assert_eq!(snapshot.classes[&CostClass::Read].requests, 1);
+        assert_eq!(snapshot.prefixes["dir/"].requests, 1);
+
+        cost.reset();
+        assert_eq!(cost.snapshot(), CostSnapshot::default());
+        Ok(())
+    }
+
+    #[test]
+    fn test_classify() {
+        let cases = [
+            (Method::GET, Some(Operation::Read), CostClass::Read),
+            // Reading parts back inside of writes is billed as reads.
+            (Method::GET, Some(Operation::Write), CostClass::Read),
+            (Method::GET, Some(Operation::List), CostClass::List),
+            (Method::GET, Some(Operation::Stat), CostClass::Metadata),
+            (Method::HEAD, Some(Operation::Write), CostClass::Metadata),
+            (Method::PUT, Some(Operation::Write), CostClass::Write),
+            (Method::PUT, None, CostClass::Write),
+            (Method::POST, Some(Operation::Delete), CostClass::Delete),
+            (Method::POST, Some(Operation::Stat), CostClass::Metadata),
+            (Method::DELETE, Some(Operation::Write), CostClass::Delete),
+            (
+                Method::from_bytes(b"PROPFIND").unwrap(),
+                Some(Operation::List),
+                CostClass::List,
+            ),
+            (Method::OPTIONS, None, CostClass::Other),
+        ];
+        for (method, op, expected) in cases {
+            let mut req = Request::builder()
+                .method(method.clone())
+                .uri("http://example.com/a")
+                .body(Buffer::new())
+                .unwrap();
+            if let Some(op) = op {
+                req.extensions_mut().insert(op);
+            }
+            assert_eq!(CostClass::classify(&req), expected, "{method} {op:?}");
+        }
+    }
+
+    #[cfg(feature = "services-s3")]
+    mod s3 {
+        use bytes::Bytes;
+
+        use super::*;
+        use crate::services::S3;
+
+        /// MockS3 responds to the requests of write, multipart upload and list, and
+        /// records them as `METHOD path?query`.
+        #[derive(Clone, Default)]
+        struct MockS3 {
+            requests: Arc<Mutex<Vec<String>>>,
+        }
+
+        impl HttpFetch for MockS3 {
+            async fn fetch(&self, req: Request<Buffer>) -> Result<Response<HttpBody>> {
+                let query = req.uri().query().unwrap_or_default().to_string();
+                self.requests.lock().unwrap().push(format!(
+                    "{} {}",
+                    req.method(),
+                    req.uri().path()
+                ));
+
+                let body = match (req.method().clone(), query.as_str()) {
+                    (Method::POST, "uploads") => {
+                        "<InitiateMultipartUploadResult><UploadId>u</UploadId></InitiateMultipartUploadResult>"
+                    }
+                    (Method::POST, q) if q.contains("uploadId") => {
+                        "<CompleteMultipartUploadResult><ETag>\"e\"</ETag></CompleteMultipartUploadResult>"
+                    }
+                    (Method::GET, q) if !q.contains("continuation-token") => {
+                        "<ListBucketResult><IsTruncated>true</IsTruncated><NextContinuationToken>next</NextContinuationToken><Contents><Key>dir/a</Key><Size>1</Size><LastModified>2024-01-01T00:00:00.000Z</LastModified></Contents></ListBucketResult>"
+                    }
+                    (Method::GET, _) => {
+                        "<ListBucketResult><IsTruncated>false</IsTruncated><Contents><Key>dir/b</Key><Size>1</Size><LastModified>2024-01-01T00:00:00.000Z</LastModified></Contents></ListBucketResult>"
+                    }
+                    _ => "",
+                };
+                let body = Buffer::from(Bytes::from_static(body.as_bytes()));
+                let size = body.len() as u64;
+                Ok(Response::builder()
+                    .status(StatusCode::OK)
+                    .header("etag", "\"e\"")
+                    .body(HttpBody::new(futures::stream::iter([Ok(body)]), Some(size)))
+                    .unwrap())
+            }
+        }
+
+        #[tokio::test]
+        async fn test_cost_s3() -> Result<()> {
+            let prices = PriceTable::new()
+                .with_price(CostClass::Write, 5.0)
+                .with_price(CostClass::List, 5.0)
+                .with_price(CostClass::Read, 1.0);
+            let cost = CostLayer::new(prices)
+                .with_path_label(MetricPathLabel::segments(1).with_max_cardinality(10));
+            let mock = MockS3::default();
+            let builder = S3::default()
+                .bucket("test")
+                .endpoint("http://127.0.0.1:9000")
+                .region("us-east-1")
+                .allow_anonymous()
+                .disable_config_load()
+                .disable_ec2_metadata();
+            let op = Operator::new(builder)?
+                .layer(HttpClientLayer::new(HttpClient::with(mock.clone())))
+                .layer(cost.clone())
+                .finish();
+
+            op.write("dir/small", "hello").await?;
+            let snapshot = cost.snapshot();
+            assert_eq!(snapshot.total.requests, 1);
+            assert_eq!(snapshot.operations["write"].requests, 1);
+            assert_eq!(snapshot.prefixes["dir/"].requests, 1);
+
+            // Every part of a multipart upload is billed as a write request.
+            cost.reset();
+            let chunk = 5 * 1024 * 1024;
+            let mut w = op.writer_with("dir/large").chunk(chunk).await?;
+            w.write(vec![0; 2 * chunk + 1]).await?;
+            w.close().await?;
+            let snapshot = cost.snapshot();
+            // Initiate, 3 parts and complete.
+            assert_eq!(
+                snapshot.classes[&CostClass::Write],
+                CostUsage {
+                    requests: 5,
+                    cost: 25.0
+                }
+            );
+            assert_eq!(snapshot.operations["write"].requests, 5);
+
+            // Every page of a listing is billed as a list request.
+            cost.reset();
+            let entries = op.list("dir/").await?;
+            assert_eq!(entries.len(), 2);
+            let snapshot = cost.snapshot();
+            assert_eq!(
+                snapshot.classes[&CostClass::List],
+                CostUsage {
+                    requests: 2,
+                    cost: 10.0
+                }
+            );
+            assert_eq!(snapshot.operations["list"].requests, 2);
+            assert_eq!(snapshot.prefixes["dir/"].requests, 2);
+            assert_eq!(snapshot.total.requests, 2);
+
+            assert_eq!(mock.requests.lock().unwrap().len(), 8);
+            Ok(())
+        }
+    }
+}
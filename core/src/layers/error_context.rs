@@ -145,6 +145,65 @@ impl<A: Access> LayeredAccess for ErrorContextAccessor<A> {
         })
     }
 
+    async fn stat_many(&self, args: Vec<(String, OpStat)>) -> Result<RpStatMany> {
+        let count = args.len();
+        self.inner.stat_many(args).await.map_err(|err| {
+            err.with_operation(Operation::Stat)
+                .with_context("service", self.info.scheme())
+                .with_context("count", count)
+        })
+    }
+
+    async fn list_uploads(&self, path: &str, args: OpListUploads) -> Result<RpListUploads> {
+        self.inner.list_uploads(path, args).await.map_err(|err| {
+            err.with_operation(Operation::ListUploads)
+                .with_context("service", self.info.scheme())
+                .with_context("path", path)
+        })
+    }
+
+    async fn abort_upload(&self, path: &str, args: OpAbortUpload) -> Result<RpAbortUpload> {
+        let upload_id = args.upload_id().to_string();
+        self.inner.abort_upload(path, args).await.map_err(|err| {
+            err.with_operation(Operation::AbortUpload)
+                .with_context("service", self.info.scheme())
+                .with_context("path", path)
+                .with_context("upload_id", upload_id)
+        })
+    }
+
+    async fn lock(&self, path: &str, args: OpLock) -> Result<RpLock> {
+        let action = args.action();
+        self.inner.lock(path, args).await.map_err(|err| {
+            err.with_operation(Operation::Lock)
+                .with_context("service", self.info.scheme())
+                .with_context("path", path)
+                .with_context("action", format!("{action:?}"))
+        })
+    }
+
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        let sources = args.sources().len();
+        self.inner.concat(path, args).await.map_err(|err| {
+            err.with_operation(Operation::Concat)
+                .with_context("service", self.info.scheme())
+                .with_context("path", path)
+                .with_context("sources", sources.to_string())
+        })
+    }
+
+    async fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> Result<RpUpdateMetadata> {
+        self.inner.update_metadata(path, args).await.map_err(|err| {
+            err.with_operation(Operation::UpdateMetadata)
+                .with_context("service", self.info.scheme())
+                .with_context("path", path)
+        })
+    }
+
     async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
         self.inner
             .delete()
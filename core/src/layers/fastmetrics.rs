@@ -128,6 +128,7 @@ use crate::*;
 #[derive(Clone, Debug)]
 pub struct FastmetricsLayer {
     interceptor: FastmetricsInterceptor,
+    path_label: Option<observe::MetricPathLabel>,
 }
 
 impl FastmetricsLayer {
@@ -141,7 +142,11 @@ impl<A: Access> Layer<A> for FastmetricsLayer {
     type LayeredAccess = observe::MetricsAccessor<A, FastmetricsInterceptor>;
 
     fn layer(&self, inner: A) -> Self::LayeredAccess {
-        observe::MetricsLayer::new(self.interceptor.clone()).layer(inner)
+        let mut layer = observe::MetricsLayer::new(self.interceptor.clone());
+        if let Some(path_label) = self.path_label.clone() {
+            layer = layer.with_path_label(path_label);
+        }
+        layer.layer(inner)
     }
 }
 
@@ -153,6 +158,7 @@ pub struct FastmetricsLayerBuilder {
     entries_rate_buckets: Vec<f64>,
     duration_seconds_buckets: Vec<f64>,
     ttfb_buckets: Vec<f64>,
+    path_label: Option<observe::MetricPathLabel>,
     disable_label_root: bool,
 }
 
@@ -165,6 +171,7 @@ impl Default for FastmetricsLayerBuilder {
             entries_rate_buckets: observe::DEFAULT_ENTRIES_RATE_BUCKETS.to_vec(),
             duration_seconds_buckets: observe::DEFAULT_DURATION_SECONDS_BUCKETS.to_vec(),
             ttfb_buckets: observe::DEFAULT_TTFB_BUCKETS.to_vec(),
+            path_label: None,
             disable_label_root: false,
         }
     }
@@ -219,6 +226,12 @@ impl FastmetricsLayerBuilder {
         self
     }
 
+    /// Label operation metrics by the path with given [`observe::MetricPathLabel`].
+    pub fn path_label(mut self, path_label: observe::MetricPathLabel) -> Self {
+        self.path_label = Some(path_label);
+        self
+    }
+
     /// The 'root' label might have risks of being high cardinality; users can choose to disable it
     /// when they found it's not useful for their metrics.
     pub fn disable_label_root(mut self, disable: bool) -> Self {
@@ -269,6 +282,9 @@ impl FastmetricsLayerBuilder {
         let operation_ttfb_seconds = Family::new(HistogramFactory {
             buckets: self.ttfb_buckets.clone(),
         });
+        let operation_list_page_seconds = Family::new(HistogramFactory {
+            buckets: self.ttfb_buckets.clone(),
+        });
 
         let http_executing = Family::default();
         let http_request_bytes = Family::new(HistogramFactory {
@@ -291,6 +307,9 @@ impl FastmetricsLayerBuilder {
         });
         let http_connection_errors_total = Family::default();
         let http_status_errors_total = Family::default();
+        let http_request_cost_total = Family::default();
+        let read_coalesce_saved_requests_total = Family::default();
+        let read_coalesce_saved_bytes_total = Family::default();
 
         let interceptor = FastmetricsInterceptor {
             operation_bytes,
@@ -301,6 +320,7 @@ impl FastmetricsLayerBuilder {
             operation_errors_total,
             operation_executing,
             operation_ttfb_seconds,
+            operation_list_page_seconds,
 
             http_executing,
             http_request_bytes,
@@ -311,6 +331,9 @@ impl FastmetricsLayerBuilder {
             http_response_duration_seconds,
             http_connection_errors_total,
             http_status_errors_total,
+            http_request_cost_total,
+            read_coalesce_saved_requests_total,
+            read_coalesce_saved_bytes_total,
 
             disable_label_root: self.disable_label_root,
         };
@@ -318,7 +341,10 @@ impl FastmetricsLayerBuilder {
             .register(registry)
             .map_err(|err| Error::new(ErrorKind::Unexpected, err.to_string()).set_source(err))?;
 
-        Ok(FastmetricsLayer { interceptor })
+        Ok(FastmetricsLayer {
+            interceptor,
+            path_label: self.path_label,
+        })
     }
 
     /// Register the metrics into the global registry and return a [`FastmetricsLayer`].
@@ -366,6 +392,7 @@ pub struct FastmetricsInterceptor {
     operation_errors_total: Family<OperationLabels, Counter>,
     operation_executing: Family<OperationLabels, Gauge>,
     operation_ttfb_seconds: Family<OperationLabels, Histogram, HistogramFactory>,
+    operation_list_page_seconds: Family<OperationLabels, Histogram, HistogramFactory>,
 
     http_executing: Family<OperationLabels, Gauge>,
     http_request_bytes: Family<OperationLabels, Histogram, HistogramFactory>,
@@ -376,6 +403,9 @@ pub struct FastmetricsInterceptor {
     http_response_duration_seconds: Family<OperationLabels, Histogram, HistogramFactory>,
     http_connection_errors_total: Family<OperationLabels, Counter>,
     http_status_errors_total: Family<OperationLabels, Counter>,
+    http_request_cost_total: Family<OperationLabels, Counter<f64>>,
+    read_coalesce_saved_requests_total: Family<OperationLabels, Counter>,
+    read_coalesce_saved_bytes_total: Family<OperationLabels, Counter>,
 
     disable_label_root: bool,
 }
@@ -403,6 +433,7 @@ impl Register for FastmetricsInterceptor {
             operation_errors_total => observe::MetricValue::OperationErrorsTotal,
             operation_executing => observe::MetricValue::OperationExecuting(0),
             operation_ttfb_seconds => observe::MetricValue::OperationTtfbSeconds(Duration::default()),
+            operation_list_page_seconds => observe::MetricValue::OperationListPageSeconds(Duration::default()),
 
             // HTTP metrics
             http_executing => observe::MetricValue::HttpExecuting(0),
@@ -414,6 +445,9 @@ impl Register for FastmetricsInterceptor {
             http_response_duration_seconds => observe::MetricValue::HttpResponseDurationSeconds(Duration::default()),
             http_connection_errors_total => observe::MetricValue::HttpConnectionErrorsTotal,
             http_status_errors_total => observe::MetricValue::HttpStatusErrorsTotal,
+            http_request_cost_total => observe::MetricValue::HttpRequestCost(0.0),
+            read_coalesce_saved_requests_total => observe::MetricValue::ReadCoalesceSavedRequests(0),
+            read_coalesce_saved_bytes_total => observe::MetricValue::ReadCoalesceSavedBytes(0),
         }
 
         Ok(())
@@ -459,6 +493,10 @@ impl observe::MetricsIntercept for FastmetricsInterceptor {
                 self.operation_ttfb_seconds
                     .with_or_new(&labels, |hist| hist.observe(v.as_secs_f64()));
             }
+            observe::MetricValue::OperationListPageSeconds(v) => {
+                self.operation_list_page_seconds
+                    .with_or_new(&labels, |hist| hist.observe(v.as_secs_f64()));
+            }
 
             observe::MetricValue::HttpExecuting(v) => {
                 self.http_executing
@@ -496,6 +534,18 @@ impl observe::MetricsIntercept for FastmetricsInterceptor {
                 self.http_status_errors_total
                     .with_or_new(&labels, |counter| counter.inc());
             }
+            observe::MetricValue::HttpRequestCost(v) => {
+                self.http_request_cost_total
+                    .with_or_new(&labels, |counter| counter.inc_by(v));
+            }
+            observe::MetricValue::ReadCoalesceSavedRequests(v) => {
+                self.read_coalesce_saved_requests_total
+                    .with_or_new(&labels, |counter| counter.inc_by(v));
+            }
+            observe::MetricValue::ReadCoalesceSavedBytes(v) => {
+                self.read_coalesce_saved_bytes_total
+                    .with_or_new(&labels, |counter| counter.inc_by(v));
+            }
         };
     }
 }
@@ -514,6 +564,9 @@ impl EncodeLabelSet for OperationLabels {
             encoder.encode(&(observe::LABEL_ROOT, self.labels.root.as_ref()))?;
         }
         encoder.encode(&(observe::LABEL_OPERATION, self.labels.operation))?;
+        if let Some(path) = &self.labels.path {
+            encoder.encode(&(observe::LABEL_PATH, path.as_ref()))?;
+        }
         if let Some(error) = &self.labels.error {
             encoder.encode(&(observe::LABEL_ERROR, error.into_static()))?;
         }
@@ -455,6 +455,203 @@ impl<A: Access, I: LoggingInterceptor> LayeredAccess for LoggingAccessor<A, I> {
             })
     }
 
+    async fn stat_many(&self, args: Vec<(String, OpStat)>) -> Result<RpStatMany> {
+        let paths = args.len().to_string();
+        self.logger.log(
+            &self.info,
+            Operation::Stat,
+            &[("paths", &paths)],
+            "started",
+            None,
+        );
+
+        self.inner
+            .stat_many(args)
+            .await
+            .inspect(|_| {
+                self.logger.log(
+                    &self.info,
+                    Operation::Stat,
+                    &[("paths", &paths)],
+                    "finished",
+                    None,
+                );
+            })
+            .inspect_err(|err| {
+                self.logger.log(
+                    &self.info,
+                    Operation::Stat,
+                    &[("paths", &paths)],
+                    "failed",
+                    Some(err),
+                );
+            })
+    }
+
+    async fn list_uploads(&self, path: &str, args: OpListUploads) -> Result<RpListUploads> {
+        self.logger.log(
+            &self.info,
+            Operation::ListUploads,
+            &[("path", path)],
+            "started",
+            None,
+        );
+
+        self.inner
+            .list_uploads(path, args)
+            .await
+            .inspect(|_| {
+                self.logger.log(
+                    &self.info,
+                    Operation::ListUploads,
+                    &[("path", path)],
+                    "finished",
+                    None,
+                );
+            })
+            .inspect_err(|err| {
+                self.logger.log(
+                    &self.info,
+                    Operation::ListUploads,
+                    &[("path", path)],
+                    "failed",
+                    Some(err),
+                );
+            })
+    }
+
+    async fn abort_upload(&self, path: &str, args: OpAbortUpload) -> Result<RpAbortUpload> {
+        self.logger.log(
+            &self.info,
+            Operation::AbortUpload,
+            &[("path", path)],
+            "started",
+            None,
+        );
+
+        self.inner
+            .abort_upload(path, args)
+            .await
+            .inspect(|_| {
+                self.logger.log(
+                    &self.info,
+                    Operation::AbortUpload,
+                    &[("path", path)],
+                    "finished",
+                    None,
+                );
+            })
+            .inspect_err(|err| {
+                self.logger.log(
+                    &self.info,
+                    Operation::AbortUpload,
+                    &[("path", path)],
+                    "failed",
+                    Some(err),
+                );
+            })
+    }
+
+    async fn lock(&self, path: &str, args: OpLock) -> Result<RpLock> {
+        self.logger.log(
+            &self.info,
+            Operation::Lock,
+            &[("path", path)],
+            "started",
+            None,
+        );
+
+        self.inner
+            .lock(path, args)
+            .await
+            .inspect(|_| {
+                self.logger.log(
+                    &self.info,
+                    Operation::Lock,
+                    &[("path", path)],
+                    "finished",
+                    None,
+                );
+            })
+            .inspect_err(|err| {
+                self.logger.log(
+                    &self.info,
+                    Operation::Lock,
+                    &[("path", path)],
+                    "failed",
+                    Some(err),
+                );
+            })
+    }
+
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        self.logger.log(
+            &self.info,
+            Operation::Concat,
+            &[("path", path)],
+            "started",
+            None,
+        );
+
+        self.inner
+            .concat(path, args)
+            .await
+            .inspect(|_| {
+                self.logger.log(
+                    &self.info,
+                    Operation::Concat,
+                    &[("path", path)],
+                    "finished",
+                    None,
+                );
+            })
+            .inspect_err(|err| {
+                self.logger.log(
+                    &self.info,
+                    Operation::Concat,
+                    &[("path", path)],
+                    "failed",
+                    Some(err),
+                );
+            })
+    }
+
+    async fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> Result<RpUpdateMetadata> {
+        self.logger.log(
+            &self.info,
+            Operation::UpdateMetadata,
+            &[("path", path)],
+            "started",
+            None,
+        );
+
+        self.inner
+            .update_metadata(path, args)
+            .await
+            .inspect(|_| {
+                self.logger.log(
+                    &self.info,
+                    Operation::UpdateMetadata,
+                    &[("path", path)],
+                    "finished",
+                    None,
+                );
+            })
+            .inspect_err(|err| {
+                self.logger.log(
+                    &self.info,
+                    Operation::UpdateMetadata,
+                    &[("path", path)],
+                    "failed",
+                    Some(err),
+                );
+            })
+    }
+
     async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
         self.logger
             .log(&self.info, Operation::Delete, &[], "started", None);
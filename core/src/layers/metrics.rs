@@ -77,14 +77,28 @@ use crate::raw::*;
 /// let recorder = builder.build_recorder().expect("failed to build recorder");
 /// ```
 #[derive(Clone, Debug, Default)]
-pub struct MetricsLayer {}
+pub struct MetricsLayer {
+    path_label: Option<observe::MetricPathLabel>,
+}
+
+impl MetricsLayer {
+    /// Label operation metrics by the path with given [`observe::MetricPathLabel`].
+    pub fn path_label(mut self, path_label: observe::MetricPathLabel) -> Self {
+        self.path_label = Some(path_label);
+        self
+    }
+}
 
 impl<A: Access> Layer<A> for MetricsLayer {
     type LayeredAccess = observe::MetricsAccessor<A, MetricsInterceptor>;
 
     fn layer(&self, inner: A) -> Self::LayeredAccess {
         let interceptor = MetricsInterceptor {};
-        observe::MetricsLayer::new(interceptor).layer(inner)
+        let mut layer = observe::MetricsLayer::new(interceptor);
+        if let Some(path_label) = self.path_label.clone() {
+            layer = layer.with_path_label(path_label);
+        }
+        layer.layer(inner)
     }
 }
 
@@ -120,6 +134,9 @@ impl observe::MetricsIntercept for MetricsInterceptor {
             observe::MetricValue::OperationTtfbSeconds(v) => {
                 histogram!(value.name(), labels).record(v)
             }
+            observe::MetricValue::OperationListPageSeconds(v) => {
+                histogram!(value.name(), labels).record(v)
+            }
 
             observe::MetricValue::HttpExecuting(v) => {
                 gauge!(value.name(), labels).increment(v as f64)
@@ -148,6 +165,14 @@ impl observe::MetricsIntercept for MetricsInterceptor {
             observe::MetricValue::HttpStatusErrorsTotal => {
                 counter!(value.name(), labels).increment(1)
             }
+            // `metrics` counters only accept integers, record the cost by gauge instead.
+            observe::MetricValue::HttpRequestCost(v) => gauge!(value.name(), labels).increment(v),
+            observe::MetricValue::ReadCoalesceSavedRequests(v) => {
+                counter!(value.name(), labels).increment(v)
+            }
+            observe::MetricValue::ReadCoalesceSavedBytes(v) => {
+                counter!(value.name(), labels).increment(v)
+            }
         }
     }
 }
@@ -157,7 +182,7 @@ struct OperationLabels(observe::MetricLabels);
 
 impl OperationLabels {
     fn into_labels(self) -> Vec<Label> {
-        let mut labels = Vec::with_capacity(6);
+        let mut labels = Vec::with_capacity(7);
 
         labels.extend([
             Label::new(observe::LABEL_SCHEME, self.0.scheme),
@@ -166,6 +191,10 @@ impl OperationLabels {
             Label::new(observe::LABEL_OPERATION, self.0.operation),
         ]);
 
+        if let Some(path) = self.0.path {
+            labels.push(Label::new(observe::LABEL_PATH, path.to_string()));
+        }
+
         if let Some(error) = self.0.error {
             labels.push(Label::new(observe::LABEL_ERROR, error.into_static()));
         }
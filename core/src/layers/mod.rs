@@ -26,12 +26,23 @@ pub(crate) use error_context::ErrorContextLayer;
 mod complete;
 pub(crate) use complete::CompleteLayer;
 
+mod op_args;
+pub(crate) use op_args::*;
+
 mod concurrent_limit;
 pub use concurrent_limit::ConcurrentLimitLayer;
 
 mod immutable_index;
 pub use immutable_index::ImmutableIndexLayer;
 
+mod append;
+pub use append::AppendLayer;
+
+mod read_coalesce;
+pub use read_coalesce::ReadCoalesceLayer;
+pub use read_coalesce::ReadCoalesceSaved;
+pub use read_coalesce::ReadCoalesceSnapshot;
+
 mod logging;
 pub use logging::LoggingInterceptor;
 pub use logging::LoggingLayer;
@@ -39,11 +50,54 @@ pub use logging::LoggingLayer;
 mod timeout;
 pub use timeout::TimeoutLayer;
 
+mod prefix;
+pub use prefix::PrefixLayer;
+
+mod access_control;
+pub use access_control::AccessControlLayer;
+
+mod quota;
+pub use quota::MemoryQuotaStore;
+pub use quota::OperatorQuotaStore;
+pub use quota::QuotaLayer;
+pub use quota::QuotaLimit;
+pub use quota::QuotaStore;
+pub use quota::QuotaUsage;
+
+mod audit;
+pub use audit::AuditLayer;
+pub use audit::AuditRecord;
+pub use audit::AuditSink;
+pub use audit::OperatorAuditSink;
+
+mod cost;
+pub use cost::CostClass;
+pub use cost::CostLayer;
+pub use cost::CostSnapshot;
+pub use cost::CostUsage;
+pub use cost::PriceTable;
+pub use cost::RequestCost;
+
 #[cfg(feature = "layers-chaos")]
 mod chaos;
 #[cfg(feature = "layers-chaos")]
+pub use chaos::ChaosFault;
+#[cfg(feature = "layers-chaos")]
+pub use chaos::ChaosLatency;
+#[cfg(feature = "layers-chaos")]
 pub use chaos::ChaosLayer;
 
+#[cfg(feature = "layers-record")]
+mod record;
+#[cfg(feature = "layers-record")]
+pub use record::RecordHttpFetch;
+#[cfg(feature = "layers-record")]
+pub use record::RecordLayer;
+#[cfg(feature = "layers-record")]
+pub use record::ReplayHttpFetch;
+#[cfg(feature = "services-replay")]
+pub(crate) use record::*;
+
 #[cfg(feature = "layers-metrics")]
 mod metrics;
 #[cfg(feature = "layers-metrics")]
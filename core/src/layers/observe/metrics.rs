@@ -15,16 +15,21 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::future::Future;
+use std::pin::pin;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::task::ready;
 use std::task::Context;
 use std::task::Poll;
 use std::time::Duration;
 use std::time::Instant;
 
+use futures::future::poll_fn;
 use futures::Stream;
 use futures::StreamExt;
 use http::StatusCode;
@@ -145,9 +150,112 @@ pub static LABEL_OPERATION: &str = "operation";
 pub static LABEL_ERROR: &str = "error";
 /// The metric label for the http code.
 pub static LABEL_STATUS_CODE: &str = "status_code";
+/// The metric label for the path mapped by [`MetricPathLabel`].
+pub static LABEL_PATH: &str = "path";
+
+/// The path label for paths mapped after reaching the max cardinality.
+pub static PATH_LABEL_OVERFLOW: &str = "__overflow__";
+
+/// MetricPathLabel maps the paths of operations into the `path` label, so
+/// that metrics could be attributed to datasets or tenants.
+///
+/// Path labels could easily lead to high cardinality. The distinct labels
+/// are bounded by [`MetricPathLabel::with_max_cardinality`], and paths mapped
+/// after reaching the limit will be labeled as [`PATH_LABEL_OVERFLOW`].
+///
+/// # Examples
+///
+/// ```
+/// # use opendal::layers::observe::MetricPathLabel;
+/// // Label `tenant/dataset/file.parquet` as `tenant/dataset/`.
+/// let _ = MetricPathLabel::segments(2).with_max_cardinality(1000);
+///
+/// // Label by a custom function.
+/// let _ = MetricPathLabel::new(|path| {
+///     if path.ends_with(".parquet") {
+///         "parquet".to_string()
+///     } else {
+///         "other".to_string()
+///     }
+/// });
+/// ```
+#[derive(Clone)]
+pub struct MetricPathLabel {
+    mapper: Arc<dyn Fn(&str) -> String + Send + Sync>,
+    max_cardinality: usize,
+    labels: Arc<Mutex<HashSet<Arc<str>>>>,
+}
+
+impl Debug for MetricPathLabel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MetricPathLabel")
+            .field("max_cardinality", &self.max_cardinality)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MetricPathLabel {
+    /// Create a new `MetricPathLabel` that maps paths by the given function.
+    pub fn new(mapper: impl Fn(&str) -> String + Send + Sync + 'static) -> Self {
+        Self {
+            mapper: Arc::new(mapper),
+            max_cardinality: 100,
+            labels: Arc::default(),
+        }
+    }
+
+    /// Create a new `MetricPathLabel` that maps paths into their first `n` dir
+    /// segments.
+    ///
+    /// For example, `a/b/c/d.txt` will be labeled as `a/b/` with `n = 2`, and
+    /// `a/d.txt` will be labeled as `a/`. Paths under the root will be
+    /// labeled as `/`.
+    pub fn segments(n: usize) -> Self {
+        Self::new(move |path| {
+            let path = path.trim_start_matches('/');
+            let depth = path.matches('/').count().min(n);
+            if depth == 0 {
+                return "/".to_string();
+            }
+            let end = path
+                .match_indices('/')
+                .nth(depth - 1)
+                .map(|(idx, _)| idx + 1)
+                .unwrap_or(path.len());
+            path[..end].to_string()
+        })
+    }
+
+    /// Set the max number of distinct path labels.
+    ///
+    /// Default to 100.
+    pub fn with_max_cardinality(mut self, max_cardinality: usize) -> Self {
+        self.max_cardinality = max_cardinality;
+        self
+    }
+
+    /// Map the path into the label.
+    pub(crate) fn label(&self, path: &str) -> Arc<str> {
+        let label = (self.mapper)(path);
+
+        let mut labels = self.labels.lock().expect("lock must succeed");
+        if let Some(v) = labels.get(label.as_str()) {
+            return v.clone();
+        }
+        if labels.len() >= self.max_cardinality {
+            return PATH_LABEL_OVERFLOW.into();
+        }
+        let label: Arc<str> = label.into();
+        labels.insert(label.clone());
+        label
+    }
+}
 
 /// MetricLabels are the labels for the metrics.
+///
+/// Use [`MetricLabels::new`] to build labels, more labels may be added in the future.
 #[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub struct MetricLabels {
     /// The storage scheme identifier (e.g., "s3", "gcs", "azblob", "fs").
     /// Used to differentiate between different storage backends.
@@ -169,31 +277,51 @@ pub struct MetricLabels {
     /// Only populated for `HttpStatusErrorsTotal` metric.
     /// Used to track frequency of specific HTTP error status codes.
     pub status_code: Option<StatusCode>,
+    /// The label mapped from the path by [`MetricPathLabel`].
+    /// Only populated for operation metrics when [`MetricsLayer::with_path_label`]
+    /// has been set, and will be empty for operations without path like `delete`.
+    pub path: Option<Arc<str>>,
 }
 
 impl MetricLabels {
     /// Create a new set of MetricLabels.
-    fn new(info: Arc<AccessorInfo>, op: &'static str) -> Self {
+    pub fn new(
+        scheme: &'static str,
+        namespace: Arc<str>,
+        root: Arc<str>,
+        operation: &'static str,
+    ) -> Self {
         MetricLabels {
-            scheme: info.scheme(),
-            namespace: info.name(),
-            root: info.root(),
-            operation: op,
+            scheme,
+            namespace,
+            root,
+            operation,
             ..MetricLabels::default()
         }
     }
 
+    /// Create a new set of MetricLabels from the accessor info.
+    fn from_info(info: &AccessorInfo, op: &'static str) -> Self {
+        Self::new(info.scheme(), info.name(), info.root(), op)
+    }
+
     /// Add error to the metric labels.
-    fn with_error(mut self, err: ErrorKind) -> Self {
+    pub fn with_error(mut self, err: ErrorKind) -> Self {
         self.error = Some(err);
         self
     }
 
     /// Add status code to the metric labels.
-    fn with_status_code(mut self, code: StatusCode) -> Self {
+    pub fn with_status_code(mut self, code: StatusCode) -> Self {
         self.status_code = Some(code);
         self
     }
+
+    /// Add path label to the metric labels.
+    pub fn with_path(mut self, path: Arc<str>) -> Self {
+        self.path = Some(path);
+        self
+    }
 }
 
 /// MetricValue is the value the opendal sends to the metrics impls.
@@ -229,6 +357,9 @@ pub enum MetricValue {
     /// Record the time to first byte duration.
     /// Metrics impl: Update a Histogram with the duration converted to seconds (as f64).
     OperationTtfbSeconds(Duration),
+    /// Record the duration of fetching a page of entries while listing.
+    /// Metrics impl: Update a Histogram with the duration converted to seconds (as f64).
+    OperationListPageSeconds(Duration),
     /// Update the current number of executing HTTP requests.
     /// Metrics impl: Add the value (positive or negative) to a Gauge.
     HttpExecuting(isize),
@@ -256,6 +387,16 @@ pub enum MetricValue {
     /// Increment the counter for HTTP status errors (non-2xx responses).
     /// Metrics impl: Increment a Counter by 1.
     HttpStatusErrorsTotal,
+    /// Increment the estimated cost of HTTP requests.
+    /// Metrics impl: Increment a Counter by the given cost.
+    HttpRequestCost(f64),
+
+    /// Increment the read requests saved by coalescing reads.
+    /// Metrics impl: Increment a Counter by the given count.
+    ReadCoalesceSavedRequests(u64),
+    /// Increment the bytes saved by coalescing reads.
+    /// Metrics impl: Increment a Counter by the given bytes.
+    ReadCoalesceSavedBytes(u64),
 }
 
 impl MetricValue {
@@ -270,6 +411,7 @@ impl MetricValue {
             MetricValue::OperationErrorsTotal => "opendal_operation_errors_total",
             MetricValue::OperationExecuting(_) => "opendal_operation_executing",
             MetricValue::OperationTtfbSeconds(_) => "opendal_operation_ttfb_seconds",
+            MetricValue::OperationListPageSeconds(_) => "opendal_operation_list_page_seconds",
 
             MetricValue::HttpConnectionErrorsTotal => "opendal_http_connection_errors_total",
             MetricValue::HttpStatusErrorsTotal => "opendal_http_status_errors_total",
@@ -280,6 +422,12 @@ impl MetricValue {
             MetricValue::HttpResponseBytes(_) => "opendal_http_response_bytes",
             MetricValue::HttpResponseBytesRate(_) => "opendal_http_response_bytes_rate",
             MetricValue::HttpResponseDurationSeconds(_) => "opendal_http_response_duration_seconds",
+            MetricValue::HttpRequestCost(_) => "opendal_http_request_cost_total",
+
+            MetricValue::ReadCoalesceSavedRequests(_) => {
+                "opendal_read_coalesce_saved_requests_total"
+            }
+            MetricValue::ReadCoalesceSavedBytes(_) => "opendal_read_coalesce_saved_bytes_total",
         }
     }
 
@@ -300,6 +448,9 @@ impl MetricValue {
             MetricValue::OperationErrorsTotal => ("opendal_operation_errors", None),
             MetricValue::OperationExecuting(_) => ("opendal_operation_executing", None),
             MetricValue::OperationTtfbSeconds(_) => ("opendal_operation_ttfb", Some("seconds")),
+            MetricValue::OperationListPageSeconds(_) => {
+                ("opendal_operation_list_page", Some("seconds"))
+            }
 
             MetricValue::HttpConnectionErrorsTotal => ("opendal_http_connection_errors", None),
             MetricValue::HttpStatusErrorsTotal => ("opendal_http_status_errors", None),
@@ -314,6 +465,14 @@ impl MetricValue {
             MetricValue::HttpResponseDurationSeconds(_) => {
                 ("opendal_http_response_duration", Some("seconds"))
             }
+            MetricValue::HttpRequestCost(_) => ("opendal_http_request_cost", None),
+
+            MetricValue::ReadCoalesceSavedRequests(_) => {
+                ("opendal_read_coalesce_saved_requests", None)
+            }
+            MetricValue::ReadCoalesceSavedBytes(_) => {
+                ("opendal_read_coalesce_saved", Some("bytes"))
+            }
         }
     }
 
@@ -328,6 +487,7 @@ impl MetricValue {
             MetricValue::OperationErrorsTotal => "Total number of failed operations",
             MetricValue::OperationExecuting(_) => "Number of operations currently being executed",
             MetricValue::OperationTtfbSeconds(_) => "Time to first byte in seconds for operations",
+            MetricValue::OperationListPageSeconds(_) => "Duration in seconds of fetching a page of entries from the storage while listing",
 
             MetricValue::HttpConnectionErrorsTotal => "Total number of HTTP requests that failed before receiving a response (DNS failures, connection refused, timeouts, TLS errors)",
             MetricValue::HttpStatusErrorsTotal => "Total number of HTTP requests that received error status codes (non-2xx responses)",
@@ -338,6 +498,10 @@ impl MetricValue {
             MetricValue::HttpResponseBytes(_) => "Histogram of HTTP response body sizes in bytes",
             MetricValue::HttpResponseBytesRate(_) => "Histogram of HTTP response bytes per second rates",
             MetricValue::HttpResponseDurationSeconds(_) => "Histogram of time durations in seconds spent receiving HTTP responses, from first byte received to last byte received",
+            MetricValue::HttpRequestCost(_) => "Total estimated cost of HTTP requests, only available while CostLayer is applied",
+
+            MetricValue::ReadCoalesceSavedRequests(_) => "Total number of read requests saved by coalescing reads, only available while ReadCoalesceLayer is applied",
+            MetricValue::ReadCoalesceSavedBytes(_) => "Total bytes saved by coalescing overlapping reads, only available while ReadCoalesceLayer is applied",
         }
     }
 }
@@ -356,12 +520,22 @@ pub trait MetricsIntercept: Debug + Clone + Send + Sync + Unpin + 'static {
 #[derive(Clone, Debug)]
 pub struct MetricsLayer<I: MetricsIntercept> {
     interceptor: I,
+    path_label: Option<MetricPathLabel>,
 }
 
 impl<I: MetricsIntercept> MetricsLayer<I> {
     /// Create a new metrics layer.
     pub fn new(interceptor: I) -> Self {
-        Self { interceptor }
+        Self {
+            interceptor,
+            path_label: None,
+        }
+    }
+
+    /// Label operation metrics by the path with given [`MetricPathLabel`].
+    pub fn with_path_label(mut self, path_label: MetricPathLabel) -> Self {
+        self.path_label = Some(path_label);
+        self
     }
 }
 
@@ -384,6 +558,7 @@ impl<A: Access, I: MetricsIntercept> Layer<A> for MetricsLayer<I> {
             inner,
             info,
             interceptor: self.interceptor.clone(),
+            path_label: self.path_label.clone(),
         }
     }
 }
@@ -397,8 +572,8 @@ pub struct MetricsHttpFetcher<I: MetricsIntercept> {
 
 impl<I: MetricsIntercept> HttpFetch for MetricsHttpFetcher<I> {
     async fn fetch(&self, req: http::Request<Buffer>) -> Result<http::Response<HttpBody>> {
-        let labels = MetricLabels::new(
-            self.info.clone(),
+        let labels = MetricLabels::from_info(
+            &self.info,
             req.extensions()
                 .get::<Operation>()
                 .copied()
@@ -415,6 +590,15 @@ impl<I: MetricsIntercept> HttpFetch for MetricsHttpFetcher<I> {
         let res = self.inner.fetch(req).await;
         let req_duration = start.elapsed();
 
+        if let Some(cost) = res
+            .as_ref()
+            .ok()
+            .and_then(|resp| resp.extensions().get::<crate::layers::RequestCost>())
+        {
+            self.interceptor
+                .observe(labels.clone(), MetricValue::HttpRequestCost(cost.cost));
+        }
+
         match res {
             Err(err) => {
                 self.interceptor
@@ -518,6 +702,18 @@ pub struct MetricsAccessor<A: Access, I: MetricsIntercept> {
     inner: A,
     info: Arc<AccessorInfo>,
     interceptor: I,
+    path_label: Option<MetricPathLabel>,
+}
+
+impl<A: Access, I: MetricsIntercept> MetricsAccessor<A, I> {
+    fn labels(&self, op: Operation, path: Option<&str>) -> MetricLabels {
+        let labels = MetricLabels::from_info(&self.info, op.into_static());
+        match (&self.path_label, path) {
+            (Some(path_label), Some(path)) => labels.with_path(path_label.label(path)),
+            (Some(_), None) => labels.with_path("".into()),
+            (None, _) => labels,
+        }
+    }
 }
 
 impl<A: Access, I: MetricsIntercept> Debug for MetricsAccessor<A, I> {
@@ -540,7 +736,7 @@ impl<A: Access, I: MetricsIntercept> LayeredAccess for MetricsAccessor<A, I> {
     }
 
     async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
-        let labels = MetricLabels::new(self.info.clone(), Operation::CreateDir.into_static());
+        let labels = self.labels(Operation::CreateDir, Some(path));
 
         let start = Instant::now();
 
@@ -570,29 +766,30 @@ impl<A: Access, I: MetricsIntercept> LayeredAccess for MetricsAccessor<A, I> {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let labels = MetricLabels::new(self.info.clone(), Operation::Read.into_static());
+        let labels = self.labels(Operation::Read, Some(path));
 
         let start = Instant::now();
 
         self.interceptor
             .observe(labels.clone(), MetricValue::OperationExecuting(1));
 
-        let (rp, reader) = self
-            .inner
-            .read(path, args)
-            .await
-            .inspect(|_| {
-                self.interceptor.observe(
-                    labels.clone(),
-                    MetricValue::OperationTtfbSeconds(start.elapsed()),
-                );
-            })
-            .inspect_err(|err| {
-                self.interceptor.observe(
-                    labels.clone().with_error(err.kind()),
-                    MetricValue::OperationErrorsTotal,
-                );
-            })?;
+        let (rp, reader) = self.inner.read(path, args).await.inspect_err(|err| {
+            self.interceptor.observe(
+                labels.clone().with_error(err.kind()),
+                MetricValue::OperationErrorsTotal,
+            );
+        })?;
+
+        if let Some(saved) = rp.coalesced() {
+            self.interceptor.observe(
+                labels.clone(),
+                MetricValue::ReadCoalesceSavedRequests(saved.requests),
+            );
+            self.interceptor.observe(
+                labels.clone(),
+                MetricValue::ReadCoalesceSavedBytes(saved.bytes),
+            );
+        }
 
         Ok((
             rp,
@@ -601,7 +798,7 @@ impl<A: Access, I: MetricsIntercept> LayeredAccess for MetricsAccessor<A, I> {
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        let labels = MetricLabels::new(self.info.clone(), Operation::Write.into_static());
+        let labels = self.labels(Operation::Write, Some(path));
 
         let start = Instant::now();
 
@@ -622,7 +819,7 @@ impl<A: Access, I: MetricsIntercept> LayeredAccess for MetricsAccessor<A, I> {
     }
 
     async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
-        let labels = MetricLabels::new(self.info.clone(), Operation::Copy.into_static());
+        let labels = self.labels(Operation::Copy, Some(from));
 
         let start = Instant::now();
 
@@ -652,7 +849,7 @@ impl<A: Access, I: MetricsIntercept> LayeredAccess for MetricsAccessor<A, I> {
     }
 
     async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
-        let labels = MetricLabels::new(self.info.clone(), Operation::Rename.into_static());
+        let labels = self.labels(Operation::Rename, Some(from));
 
         let start = Instant::now();
 
@@ -682,7 +879,7 @@ impl<A: Access, I: MetricsIntercept> LayeredAccess for MetricsAccessor<A, I> {
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        let labels = MetricLabels::new(self.info.clone(), Operation::Stat.into_static());
+        let labels = self.labels(Operation::Stat, Some(path));
 
         let start = Instant::now();
 
@@ -711,8 +908,192 @@ impl<A: Access, I: MetricsIntercept> LayeredAccess for MetricsAccessor<A, I> {
         res
     }
 
+    async fn stat_many(&self, args: Vec<(String, OpStat)>) -> Result<RpStatMany> {
+        let labels = self.labels(Operation::Stat, None);
+
+        let start = Instant::now();
+
+        self.interceptor
+            .observe(labels.clone(), MetricValue::OperationExecuting(1));
+
+        let res = self
+            .inner()
+            .stat_many(args)
+            .await
+            .inspect(|_| {
+                self.interceptor.observe(
+                    labels.clone(),
+                    MetricValue::OperationDurationSeconds(start.elapsed()),
+                );
+            })
+            .inspect_err(|err| {
+                self.interceptor.observe(
+                    labels.clone().with_error(err.kind()),
+                    MetricValue::OperationErrorsTotal,
+                );
+            });
+
+        self.interceptor
+            .observe(labels, MetricValue::OperationExecuting(-1));
+        res
+    }
+
+    async fn list_uploads(&self, path: &str, args: OpListUploads) -> Result<RpListUploads> {
+        let labels = self.labels(Operation::ListUploads, Some(path));
+
+        let start = Instant::now();
+
+        self.interceptor
+            .observe(labels.clone(), MetricValue::OperationExecuting(1));
+
+        let res = self
+            .inner()
+            .list_uploads(path, args)
+            .await
+            .inspect(|_| {
+                self.interceptor.observe(
+                    labels.clone(),
+                    MetricValue::OperationDurationSeconds(start.elapsed()),
+                );
+            })
+            .inspect_err(|err| {
+                self.interceptor.observe(
+                    labels.clone().with_error(err.kind()),
+                    MetricValue::OperationErrorsTotal,
+                );
+            });
+
+        self.interceptor
+            .observe(labels, MetricValue::OperationExecuting(-1));
+        res
+    }
+
+    async fn abort_upload(&self, path: &str, args: OpAbortUpload) -> Result<RpAbortUpload> {
+        let labels = self.labels(Operation::AbortUpload, Some(path));
+
+        let start = Instant::now();
+
+        self.interceptor
+            .observe(labels.clone(), MetricValue::OperationExecuting(1));
+
+        let res = self
+            .inner()
+            .abort_upload(path, args)
+            .await
+            .inspect(|_| {
+                self.interceptor.observe(
+                    labels.clone(),
+                    MetricValue::OperationDurationSeconds(start.elapsed()),
+                );
+            })
+            .inspect_err(|err| {
+                self.interceptor.observe(
+                    labels.clone().with_error(err.kind()),
+                    MetricValue::OperationErrorsTotal,
+                );
+            });
+
+        self.interceptor
+            .observe(labels, MetricValue::OperationExecuting(-1));
+        res
+    }
+
+    async fn lock(&self, path: &str, args: OpLock) -> Result<RpLock> {
+        let labels = self.labels(Operation::Lock, Some(path));
+
+        let start = Instant::now();
+
+        self.interceptor
+            .observe(labels.clone(), MetricValue::OperationExecuting(1));
+
+        let res = self
+            .inner()
+            .lock(path, args)
+            .await
+            .inspect(|_| {
+                self.interceptor.observe(
+                    labels.clone(),
+                    MetricValue::OperationDurationSeconds(start.elapsed()),
+                );
+            })
+            .inspect_err(|err| {
+                self.interceptor.observe(
+                    labels.clone().with_error(err.kind()),
+                    MetricValue::OperationErrorsTotal,
+                );
+            });
+
+        self.interceptor
+            .observe(labels, MetricValue::OperationExecuting(-1));
+        res
+    }
+
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        let labels = self.labels(Operation::Concat, Some(path));
+
+        let start = Instant::now();
+
+        self.interceptor
+            .observe(labels.clone(), MetricValue::OperationExecuting(1));
+
+        let res = self
+            .inner()
+            .concat(path, args)
+            .await
+            .inspect(|_| {
+                self.interceptor.observe(
+                    labels.clone(),
+                    MetricValue::OperationDurationSeconds(start.elapsed()),
+                );
+            })
+            .inspect_err(|err| {
+                self.interceptor.observe(
+                    labels.clone().with_error(err.kind()),
+                    MetricValue::OperationErrorsTotal,
+                );
+            });
+
+        self.interceptor
+            .observe(labels, MetricValue::OperationExecuting(-1));
+        res
+    }
+
+    async fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> Result<RpUpdateMetadata> {
+        let labels = self.labels(Operation::UpdateMetadata, Some(path));
+
+        let start = Instant::now();
+
+        self.interceptor
+            .observe(labels.clone(), MetricValue::OperationExecuting(1));
+
+        let res = self
+            .inner()
+            .update_metadata(path, args)
+            .await
+            .inspect(|_| {
+                self.interceptor.observe(
+                    labels.clone(),
+                    MetricValue::OperationDurationSeconds(start.elapsed()),
+                );
+            })
+            .inspect_err(|err| {
+                self.interceptor.observe(
+                    labels.clone().with_error(err.kind()),
+                    MetricValue::OperationErrorsTotal,
+                );
+            });
+
+        self.interceptor
+            .observe(labels, MetricValue::OperationExecuting(-1));
+        res
+    }
+
     async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
-        let labels = MetricLabels::new(self.info.clone(), Operation::Delete.into_static());
+        let labels = self.labels(Operation::Delete, None);
 
         let start = Instant::now();
 
@@ -733,7 +1114,7 @@ impl<A: Access, I: MetricsIntercept> LayeredAccess for MetricsAccessor<A, I> {
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
-        let labels = MetricLabels::new(self.info.clone(), Operation::List.into_static());
+        let labels = self.labels(Operation::List, Some(path));
 
         let start = Instant::now();
 
@@ -754,7 +1135,7 @@ impl<A: Access, I: MetricsIntercept> LayeredAccess for MetricsAccessor<A, I> {
     }
 
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
-        let labels = MetricLabels::new(self.info.clone(), Operation::Presign.into_static());
+        let labels = self.labels(Operation::Presign, Some(path));
 
         let start = Instant::now();
 
@@ -791,6 +1172,7 @@ pub struct MetricsWrapper<R, I: MetricsIntercept> {
 
     start: Instant,
     size: u64,
+    first_byte: bool,
 }
 
 impl<R, I: MetricsIntercept> Drop for MetricsWrapper<R, I> {
@@ -835,6 +1217,7 @@ impl<R, I: MetricsIntercept> MetricsWrapper<R, I> {
             labels,
             start,
             size: 0,
+            first_byte: false,
         }
     }
 }
@@ -845,6 +1228,13 @@ impl<R: oio::Read, I: MetricsIntercept> oio::Read for MetricsWrapper<R, I> {
             .read()
             .await
             .inspect(|bs| {
+                if !self.first_byte {
+                    self.first_byte = true;
+                    self.interceptor.observe(
+                        self.labels.clone(),
+                        MetricValue::OperationTtfbSeconds(self.start.elapsed()),
+                    );
+                }
                 self.size += bs.len() as u64;
             })
             .inspect_err(|err| {
@@ -894,19 +1284,35 @@ impl<R: oio::Write, I: MetricsIntercept> oio::Write for MetricsWrapper<R, I> {
 }
 
 impl<R: oio::List, I: MetricsIntercept> oio::List for MetricsWrapper<R, I> {
+    /// A page is considered to be fetched if `next` has to wait for the
+    /// underlying storage, entries returned from the buffered page will be
+    /// ready immediately.
     async fn next(&mut self) -> Result<Option<oio::Entry>> {
-        self.inner
-            .next()
-            .await
-            .inspect(|_| {
-                self.size += 1;
-            })
-            .inspect_err(|err| {
-                self.interceptor.observe(
-                    self.labels.clone().with_error(err.kind()),
-                    MetricValue::OperationErrorsTotal,
-                );
-            })
+        let start = Instant::now();
+        let mut pending = false;
+        let mut fut = pin!(self.inner.next());
+        let res = poll_fn(|cx| {
+            let poll = fut.as_mut().poll(cx);
+            pending |= poll.is_pending();
+            poll
+        })
+        .await;
+        if pending {
+            self.interceptor.observe(
+                self.labels.clone(),
+                MetricValue::OperationListPageSeconds(start.elapsed()),
+            );
+        }
+
+        res.inspect(|_| {
+            self.size += 1;
+        })
+        .inspect_err(|err| {
+            self.interceptor.observe(
+                self.labels.clone().with_error(err.kind()),
+                MetricValue::OperationErrorsTotal,
+            );
+        })
     }
 }
 
@@ -934,3 +1340,35 @@ impl<R: oio::Delete, I: MetricsIntercept> oio::Delete for MetricsWrapper<R, I> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metric_path_label_segments() {
+        let cases = vec![
+            (1, "a/b/c.txt", "a/"),
+            (2, "a/b/c.txt", "a/b/"),
+            (2, "a/c.txt", "a/"),
+            (2, "a/b/c/", "a/b/"),
+            (2, "c.txt", "/"),
+            (0, "a/b/c.txt", "/"),
+        ];
+
+        for (n, path, expected) in cases {
+            let label = MetricPathLabel::segments(n);
+            assert_eq!(label.label(path).as_ref(), expected, "{n} {path}");
+        }
+    }
+
+    #[test]
+    fn test_metric_path_label_max_cardinality() {
+        let label = MetricPathLabel::segments(1).with_max_cardinality(2);
+
+        assert_eq!(label.label("a/x").as_ref(), "a/");
+        assert_eq!(label.label("b/x").as_ref(), "b/");
+        assert_eq!(label.label("c/x").as_ref(), PATH_LABEL_OVERFLOW);
+        assert_eq!(label.label("a/y").as_ref(), "a/");
+    }
+}
@@ -35,8 +35,11 @@
 //! | operation_entries_rate           | Histogram | Histogram of entries processing rates in entries per second within individual operations   | scheme, namespace, root, operation, path        |
 //! | operation_duration_seconds       | Histogram | Duration of operations in seconds, measured from start to completion                       | scheme, namespace, root, operation, path        |
 //! | operation_errors_total           | Counter   | Total number of failed operations                                                         | scheme, namespace, root, operation, path, error |
-//! | operation_executing              | Gauge     | Number of operations currently being executed                                             | scheme, namespace, root, operation              |
+//! | operation_executing              | Gauge     | Number of operations currently being executed                                             | scheme, namespace, root, operation, path        |
 //! | operation_ttfb_seconds           | Histogram | Time to first byte in seconds for operations                                              | scheme, namespace, root, operation, path        |
+//! | operation_list_page_seconds      | Histogram | Duration in seconds of fetching a page of entries from the storage while listing          | scheme, namespace, root, operation, path        |
+//! | read_coalesce_saved_requests_total | Counter | Total number of read requests saved by coalescing reads, only available while `ReadCoalesceLayer` is applied | scheme, namespace, root, operation, path |
+//! | read_coalesce_saved_bytes_total  | Counter   | Total bytes saved by coalescing overlapping reads, only available while `ReadCoalesceLayer` is applied | scheme, namespace, root, operation, path |
 //!
 //! ## HTTP Metrics
 //!
@@ -53,6 +56,7 @@
 //! | http_response_bytes              | Histogram | Histogram of HTTP response body sizes in bytes                                            | scheme, namespace, root, operation              |
 //! | http_response_bytes_rate         | Histogram | Histogram of HTTP response bytes per second rates                                         | scheme, namespace, root, operation              |
 //! | http_response_duration_seconds   | Histogram | Histogram of time spent receiving HTTP responses, from first byte to last byte received   | scheme, namespace, root, operation              |
+//! | http_request_cost_total          | Counter   | Total estimated cost of HTTP requests, only available while `CostLayer` is applied        | scheme, namespace, root, operation              |
 //!
 //! ## Label Descriptions
 //!
@@ -62,7 +66,7 @@
 //! | namespace | The storage service namespace (bucket, container, etc.)       | my-bucket, my-container                |
 //! | root      | The root path within the namespace                            | /data, /backup                         |
 //! | operation | The operation being performed                                 | read, write, stat, list, delete        |
-//! | path      | The path of the object being operated on                      | /path/to/file.txt                      |
+//! | path      | The label mapped from path by [`MetricPathLabel`]             | tenant-a/, dataset/2024/               |
 //! | error     | The error type or message for error metrics                   | not_found, permission_denied           |
 //! | status    | The HTTP status code for HTTP error metrics                   | 404, 403, 500                          |
 //!
@@ -75,6 +79,7 @@
 mod metrics;
 
 pub use metrics::MetricLabels;
+pub use metrics::MetricPathLabel;
 pub use metrics::MetricValue;
 pub use metrics::MetricsAccessor;
 pub use metrics::MetricsIntercept;
@@ -88,6 +93,8 @@ pub use metrics::DEFAULT_TTFB_BUCKETS;
 pub use metrics::LABEL_ERROR;
 pub use metrics::LABEL_NAMESPACE;
 pub use metrics::LABEL_OPERATION;
+pub use metrics::LABEL_PATH;
 pub use metrics::LABEL_ROOT;
 pub use metrics::LABEL_SCHEME;
 pub use metrics::LABEL_STATUS_CODE;
+pub use metrics::PATH_LABEL_OVERFLOW;
@@ -0,0 +1,173 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Helpers to describe the arguments of operations as string maps.
+
+use std::collections::BTreeMap;
+
+use crate::raw::*;
+
+fn insert_arg(args: &mut BTreeMap<String, String>, key: &str, value: Option<impl ToString>) {
+    if let Some(value) = value {
+        args.insert(key.to_string(), value.to_string());
+    }
+}
+
+pub(crate) fn copy_args(to: &str, args: &OpCopy) -> BTreeMap<String, String> {
+    let mut m = BTreeMap::new();
+    insert_arg(&mut m, "to", Some(to));
+    insert_arg(
+        &mut m,
+        "if_not_exists",
+        args.if_not_exists().then_some(true),
+    );
+    insert_arg(&mut m, "if_match", args.if_match());
+    insert_arg(&mut m, "source_if_match", args.source_if_match());
+    insert_arg(&mut m, "source_if_none_match", args.source_if_none_match());
+    insert_arg(
+        &mut m,
+        "source_if_unmodified_since",
+        args.source_if_unmodified_since(),
+    );
+    insert_arg(&mut m, "source_version", args.source_version());
+    m
+}
+
+pub(crate) fn rename_args(to: &str, args: &OpRename) -> BTreeMap<String, String> {
+    let mut m = BTreeMap::new();
+    insert_arg(&mut m, "to", Some(to));
+    insert_arg(
+        &mut m,
+        "if_not_exists",
+        args.if_not_exists().then_some(true),
+    );
+    insert_arg(&mut m, "if_match", args.if_match());
+    insert_arg(&mut m, "source_if_match", args.source_if_match());
+    insert_arg(&mut m, "source_if_none_match", args.source_if_none_match());
+    insert_arg(
+        &mut m,
+        "source_if_unmodified_since",
+        args.source_if_unmodified_since(),
+    );
+    m
+}
+
+pub(crate) fn stat_args(args: &OpStat) -> BTreeMap<String, String> {
+    let mut m = BTreeMap::new();
+    insert_arg(&mut m, "if_match", args.if_match());
+    insert_arg(&mut m, "if_none_match", args.if_none_match());
+    insert_arg(&mut m, "if_modified_since", args.if_modified_since());
+    insert_arg(&mut m, "if_unmodified_since", args.if_unmodified_since());
+    insert_arg(&mut m, "version", args.version());
+    m
+}
+
+pub(crate) fn read_args(args: &OpRead) -> BTreeMap<String, String> {
+    let mut m = BTreeMap::new();
+    insert_arg(&mut m, "range", Some(args.range()));
+    insert_arg(&mut m, "if_match", args.if_match());
+    insert_arg(&mut m, "if_none_match", args.if_none_match());
+    insert_arg(&mut m, "if_modified_since", args.if_modified_since());
+    insert_arg(&mut m, "if_unmodified_since", args.if_unmodified_since());
+    insert_arg(&mut m, "version", args.version());
+    m
+}
+
+pub(crate) fn write_args(args: &OpWrite) -> BTreeMap<String, String> {
+    let mut m = BTreeMap::new();
+    insert_arg(&mut m, "append", args.append().then_some(true));
+    insert_arg(&mut m, "content_type", args.content_type());
+    insert_arg(&mut m, "content_disposition", args.content_disposition());
+    insert_arg(&mut m, "content_encoding", args.content_encoding());
+    insert_arg(&mut m, "cache_control", args.cache_control());
+    insert_arg(&mut m, "if_match", args.if_match());
+    insert_arg(&mut m, "if_none_match", args.if_none_match());
+    insert_arg(
+        &mut m,
+        "if_not_exists",
+        args.if_not_exists().then_some(true),
+    );
+    if let Some(v) = args.user_metadata() {
+        let v: BTreeMap<_, _> = v.iter().collect();
+        insert_arg(&mut m, "user_metadata", serde_json::to_string(&v).ok());
+    }
+    m
+}
+
+pub(crate) fn list_args(args: &OpList) -> BTreeMap<String, String> {
+    let mut m = BTreeMap::new();
+    insert_arg(&mut m, "recursive", args.recursive().then_some(true));
+    insert_arg(&mut m, "limit", args.limit());
+    insert_arg(&mut m, "start_after", args.start_after());
+    insert_arg(&mut m, "versions", args.versions().then_some(true));
+    insert_arg(&mut m, "deleted", args.deleted().then_some(true));
+    m
+}
+
+pub(crate) fn delete_args(args: &OpDelete) -> BTreeMap<String, String> {
+    let mut m = BTreeMap::new();
+    insert_arg(&mut m, "version", args.version());
+    insert_arg(&mut m, "if_match", args.if_match());
+    insert_arg(&mut m, "if_none_match", args.if_none_match());
+    insert_arg(&mut m, "if_unmodified_since", args.if_unmodified_since());
+    m
+}
+
+pub(crate) fn abort_upload_args(args: &OpAbortUpload) -> BTreeMap<String, String> {
+    let mut m = BTreeMap::new();
+    insert_arg(&mut m, "upload_id", Some(args.upload_id()));
+    m
+}
+
+/// The token of lock is excluded since it's generated for every lease.
+pub(crate) fn lock_args(args: &OpLock) -> BTreeMap<String, String> {
+    let mut m = BTreeMap::new();
+    insert_arg(&mut m, "action", Some(format!("{:?}", args.action())));
+    insert_arg(&mut m, "ttl", Some(format!("{:?}", args.ttl())));
+    m
+}
+
+pub(crate) fn concat_args(args: &OpConcat) -> BTreeMap<String, String> {
+    let mut m = write_args(args.write_args());
+    insert_arg(&mut m, "sources", Some(args.sources().join(",")));
+    insert_arg(&mut m, "if_match", args.if_match());
+    insert_arg(
+        &mut m,
+        "if_not_exists",
+        args.if_not_exists().then_some(true),
+    );
+    m
+}
+
+/// Values of metadata and tags are kept so that replayed calls can be matched
+/// exactly. `AuditAccessor` builds its own args with only the keys instead.
+#[cfg(feature = "layers-record")]
+pub(crate) fn update_metadata_args(args: &OpUpdateMetadata) -> BTreeMap<String, String> {
+    let mut m = BTreeMap::new();
+    insert_arg(&mut m, "content_type", args.content_type());
+    insert_arg(&mut m, "cache_control", args.cache_control());
+    insert_arg(&mut m, "content_disposition", args.content_disposition());
+    if !args.user_metadata().is_empty() {
+        let v: BTreeMap<_, _> = args.user_metadata().iter().collect();
+        insert_arg(&mut m, "user_metadata", serde_json::to_string(&v).ok());
+    }
+    if let Some(v) = args.tags() {
+        let v: BTreeMap<_, _> = v.iter().collect();
+        insert_arg(&mut m, "tags", serde_json::to_string(&v).ok());
+    }
+    m
+}
@@ -47,6 +47,7 @@ use crate::raw::*;
 #[derive(Clone, Debug)]
 pub struct OtelMetricsLayer {
     interceptor: OtelMetricsInterceptor,
+    path_label: Option<observe::MetricPathLabel>,
 }
 
 impl OtelMetricsLayer {
@@ -83,6 +84,7 @@ pub struct OtelMetricsLayerBuilder {
     entries_rate_boundaries: Vec<f64>,
     duration_seconds_boundaries: Vec<f64>,
     ttfb_boundaries: Vec<f64>,
+    path_label: Option<observe::MetricPathLabel>,
 }
 
 impl Default for OtelMetricsLayerBuilder {
@@ -94,6 +96,7 @@ impl Default for OtelMetricsLayerBuilder {
             entries_rate_boundaries: observe::DEFAULT_ENTRIES_RATE_BUCKETS.to_vec(),
             duration_seconds_boundaries: observe::DEFAULT_DURATION_SECONDS_BUCKETS.to_vec(),
             ttfb_boundaries: observe::DEFAULT_TTFB_BUCKETS.to_vec(),
+            path_label: None,
         }
     }
 }
@@ -147,6 +150,12 @@ impl OtelMetricsLayerBuilder {
         self
     }
 
+    /// Label operation metrics by the path with given [`observe::MetricPathLabel`].
+    pub fn path_label(mut self, path_label: observe::MetricPathLabel) -> Self {
+        self.path_label = Some(path_label);
+        self
+    }
+
     /// Register the metrics and return a [`OtelMetricsLayer`].
     ///
     /// # Examples
@@ -236,6 +245,15 @@ impl OtelMetricsLayerBuilder {
                 self.duration_seconds_boundaries.clone(),
             )
         };
+        let operation_list_page_seconds = {
+            let metric = observe::MetricValue::OperationListPageSeconds(Duration::default());
+            register_f64_histogram_meter(
+                meter,
+                "opendal.operation.list_page",
+                metric,
+                self.ttfb_boundaries.clone(),
+            )
+        };
 
         let http_executing = {
             let metric = observe::MetricValue::HttpExecuting(0);
@@ -312,6 +330,28 @@ impl OtelMetricsLayerBuilder {
                 .with_description(metric.help())
                 .build()
         };
+        let http_request_cost_total = {
+            let metric = observe::MetricValue::HttpRequestCost(0.0);
+            meter
+                .f64_counter("opendal.http.request_cost")
+                .with_description(metric.help())
+                .build()
+        };
+        let read_coalesce_saved_requests_total = {
+            let metric = observe::MetricValue::ReadCoalesceSavedRequests(0);
+            meter
+                .u64_counter("opendal.read_coalesce.saved_requests")
+                .with_description(metric.help())
+                .build()
+        };
+        let read_coalesce_saved_bytes_total = {
+            let metric = observe::MetricValue::ReadCoalesceSavedBytes(0);
+            meter
+                .u64_counter("opendal.read_coalesce.saved_bytes")
+                .with_description(metric.help())
+                .with_unit("bytes")
+                .build()
+        };
 
         OtelMetricsLayer {
             interceptor: OtelMetricsInterceptor {
@@ -323,6 +363,7 @@ impl OtelMetricsLayerBuilder {
                 operation_errors_total,
                 operation_executing,
                 operation_ttfb_seconds,
+                operation_list_page_seconds,
 
                 http_executing,
                 http_request_bytes,
@@ -333,7 +374,11 @@ impl OtelMetricsLayerBuilder {
                 http_response_duration_seconds,
                 http_connection_errors_total,
                 http_status_errors_total,
+                http_request_cost_total,
+                read_coalesce_saved_requests_total,
+                read_coalesce_saved_bytes_total,
             },
+            path_label: self.path_label,
         }
     }
 }
@@ -342,7 +387,11 @@ impl<A: Access> Layer<A> for OtelMetricsLayer {
     type LayeredAccess = observe::MetricsAccessor<A, OtelMetricsInterceptor>;
 
     fn layer(&self, inner: A) -> Self::LayeredAccess {
-        observe::MetricsLayer::new(self.interceptor.clone()).layer(inner)
+        let mut layer = observe::MetricsLayer::new(self.interceptor.clone());
+        if let Some(path_label) = self.path_label.clone() {
+            layer = layer.with_path_label(path_label);
+        }
+        layer.layer(inner)
     }
 }
 
@@ -356,6 +405,7 @@ pub struct OtelMetricsInterceptor {
     operation_errors_total: Counter<u64>,
     operation_executing: UpDownCounter<i64>,
     operation_ttfb_seconds: Histogram<f64>,
+    operation_list_page_seconds: Histogram<f64>,
 
     http_executing: UpDownCounter<i64>,
     http_request_bytes: Histogram<u64>,
@@ -366,6 +416,9 @@ pub struct OtelMetricsInterceptor {
     http_response_duration_seconds: Histogram<f64>,
     http_connection_errors_total: Counter<u64>,
     http_status_errors_total: Counter<u64>,
+    http_request_cost_total: Counter<f64>,
+    read_coalesce_saved_requests_total: Counter<u64>,
+    read_coalesce_saved_bytes_total: Counter<u64>,
 }
 
 impl observe::MetricsIntercept for OtelMetricsInterceptor {
@@ -395,6 +448,9 @@ impl observe::MetricsIntercept for OtelMetricsInterceptor {
             observe::MetricValue::OperationTtfbSeconds(v) => self
                 .operation_ttfb_seconds
                 .record(v.as_secs_f64(), &attributes),
+            observe::MetricValue::OperationListPageSeconds(v) => self
+                .operation_list_page_seconds
+                .record(v.as_secs_f64(), &attributes),
 
             observe::MetricValue::HttpExecuting(v) => {
                 self.http_executing.add(v as i64, &attributes)
@@ -423,13 +479,22 @@ impl observe::MetricsIntercept for OtelMetricsInterceptor {
             observe::MetricValue::HttpStatusErrorsTotal => {
                 self.http_status_errors_total.add(1, &attributes)
             }
+            observe::MetricValue::HttpRequestCost(v) => {
+                self.http_request_cost_total.add(v, &attributes)
+            }
+            observe::MetricValue::ReadCoalesceSavedRequests(v) => {
+                self.read_coalesce_saved_requests_total.add(v, &attributes)
+            }
+            observe::MetricValue::ReadCoalesceSavedBytes(v) => {
+                self.read_coalesce_saved_bytes_total.add(v, &attributes)
+            }
         }
     }
 }
 
 impl OtelMetricsInterceptor {
     fn create_attributes(&self, attrs: observe::MetricLabels) -> Vec<KeyValue> {
-        let mut attributes = Vec::with_capacity(6);
+        let mut attributes = Vec::with_capacity(7);
 
         attributes.extend([
             KeyValue::new(observe::LABEL_SCHEME, attrs.scheme),
@@ -438,6 +503,10 @@ impl OtelMetricsInterceptor {
             KeyValue::new(observe::LABEL_OPERATION, attrs.operation),
         ]);
 
+        if let Some(path) = attrs.path {
+            attributes.push(KeyValue::new(observe::LABEL_PATH, path));
+        }
+
         if let Some(error) = attrs.error {
             attributes.push(KeyValue::new(observe::LABEL_ERROR, error.into_static()));
         }
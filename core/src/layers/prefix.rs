@@ -0,0 +1,397 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::sync::Arc;
+
+use crate::raw::*;
+use crate::*;
+
+/// Add a prefix for every path of the underlying services.
+///
+/// `PrefixLayer` jails all operations under the given prefix, which makes it
+/// possible to serve many tenants from one bucket:
+///
+/// - Paths of all operations, including the `start_after` of list, the
+///   targets of copy and rename and the paths of presign, will be rewritten
+///   under the prefix.
+/// - Paths of returned entries and uploads will have the prefix stripped.
+/// - Paths that contain `..` will be rejected with [`ErrorKind::PermissionDenied`]
+///   so that they can't escape the prefix.
+///
+/// Unlike the `root` of services, `PrefixLayer` could be applied to an
+/// already built [`Operator`] at request time.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::PrefixLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # async fn test(op: Operator) -> Result<()> {
+/// let tenant = op.layer(PrefixLayer::new("tenants/alice"));
+/// // Write into `tenants/alice/hello.txt` of the underlying operator.
+/// tenant.write("hello.txt", "Hello, World!").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PrefixLayer {
+    prefix: Arc<str>,
+}
+
+impl PrefixLayer {
+    /// Create a new `PrefixLayer` with given prefix.
+    ///
+    /// The prefix will be normalized like `path/to/prefix/`.
+    pub fn new(prefix: &str) -> Self {
+        let root = normalize_root(prefix);
+        Self {
+            prefix: root[1..].into(),
+        }
+    }
+}
+
+impl<A: Access> Layer<A> for PrefixLayer {
+    type LayeredAccess = PrefixAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        PrefixAccessor {
+            inner,
+            prefix: self.prefix.clone(),
+        }
+    }
+}
+
+/// Build the path under given prefix.
+fn build_prefixed_path(prefix: &str, path: &str) -> Result<String> {
+    if path.split('/').any(|v| v == "..") || prefix.split('/').any(|v| v == "..") {
+        return Err(Error::new(
+            ErrorKind::PermissionDenied,
+            "path contains `..` which could escape the prefix",
+        )
+        .with_operation("PrefixLayer")
+        .with_context("prefix", prefix)
+        .with_context("path", path));
+    }
+
+    if path == "/" {
+        if prefix.is_empty() {
+            return Ok(path.to_string());
+        }
+        return Ok(prefix.to_string());
+    }
+    Ok(format!("{prefix}{path}"))
+}
+
+/// Strip the prefix from given path.
+fn strip_prefixed_path(prefix: &str, path: &str) -> Result<String> {
+    match path.strip_prefix(prefix) {
+        Some("") => Ok("/".to_string()),
+        Some(v) => Ok(v.to_string()),
+        None => Err(Error::new(
+            ErrorKind::Unexpected,
+            "returned path is not under the prefix",
+        )
+        .with_operation("PrefixLayer")
+        .with_context("prefix", prefix)
+        .with_context("path", path)),
+    }
+}
+
+#[derive(Debug)]
+pub struct PrefixAccessor<A: Access> {
+    inner: A,
+    prefix: Arc<str>,
+}
+
+impl<A: Access> PrefixAccessor<A> {
+    fn path(&self, path: &str) -> Result<String> {
+        build_prefixed_path(&self.prefix, path)
+    }
+}
+
+impl<A: Access> LayeredAccess for PrefixAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type Writer = A::Writer;
+    type Lister = PrefixLister<A::Lister>;
+    type Deleter = PrefixDeleter<A::Deleter>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.inner.create_dir(&self.path(path)?, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(&self.path(path)?, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(&self.path(path)?, args).await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.inner
+            .copy(&self.path(from)?, &self.path(to)?, args)
+            .await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.inner
+            .rename(&self.path(from)?, &self.path(to)?, args)
+            .await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.inner.stat(&self.path(path)?, args).await
+    }
+
+    async fn stat_many(&self, args: Vec<(String, OpStat)>) -> Result<RpStatMany> {
+        let mut results: Vec<Option<Result<Metadata>>> = Vec::with_capacity(args.len());
+        let mut prefixed = Vec::with_capacity(args.len());
+        for (path, op) in args {
+            match self.path(&path) {
+                Ok(path) => {
+                    results.push(None);
+                    prefixed.push((path, op));
+                }
+                Err(err) => results.push(Some(Err(err))),
+            }
+        }
+
+        let mut inner_results = if prefixed.is_empty() {
+            Vec::new()
+        } else {
+            self.inner.stat_many(prefixed).await?.into_results()
+        }
+        .into_iter();
+
+        let results = results
+            .into_iter()
+            .map(|v| {
+                v.or_else(|| inner_results.next()).unwrap_or_else(|| {
+                    Err(Error::new(
+                        ErrorKind::Unexpected,
+                        "stat_many returned less results than expected",
+                    ))
+                })
+            })
+            .collect();
+        Ok(RpStatMany::new(results))
+    }
+
+    async fn list_uploads(&self, path: &str, args: OpListUploads) -> Result<RpListUploads> {
+        let rp = self.inner.list_uploads(&self.path(path)?, args).await?;
+        let uploads = rp
+            .into_uploads()
+            .into_iter()
+            .map(|v| {
+                let path = strip_prefixed_path(&self.prefix, v.path())?;
+                let upload = Upload::new(&path, v.upload_id());
+                Ok(match v.initiated() {
+                    Some(initiated) => upload.with_initiated(initiated),
+                    None => upload,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(RpListUploads::new(uploads))
+    }
+
+    async fn abort_upload(&self, path: &str, args: OpAbortUpload) -> Result<RpAbortUpload> {
+        self.inner.abort_upload(&self.path(path)?, args).await
+    }
+
+    async fn lock(&self, path: &str, args: OpLock) -> Result<RpLock> {
+        self.inner.lock(&self.path(path)?, args).await
+    }
+
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        let sources = args
+            .sources()
+            .iter()
+            .map(|v| self.path(v))
+            .collect::<Result<Vec<_>>>()?;
+        self.inner
+            .concat(&self.path(path)?, args.with_sources(sources))
+            .await
+    }
+
+    async fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> Result<RpUpdateMetadata> {
+        self.inner.update_metadata(&self.path(path)?, args).await
+    }
+
+    async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        self.inner
+            .delete()
+            .await
+            .map(|(rp, d)| (rp, PrefixDeleter::new(d, self.prefix.clone())))
+    }
+
+    async fn list(&self, path: &str, mut args: OpList) -> Result<(RpList, Self::Lister)> {
+        if let Some(start_after) = args.start_after() {
+            let start_after = self.path(start_after)?;
+            args = args.with_start_after(&start_after);
+        }
+
+        self.inner
+            .list(&self.path(path)?, args)
+            .await
+            .map(|(rp, l)| (rp, PrefixLister::new(l, self.prefix.clone())))
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.inner.presign(&self.path(path)?, args).await
+    }
+}
+
+/// PrefixLister strips the prefix from the paths of returned entries.
+pub struct PrefixLister<L> {
+    inner: L,
+    prefix: Arc<str>,
+}
+
+impl<L> PrefixLister<L> {
+    fn new(inner: L, prefix: Arc<str>) -> Self {
+        Self { inner, prefix }
+    }
+}
+
+impl<L: oio::List> oio::List for PrefixLister<L> {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        let Some(mut entry) = self.inner.next().await? else {
+            return Ok(None);
+        };
+        let path = strip_prefixed_path(&self.prefix, entry.path())?;
+        entry.set_path(&path);
+        Ok(Some(entry))
+    }
+}
+
+/// PrefixDeleter deletes paths under the prefix.
+pub struct PrefixDeleter<D> {
+    inner: D,
+    prefix: Arc<str>,
+}
+
+impl<D> PrefixDeleter<D> {
+    fn new(inner: D, prefix: Arc<str>) -> Self {
+        Self { inner, prefix }
+    }
+}
+
+impl<D: oio::Delete> oio::Delete for PrefixDeleter<D> {
+    fn delete(&mut self, path: &str, args: OpDelete) -> Result<()> {
+        let path = build_prefixed_path(&self.prefix, path)?;
+        self.inner.delete(&path, args)
+    }
+
+    async fn flush(&mut self) -> Result<usize> {
+        self.inner.flush().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::services::Memory;
+
+    #[test]
+    fn test_build_prefixed_path() {
+        let cases = vec![
+            ("tenant/", "/", Some("tenant/")),
+            ("tenant/", "a/b", Some("tenant/a/b")),
+            ("tenant/", "dir/", Some("tenant/dir/")),
+            ("", "/", Some("/")),
+            ("", "a", Some("a")),
+            ("tenant/", "../other/a", None),
+            ("tenant/", "a/../../other/a", None),
+            ("tenant/../", "a", None),
+        ];
+
+        for (prefix, path, expected) in cases {
+            let actual = build_prefixed_path(prefix, path);
+            match expected {
+                Some(v) => assert_eq!(actual.unwrap(), v, "{prefix} {path}"),
+                None => assert_eq!(
+                    actual.unwrap_err().kind(),
+                    ErrorKind::PermissionDenied,
+                    "{prefix} {path}"
+                ),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prefix_layer() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        let alice = op.clone().layer(PrefixLayer::new("/tenants/alice"));
+        let bob = op.clone().layer(PrefixLayer::new("tenants/bob/"));
+
+        alice.write("a", "alice").await?;
+        alice.write("b", "alice").await?;
+        bob.write("a", "bob").await?;
+        assert_eq!(op.read("tenants/alice/a").await?.to_vec(), b"alice");
+        assert_eq!(op.read("tenants/bob/a").await?.to_vec(), b"bob");
+
+        let mut paths: Vec<_> = alice
+            .lister_with("/")
+            .recursive(true)
+            .await?
+            .map_ok(|v| v.path().to_string())
+            .try_collect()
+            .await?;
+        paths.sort();
+        assert_eq!(paths, vec!["a", "b"]);
+
+        alice.delete("a").await?;
+        assert!(!op.exists("tenants/alice/a").await?);
+        assert!(op.exists("tenants/bob/a").await?);
+
+        let err = alice.read("../bob/a").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = alice
+            .list_with("/")
+            .start_after("../bob/a")
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        let results = alice
+            .into_inner()
+            .stat_many(vec![
+                ("../bob/a".to_string(), OpStat::new()),
+                ("a/../../bob/b".to_string(), OpStat::new()),
+            ])
+            .await?
+            .into_results();
+        assert_eq!(results.len(), 2);
+        for res in results {
+            assert_eq!(res.unwrap_err().kind(), ErrorKind::PermissionDenied);
+        }
+        Ok(())
+    }
+}
@@ -21,9 +21,11 @@ use prometheus::core::AtomicI64;
 use prometheus::core::AtomicU64;
 use prometheus::core::GenericCounterVec;
 use prometheus::core::GenericGaugeVec;
+use prometheus::register_counter_vec_with_registry;
 use prometheus::register_histogram_vec_with_registry;
 use prometheus::register_int_counter_vec_with_registry;
 use prometheus::register_int_gauge_vec_with_registry;
+use prometheus::CounterVec;
 use prometheus::HistogramVec;
 use prometheus::Registry;
 
@@ -140,6 +142,7 @@ use crate::*;
 #[derive(Clone, Debug)]
 pub struct PrometheusLayer {
     interceptor: PrometheusInterceptor,
+    path_label: Option<observe::MetricPathLabel>,
 }
 
 impl PrometheusLayer {
@@ -182,7 +185,11 @@ impl<A: Access> Layer<A> for PrometheusLayer {
     type LayeredAccess = observe::MetricsAccessor<A, PrometheusInterceptor>;
 
     fn layer(&self, inner: A) -> Self::LayeredAccess {
-        observe::MetricsLayer::new(self.interceptor.clone()).layer(inner)
+        let mut layer = observe::MetricsLayer::new(self.interceptor.clone());
+        if let Some(path_label) = self.path_label.clone() {
+            layer = layer.with_path_label(path_label);
+        }
+        layer.layer(inner)
     }
 }
 
@@ -194,6 +201,7 @@ pub struct PrometheusLayerBuilder {
     entries_rate_buckets: Vec<f64>,
     duration_seconds_buckets: Vec<f64>,
     ttfb_buckets: Vec<f64>,
+    path_label: Option<observe::MetricPathLabel>,
 }
 
 impl Default for PrometheusLayerBuilder {
@@ -205,6 +213,7 @@ impl Default for PrometheusLayerBuilder {
             entries_rate_buckets: observe::DEFAULT_ENTRIES_RATE_BUCKETS.to_vec(),
             duration_seconds_buckets: observe::DEFAULT_DURATION_SECONDS_BUCKETS.to_vec(),
             ttfb_buckets: observe::DEFAULT_TTFB_BUCKETS.to_vec(),
+            path_label: None,
         }
     }
 }
@@ -258,6 +267,12 @@ impl PrometheusLayerBuilder {
         self
     }
 
+    /// Label operation metrics by the path with given [`observe::MetricPathLabel`].
+    pub fn path_label(mut self, path_label: observe::MetricPathLabel) -> Self {
+        self.path_label = Some(path_label);
+        self
+    }
+
     /// Register the metrics into the given registry and return a [`PrometheusLayer`].
     ///
     /// # Example
@@ -283,7 +298,7 @@ impl PrometheusLayerBuilder {
     /// # }
     /// ```
     pub fn register(self, registry: &Registry) -> Result<PrometheusLayer> {
-        let labels = OperationLabels::names();
+        let labels = OperationLabels::names().with_path(self.path_label.is_some());
         let operation_bytes = {
             let metric = observe::MetricValue::OperationBytes(0);
             register_histogram_vec_with_registry!(
@@ -360,8 +375,21 @@ impl PrometheusLayerBuilder {
             )
             .map_err(parse_prometheus_error)?
         };
+        let operation_list_page_seconds = {
+            let metric = observe::MetricValue::OperationListPageSeconds(Duration::default());
+            register_histogram_vec_with_registry!(
+                metric.name(),
+                metric.help(),
+                labels.as_ref(),
+                self.ttfb_buckets.clone(),
+                registry
+            )
+            .map_err(parse_prometheus_error)?
+        };
 
-        let labels_with_error = OperationLabels::names().with_error();
+        let labels_with_error = OperationLabels::names()
+            .with_path(self.path_label.is_some())
+            .with_error();
         let operation_errors_total = {
             let metric = observe::MetricValue::OperationErrorsTotal;
             register_int_counter_vec_with_registry!(
@@ -373,12 +401,13 @@ impl PrometheusLayerBuilder {
             .map_err(parse_prometheus_error)?
         };
 
+        let http_labels = OperationLabels::names();
         let http_executing = {
             let metric = observe::MetricValue::HttpExecuting(0);
             register_int_gauge_vec_with_registry!(
                 metric.name(),
                 metric.help(),
-                labels.as_ref(),
+                http_labels.as_ref(),
                 registry
             )
             .map_err(parse_prometheus_error)?
@@ -388,7 +417,7 @@ impl PrometheusLayerBuilder {
             register_histogram_vec_with_registry!(
                 metric.name(),
                 metric.help(),
-                labels.as_ref(),
+                http_labels.as_ref(),
                 self.bytes_buckets.clone(),
                 registry
             )
@@ -399,7 +428,7 @@ impl PrometheusLayerBuilder {
             register_histogram_vec_with_registry!(
                 metric.name(),
                 metric.help(),
-                labels.as_ref(),
+                http_labels.as_ref(),
                 self.bytes_rate_buckets.clone(),
                 registry
             )
@@ -410,7 +439,7 @@ impl PrometheusLayerBuilder {
             register_histogram_vec_with_registry!(
                 metric.name(),
                 metric.help(),
-                labels.as_ref(),
+                http_labels.as_ref(),
                 self.duration_seconds_buckets.clone(),
                 registry
             )
@@ -421,7 +450,7 @@ impl PrometheusLayerBuilder {
             register_histogram_vec_with_registry!(
                 metric.name(),
                 metric.help(),
-                labels.as_ref(),
+                http_labels.as_ref(),
                 self.bytes_buckets,
                 registry
             )
@@ -432,7 +461,7 @@ impl PrometheusLayerBuilder {
             register_histogram_vec_with_registry!(
                 metric.name(),
                 metric.help(),
-                labels.as_ref(),
+                http_labels.as_ref(),
                 self.bytes_rate_buckets,
                 registry
             )
@@ -443,7 +472,7 @@ impl PrometheusLayerBuilder {
             register_histogram_vec_with_registry!(
                 metric.name(),
                 metric.help(),
-                labels.as_ref(),
+                http_labels.as_ref(),
                 self.duration_seconds_buckets,
                 registry
             )
@@ -451,6 +480,36 @@ impl PrometheusLayerBuilder {
         };
         let http_connection_errors_total = {
             let metric = observe::MetricValue::HttpConnectionErrorsTotal;
+            register_int_counter_vec_with_registry!(
+                metric.name(),
+                metric.help(),
+                http_labels.as_ref(),
+                registry
+            )
+            .map_err(parse_prometheus_error)?
+        };
+        let http_request_cost_total = {
+            let metric = observe::MetricValue::HttpRequestCost(0.0);
+            register_counter_vec_with_registry!(
+                metric.name(),
+                metric.help(),
+                http_labels.as_ref(),
+                registry
+            )
+            .map_err(parse_prometheus_error)?
+        };
+        let read_coalesce_saved_requests_total = {
+            let metric = observe::MetricValue::ReadCoalesceSavedRequests(0);
+            register_int_counter_vec_with_registry!(
+                metric.name(),
+                metric.help(),
+                labels.as_ref(),
+                registry
+            )
+            .map_err(parse_prometheus_error)?
+        };
+        let read_coalesce_saved_bytes_total = {
+            let metric = observe::MetricValue::ReadCoalesceSavedBytes(0);
             register_int_counter_vec_with_registry!(
                 metric.name(),
                 metric.help(),
@@ -482,6 +541,7 @@ impl PrometheusLayerBuilder {
                 operation_errors_total,
                 operation_executing,
                 operation_ttfb_seconds,
+                operation_list_page_seconds,
 
                 http_executing,
                 http_request_bytes,
@@ -492,7 +552,11 @@ impl PrometheusLayerBuilder {
                 http_response_duration_seconds,
                 http_connection_errors_total,
                 http_status_errors_total,
+                http_request_cost_total,
+                read_coalesce_saved_requests_total,
+                read_coalesce_saved_bytes_total,
             },
+            path_label: self.path_label,
         })
     }
 
@@ -541,6 +605,7 @@ pub struct PrometheusInterceptor {
     operation_errors_total: GenericCounterVec<AtomicU64>,
     operation_executing: GenericGaugeVec<AtomicI64>,
     operation_ttfb_seconds: HistogramVec,
+    operation_list_page_seconds: HistogramVec,
 
     http_executing: GenericGaugeVec<AtomicI64>,
     http_request_bytes: HistogramVec,
@@ -551,6 +616,9 @@ pub struct PrometheusInterceptor {
     http_response_duration_seconds: HistogramVec,
     http_connection_errors_total: GenericCounterVec<AtomicU64>,
     http_status_errors_total: GenericCounterVec<AtomicU64>,
+    http_request_cost_total: CounterVec,
+    read_coalesce_saved_requests_total: GenericCounterVec<AtomicU64>,
+    read_coalesce_saved_bytes_total: GenericCounterVec<AtomicU64>,
 }
 
 impl observe::MetricsIntercept for PrometheusInterceptor {
@@ -589,6 +657,10 @@ impl observe::MetricsIntercept for PrometheusInterceptor {
                 .operation_ttfb_seconds
                 .with_label_values(&labels.values())
                 .observe(v.as_secs_f64()),
+            observe::MetricValue::OperationListPageSeconds(v) => self
+                .operation_list_page_seconds
+                .with_label_values(&labels.values())
+                .observe(v.as_secs_f64()),
 
             observe::MetricValue::HttpExecuting(v) => self
                 .http_executing
@@ -626,6 +698,18 @@ impl observe::MetricsIntercept for PrometheusInterceptor {
                 .http_status_errors_total
                 .with_label_values(&labels.values())
                 .inc(),
+            observe::MetricValue::HttpRequestCost(v) => self
+                .http_request_cost_total
+                .with_label_values(&labels.values())
+                .inc_by(v),
+            observe::MetricValue::ReadCoalesceSavedRequests(v) => self
+                .read_coalesce_saved_requests_total
+                .with_label_values(&labels.values())
+                .inc_by(v),
+            observe::MetricValue::ReadCoalesceSavedBytes(v) => self
+                .read_coalesce_saved_bytes_total
+                .with_label_values(&labels.values())
+                .inc_by(v),
         }
     }
 }
@@ -639,6 +723,13 @@ impl AsRef<[&'static str]> for OperationLabelNames {
 }
 
 impl OperationLabelNames {
+    fn with_path(mut self, enabled: bool) -> Self {
+        if enabled {
+            self.0.push(observe::LABEL_PATH);
+        }
+        self
+    }
+
     fn with_error(mut self) -> Self {
         self.0.push(observe::LABEL_ERROR);
         self
@@ -664,7 +755,7 @@ impl OperationLabels {
     }
 
     fn values(&self) -> Vec<&str> {
-        let mut labels = Vec::with_capacity(6);
+        let mut labels = Vec::with_capacity(7);
 
         labels.extend([
             self.0.scheme,
@@ -673,6 +764,10 @@ impl OperationLabels {
             self.0.operation,
         ]);
 
+        if let Some(path) = &self.0.path {
+            labels.push(path.as_ref());
+        }
+
         if let Some(error) = self.0.error {
             labels.push(error.into_static());
         }
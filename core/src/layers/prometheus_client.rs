@@ -16,6 +16,7 @@
 // under the License.
 
 use std::fmt;
+use std::sync::atomic::AtomicU64;
 use std::time::Duration;
 
 use prometheus_client::encoding::EncodeLabel;
@@ -79,6 +80,7 @@ use crate::*;
 #[derive(Clone, Debug)]
 pub struct PrometheusClientLayer {
     interceptor: PrometheusClientInterceptor,
+    path_label: Option<observe::MetricPathLabel>,
 }
 
 impl PrometheusClientLayer {
@@ -92,7 +94,11 @@ impl<A: Access> Layer<A> for PrometheusClientLayer {
     type LayeredAccess = observe::MetricsAccessor<A, PrometheusClientInterceptor>;
 
     fn layer(&self, inner: A) -> Self::LayeredAccess {
-        observe::MetricsLayer::new(self.interceptor.clone()).layer(inner)
+        let mut layer = observe::MetricsLayer::new(self.interceptor.clone());
+        if let Some(path_label) = self.path_label.clone() {
+            layer = layer.with_path_label(path_label);
+        }
+        layer.layer(inner)
     }
 }
 
@@ -104,6 +110,7 @@ pub struct PrometheusClientLayerBuilder {
     entries_rate_buckets: Vec<f64>,
     duration_seconds_buckets: Vec<f64>,
     ttfb_buckets: Vec<f64>,
+    path_label: Option<observe::MetricPathLabel>,
     disable_label_root: bool,
 }
 
@@ -116,6 +123,7 @@ impl Default for PrometheusClientLayerBuilder {
             entries_rate_buckets: observe::DEFAULT_ENTRIES_RATE_BUCKETS.to_vec(),
             duration_seconds_buckets: observe::DEFAULT_DURATION_SECONDS_BUCKETS.to_vec(),
             ttfb_buckets: observe::DEFAULT_TTFB_BUCKETS.to_vec(),
+            path_label: None,
             disable_label_root: false,
         }
     }
@@ -170,6 +178,12 @@ impl PrometheusClientLayerBuilder {
         self
     }
 
+    /// Label operation metrics by the path with given [`observe::MetricPathLabel`].
+    pub fn path_label(mut self, path_label: observe::MetricPathLabel) -> Self {
+        self.path_label = Some(path_label);
+        self
+    }
+
     /// The 'root' label might have risks of being high cardinality, users can choose to disable it
     /// when they found it's not useful for their metrics.
     pub fn disable_label_root(mut self, disable: bool) -> Self {
@@ -226,6 +240,10 @@ impl PrometheusClientLayerBuilder {
             Family::<OperationLabels, Histogram, _>::new_with_constructor(HistogramConstructor {
                 buckets: self.ttfb_buckets.clone(),
             });
+        let operation_list_page_seconds =
+            Family::<OperationLabels, Histogram, _>::new_with_constructor(HistogramConstructor {
+                buckets: self.ttfb_buckets.clone(),
+            });
 
         let http_executing = Family::<OperationLabels, Gauge>::default();
         let http_request_bytes =
@@ -254,6 +272,9 @@ impl PrometheusClientLayerBuilder {
             });
         let http_connection_errors_total = Family::<OperationLabels, Counter>::default();
         let http_status_errors_total = Family::<OperationLabels, Counter>::default();
+        let http_request_cost_total = Family::<OperationLabels, Counter<f64, AtomicU64>>::default();
+        let read_coalesce_saved_requests_total = Family::<OperationLabels, Counter>::default();
+        let read_coalesce_saved_bytes_total = Family::<OperationLabels, Counter>::default();
 
         register_metric(
             registry,
@@ -295,6 +316,11 @@ impl PrometheusClientLayerBuilder {
             operation_ttfb_seconds.clone(),
             observe::MetricValue::OperationTtfbSeconds(Duration::default()),
         );
+        register_metric(
+            registry,
+            operation_list_page_seconds.clone(),
+            observe::MetricValue::OperationListPageSeconds(Duration::default()),
+        );
 
         register_metric(
             registry,
@@ -341,6 +367,21 @@ impl PrometheusClientLayerBuilder {
             http_status_errors_total.clone(),
             observe::MetricValue::HttpStatusErrorsTotal,
         );
+        register_metric(
+            registry,
+            http_request_cost_total.clone(),
+            observe::MetricValue::HttpRequestCost(0.0),
+        );
+        register_metric(
+            registry,
+            read_coalesce_saved_requests_total.clone(),
+            observe::MetricValue::ReadCoalesceSavedRequests(0),
+        );
+        register_metric(
+            registry,
+            read_coalesce_saved_bytes_total.clone(),
+            observe::MetricValue::ReadCoalesceSavedBytes(0),
+        );
 
         PrometheusClientLayer {
             interceptor: PrometheusClientInterceptor {
@@ -352,6 +393,7 @@ impl PrometheusClientLayerBuilder {
                 operation_errors_total,
                 operation_executing,
                 operation_ttfb_seconds,
+                operation_list_page_seconds,
 
                 http_executing,
                 http_request_bytes,
@@ -362,9 +404,13 @@ impl PrometheusClientLayerBuilder {
                 http_response_duration_seconds,
                 http_connection_errors_total,
                 http_status_errors_total,
+                http_request_cost_total,
+                read_coalesce_saved_requests_total,
+                read_coalesce_saved_bytes_total,
 
                 disable_label_root: self.disable_label_root,
             },
+            path_label: self.path_label,
         }
     }
 }
@@ -390,6 +436,7 @@ pub struct PrometheusClientInterceptor {
     operation_errors_total: Family<OperationLabels, Counter>,
     operation_executing: Family<OperationLabels, Gauge>,
     operation_ttfb_seconds: Family<OperationLabels, Histogram, HistogramConstructor>,
+    operation_list_page_seconds: Family<OperationLabels, Histogram, HistogramConstructor>,
 
     http_executing: Family<OperationLabels, Gauge>,
     http_request_bytes: Family<OperationLabels, Histogram, HistogramConstructor>,
@@ -400,6 +447,9 @@ pub struct PrometheusClientInterceptor {
     http_response_duration_seconds: Family<OperationLabels, Histogram, HistogramConstructor>,
     http_connection_errors_total: Family<OperationLabels, Counter>,
     http_status_errors_total: Family<OperationLabels, Counter>,
+    http_request_cost_total: Family<OperationLabels, Counter<f64, AtomicU64>>,
+    read_coalesce_saved_requests_total: Family<OperationLabels, Counter>,
+    read_coalesce_saved_bytes_total: Family<OperationLabels, Counter>,
 
     disable_label_root: bool,
 }
@@ -442,6 +492,10 @@ impl observe::MetricsIntercept for PrometheusClientInterceptor {
                 .operation_ttfb_seconds
                 .get_or_create(&labels)
                 .observe(v.as_secs_f64()),
+            observe::MetricValue::OperationListPageSeconds(v) => self
+                .operation_list_page_seconds
+                .get_or_create(&labels)
+                .observe(v.as_secs_f64()),
 
             observe::MetricValue::HttpExecuting(v) => {
                 self.http_executing.get_or_create(&labels).inc_by(v as i64);
@@ -478,6 +532,21 @@ impl observe::MetricsIntercept for PrometheusClientInterceptor {
             observe::MetricValue::HttpStatusErrorsTotal => {
                 self.http_status_errors_total.get_or_create(&labels).inc();
             }
+            observe::MetricValue::HttpRequestCost(v) => {
+                self.http_request_cost_total
+                    .get_or_create(&labels)
+                    .inc_by(v);
+            }
+            observe::MetricValue::ReadCoalesceSavedRequests(v) => {
+                self.read_coalesce_saved_requests_total
+                    .get_or_create(&labels)
+                    .inc_by(v);
+            }
+            observe::MetricValue::ReadCoalesceSavedBytes(v) => {
+                self.read_coalesce_saved_bytes_total
+                    .get_or_create(&labels)
+                    .inc_by(v);
+            }
         };
     }
 }
@@ -497,6 +566,9 @@ impl EncodeLabelSet for OperationLabels {
             (observe::LABEL_ROOT, self.labels.root.as_ref()).encode(encoder.encode_label())?;
         }
         (observe::LABEL_OPERATION, self.labels.operation).encode(encoder.encode_label())?;
+        if let Some(path) = &self.labels.path {
+            (observe::LABEL_PATH, path.as_ref()).encode(encoder.encode_label())?;
+        }
 
         if let Some(error) = &self.labels.error {
             (observe::LABEL_ERROR, error.into_static()).encode(encoder.encode_label())?;
@@ -0,0 +1,984 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::raw::*;
+use crate::*;
+
+/// Add storage quota for the underlying services.
+///
+/// # Quota
+///
+/// `QuotaLayer` tracks the bytes and objects stored under configured
+/// prefixes, and rejects calls that would exceed their [`QuotaLimit`] with
+/// [`ErrorKind::RateLimited`]. Prefixes are treated as directories, so
+/// `tenant-a` covers every path under `tenant-a/`, and the empty prefix
+/// covers the whole operator. A path will be checked against every prefix
+/// that covers it.
+///
+/// Usage will be accounted as follows:
+///
+/// - `write` stats the path first, so overwriting an object only counts the
+///   difference in size. Bytes (and the object itself if it's new) are
+///   reserved while writing and only committed once the writer closed
+///   successfully, so aborted or failed (multipart) writes will not be
+///   counted. Every write reloads the usage from the store and checks it
+///   together with the reservations of other in-progress writers, so
+///   concurrent writers can't exceed the limit together.
+/// - `delete` stats the paths before deleting them and releases their usage.
+/// - `copy` and `rename` account for the target (and the source of rename)
+///   by their sizes.
+///
+/// Usage of objects written before enabling this layer or written without it
+/// will not be tracked.
+///
+/// # Store
+///
+/// Counters are kept in a [`QuotaStore`]. [`MemoryQuotaStore`] keeps them in
+/// memory, and [`OperatorQuotaStore`] persists them into a file of another
+/// [`Operator`]. Current usage can be fetched by [`QuotaLayer::usage`], for
+/// example to report filesystem statistics.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::MemoryQuotaStore;
+/// # use opendal::layers::QuotaLayer;
+/// # use opendal::layers::QuotaLimit;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(QuotaLayer::new(MemoryQuotaStore::default()).with_limit(
+///         "tenant-a",
+///         QuotaLimit {
+///             max_bytes: Some(1024 * 1024 * 1024),
+///             max_objects: Some(10000),
+///         },
+///     ))
+///     .finish();
+/// Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct QuotaLayer<S: QuotaStore = MemoryQuotaStore> {
+    store: Arc<S>,
+    limits: Vec<(String, QuotaLimit)>,
+    pending: Arc<Mutex<HashMap<String, QuotaUsage>>>,
+}
+
+impl<S: QuotaStore> Clone for QuotaLayer<S> {
+    fn clone(&self) -> Self {
+        Self {
+            store: self.store.clone(),
+            limits: self.limits.clone(),
+            pending: self.pending.clone(),
+        }
+    }
+}
+
+impl<S: QuotaStore> QuotaLayer<S> {
+    /// Create a new `QuotaLayer` that keeps usage in the given store.
+    pub fn new(store: S) -> Self {
+        Self {
+            store: Arc::new(store),
+            limits: Vec::new(),
+            pending: Arc::default(),
+        }
+    }
+
+    /// Set the limit for the given prefix.
+    ///
+    /// Setting limit for the same prefix again will replace the previous one.
+    pub fn with_limit(mut self, prefix: &str, limit: QuotaLimit) -> Self {
+        let prefix = normalize_quota_prefix(prefix);
+        self.limits.retain(|(p, _)| p != &prefix);
+        self.limits.push((prefix, limit));
+        self
+    }
+
+    /// Fetch the current usage of the given prefix from the store.
+    pub async fn usage(&self, prefix: &str) -> Result<QuotaUsage> {
+        self.store.load(&normalize_quota_prefix(prefix)).await
+    }
+}
+
+impl<A: Access, S: QuotaStore> Layer<A> for QuotaLayer<S> {
+    type LayeredAccess = QuotaAccessor<A, S>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        QuotaAccessor {
+            inner: Arc::new(inner),
+            core: Arc::new(QuotaCore {
+                store: self.store.clone(),
+                limits: self.limits.clone(),
+                pending: self.pending.clone(),
+            }),
+        }
+    }
+}
+
+/// The limit of a quota prefix.
+///
+/// `None` means no limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QuotaLimit {
+    /// The max bytes that could be stored under the prefix.
+    pub max_bytes: Option<u64>,
+    /// The max objects that could be stored under the prefix.
+    pub max_objects: Option<u64>,
+}
+
+/// The usage of a quota prefix.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuotaUsage {
+    /// The bytes stored under the prefix.
+    pub bytes: u64,
+    /// The objects stored under the prefix.
+    pub objects: u64,
+}
+
+impl QuotaUsage {
+    fn apply(&mut self, bytes: i64, objects: i64) {
+        self.bytes = self.bytes.saturating_add_signed(bytes);
+        self.objects = self.objects.saturating_add_signed(objects);
+    }
+}
+
+/// QuotaStore keeps the usage counters of [`QuotaLayer`].
+pub trait QuotaStore: Debug + Send + Sync + Unpin + 'static {
+    /// Load the usage of the given prefix.
+    ///
+    /// Prefixes that have never been updated should return the default usage.
+    fn load(&self, prefix: &str) -> impl Future<Output = Result<QuotaUsage>> + MaybeSend;
+
+    /// Add the given deltas to the usage of the prefix and return the updated
+    /// usage.
+    fn update(
+        &self,
+        prefix: &str,
+        bytes: i64,
+        objects: i64,
+    ) -> impl Future<Output = Result<QuotaUsage>> + MaybeSend;
+}
+
+/// MemoryQuotaStore keeps usage in memory.
+///
+/// Usage will be lost after the store dropped.
+#[derive(Debug, Default)]
+pub struct MemoryQuotaStore {
+    usages: Mutex<HashMap<String, QuotaUsage>>,
+}
+
+impl QuotaStore for MemoryQuotaStore {
+    async fn load(&self, prefix: &str) -> Result<QuotaUsage> {
+        let usages = self.usages.lock().expect("lock must succeed");
+        Ok(usages.get(prefix).copied().unwrap_or_default())
+    }
+
+    async fn update(&self, prefix: &str, bytes: i64, objects: i64) -> Result<QuotaUsage> {
+        let mut usages = self.usages.lock().expect("lock must succeed");
+        let usage = usages.entry(prefix.to_string()).or_default();
+        usage.apply(bytes, objects);
+        Ok(*usage)
+    }
+}
+
+/// OperatorQuotaStore persists usage into a JSON file of the given operator.
+///
+/// Updates are serialized within this store, please don't share the same
+/// file between different stores.
+#[derive(Debug)]
+pub struct OperatorQuotaStore {
+    op: Operator,
+    path: String,
+    lock: tokio::sync::Mutex<()>,
+}
+
+impl OperatorQuotaStore {
+    /// Create a new `OperatorQuotaStore` that stores usage at `path` of `op`.
+    pub fn new(op: Operator, path: &str) -> Self {
+        Self {
+            op,
+            path: path.to_string(),
+            lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    async fn read(&self) -> Result<HashMap<String, QuotaUsage>> {
+        match self.op.read(&self.path).await {
+            Ok(bs) => serde_json::from_slice(&bs.to_bytes()).map_err(new_json_deserialize_error),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(HashMap::new()),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl QuotaStore for OperatorQuotaStore {
+    async fn load(&self, prefix: &str) -> Result<QuotaUsage> {
+        let _guard = self.lock.lock().await;
+        let usages = self.read().await?;
+        Ok(usages.get(prefix).copied().unwrap_or_default())
+    }
+
+    async fn update(&self, prefix: &str, bytes: i64, objects: i64) -> Result<QuotaUsage> {
+        let _guard = self.lock.lock().await;
+        let mut usages = self.read().await?;
+        let usage = usages.entry(prefix.to_string()).or_default();
+        usage.apply(bytes, objects);
+        let usage = *usage;
+
+        let bs = serde_json::to_vec(&usages).map_err(new_json_serialize_error)?;
+        self.op.write(&self.path, bs).await?;
+        Ok(usage)
+    }
+}
+
+/// Normalize prefix into `""` or `abc/def/`.
+fn normalize_quota_prefix(prefix: &str) -> String {
+    normalize_root(prefix).trim_start_matches('/').to_string()
+}
+
+#[derive(Debug)]
+struct QuotaCore<S> {
+    store: Arc<S>,
+    limits: Vec<(String, QuotaLimit)>,
+    /// Bytes and objects reserved by in-progress writers for every prefix.
+    pending: Arc<Mutex<HashMap<String, QuotaUsage>>>,
+}
+
+impl<S: QuotaStore> QuotaCore<S> {
+    /// Return the indexes of limits that cover the given path.
+    fn matched(&self, path: &str) -> Vec<usize> {
+        self.limits
+            .iter()
+            .enumerate()
+            .filter(|(_, (prefix, _))| path.starts_with(prefix.as_str()))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Collect the deltas for every limit that covers the given path.
+    fn collect(&self, changes: &mut Vec<(usize, i64, i64)>, path: &str, bytes: i64, objects: i64) {
+        for idx in self.matched(path) {
+            match changes.iter_mut().find(|(i, _, _)| *i == idx) {
+                Some((_, b, o)) => {
+                    *b += bytes;
+                    *o += objects;
+                }
+                None => changes.push((idx, bytes, objects)),
+            }
+        }
+    }
+
+    fn pending(&self, idx: usize) -> QuotaUsage {
+        let pending = self.pending.lock().expect("lock must succeed");
+        pending
+            .get(&self.limits[idx].0)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Reserve `bytes` and `objects` for the given limits if none of them
+    /// would be exceeded.
+    ///
+    /// `usages` must be loaded from the store right before, so that usage
+    /// committed by other writers is counted. The check and the reservation
+    /// happen under the same lock, so concurrent writers can't pass the check
+    /// together. `delta` and `reserved` are passed to [`Self::check_limit`].
+    fn try_reserve(
+        &self,
+        op: Operation,
+        usages: &[(usize, QuotaUsage)],
+        delta: i64,
+        reserved: u64,
+        bytes: u64,
+        objects: u64,
+    ) -> Result<()> {
+        let mut pending = self.pending.lock().expect("lock must succeed");
+        for (idx, usage) in usages {
+            let current = pending
+                .get(&self.limits[*idx].0)
+                .copied()
+                .unwrap_or_default();
+            self.check_limit(op, *idx, usage, &current, delta, objects as i64, reserved)?;
+        }
+        for (idx, _) in usages {
+            let v = pending.entry(self.limits[*idx].0.clone()).or_default();
+            v.bytes += bytes;
+            v.objects += objects;
+        }
+        Ok(())
+    }
+
+    fn release(&self, idx: usize, bytes: u64, objects: u64) {
+        let mut pending = self.pending.lock().expect("lock must succeed");
+        if let Some(v) = pending.get_mut(&self.limits[idx].0) {
+            v.bytes = v.bytes.saturating_sub(bytes);
+            v.objects = v.objects.saturating_sub(objects);
+        }
+    }
+
+    /// Load the usage of the given limits from the store.
+    async fn load(&self, matched: &[usize]) -> Result<Vec<(usize, QuotaUsage)>> {
+        let mut usages = Vec::with_capacity(matched.len());
+        for idx in matched {
+            let usage = self.store.load(&self.limits[*idx].0).await?;
+            usages.push((*idx, usage));
+        }
+        Ok(usages)
+    }
+
+    /// Check if adding the deltas to the usage would exceed the limit.
+    ///
+    /// `pending` is the usage reserved by in-progress writers, and `reserved`
+    /// is the bytes reserved by the caller itself, which has been included in
+    /// both `pending` and `bytes` already.
+    #[allow(clippy::too_many_arguments)]
+    fn check_limit(
+        &self,
+        op: Operation,
+        idx: usize,
+        usage: &QuotaUsage,
+        pending: &QuotaUsage,
+        bytes: i64,
+        objects: i64,
+        reserved: u64,
+    ) -> Result<()> {
+        let (prefix, limit) = &self.limits[idx];
+
+        if let Some(max) = limit.max_bytes {
+            let current = (usage.bytes + pending.bytes).saturating_sub(reserved);
+            if bytes > 0 && current.saturating_add_signed(bytes) > max {
+                return Err(new_quota_exceeded_error(
+                    op,
+                    prefix,
+                    "max_bytes",
+                    max,
+                    usage,
+                ));
+            }
+        }
+        if let Some(max) = limit.max_objects {
+            let current = usage.objects + pending.objects;
+            if objects > 0 && current.saturating_add_signed(objects) > max {
+                return Err(new_quota_exceeded_error(
+                    op,
+                    prefix,
+                    "max_objects",
+                    max,
+                    usage,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    async fn check(&self, op: Operation, changes: &[(usize, i64, i64)]) -> Result<()> {
+        for (idx, bytes, objects) in changes {
+            if *bytes <= 0 && *objects <= 0 {
+                continue;
+            }
+            let usage = self.store.load(&self.limits[*idx].0).await?;
+            let pending = self.pending(*idx);
+            self.check_limit(op, *idx, &usage, &pending, *bytes, *objects, 0)?;
+        }
+        Ok(())
+    }
+
+    async fn commit(&self, changes: &[(usize, i64, i64)]) -> Result<()> {
+        for (idx, bytes, objects) in changes {
+            if *bytes == 0 && *objects == 0 {
+                continue;
+            }
+            self.store
+                .update(&self.limits[*idx].0, *bytes, *objects)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+fn new_quota_exceeded_error(
+    op: Operation,
+    prefix: &str,
+    limit: &'static str,
+    max: u64,
+    usage: &QuotaUsage,
+) -> Error {
+    Error::new(ErrorKind::RateLimited, "quota exceeded")
+        .with_operation(op)
+        .with_context("prefix", prefix)
+        .with_context(limit, max)
+        .with_context("bytes", usage.bytes)
+        .with_context("objects", usage.objects)
+}
+
+/// Stat the size of the given path, returns `None` if not exist.
+async fn stat_size<A: Access>(inner: &A, path: &str) -> Result<Option<u64>> {
+    match inner.stat(path, OpStat::new()).await {
+        Ok(rp) => Ok(Some(rp.into_metadata().content_length())),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+#[derive(Debug)]
+pub struct QuotaAccessor<A: Access, S: QuotaStore> {
+    inner: Arc<A>,
+    core: Arc<QuotaCore<S>>,
+}
+
+impl<A: Access, S: QuotaStore> LayeredAccess for QuotaAccessor<A, S> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type Writer = QuotaWriter<A::Writer, S>;
+    type Lister = A::Lister;
+    type Deleter = QuotaDeleter<A, S>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let matched = self.core.matched(path);
+        if matched.is_empty() {
+            let (rp, w) = self.inner.write(path, args).await?;
+            return Ok((
+                rp,
+                QuotaWriter::new(w, self.core.clone(), Vec::new(), 0, false),
+            ));
+        }
+
+        let existing = stat_size(self.inner.as_ref(), path).await?;
+        let replaced = if args.append() {
+            0
+        } else {
+            existing.unwrap_or_default()
+        };
+        let new_object = existing.is_none();
+
+        // Reserve the new object until the writer closed, so that concurrent
+        // writers can't exceed `max_objects` together.
+        if new_object {
+            let usages = self.core.load(&matched).await?;
+            self.core
+                .try_reserve(Operation::Write, &usages, 0, 0, 0, 1)?;
+        }
+
+        let (rp, w) = match self.inner.write(path, args).await {
+            Ok(v) => v,
+            Err(err) => {
+                for idx in &matched {
+                    self.core.release(*idx, 0, new_object as u64);
+                }
+                return Err(err);
+            }
+        };
+        Ok((
+            rp,
+            QuotaWriter::new(w, self.core.clone(), matched, replaced, new_object),
+        ))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        if self.core.matched(to).is_empty() {
+            return self.inner.copy(from, to, args).await;
+        }
+        // Let the underlying services report the error if source not exist.
+        let Some(size) = stat_size(self.inner.as_ref(), from).await? else {
+            return self.inner.copy(from, to, args).await;
+        };
+        let existing = stat_size(self.inner.as_ref(), to).await?;
+
+        let mut changes = Vec::new();
+        self.core.collect(
+            &mut changes,
+            to,
+            size as i64 - existing.unwrap_or_default() as i64,
+            existing.is_none() as i64,
+        );
+        self.core.check(Operation::Copy, &changes).await?;
+
+        let rp = self.inner.copy(from, to, args).await?;
+        self.core.commit(&changes).await?;
+        Ok(rp)
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        if self.core.matched(from).is_empty() && self.core.matched(to).is_empty() {
+            return self.inner.rename(from, to, args).await;
+        }
+        // Let the underlying services report the error if source not exist.
+        let Some(size) = stat_size(self.inner.as_ref(), from).await? else {
+            return self.inner.rename(from, to, args).await;
+        };
+        let existing = stat_size(self.inner.as_ref(), to).await?;
+
+        let mut changes = Vec::new();
+        self.core.collect(&mut changes, from, -(size as i64), -1);
+        self.core.collect(
+            &mut changes,
+            to,
+            size as i64 - existing.unwrap_or_default() as i64,
+            existing.is_none() as i64,
+        );
+        self.core.check(Operation::Rename, &changes).await?;
+
+        let rp = self.inner.rename(from, to, args).await?;
+        self.core.commit(&changes).await?;
+        Ok(rp)
+    }
+
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        if self.core.matched(path).is_empty() {
+            return self.inner.concat(path, args).await;
+        }
+        let mut size = 0;
+        for source in args.sources() {
+            // Let the underlying services report the error if source not exist.
+            let Some(v) = stat_size(self.inner.as_ref(), source).await? else {
+                return self.inner.concat(path, args).await;
+            };
+            size += v;
+        }
+        let existing = stat_size(self.inner.as_ref(), path).await?;
+
+        // Sources are kept, so only the target is accounted like a write.
+        let mut changes = Vec::new();
+        self.core.collect(
+            &mut changes,
+            path,
+            size as i64 - existing.unwrap_or_default() as i64,
+            existing.is_none() as i64,
+        );
+        self.core.check(Operation::Write, &changes).await?;
+
+        let rp = self.inner.concat(path, args).await?;
+        self.core.commit(&changes).await?;
+        Ok(rp)
+    }
+
+    async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        let (rp, d) = self.inner.delete().await?;
+        Ok((
+            rp,
+            QuotaDeleter {
+                inner: d,
+                accessor: self.inner.clone(),
+                core: self.core.clone(),
+                queue: VecDeque::new(),
+            },
+        ))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+}
+
+/// QuotaWriter reserves bytes while writing and commits usage after closed.
+pub struct QuotaWriter<W, S: QuotaStore> {
+    inner: W,
+    core: Arc<QuotaCore<S>>,
+    /// The limits that cover the path.
+    matched: Vec<usize>,
+    /// The size of the object that will be replaced by this write.
+    replaced: u64,
+    /// Whether this write creates a new object, which has been reserved
+    /// while opening.
+    new_object: bool,
+
+    written: u64,
+    reserved: u64,
+}
+
+impl<W, S: QuotaStore> QuotaWriter<W, S> {
+    fn new(
+        inner: W,
+        core: Arc<QuotaCore<S>>,
+        matched: Vec<usize>,
+        replaced: u64,
+        new_object: bool,
+    ) -> Self {
+        Self {
+            inner,
+            core,
+            matched,
+            replaced,
+            new_object,
+            written: 0,
+            reserved: 0,
+        }
+    }
+
+    fn release(&mut self) {
+        if self.reserved == 0 && !self.new_object {
+            return;
+        }
+        for idx in &self.matched {
+            self.core
+                .release(*idx, self.reserved, self.new_object as u64);
+        }
+        self.reserved = 0;
+        self.new_object = false;
+    }
+}
+
+impl<W, S: QuotaStore> Drop for QuotaWriter<W, S> {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+impl<W: oio::Write, S: QuotaStore> oio::Write for QuotaWriter<W, S> {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        let size = bs.len() as u64;
+        if !self.matched.is_empty() {
+            // Reload usage so that bytes committed by other writers since
+            // opening are counted.
+            let usages = self.core.load(&self.matched).await?;
+            let delta = (self.reserved + size) as i64 - self.replaced as i64;
+            self.core
+                .try_reserve(Operation::Write, &usages, delta, self.reserved, size, 0)?;
+            self.reserved += size;
+        }
+
+        self.inner.write(bs).await?;
+        self.written += size;
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        let res = self.inner.close().await;
+        if res.is_ok() {
+            let bytes = self.written as i64 - self.replaced as i64;
+            let objects = self.new_object as i64;
+            for idx in &self.matched {
+                if let Err(err) = self
+                    .core
+                    .store
+                    .update(&self.core.limits[*idx].0, bytes, objects)
+                    .await
+                {
+                    self.release();
+                    return Err(err);
+                }
+            }
+        }
+        self.release();
+        res
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.release();
+        self.inner.abort().await
+    }
+}
+
+/// QuotaDeleter releases usage of the deleted paths.
+pub struct QuotaDeleter<A: Access, S: QuotaStore> {
+    inner: A::Deleter,
+    accessor: Arc<A>,
+    core: Arc<QuotaCore<S>>,
+    queue: VecDeque<QuotaDeleteEntry>,
+}
+
+struct QuotaDeleteEntry {
+    path: String,
+    /// Whether this path is covered by any limit.
+    tracked: bool,
+    /// The size of the path, `None` if not stated yet or not exist.
+    size: Option<u64>,
+    stated: bool,
+}
+
+impl<A: Access, S: QuotaStore> oio::Delete for QuotaDeleter<A, S> {
+    fn delete(&mut self, path: &str, args: OpDelete) -> Result<()> {
+        // Deleting a version or a dir won't change the usage of objects.
+        let tracked =
+            args.version().is_none() && !path.ends_with('/') && !self.core.matched(path).is_empty();
+        self.inner.delete(path, args)?;
+        self.queue.push_back(QuotaDeleteEntry {
+            path: path.to_string(),
+            tracked,
+            size: None,
+            stated: false,
+        });
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<usize> {
+        for entry in self.queue.iter_mut() {
+            if entry.tracked && !entry.stated {
+                entry.size = stat_size(self.accessor.as_ref(), &entry.path).await?;
+                entry.stated = true;
+            }
+        }
+
+        let n = self.inner.flush().await?;
+
+        let mut changes = Vec::new();
+        for entry in self.queue.drain(..n.min(self.queue.len())) {
+            if let Some(size) = entry.size {
+                self.core
+                    .collect(&mut changes, &entry.path, -(size as i64), -1);
+            }
+        }
+        self.core.commit(&changes).await?;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raw::oio::Read;
+    use crate::raw::oio::Write;
+    use crate::services::Memory;
+
+    /// Memory with concat implemented by reading sources and writing the target.
+    #[derive(Debug)]
+    struct MockConcatAccessor {
+        inner: Accessor,
+    }
+
+    impl LayeredAccess for MockConcatAccessor {
+        type Inner = Accessor;
+        type Reader = oio::Reader;
+        type Writer = oio::Writer;
+        type Lister = oio::Lister;
+        type Deleter = oio::Deleter;
+
+        fn inner(&self) -> &Self::Inner {
+            &self.inner
+        }
+
+        fn info(&self) -> Arc<AccessorInfo> {
+            let info = self.inner.info();
+            info.update_full_capability(|mut cap| {
+                cap.concat = true;
+                cap
+            });
+            info
+        }
+
+        async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            self.inner.read(path, args).await
+        }
+
+        async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+            self.inner.write(path, args).await
+        }
+
+        async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+            self.inner.list(path, args).await
+        }
+
+        async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+            self.inner.delete().await
+        }
+
+        async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+            let mut content = vec![];
+            for source in args.sources() {
+                let (_, mut r) = self.inner.read(source, OpRead::new()).await?;
+                content.extend(r.read_all().await?.to_vec());
+            }
+            let (_, mut w) = self.inner.write(path, OpWrite::new()).await?;
+            w.write(content.into()).await?;
+            w.close().await?;
+            Ok(RpConcat::default())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_quota() -> Result<()> {
+        let layer = QuotaLayer::new(MemoryQuotaStore::default()).with_limit(
+            "tenant",
+            QuotaLimit {
+                max_bytes: Some(10),
+                max_objects: Some(2),
+            },
+        );
+        let op = Operator::new(Memory::default())?
+            .layer(layer.clone())
+            .finish();
+
+        op.write("tenant/a", "12345").await?;
+        op.write("other/a", "1234567890abc").await?;
+        assert_eq!(
+            layer.usage("tenant").await?,
+            QuotaUsage {
+                bytes: 5,
+                objects: 1
+            }
+        );
+
+        let err = op.write("tenant/b", "1234567").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+
+        // Overwrite only counts the difference.
+        op.write("tenant/a", "1234567890").await?;
+        assert_eq!(
+            layer.usage("tenant").await?,
+            QuotaUsage {
+                bytes: 10,
+                objects: 1
+            }
+        );
+
+        // Aborted writes are not counted.
+        op.write("tenant/a", "123").await?;
+        let mut w = op.writer("tenant/b").await?;
+        w.write("1234").await?;
+        w.abort().await?;
+        assert_eq!(
+            layer.usage("tenant").await?,
+            QuotaUsage {
+                bytes: 3,
+                objects: 1
+            }
+        );
+
+        op.write("tenant/b", "1").await?;
+        let err = op.write("tenant/c", "1").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+
+        op.delete("tenant/a").await?;
+        assert_eq!(
+            layer.usage("tenant").await?,
+            QuotaUsage {
+                bytes: 1,
+                objects: 1
+            }
+        );
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_quota_interleaved_writers() -> Result<()> {
+        let layer = QuotaLayer::new(MemoryQuotaStore::default()).with_limit(
+            "tenant",
+            QuotaLimit {
+                max_bytes: Some(10),
+                max_objects: Some(2),
+            },
+        );
+        let op = Operator::new(Memory::default())?
+            .layer(layer.clone())
+            .finish();
+
+        let mut a = op.writer("tenant/a").await?;
+        let mut b = op.writer("tenant/b").await?;
+        // Both objects are reserved, so a third writer can't be opened.
+        let err = op
+            .writer("tenant/c")
+            .await
+            .err()
+            .expect("must exceed max_objects");
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+
+        a.write("123456").await?;
+        a.close().await?;
+        // Bytes committed by `a` after `b` opened are still counted.
+        let err = b.write("123456").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+        b.write("1234").await?;
+        b.close().await?;
+        assert_eq!(
+            layer.usage("tenant").await?,
+            QuotaUsage {
+                bytes: 10,
+                objects: 2
+            }
+        );
+
+        // Aborted writers release the reserved object.
+        op.delete("tenant/b").await?;
+        let mut c = op.writer("tenant/c").await?;
+        c.abort().await?;
+        drop(c);
+        op.write("tenant/d", "1").await?;
+        assert_eq!(
+            layer.usage("tenant").await?,
+            QuotaUsage {
+                bytes: 7,
+                objects: 2
+            }
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_quota_concat() -> Result<()> {
+        let layer = QuotaLayer::new(MemoryQuotaStore::default()).with_limit(
+            "tenant",
+            QuotaLimit {
+                max_bytes: Some(10),
+                max_objects: Some(3),
+            },
+        );
+        let inner = MockConcatAccessor {
+            inner: Operator::new(Memory::default())?.finish().into_inner(),
+        };
+        let op = OperatorBuilder::new(inner).layer(layer.clone()).finish();
+
+        op.write("tenant/a", "123").await?;
+        op.write("tenant/b", "45").await?;
+        op.concat(["tenant/a", "tenant/b"], "tenant/c").await?;
+        assert_eq!(
+            layer.usage("tenant").await?,
+            QuotaUsage {
+                bytes: 10,
+                objects: 3
+            }
+        );
+
+        // Concat onto an existing target only counts the size difference.
+        op.concat(["tenant/b", "tenant/b"], "tenant/c").await?;
+        assert_eq!(
+            layer.usage("tenant").await?,
+            QuotaUsage {
+                bytes: 9,
+                objects: 3
+            }
+        );
+
+        let err = op
+            .concat(["tenant/a", "tenant/a"], "tenant/c")
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+        assert_eq!(op.read("tenant/c").await?.to_vec(), b"4545");
+        Ok(())
+    }
+}
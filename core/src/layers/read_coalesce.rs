@@ -0,0 +1,571 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::ops::Range;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use futures::channel::oneshot;
+
+use crate::raw::oio::Read;
+use crate::raw::*;
+use crate::*;
+
+/// The default time window to wait for other reads: 1ms.
+const DEFAULT_WINDOW: Duration = Duration::from_millis(1);
+/// The default max size of a coalesced request: 8MiB.
+const DEFAULT_MAX_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Coalesce ranged reads of the same file from concurrent readers.
+///
+/// # Coalesce
+///
+/// [`Reader::fetch`] merges ranges within a single call, but concurrent callers
+/// reading nearby ranges of the same file, like the column readers of a parquet
+/// file, still send separate requests.
+///
+/// `ReadCoalesceLayer` schedules ranged reads by path and version. The first read
+/// waits for a small time window, reads that arrive within the window and overlap
+/// or are adjacent to the pending range (within the configured gap) join it.
+/// After the window, a single ranged read is sent for the whole range and the
+/// data is fanned out to all callers.
+///
+/// Reads will be sent as is when:
+///
+/// - The range is open-ended, those reads are not accounted.
+/// - Conditions like `if_match` are set.
+/// - The range can't join the pending read without exceeding the max size.
+///
+/// If the coalesced read fails, every caller retries its own range, so that
+/// errors are reported to the caller that triggers them.
+///
+/// The time window is waited via `tokio::time::sleep`, so this layer requires a
+/// tokio runtime with time enabled.
+///
+/// # Metrics
+///
+/// Reads and requests are accounted in [`ReadCoalesceSnapshot`], which could be
+/// fetched by [`ReadCoalesceLayer::snapshot`].
+///
+/// The requests and bytes saved by a coalesced read will also be attached to its
+/// reply as [`ReadCoalesceSaved`]. Metrics layers like `PrometheusLayer` will export
+/// them as `opendal_read_coalesce_saved_requests_total` and
+/// `opendal_read_coalesce_saved_bytes_total` if they are added after this layer.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::time::Duration;
+///
+/// # use opendal::layers::ReadCoalesceLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<()> {
+/// let coalesce = ReadCoalesceLayer::new()
+///     .with_window(Duration::from_millis(2))
+///     .with_max_size(16 * 1024 * 1024);
+/// let op = Operator::new(services::Memory::default())?
+///     .layer(coalesce.clone())
+///     .finish();
+///
+/// let r = op.reader("test.parquet").await?;
+/// let _ = futures::join!(r.read(0..1024), r.read(1024..4096));
+/// println!("saved requests: {}", coalesce.snapshot().saved_requests());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReadCoalesceLayer {
+    window: Duration,
+    max_size: u64,
+    gap: u64,
+    snapshot: Arc<Mutex<ReadCoalesceSnapshot>>,
+}
+
+impl Default for ReadCoalesceLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReadCoalesceLayer {
+    /// Create a new `ReadCoalesceLayer`.
+    pub fn new() -> Self {
+        Self {
+            window: DEFAULT_WINDOW,
+            max_size: DEFAULT_MAX_SIZE,
+            gap: 0,
+            snapshot: Arc::default(),
+        }
+    }
+
+    /// Set the time window to wait for other reads.
+    ///
+    /// The default value is 1ms.
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Set the max size of a coalesced request.
+    ///
+    /// The default value is 8MiB.
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Set the max gap between ranges that can be coalesced.
+    ///
+    /// Bytes in the gap will be read and dropped. The default value is 0, which
+    /// means only overlapping and adjacent ranges will be coalesced.
+    pub fn with_gap(mut self, gap: u64) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Return the snapshot of the coalesced reads.
+    pub fn snapshot(&self) -> ReadCoalesceSnapshot {
+        *self.snapshot.lock().expect("lock must succeed")
+    }
+
+    /// Reset the snapshot of the coalesced reads.
+    pub fn reset(&self) {
+        *self.snapshot.lock().expect("lock must succeed") = ReadCoalesceSnapshot::default();
+    }
+}
+
+impl<A: Access> Layer<A> for ReadCoalesceLayer {
+    type LayeredAccess = ReadCoalesceAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        ReadCoalesceAccessor {
+            inner,
+            core: Arc::new(ReadCoalesceCore {
+                window: self.window,
+                max_size: self.max_size,
+                gap: self.gap,
+                snapshot: self.snapshot.clone(),
+                pending: Mutex::default(),
+            }),
+        }
+    }
+}
+
+/// The snapshot of reads accounted by [`ReadCoalesceLayer`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReadCoalesceSnapshot {
+    /// The number of ranged reads issued by callers.
+    pub reads: u64,
+    /// The number of reads served by a request sent for other callers.
+    pub coalesced_reads: u64,
+    /// The number of read requests sent to the underlying service.
+    pub requests: u64,
+    /// The bytes requested by callers.
+    pub bytes_requested: u64,
+    /// The bytes fetched from the underlying service.
+    pub bytes_fetched: u64,
+}
+
+impl ReadCoalesceSnapshot {
+    /// The number of requests saved by coalescing.
+    pub fn saved_requests(&self) -> u64 {
+        self.reads.saturating_sub(self.requests)
+    }
+
+    /// The bytes of overlapping ranges that have not been fetched again.
+    pub fn saved_bytes(&self) -> u64 {
+        self.bytes_requested.saturating_sub(self.bytes_fetched)
+    }
+}
+
+/// The requests and bytes saved by a coalesced read, attached to its reply.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReadCoalesceSaved {
+    /// The number of reads served without sending their own requests.
+    pub requests: u64,
+    /// The bytes of overlapping ranges that have not been fetched again.
+    pub bytes: u64,
+}
+
+type PendingKey = (String, Option<String>);
+
+/// A pending read that collects ranges until the window ends.
+struct PendingRead {
+    id: u64,
+    range: Range<u64>,
+    waiters: Vec<(Range<u64>, oneshot::Sender<Option<Buffer>>)>,
+}
+
+#[derive(Default)]
+struct PendingReads {
+    next_id: u64,
+    reads: HashMap<PendingKey, PendingRead>,
+}
+
+struct ReadCoalesceCore {
+    window: Duration,
+    max_size: u64,
+    gap: u64,
+    snapshot: Arc<Mutex<ReadCoalesceSnapshot>>,
+    pending: Mutex<PendingReads>,
+}
+
+impl Debug for ReadCoalesceCore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReadCoalesceCore")
+            .field("window", &self.window)
+            .field("max_size", &self.max_size)
+            .field("gap", &self.gap)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The way a read is scheduled.
+enum Schedule {
+    /// Send the read as is.
+    Direct,
+    /// Collect other reads and send the coalesced read.
+    Leader(u64),
+    /// Wait for the coalesced read sent by others.
+    Follower(oneshot::Receiver<Option<Buffer>>),
+}
+
+impl ReadCoalesceCore {
+    fn record(&self, f: impl FnOnce(&mut ReadCoalesceSnapshot)) {
+        f(&mut self.snapshot.lock().expect("lock must succeed"))
+    }
+
+    fn schedule(&self, key: &PendingKey, range: &Range<u64>) -> Schedule {
+        let mut pending = self.pending.lock().expect("lock must succeed");
+
+        let Some(read) = pending.reads.get_mut(key) else {
+            pending.next_id += 1;
+            let id = pending.next_id;
+            pending.reads.insert(
+                key.clone(),
+                PendingRead {
+                    id,
+                    range: range.clone(),
+                    waiters: vec![],
+                },
+            );
+            return Schedule::Leader(id);
+        };
+
+        let start = read.range.start.min(range.start);
+        let end = read.range.end.max(range.end);
+        let joinable = range.start <= read.range.end + self.gap
+            && read.range.start <= range.end + self.gap
+            && end - start <= self.max_size;
+        if !joinable {
+            return Schedule::Direct;
+        }
+
+        let (tx, rx) = oneshot::channel();
+        read.range = start..end;
+        read.waiters.push((range.clone(), tx));
+        Schedule::Follower(rx)
+    }
+
+    /// Take the pending read out so that no other reads can join it.
+    fn take(&self, key: &PendingKey, id: u64) -> Option<PendingRead> {
+        let mut pending = self.pending.lock().expect("lock must succeed");
+        match pending.reads.get(key) {
+            Some(read) if read.id == id => pending.reads.remove(key),
+            _ => None,
+        }
+    }
+}
+
+/// Make sure the pending read will be removed if the leader is dropped
+/// before sending the coalesced read.
+struct PendingGuard<'a> {
+    core: &'a ReadCoalesceCore,
+    key: &'a PendingKey,
+    id: u64,
+}
+
+impl Drop for PendingGuard<'_> {
+    fn drop(&mut self) {
+        let _ = self.core.take(self.key, self.id);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReadCoalesceAccessor<A: Access> {
+    inner: A,
+    core: Arc<ReadCoalesceCore>,
+}
+
+impl<A: Access> ReadCoalesceAccessor<A> {
+    async fn read_direct(
+        &self,
+        path: &str,
+        args: OpRead,
+    ) -> Result<(RpRead, TwoWays<A::Reader, Buffer>)> {
+        let size = args.range().size().unwrap_or_default();
+        self.core.record(|s| {
+            s.requests += 1;
+            s.bytes_fetched += size;
+        });
+        let (rp, r) = self.inner.read(path, args).await?;
+        Ok((rp, TwoWays::One(r)))
+    }
+
+    /// Send the coalesced read and fan out the data to all waiters.
+    async fn read_coalesced(
+        &self,
+        path: &str,
+        args: OpRead,
+        key: &PendingKey,
+        id: u64,
+    ) -> Result<(RpRead, TwoWays<A::Reader, Buffer>)> {
+        let guard = PendingGuard {
+            core: &self.core,
+            key,
+            id,
+        };
+        tokio::time::sleep(self.core.window).await;
+        let Some(read) = self.core.take(key, id) else {
+            return self.read_direct(path, args).await;
+        };
+        drop(guard);
+
+        if read.waiters.is_empty() {
+            return self.read_direct(path, args).await;
+        }
+
+        let offset = args.range().offset();
+        let size = args.range().size().unwrap_or_default();
+        let span = read.range.clone();
+        self.core.record(|s| {
+            s.requests += 1;
+            s.bytes_fetched += span.end - span.start;
+        });
+
+        let res = async {
+            let (_, mut r) = self
+                .inner
+                .read(
+                    path,
+                    args.clone()
+                        .with_range(BytesRange::new(span.start, Some(span.end - span.start))),
+                )
+                .await?;
+            r.read_all().await
+        }
+        .await;
+
+        let slice = |buf: &Buffer, range: Range<u64>| {
+            let len = buf.len() as u64;
+            let start = (range.start - span.start).min(len);
+            let end = (range.end - span.start).min(len);
+            buf.slice(start as usize..end as usize)
+        };
+
+        match res {
+            Ok(buf) => {
+                let mut saved = ReadCoalesceSaved {
+                    requests: read.waiters.len() as u64,
+                    bytes: size,
+                };
+                for (range, tx) in read.waiters {
+                    saved.bytes += range.end - range.start;
+                    let _ = tx.send(Some(slice(&buf, range)));
+                }
+                saved.bytes = saved.bytes.saturating_sub(span.end - span.start);
+
+                let bs = slice(&buf, offset..offset + size);
+                let rp = RpRead::new()
+                    .with_size(Some(bs.len() as u64))
+                    .with_coalesced(Some(saved));
+                Ok((rp, TwoWays::Two(bs)))
+            }
+            Err(_) => {
+                // Let every caller retry its own range to get its own error.
+                for (_, tx) in read.waiters {
+                    let _ = tx.send(None);
+                }
+                self.read_direct(path, args).await
+            }
+        }
+    }
+}
+
+impl<A: Access> LayeredAccess for ReadCoalesceAccessor<A> {
+    type Inner = A;
+    type Reader = TwoWays<A::Reader, Buffer>;
+    type Writer = A::Writer;
+    type Lister = A::Lister;
+    type Deleter = A::Deleter;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let Some(size) = args.range().size() else {
+            let (rp, r) = self.inner.read(path, args).await?;
+            return Ok((rp, TwoWays::One(r)));
+        };
+        let offset = args.range().offset();
+        self.core.record(|s| {
+            s.reads += 1;
+            s.bytes_requested += size;
+        });
+
+        let conditional = args.if_match().is_some()
+            || args.if_none_match().is_some()
+            || args.if_modified_since().is_some()
+            || args.if_unmodified_since().is_some();
+        if conditional || size == 0 || size > self.core.max_size {
+            return self.read_direct(path, args).await;
+        }
+
+        let key = (path.to_string(), args.version().map(|v| v.to_string()));
+        match self.core.schedule(&key, &(offset..offset + size)) {
+            Schedule::Direct => self.read_direct(path, args).await,
+            Schedule::Leader(id) => self.read_coalesced(path, args, &key, id).await,
+            Schedule::Follower(rx) => match rx.await {
+                Ok(Some(bs)) => {
+                    self.core.record(|s| s.coalesced_reads += 1);
+                    let rp = RpRead::new().with_size(Some(bs.len() as u64));
+                    Ok((rp, TwoWays::Two(bs)))
+                }
+                _ => self.read_direct(path, args).await,
+            },
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        self.inner.delete().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layers::observe;
+    use crate::services;
+
+    async fn new_operator(layer: ReadCoalesceLayer) -> Result<Operator> {
+        let op = Operator::new(services::Memory::default())?
+            .layer(layer)
+            .finish();
+        let content: Vec<u8> = (0..4096u32).map(|v| v as u8).collect();
+        op.write("test", content).await?;
+        Ok(op)
+    }
+
+    #[tokio::test]
+    async fn test_coalesce_concurrent_reads() -> Result<()> {
+        let layer = ReadCoalesceLayer::new().with_window(Duration::from_millis(50));
+        let op = new_operator(layer.clone()).await?;
+        let content: Vec<u8> = (0..4096u32).map(|v| v as u8).collect();
+
+        let r = op.reader("test").await?;
+        let (a, b, c) = futures::join!(r.read(0..1024), r.read(512..2048), r.read(2048..3000));
+        assert_eq!(a?.to_vec(), content[0..1024]);
+        assert_eq!(b?.to_vec(), content[512..2048]);
+        assert_eq!(c?.to_vec(), content[2048..3000]);
+
+        let snapshot = layer.snapshot();
+        assert_eq!(snapshot.reads, 3);
+        assert_eq!(snapshot.requests, 1);
+        assert_eq!(snapshot.coalesced_reads, 2);
+        assert_eq!(snapshot.saved_requests(), 2);
+        assert_eq!(snapshot.bytes_requested, 1024 + 1536 + 952);
+        assert_eq!(snapshot.bytes_fetched, 3000);
+        assert_eq!(snapshot.saved_bytes(), 512);
+        Ok(())
+    }
+
+    #[derive(Debug, Clone, Default)]
+    struct MockInterceptor {
+        saved: Arc<Mutex<ReadCoalesceSaved>>,
+    }
+
+    impl observe::MetricsIntercept for MockInterceptor {
+        fn observe(&self, _: observe::MetricLabels, value: observe::MetricValue) {
+            let mut saved = self.saved.lock().unwrap();
+            match value {
+                observe::MetricValue::ReadCoalesceSavedRequests(v) => saved.requests += v,
+                observe::MetricValue::ReadCoalesceSavedBytes(v) => saved.bytes += v,
+                _ => {}
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_coalesce_metrics() -> Result<()> {
+        let interceptor = MockInterceptor::default();
+        let layer = ReadCoalesceLayer::new().with_window(Duration::from_millis(50));
+        let op = new_operator(layer.clone())
+            .await?
+            .layer(observe::MetricsLayer::new(interceptor.clone()));
+
+        let r = op.reader("test").await?;
+        let (a, b, c) = futures::join!(r.read(0..1024), r.read(512..2048), r.read(2048..3000));
+        a?;
+        b?;
+        c?;
+
+        assert_eq!(
+            *interceptor.saved.lock().unwrap(),
+            ReadCoalesceSaved {
+                requests: 2,
+                bytes: 512
+            }
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_coalesce_respects_gap_and_max_size() -> Result<()> {
+        let layer = ReadCoalesceLayer::new()
+            .with_window(Duration::from_millis(50))
+            .with_max_size(2048);
+        let op = new_operator(layer.clone()).await?;
+        let content: Vec<u8> = (0..4096u32).map(|v| v as u8).collect();
+
+        let r = op.reader("test").await?;
+        let (a, b, c) = futures::join!(r.read(0..1024), r.read(1100..2000), r.read(1024..3072));
+        assert_eq!(a?.to_vec(), content[0..1024]);
+        assert_eq!(b?.to_vec(), content[1100..2000]);
+        assert_eq!(c?.to_vec(), content[1024..3072]);
+
+        let snapshot = layer.snapshot();
+        assert_eq!(snapshot.reads, 3);
+        assert_eq!(snapshot.requests, 3);
+        assert_eq!(snapshot.saved_requests(), 0);
+        Ok(())
+    }
+}
@@ -0,0 +1,1098 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use futures::channel::oneshot;
+use futures::stream;
+use http::Request;
+use http::Response;
+use http::StatusCode;
+use http::Uri;
+use log::warn;
+use oio::Read;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+
+use super::abort_upload_args;
+use super::concat_args;
+use super::copy_args;
+use super::delete_args;
+use super::list_args;
+use super::lock_args;
+use super::read_args;
+use super::rename_args;
+use super::stat_args;
+use super::update_metadata_args;
+use super::write_args;
+use crate::raw::*;
+use crate::*;
+
+/// Record every call to the underlying services into a file.
+///
+/// # Recording
+///
+/// `RecordLayer` captures every call of `create_dir`, `read`, `write`, `copy`,
+/// `rename`, `stat`, `stat_many`, `list`, `delete`, `list_uploads`,
+/// `abort_upload`, `lock`, `concat` and `update_metadata` together with its
+/// arguments and results, and appends them as JSON lines to the given file:
+///
+/// - Data read from the services is stored in the recording.
+/// - Data written into the services is stored as its size and sha256.
+/// - Errors are stored as their kind and message.
+/// - Results of `stat_many` are stored as a `stat` of every path.
+///
+/// Records are written into the file by a dedicated thread. Call
+/// [`RecordLayer::flush`] or drop the operator to make sure all records have
+/// been written.
+///
+/// `presign` is passed through without recording since the signed requests
+/// change over time.
+///
+/// # Replay
+///
+/// The recording could be served back by `services::Replay`, which allows
+/// capturing a session against real services once and then running the same
+/// code offline in a deterministic way. Replay returns an error for every call
+/// that was not recorded.
+///
+/// Please note that recording and replay should happen at the same position
+/// of the layer stack, otherwise the recorded calls will not match.
+///
+/// To record the raw http requests instead of the calls to services, use
+/// [`RecordHttpFetch`] and [`ReplayHttpFetch`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::RecordLayer;
+/// # use opendal::services;
+/// # use opendal::Operator;
+/// # use opendal::Result;
+///
+/// # async fn test() -> Result<()> {
+/// let layer = RecordLayer::new("/tmp/session.jsonl")?;
+/// let op = Operator::new(services::Memory::default())?
+///     .layer(layer.clone())
+///     .finish();
+/// op.write("test", "Hello, World!").await?;
+/// layer.flush().await?;
+///
+/// let op = Operator::new(services::Replay::default().path("/tmp/session.jsonl"))?.finish();
+/// op.write("test", "Hello, World!").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RecordLayer {
+    recorder: Arc<Recorder>,
+}
+
+impl RecordLayer {
+    /// Create a new record layer that writes the recording into given file.
+    ///
+    /// The file will be truncated if it already exists.
+    pub fn new(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self {
+            recorder: Arc::new(Recorder::create(path.as_ref())?),
+        })
+    }
+
+    /// Wait until all records have been written into the file.
+    ///
+    /// Records are written in background, call this before reading the
+    /// recording while the layer is still in use. Dropping all operators
+    /// and layers will also write all records.
+    pub async fn flush(&self) -> Result<()> {
+        self.recorder.flush().await
+    }
+}
+
+impl<A: Access> Layer<A> for RecordLayer {
+    type LayeredAccess = RecordAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        RecordAccessor {
+            inner,
+            recorder: self.recorder.clone(),
+        }
+    }
+}
+
+/// Recorder appends records into a file as JSON lines.
+///
+/// Records are written by a dedicated thread, so that appending never blocks
+/// the async runtime and is safe to be called in drop.
+#[derive(Debug)]
+pub(crate) struct Recorder {
+    tx: Option<mpsc::Sender<RecorderMessage>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+enum RecorderMessage {
+    Line(Vec<u8>),
+    Flush(oneshot::Sender<std::io::Result<()>>),
+}
+
+impl Recorder {
+    fn create(path: &Path) -> Result<Self> {
+        let file = File::create(path).map_err(|err| {
+            new_std_io_error(err)
+                .with_operation("Recorder::create")
+                .with_context("path", path.display())
+        })?;
+
+        let (tx, rx) = mpsc::channel();
+        let handle = std::thread::Builder::new()
+            .name("opendal-recorder".to_string())
+            .spawn(move || Self::run(file, rx))
+            .map_err(|err| new_std_io_error(err).with_operation("Recorder::create"))?;
+        Ok(Self {
+            tx: Some(tx),
+            handle: Some(handle),
+        })
+    }
+
+    fn run(file: File, rx: mpsc::Receiver<RecorderMessage>) {
+        let mut w = BufWriter::new(file);
+        // The first write error is kept and reported by the next flush.
+        let mut error = None;
+        for msg in rx {
+            match msg {
+                RecorderMessage::Line(line) => {
+                    if let Err(err) = w.write_all(&line) {
+                        warn!("failed to write record: {err}");
+                        error.get_or_insert(err);
+                    }
+                }
+                RecorderMessage::Flush(tx) => {
+                    let res = match error.take() {
+                        Some(err) => Err(err),
+                        None => w.flush(),
+                    };
+                    let _ = tx.send(res);
+                }
+            }
+        }
+        if let Err(err) = w.flush() {
+            warn!("failed to flush records: {err}");
+        }
+    }
+
+    fn send(&self, msg: RecorderMessage) -> Result<()> {
+        self.tx
+            .as_ref()
+            .and_then(|tx| tx.send(msg).ok())
+            .ok_or_else(|| Error::new(ErrorKind::Unexpected, "recorder has been closed"))
+    }
+
+    fn append<T: Serialize>(&self, record: &T) -> Result<()> {
+        let mut line = serde_json::to_vec(record).map_err(new_json_serialize_error)?;
+        line.push(b'\n');
+        self.send(RecorderMessage::Line(line))
+    }
+
+    /// Wait until all appended records have been written into the file.
+    async fn flush(&self) -> Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.send(RecorderMessage::Flush(tx))?;
+        rx.await
+            .map_err(|_| Error::new(ErrorKind::Unexpected, "recorder has been closed"))?
+            .map_err(new_std_io_error)
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        // Close the channel and wait for all records to be written.
+        drop(self.tx.take());
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Load all records from a recording.
+pub(crate) fn load_records<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>> {
+    let file = File::open(path).map_err(|err| {
+        new_std_io_error(err)
+            .with_operation("load_records")
+            .with_context("path", path.display())
+    })?;
+
+    let mut records = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(new_std_io_error)?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        records.push(serde_json::from_str(&line).map_err(new_json_deserialize_error)?);
+    }
+    Ok(records)
+}
+
+/// Record is a call to the underlying services.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct Record {
+    pub op: String,
+    pub path: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub args: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RecordError>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<RecordMetadata>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entries: Vec<RecordEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<RecordPayload>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub uploads: Vec<RecordUpload>,
+}
+
+impl Record {
+    pub(crate) fn new(op: Operation, path: &str, args: BTreeMap<String, String>) -> Self {
+        Self {
+            op: op.into_static().to_string(),
+            path: path.to_string(),
+            args,
+            ..Default::default()
+        }
+    }
+
+    fn with_error(mut self, err: Option<&Error>) -> Self {
+        self.error = err.map(RecordError::from);
+        self
+    }
+
+    fn with_metadata(mut self, meta: Option<&Metadata>) -> Self {
+        self.metadata = meta.map(RecordMetadata::from);
+        self
+    }
+}
+
+/// RecordError is the recorded form of [`Error`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct RecordError {
+    pub kind: String,
+    pub message: String,
+    #[serde(default)]
+    pub temporary: bool,
+}
+
+impl From<&Error> for RecordError {
+    fn from(err: &Error) -> Self {
+        Self {
+            kind: err.kind().to_string(),
+            message: err.to_string(),
+            temporary: err.is_temporary(),
+        }
+    }
+}
+
+/// RecordMetadata is the recorded form of [`Metadata`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct RecordMetadata {
+    pub mode: String,
+    pub content_length: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_disposition: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_encoding: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_md5: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_current: Option<bool>,
+    pub is_deleted: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_metadata: Option<HashMap<String, String>>,
+}
+
+impl From<&Metadata> for RecordMetadata {
+    fn from(meta: &Metadata) -> Self {
+        Self {
+            mode: meta.mode().to_string(),
+            content_length: meta.content_length(),
+            content_type: meta.content_type().map(String::from),
+            content_disposition: meta.content_disposition().map(String::from),
+            content_encoding: meta.content_encoding().map(String::from),
+            content_md5: meta.content_md5().map(String::from),
+            cache_control: meta.cache_control().map(String::from),
+            etag: meta.etag().map(String::from),
+            last_modified: meta.last_modified().map(|v| v.to_rfc3339()),
+            version: meta.version().map(String::from),
+            is_current: meta.is_current(),
+            is_deleted: meta.is_deleted(),
+            user_metadata: meta.user_metadata().cloned(),
+        }
+    }
+}
+
+/// RecordEntry is the recorded form of [`oio::Entry`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct RecordEntry {
+    pub path: String,
+    pub metadata: RecordMetadata,
+}
+
+/// RecordUpload is the recorded form of [`Upload`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct RecordUpload {
+    pub path: String,
+    pub upload_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initiated: Option<String>,
+}
+
+impl From<&Upload> for RecordUpload {
+    fn from(upload: &Upload) -> Self {
+        Self {
+            path: upload.path().to_string(),
+            upload_id: upload.upload_id().to_string(),
+            initiated: upload.initiated().map(|v| v.to_rfc3339()),
+        }
+    }
+}
+
+/// RecordPayload is the data transferred by a call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct RecordPayload {
+    pub size: u64,
+    pub sha256: String,
+    /// The base64 encoded data, only recorded for reads.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+}
+
+impl RecordPayload {
+    fn with_data(data: &[u8]) -> Self {
+        Self {
+            size: data.len() as u64,
+            sha256: format!("{:x}", Sha256::digest(data)),
+            data: Some(BASE64_STANDARD.encode(data)),
+        }
+    }
+}
+
+/// PayloadHasher calculates the size and sha256 of written data.
+#[derive(Default)]
+pub(crate) struct PayloadHasher {
+    size: u64,
+    hasher: Sha256,
+}
+
+impl PayloadHasher {
+    pub(crate) fn update(&mut self, bs: &Buffer) {
+        self.size += bs.len() as u64;
+        for chunk in bs.clone() {
+            self.hasher.update(&chunk);
+        }
+    }
+
+    pub(crate) fn finish(self) -> RecordPayload {
+        RecordPayload {
+            size: self.size,
+            sha256: format!("{:x}", self.hasher.finalize()),
+            data: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RecordAccessor<A: Access> {
+    inner: A,
+    recorder: Arc<Recorder>,
+}
+
+impl<A: Access> RecordAccessor<A> {
+    fn record<T>(&self, record: Record, res: &Result<T>) -> Result<()> {
+        self.recorder.append(&record.with_error(res.as_ref().err()))
+    }
+}
+
+impl<A: Access> LayeredAccess for RecordAccessor<A> {
+    type Inner = A;
+    type Reader = RecordReader<A::Reader>;
+    type Writer = RecordWriter<A::Writer>;
+    type Lister = RecordLister<A::Lister>;
+    type Deleter = RecordDeleter<A::Deleter>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let res = self.inner.create_dir(path, args).await;
+        let record = Record::new(Operation::CreateDir, path, BTreeMap::new());
+        self.record(record, &res)?;
+        res
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let record = Record::new(Operation::Read, path, read_args(&args));
+        match self.inner.read(path, args).await {
+            Ok((rp, r)) => Ok((rp, RecordReader::new(r, self.recorder.clone(), record))),
+            Err(err) => {
+                self.recorder.append(&record.with_error(Some(&err)))?;
+                Err(err)
+            }
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let record = Record::new(Operation::Write, path, write_args(&args));
+        match self.inner.write(path, args).await {
+            Ok((rp, w)) => Ok((rp, RecordWriter::new(w, self.recorder.clone(), record))),
+            Err(err) => {
+                self.recorder.append(&record.with_error(Some(&err)))?;
+                Err(err)
+            }
+        }
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let record = Record::new(Operation::Copy, from, copy_args(to, &args));
+        let res = self.inner.copy(from, to, args).await;
+        self.record(record, &res)?;
+        res
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let record = Record::new(Operation::Rename, from, rename_args(to, &args));
+        let res = self.inner.rename(from, to, args).await;
+        self.record(record, &res)?;
+        res
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let record = Record::new(Operation::Stat, path, stat_args(&args));
+        match self.inner.stat(path, args).await {
+            Ok(rp) => {
+                let meta = rp.into_metadata();
+                self.recorder.append(&record.with_metadata(Some(&meta)))?;
+                Ok(RpStat::new(meta))
+            }
+            Err(err) => {
+                self.recorder.append(&record.with_error(Some(&err)))?;
+                Err(err)
+            }
+        }
+    }
+
+    async fn stat_many(&self, args: Vec<(String, OpStat)>) -> Result<RpStatMany> {
+        // Results are recorded as stat of every path, so that they could be
+        // replayed no matter the batch is sent natively or not.
+        let records: Vec<_> = args
+            .iter()
+            .map(|(path, op)| Record::new(Operation::Stat, path, stat_args(op)))
+            .collect();
+        let res = self.inner.stat_many(args).await;
+        match &res {
+            Ok(rp) => {
+                for (record, res) in records.into_iter().zip(rp.results()) {
+                    let record = record
+                        .with_metadata(res.as_ref().ok())
+                        .with_error(res.as_ref().err());
+                    self.recorder.append(&record)?;
+                }
+            }
+            Err(err) => {
+                for record in records {
+                    self.recorder.append(&record.with_error(Some(err)))?;
+                }
+            }
+        }
+        res
+    }
+
+    async fn list_uploads(&self, path: &str, args: OpListUploads) -> Result<RpListUploads> {
+        let mut record = Record::new(Operation::ListUploads, path, BTreeMap::new());
+        let res = self.inner.list_uploads(path, args).await;
+        if let Ok(rp) = &res {
+            record.uploads = rp.uploads().iter().map(RecordUpload::from).collect();
+        }
+        self.record(record, &res)?;
+        res
+    }
+
+    async fn abort_upload(&self, path: &str, args: OpAbortUpload) -> Result<RpAbortUpload> {
+        let record = Record::new(Operation::AbortUpload, path, abort_upload_args(&args));
+        let res = self.inner.abort_upload(path, args).await;
+        self.record(record, &res)?;
+        res
+    }
+
+    async fn lock(&self, path: &str, args: OpLock) -> Result<RpLock> {
+        let record = Record::new(Operation::Lock, path, lock_args(&args));
+        let res = self.inner.lock(path, args).await;
+        self.record(record, &res)?;
+        res
+    }
+
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        let record = Record::new(Operation::Concat, path, concat_args(&args));
+        let res = self.inner.concat(path, args).await;
+        self.record(record, &res)?;
+        res
+    }
+
+    async fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> Result<RpUpdateMetadata> {
+        let record = Record::new(Operation::UpdateMetadata, path, update_metadata_args(&args));
+        let res = self.inner.update_metadata(path, args).await;
+        self.record(record, &res)?;
+        res
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let record = Record::new(Operation::List, path, list_args(&args));
+        match self.inner.list(path, args).await {
+            Ok((rp, l)) => Ok((rp, RecordLister::new(l, self.recorder.clone(), record))),
+            Err(err) => {
+                self.recorder.append(&record.with_error(Some(&err)))?;
+                Err(err)
+            }
+        }
+    }
+
+    async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        self.inner
+            .delete()
+            .await
+            .map(|(rp, d)| (rp, RecordDeleter::new(d, self.recorder.clone())))
+    }
+}
+
+/// RecordReader records all data read from the inner reader.
+///
+/// The record will be appended once the reader reaches the end, meets an
+/// error or is dropped.
+pub struct RecordReader<R> {
+    inner: R,
+    recorder: Arc<Recorder>,
+    record: Option<Record>,
+    data: Vec<u8>,
+}
+
+impl<R> RecordReader<R> {
+    fn new(inner: R, recorder: Arc<Recorder>, record: Record) -> Self {
+        Self {
+            inner,
+            recorder,
+            record: Some(record),
+            data: Vec::new(),
+        }
+    }
+
+    fn finish(&mut self, err: Option<&Error>) -> Result<()> {
+        let Some(mut record) = self.record.take() else {
+            return Ok(());
+        };
+        record.payload = Some(RecordPayload::with_data(&self.data));
+        self.recorder.append(&record.with_error(err))
+    }
+}
+
+impl<R> Drop for RecordReader<R> {
+    fn drop(&mut self) {
+        if let Err(err) = self.finish(None) {
+            warn!("failed to append record of read: {err}");
+        }
+    }
+}
+
+impl<R: oio::Read> oio::Read for RecordReader<R> {
+    async fn read(&mut self) -> Result<Buffer> {
+        match self.inner.read().await {
+            Ok(bs) if bs.is_empty() => {
+                self.finish(None)?;
+                Ok(bs)
+            }
+            Ok(bs) => {
+                for chunk in bs.clone() {
+                    self.data.extend_from_slice(&chunk);
+                }
+                Ok(bs)
+            }
+            Err(err) => {
+                self.finish(Some(&err))?;
+                Err(err)
+            }
+        }
+    }
+}
+
+/// RecordWriter records the size and sha256 of all data written into the
+/// inner writer.
+pub struct RecordWriter<W> {
+    inner: W,
+    recorder: Arc<Recorder>,
+    record: Option<Record>,
+    hasher: PayloadHasher,
+}
+
+impl<W> RecordWriter<W> {
+    fn new(inner: W, recorder: Arc<Recorder>, record: Record) -> Self {
+        Self {
+            inner,
+            recorder,
+            record: Some(record),
+            hasher: PayloadHasher::default(),
+        }
+    }
+}
+
+impl<W: oio::Write> oio::Write for RecordWriter<W> {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        self.hasher.update(&bs);
+        self.inner.write(bs).await
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        let res = self.inner.close().await;
+        if let Some(mut record) = self.record.take() {
+            record.payload = Some(std::mem::take(&mut self.hasher).finish());
+            let record = record
+                .with_metadata(res.as_ref().ok())
+                .with_error(res.as_ref().err());
+            self.recorder.append(&record)?;
+        }
+        res
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+}
+
+/// RecordLister records all entries returned by the inner lister.
+///
+/// The record will be appended once the lister reaches the end, meets an
+/// error or is dropped.
+pub struct RecordLister<L> {
+    inner: L,
+    recorder: Arc<Recorder>,
+    record: Option<Record>,
+}
+
+impl<L> RecordLister<L> {
+    fn new(inner: L, recorder: Arc<Recorder>, record: Record) -> Self {
+        Self {
+            inner,
+            recorder,
+            record: Some(record),
+        }
+    }
+
+    fn finish(&mut self, err: Option<&Error>) -> Result<()> {
+        match self.record.take() {
+            Some(record) => self.recorder.append(&record.with_error(err)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<L> Drop for RecordLister<L> {
+    fn drop(&mut self) {
+        if let Err(err) = self.finish(None) {
+            warn!("failed to append record of list: {err}");
+        }
+    }
+}
+
+impl<L: oio::List> oio::List for RecordLister<L> {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        match self.inner.next().await {
+            Ok(Some(entry)) => {
+                if let Some(record) = &mut self.record {
+                    record.entries.push(RecordEntry {
+                        path: entry.path().to_string(),
+                        metadata: entry.metadata().into(),
+                    });
+                }
+                Ok(Some(entry))
+            }
+            Ok(None) => {
+                self.finish(None)?;
+                Ok(None)
+            }
+            Err(err) => {
+                self.finish(Some(&err))?;
+                Err(err)
+            }
+        }
+    }
+}
+
+/// RecordDeleter records every path deleted by the inner deleter.
+pub struct RecordDeleter<D> {
+    inner: D,
+    recorder: Arc<Recorder>,
+    queue: VecDeque<Record>,
+}
+
+impl<D> RecordDeleter<D> {
+    fn new(inner: D, recorder: Arc<Recorder>) -> Self {
+        Self {
+            inner,
+            recorder,
+            queue: VecDeque::new(),
+        }
+    }
+}
+
+impl<D: oio::Delete> oio::Delete for RecordDeleter<D> {
+    fn delete(&mut self, path: &str, args: OpDelete) -> Result<()> {
+        let record = Record::new(Operation::Delete, path, delete_args(&args));
+        self.inner.delete(path, args)?;
+        self.queue.push_back(record);
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<usize> {
+        match self.inner.flush().await {
+            Ok(n) => {
+                for record in self.queue.drain(..n.min(self.queue.len())) {
+                    self.recorder.append(&record)?;
+                }
+                Ok(n)
+            }
+            Err(err) => {
+                // The failed path will be retried by the next flush, so we only
+                // record the error against the first queued path.
+                if let Some(record) = self.queue.front() {
+                    self.recorder
+                        .append(&record.clone().with_error(Some(&err)))?;
+                }
+                Err(err)
+            }
+        }
+    }
+}
+
+/// HttpRecord is a http request and its response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HttpRecord {
+    method: String,
+    uri: String,
+    /// The sha256 of the request body.
+    body: String,
+    status: u16,
+    headers: Vec<(String, String)>,
+    /// The base64 encoded response body.
+    response: String,
+}
+
+type HttpRecordKey = (String, String, String);
+
+impl HttpRecord {
+    fn key(&self) -> HttpRecordKey {
+        (self.method.clone(), self.uri.clone(), self.body.clone())
+    }
+}
+
+/// Query parameters that carry signatures and will change for every request.
+const VOLATILE_QUERY_PREFIXES: &[&str] = &["x-amz-", "x-goog-"];
+const VOLATILE_QUERY_KEYS: &[&str] = &["signature", "expires", "googleaccessid", "se", "st", "sig"];
+
+/// Build the key of given request which is stable across sessions.
+fn http_request_key(req: &Request<Buffer>) -> HttpRecordKey {
+    let uri = req.uri();
+    let mut key = format!(
+        "{}://{}{}",
+        uri.scheme_str().unwrap_or_default(),
+        uri.authority().map(|v| v.as_str()).unwrap_or_default(),
+        uri.path()
+    );
+    let query: Vec<_> = uri
+        .query()
+        .unwrap_or_default()
+        .split('&')
+        .filter(|pair| {
+            let name = pair.split('=').next().unwrap_or_default().to_lowercase();
+            !name.is_empty()
+                && !VOLATILE_QUERY_KEYS.contains(&name.as_str())
+                && !VOLATILE_QUERY_PREFIXES.iter().any(|p| name.starts_with(p))
+        })
+        .collect();
+    if !query.is_empty() {
+        key.push('?');
+        key.push_str(&query.join("&"));
+    }
+
+    let mut hasher = PayloadHasher::default();
+    hasher.update(req.body());
+    (req.method().to_string(), key, hasher.finish().sha256)
+}
+
+fn new_http_body(bs: Buffer) -> HttpBody {
+    let size = bs.len() as u64;
+    HttpBody::new(stream::iter(Some(Ok(bs))), Some(size))
+}
+
+/// RecordHttpFetch records every http request and its response into a file.
+///
+/// The recording could be served back by [`ReplayHttpFetch`]. Requests are
+/// matched by method, uri and the sha256 of body. Headers and query
+/// parameters that carry signatures are ignored while matching.
+///
+/// Please note that the response body will be fully buffered before returning.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::layers::HttpClientLayer;
+/// # use opendal::layers::RecordHttpFetch;
+/// # use opendal::layers::ReplayHttpFetch;
+/// # use opendal::raw::HttpClient;
+/// # use opendal::Result;
+///
+/// # fn main() -> Result<()> {
+/// // Record all requests sent by services.
+/// let fetcher = RecordHttpFetch::new(HttpClient::new()?, "/tmp/session.jsonl")?;
+/// let _ = HttpClientLayer::new(HttpClient::with(fetcher));
+///
+/// // Serve the recorded responses offline.
+/// let fetcher = ReplayHttpFetch::new("/tmp/session.jsonl")?;
+/// let _ = HttpClientLayer::new(HttpClient::with(fetcher));
+/// # Ok(())
+/// # }
+/// ```
+pub struct RecordHttpFetch {
+    inner: HttpFetcher,
+    recorder: Recorder,
+}
+
+impl RecordHttpFetch {
+    /// Create a new `RecordHttpFetch` that sends requests via given client
+    /// and writes the recording into given file.
+    pub fn new(client: HttpClient, path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self {
+            inner: client.into_inner(),
+            recorder: Recorder::create(path.as_ref())?,
+        })
+    }
+}
+
+impl HttpFetch for RecordHttpFetch {
+    async fn fetch(&self, req: Request<Buffer>) -> Result<Response<HttpBody>> {
+        let (method, uri, body) = http_request_key(&req);
+
+        let (parts, mut resp) = self.inner.fetch(req).await?.into_parts();
+        let bs = resp.read_all().await?;
+
+        self.recorder.append(&HttpRecord {
+            method,
+            uri,
+            body,
+            status: parts.status.as_u16(),
+            headers: parts
+                .headers
+                .iter()
+                .map(|(k, v)| (k.to_string(), String::from_utf8_lossy(v.as_bytes()).into()))
+                .collect(),
+            response: BASE64_STANDARD.encode(bs.to_bytes()),
+        })?;
+
+        Ok(Response::from_parts(parts, new_http_body(bs)))
+    }
+}
+
+/// ReplayHttpFetch serves the responses recorded by [`RecordHttpFetch`].
+///
+/// Requests are matched by method, uri and the sha256 of body, and the same
+/// request will get its responses in the recorded order. An error will be
+/// returned if the request was not recorded.
+pub struct ReplayHttpFetch {
+    records: Mutex<HashMap<HttpRecordKey, VecDeque<HttpRecord>>>,
+}
+
+impl ReplayHttpFetch {
+    /// Create a new `ReplayHttpFetch` from given recording.
+    pub fn new(path: impl AsRef<Path>) -> Result<Self> {
+        let mut records: HashMap<_, VecDeque<_>> = HashMap::new();
+        for record in load_records::<HttpRecord>(path.as_ref())? {
+            records.entry(record.key()).or_default().push_back(record);
+        }
+        Ok(Self {
+            records: Mutex::new(records),
+        })
+    }
+}
+
+impl HttpFetch for ReplayHttpFetch {
+    async fn fetch(&self, req: Request<Buffer>) -> Result<Response<HttpBody>> {
+        let key = http_request_key(&req);
+        let record = self
+            .records
+            .lock()
+            .unwrap()
+            .get_mut(&key)
+            .and_then(|v| v.pop_front());
+        let Some(record) = record else {
+            return Err(
+                Error::new(ErrorKind::Unexpected, "http request is not recorded")
+                    .with_operation("ReplayHttpFetch::fetch")
+                    .with_context("method", key.0)
+                    .with_context("uri", key.1),
+            );
+        };
+
+        let status = StatusCode::from_u16(record.status).map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "recorded status is invalid").set_source(err)
+        })?;
+        let bs = BASE64_STANDARD.decode(record.response).map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "recorded response is invalid").set_source(err)
+        })?;
+
+        let mut resp = Response::builder()
+            .status(status)
+            .extension::<Uri>(req.uri().clone());
+        for (k, v) in &record.headers {
+            resp = resp.header(k, v);
+        }
+        resp.body(new_http_body(Buffer::from(bs)))
+            .map_err(new_request_build_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::services::Memory;
+
+    fn temp_path() -> PathBuf {
+        std::env::temp_dir().join(format!("opendal-record-{}.jsonl", uuid::Uuid::new_v4()))
+    }
+
+    #[tokio::test]
+    async fn test_record_calls() -> Result<()> {
+        let path = temp_path();
+        let layer = RecordLayer::new(&path)?;
+        let op = Operator::new(Memory::default())?
+            .layer(layer.clone())
+            .finish();
+        op.write("dir/file", "Hello, World!").await?;
+        assert_eq!(op.read("dir/file").await?.to_vec(), b"Hello, World!");
+        assert_eq!(op.list("dir/").await?.len(), 1);
+        assert!(op.stat("not_exist").await.is_err());
+        op.delete("dir/file").await?;
+        let _ = op
+            .into_inner()
+            .stat_many(vec![("dir/file".to_string(), OpStat::new())])
+            .await;
+        layer.flush().await?;
+
+        let records: Vec<Record> = load_records(&path)?;
+        let ops: Vec<_> = records.iter().map(|v| v.op.as_str()).collect();
+        // Results of stat_many are recorded as stat of every path.
+        assert_eq!(ops, ["write", "read", "list", "stat", "delete", "stat"]);
+        assert_eq!(
+            records[0].payload.as_ref().unwrap().sha256,
+            format!("{:x}", Sha256::digest(b"Hello, World!"))
+        );
+        assert_eq!(
+            records[1].payload.as_ref().unwrap().data.as_deref(),
+            Some(BASE64_STANDARD.encode(b"Hello, World!").as_str())
+        );
+        assert_eq!(records[2].entries.len(), 1);
+        assert_eq!(records[3].error.as_ref().unwrap().kind, "NotFound");
+
+        std::fs::remove_file(path).unwrap();
+        Ok(())
+    }
+
+    struct MockFetch;
+
+    impl HttpFetch for MockFetch {
+        async fn fetch(&self, req: Request<Buffer>) -> Result<Response<HttpBody>> {
+            let bs = Buffer::from(format!("{} {}", req.method(), req.uri().path()));
+            Ok(Response::builder()
+                .status(StatusCode::OK)
+                .header("x-test", "value")
+                .body(new_http_body(bs))
+                .unwrap())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay_http() -> Result<()> {
+        let path = temp_path();
+        let build = |uri: &str| {
+            Request::get(uri)
+                .body(Buffer::new())
+                .expect("request must be valid")
+        };
+
+        let client = HttpClient::with(RecordHttpFetch::new(HttpClient::with(MockFetch), &path)?);
+        client
+            .send(build("http://example.com/a?X-Amz-Signature=abc"))
+            .await?;
+        // Dropping the fetcher writes all records.
+        drop(client);
+
+        let client = HttpClient::with(ReplayHttpFetch::new(&path)?);
+        let resp = client
+            .send(build("http://example.com/a?X-Amz-Signature=def"))
+            .await?;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers()["x-test"], "value");
+        assert_eq!(resp.body().to_vec(), b"GET /a");
+
+        // Every recorded response will be served only once.
+        let err = client
+            .send(build("http://example.com/a"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        std::fs::remove_file(path).unwrap();
+        Ok(())
+    }
+}
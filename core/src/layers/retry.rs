@@ -343,6 +343,64 @@ impl<A: Access, I: RetryInterceptor> LayeredAccess for RetryAccessor<A, I> {
             .map_err(|e| e.set_persistent())
     }
 
+    async fn stat_many(&self, args: Vec<(String, OpStat)>) -> Result<RpStatMany> {
+        { || self.inner.stat_many(args.clone()) }
+            .retry(self.builder)
+            .when(|e| e.is_temporary())
+            .notify(|err, dur| self.notify.intercept(err, dur))
+            .await
+            .map_err(|e| e.set_persistent())
+    }
+
+    async fn list_uploads(&self, path: &str, args: OpListUploads) -> Result<RpListUploads> {
+        { || self.inner.list_uploads(path, args.clone()) }
+            .retry(self.builder)
+            .when(|e| e.is_temporary())
+            .notify(|err, dur| self.notify.intercept(err, dur))
+            .await
+            .map_err(|e| e.set_persistent())
+    }
+
+    async fn abort_upload(&self, path: &str, args: OpAbortUpload) -> Result<RpAbortUpload> {
+        { || self.inner.abort_upload(path, args.clone()) }
+            .retry(self.builder)
+            .when(|e| e.is_temporary())
+            .notify(|err, dur| self.notify.intercept(err, dur))
+            .await
+            .map_err(|e| e.set_persistent())
+    }
+
+    async fn lock(&self, path: &str, args: OpLock) -> Result<RpLock> {
+        { || self.inner.lock(path, args.clone()) }
+            .retry(self.builder)
+            .when(|e| e.is_temporary())
+            .notify(|err, dur| self.notify.intercept(err, dur))
+            .await
+            .map_err(|e| e.set_persistent())
+    }
+
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        { || self.inner.concat(path, args.clone()) }
+            .retry(self.builder)
+            .when(|e| e.is_temporary())
+            .notify(|err, dur| self.notify.intercept(err, dur))
+            .await
+            .map_err(|e| e.set_persistent())
+    }
+
+    async fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> Result<RpUpdateMetadata> {
+        { || self.inner.update_metadata(path, args.clone()) }
+            .retry(self.builder)
+            .when(|e| e.is_temporary())
+            .notify(|err, dur| self.notify.intercept(err, dur))
+            .await
+            .map_err(|e| e.set_persistent())
+    }
+
     async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
         { || self.inner.delete() }
             .retry(self.builder)
@@ -38,6 +38,10 @@ use crate::*;
 /// [Governor](https://docs.rs/governor/latest/governor/index.html).
 /// By setting the `bandwidth` and `burst`, we can control the byte flow rate of underlying services.
 ///
+/// Only the bytes read and written are throttled. Calls that don't transfer
+/// data through OpenDAL, such as `stat`, `stat_many`, `lock`, `concat` and
+/// `update_metadata`, are passed through directly.
+///
 /// # Note
 ///
 /// When setting the ThrottleLayer, always consider the largest possible operation size as the burst size,
@@ -260,6 +260,43 @@ impl<A: Access> LayeredAccess for TimeoutAccessor<A> {
             .await
     }
 
+    async fn stat_many(&self, args: Vec<(String, OpStat)>) -> Result<RpStatMany> {
+        self.timeout(Operation::Stat, self.inner.stat_many(args))
+            .await
+    }
+
+    async fn list_uploads(&self, path: &str, args: OpListUploads) -> Result<RpListUploads> {
+        self.timeout(Operation::ListUploads, self.inner.list_uploads(path, args))
+            .await
+    }
+
+    async fn abort_upload(&self, path: &str, args: OpAbortUpload) -> Result<RpAbortUpload> {
+        self.timeout(Operation::AbortUpload, self.inner.abort_upload(path, args))
+            .await
+    }
+
+    async fn lock(&self, path: &str, args: OpLock) -> Result<RpLock> {
+        self.timeout(Operation::Lock, self.inner.lock(path, args))
+            .await
+    }
+
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        self.timeout(Operation::Concat, self.inner.concat(path, args))
+            .await
+    }
+
+    async fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> Result<RpUpdateMetadata> {
+        self.timeout(
+            Operation::UpdateMetadata,
+            self.inner.update_metadata(path, args),
+        )
+        .await
+    }
+
     async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
         self.timeout(Operation::Delete, self.inner.delete())
             .await
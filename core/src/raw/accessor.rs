@@ -119,6 +119,129 @@ pub trait Access: Send + Sync + Debug + Unpin + 'static {
         )))
     }
 
+    /// Invoke the `stat` operation on multiple paths at once.
+    ///
+    /// Require [`Capability::stat_many`]
+    ///
+    /// # Behavior
+    ///
+    /// - The returning results MUST be in the same order as the input.
+    /// - The number of input paths will not exceed [`Capability::stat_many_max_size`].
+    /// - Error of a single path SHOULD be returned in its result instead of failing the whole batch.
+    fn stat_many(
+        &self,
+        args: Vec<(String, OpStat)>,
+    ) -> impl Future<Output = Result<RpStatMany>> + MaybeSend {
+        let _ = args;
+
+        ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        )))
+    }
+
+    /// Invoke the `list_uploads` operation on the specified path.
+    ///
+    /// Require [`Capability::list_uploads`]
+    ///
+    /// # Behavior
+    ///
+    /// - Returns all incomplete multipart uploads whose path starts with the given path.
+    fn list_uploads(
+        &self,
+        path: &str,
+        args: OpListUploads,
+    ) -> impl Future<Output = Result<RpListUploads>> + MaybeSend {
+        let (_, _) = (path, args);
+
+        ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        )))
+    }
+
+    /// Invoke the `abort_upload` operation on the specified path.
+    ///
+    /// Require [`Capability::abort_upload`]
+    ///
+    /// # Behavior
+    ///
+    /// - Aborting an upload that doesn't exist SHOULD return `Ok(())`.
+    fn abort_upload(
+        &self,
+        path: &str,
+        args: OpAbortUpload,
+    ) -> impl Future<Output = Result<RpAbortUpload>> + MaybeSend {
+        let (_, _) = (path, args);
+
+        ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        )))
+    }
+
+    /// Invoke the `lock` operation on the specified path.
+    ///
+    /// Require [`Capability::lock`]
+    ///
+    /// # Behavior
+    ///
+    /// - Acquiring a lock held by another token that hasn't expired MUST return
+    ///   [`ErrorKind::ConditionNotMatch`].
+    /// - Renewing or releasing a lock that isn't held by the token MUST return
+    ///   [`ErrorKind::ConditionNotMatch`].
+    fn lock(&self, path: &str, args: OpLock) -> impl Future<Output = Result<RpLock>> + MaybeSend {
+        let (_, _) = (path, args);
+
+        ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        )))
+    }
+
+    /// Invoke the `concat` operation on the specified path.
+    ///
+    /// Require [`Capability::concat`]
+    ///
+    /// # Behavior
+    ///
+    /// - The target will be replaced by the concatenation of all sources in order.
+    /// - The target itself could be one of the sources, which makes append possible.
+    /// - Sources MUST NOT be removed by this operation.
+    fn concat(
+        &self,
+        path: &str,
+        args: OpConcat,
+    ) -> impl Future<Output = Result<RpConcat>> + MaybeSend {
+        let (_, _) = (path, args);
+
+        ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        )))
+    }
+
+    /// Invoke the `update_metadata` operation on the specified path.
+    ///
+    /// Require [`Capability::update_metadata`]
+    ///
+    /// # Behavior
+    ///
+    /// - The content of the file MUST NOT be changed.
+    /// - Metadata that is not changed by args MUST be kept.
+    fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> impl Future<Output = Result<RpUpdateMetadata>> + MaybeSend {
+        let (_, _) = (path, args);
+
+        ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        )))
+    }
+
     /// Invoke the `read` operation on the specified path, returns a
     /// [`Reader`][crate::Reader] if operate successful.
     ///
@@ -272,6 +395,31 @@ pub trait AccessDyn: Send + Sync + Debug + Unpin {
     ) -> BoxedFuture<'a, Result<RpCreateDir>>;
     /// Dyn version of [`Accessor::stat`]
     fn stat_dyn<'a>(&'a self, path: &'a str, args: OpStat) -> BoxedFuture<'a, Result<RpStat>>;
+    /// Dyn version of [`Accessor::stat_many`]
+    fn stat_many_dyn(&self, args: Vec<(String, OpStat)>) -> BoxedFuture<'_, Result<RpStatMany>>;
+    /// Dyn version of [`Accessor::list_uploads`]
+    fn list_uploads_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpListUploads,
+    ) -> BoxedFuture<'a, Result<RpListUploads>>;
+    /// Dyn version of [`Accessor::abort_upload`]
+    fn abort_upload_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpAbortUpload,
+    ) -> BoxedFuture<'a, Result<RpAbortUpload>>;
+    /// Dyn version of [`Accessor::lock`]
+    fn lock_dyn<'a>(&'a self, path: &'a str, args: OpLock) -> BoxedFuture<'a, Result<RpLock>>;
+    /// Dyn version of [`Accessor::concat`]
+    fn concat_dyn<'a>(&'a self, path: &'a str, args: OpConcat)
+        -> BoxedFuture<'a, Result<RpConcat>>;
+    /// Dyn version of [`Accessor::update_metadata`]
+    fn update_metadata_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpUpdateMetadata,
+    ) -> BoxedFuture<'a, Result<RpUpdateMetadata>>;
     /// Dyn version of [`Accessor::read`]
     fn read_dyn<'a>(
         &'a self,
@@ -339,6 +487,46 @@ where
         Box::pin(self.stat(path, args))
     }
 
+    fn stat_many_dyn(&self, args: Vec<(String, OpStat)>) -> BoxedFuture<'_, Result<RpStatMany>> {
+        Box::pin(self.stat_many(args))
+    }
+
+    fn list_uploads_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpListUploads,
+    ) -> BoxedFuture<'a, Result<RpListUploads>> {
+        Box::pin(self.list_uploads(path, args))
+    }
+
+    fn abort_upload_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpAbortUpload,
+    ) -> BoxedFuture<'a, Result<RpAbortUpload>> {
+        Box::pin(self.abort_upload(path, args))
+    }
+
+    fn lock_dyn<'a>(&'a self, path: &'a str, args: OpLock) -> BoxedFuture<'a, Result<RpLock>> {
+        Box::pin(self.lock(path, args))
+    }
+
+    fn concat_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpConcat,
+    ) -> BoxedFuture<'a, Result<RpConcat>> {
+        Box::pin(self.concat(path, args))
+    }
+
+    fn update_metadata_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpUpdateMetadata,
+    ) -> BoxedFuture<'a, Result<RpUpdateMetadata>> {
+        Box::pin(self.update_metadata(path, args))
+    }
+
     fn read_dyn<'a>(
         &'a self,
         path: &'a str,
@@ -412,6 +600,34 @@ impl Access for dyn AccessDyn {
         self.stat_dyn(path, args).await
     }
 
+    async fn stat_many(&self, args: Vec<(String, OpStat)>) -> Result<RpStatMany> {
+        self.stat_many_dyn(args).await
+    }
+
+    async fn list_uploads(&self, path: &str, args: OpListUploads) -> Result<RpListUploads> {
+        self.list_uploads_dyn(path, args).await
+    }
+
+    async fn abort_upload(&self, path: &str, args: OpAbortUpload) -> Result<RpAbortUpload> {
+        self.abort_upload_dyn(path, args).await
+    }
+
+    async fn lock(&self, path: &str, args: OpLock) -> Result<RpLock> {
+        self.lock_dyn(path, args).await
+    }
+
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        self.concat_dyn(path, args).await
+    }
+
+    async fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> Result<RpUpdateMetadata> {
+        self.update_metadata_dyn(path, args).await
+    }
+
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
         self.read_dyn(path, args).await
     }
@@ -485,6 +701,49 @@ impl<T: Access + ?Sized> Access for Arc<T> {
         async move { self.as_ref().stat(path, args).await }
     }
 
+    fn stat_many(
+        &self,
+        args: Vec<(String, OpStat)>,
+    ) -> impl Future<Output = Result<RpStatMany>> + MaybeSend {
+        async move { self.as_ref().stat_many(args).await }
+    }
+
+    fn list_uploads(
+        &self,
+        path: &str,
+        args: OpListUploads,
+    ) -> impl Future<Output = Result<RpListUploads>> + MaybeSend {
+        async move { self.as_ref().list_uploads(path, args).await }
+    }
+
+    fn abort_upload(
+        &self,
+        path: &str,
+        args: OpAbortUpload,
+    ) -> impl Future<Output = Result<RpAbortUpload>> + MaybeSend {
+        async move { self.as_ref().abort_upload(path, args).await }
+    }
+
+    fn lock(&self, path: &str, args: OpLock) -> impl Future<Output = Result<RpLock>> + MaybeSend {
+        async move { self.as_ref().lock(path, args).await }
+    }
+
+    fn concat(
+        &self,
+        path: &str,
+        args: OpConcat,
+    ) -> impl Future<Output = Result<RpConcat>> + MaybeSend {
+        async move { self.as_ref().concat(path, args).await }
+    }
+
+    fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> impl Future<Output = Result<RpUpdateMetadata>> + MaybeSend {
+        async move { self.as_ref().update_metadata(path, args).await }
+    }
+
     fn read(
         &self,
         path: &str,
@@ -614,6 +873,9 @@ impl Default for AccessorInfoInner {
 #[derive(Debug, Default)]
 pub struct AccessorInfo {
     inner: std::sync::RwLock<AccessorInfoInner>,
+    /// The info this one is derived from, everything except the full capability
+    /// is forwarded to it.
+    parent: Option<Arc<AccessorInfo>>,
 }
 
 impl PartialEq for AccessorInfo {
@@ -635,6 +897,22 @@ impl Hash for AccessorInfo {
 }
 
 impl AccessorInfo {
+    /// Derive a new info from the given one.
+    ///
+    /// The derived info has its own full capability, all other fields and updates
+    /// are forwarded to the parent. It's used by layers that want to adjust the
+    /// capability without affecting other operators that share the same service.
+    pub(crate) fn derive(parent: Arc<AccessorInfo>) -> Self {
+        let inner = AccessorInfoInner {
+            full_capability: parent.full_capability(),
+            ..Default::default()
+        };
+        Self {
+            inner: std::sync::RwLock::new(inner),
+            parent: Some(parent),
+        }
+    }
+
     /// Scheme of backend.
     ///
     /// # Panic Safety
@@ -642,6 +920,9 @@ impl AccessorInfo {
     /// This method safely handles lock poisoning scenarios. If the inner `RwLock` is poisoned,
     /// this method will gracefully continue execution by simply returning the current scheme.
     pub fn scheme(&self) -> &'static str {
+        if let Some(parent) = &self.parent {
+            return parent.scheme();
+        }
         match self.inner.read() {
             Ok(v) => v.scheme,
             Err(err) => err.get_ref().scheme,
@@ -656,6 +937,10 @@ impl AccessorInfo {
     /// this method will gracefully continue execution by simply skipping the update operation
     /// rather than propagating the panic.
     pub fn set_scheme(&self, scheme: &'static str) -> &Self {
+        if let Some(parent) = &self.parent {
+            parent.set_scheme(scheme);
+            return self;
+        }
         if let Ok(mut v) = self.inner.write() {
             v.scheme = scheme;
         }
@@ -670,6 +955,9 @@ impl AccessorInfo {
     /// This method safely handles lock poisoning scenarios. If the inner `RwLock` is poisoned,
     /// this method will gracefully continue execution by simply returning the current root.
     pub fn root(&self) -> Arc<str> {
+        if let Some(parent) = &self.parent {
+            return parent.root();
+        }
         match self.inner.read() {
             Ok(v) => v.root.clone(),
             Err(err) => err.get_ref().root.clone(),
@@ -686,6 +974,10 @@ impl AccessorInfo {
     /// this method will gracefully continue execution by simply skipping the update operation
     /// rather than propagating the panic.
     pub fn set_root(&self, root: &str) -> &Self {
+        if let Some(parent) = &self.parent {
+            parent.set_root(root);
+            return self;
+        }
         if let Ok(mut v) = self.inner.write() {
             v.root = Arc::from(root);
         }
@@ -707,6 +999,9 @@ impl AccessorInfo {
     /// This method safely handles lock poisoning scenarios. If the inner `RwLock` is poisoned,
     /// this method will gracefully continue execution by simply returning the current scheme.
     pub fn name(&self) -> Arc<str> {
+        if let Some(parent) = &self.parent {
+            return parent.name();
+        }
         match self.inner.read() {
             Ok(v) => v.name.clone(),
             Err(err) => err.get_ref().name.clone(),
@@ -721,6 +1016,10 @@ impl AccessorInfo {
     /// this method will gracefully continue execution by simply skipping the update operation
     /// rather than propagating the panic.
     pub fn set_name(&self, name: &str) -> &Self {
+        if let Some(parent) = &self.parent {
+            parent.set_name(name);
+            return self;
+        }
         if let Ok(mut v) = self.inner.write() {
             v.name = Arc::from(name)
         }
@@ -735,6 +1034,9 @@ impl AccessorInfo {
     /// This method safely handles lock poisoning scenarios. If the inner `RwLock` is poisoned,
     /// this method will gracefully continue execution by simply returning the current native capability.
     pub fn native_capability(&self) -> Capability {
+        if let Some(parent) = &self.parent {
+            return parent.native_capability();
+        }
         match self.inner.read() {
             Ok(v) => v.native_capability,
             Err(err) => err.get_ref().native_capability,
@@ -754,6 +1056,9 @@ impl AccessorInfo {
     /// this method will gracefully continue execution by simply skipping the update operation
     /// rather than propagating the panic.
     pub fn set_native_capability(&self, capability: Capability) -> &Self {
+        if let Some(parent) = &self.parent {
+            parent.set_native_capability(capability);
+        }
         if let Ok(mut v) = self.inner.write() {
             v.native_capability = capability;
             v.full_capability = capability;
@@ -797,6 +1102,9 @@ impl AccessorInfo {
     /// This method safely handles lock poisoning scenarios. If the inner `RwLock` is poisoned,
     /// this method will gracefully continue execution by simply returning the current http client.
     pub fn http_client(&self) -> HttpClient {
+        if let Some(parent) = &self.parent {
+            return parent.http_client();
+        }
         match self.inner.read() {
             Ok(v) => v.http_client.clone(),
             Err(err) => err.get_ref().http_client.clone(),
@@ -814,6 +1122,10 @@ impl AccessorInfo {
     /// This method safely handles lock poisoning scenarios. If the inner `RwLock` is poisoned,
     /// this method will gracefully continue execution by simply skipping the update operation.
     pub fn update_http_client(&self, f: impl FnOnce(HttpClient) -> HttpClient) -> &Self {
+        if let Some(parent) = &self.parent {
+            parent.update_http_client(f);
+            return self;
+        }
         if let Ok(mut v) = self.inner.write() {
             let client = mem::take(&mut v.http_client);
             v.http_client = f(client);
@@ -829,6 +1141,9 @@ impl AccessorInfo {
     /// This method safely handles lock poisoning scenarios. If the inner `RwLock` is poisoned,
     /// this method will gracefully continue execution by simply returning the current executor.
     pub fn executor(&self) -> Executor {
+        if let Some(parent) = &self.parent {
+            return parent.executor();
+        }
         match self.inner.read() {
             Ok(v) => v.executor.clone(),
             Err(err) => err.get_ref().executor.clone(),
@@ -846,6 +1161,10 @@ impl AccessorInfo {
     /// This method safely handles lock poisoning scenarios. If the inner `RwLock` is poisoned,
     /// this method will gracefully continue execution by simply skipping the update operation.
     pub fn update_executor(&self, f: impl FnOnce(Executor) -> Executor) -> &Self {
+        if let Some(parent) = &self.parent {
+            parent.update_executor(f);
+            return self;
+        }
         if let Ok(mut v) = self.inner.write() {
             let executor = mem::take(&mut v.executor);
             v.executor = f(executor);
@@ -110,6 +110,25 @@ pub trait Adapter: Send + Sync + Debug + Unpin + 'static {
     /// - return `Ok(())` even if this key is not exist.
     fn delete(&self, path: &str) -> impl Future<Output = Result<()>> + MaybeSend;
 
+    /// Get the size of values of keys in one batch.
+    ///
+    /// - return `None` for keys that don't exist.
+    /// - results must be in the same order as the input paths.
+    ///
+    /// Adapters that implement this should claim `stat_many` in capabilities.
+    fn size_many(
+        &self,
+        paths: &[String],
+    ) -> impl Future<Output = Result<Vec<Option<u64>>>> + MaybeSend {
+        let _ = paths;
+
+        ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "kv adapter doesn't support this operation",
+        )
+        .with_operation("kv::Adapter::size_many")))
+    }
+
     /// Scan a key prefix to get all keys that start with this key.
     fn scan(&self, path: &str) -> impl Future<Output = Result<Self::Scanner>> + MaybeSend {
         let _ = path;
@@ -132,6 +151,18 @@ pub trait Adapter: Send + Sync + Debug + Unpin + 'static {
         )
         .with_operation("kv::Adapter::append")))
     }
+
+    /// Acquire, renew or release a lock of key with the token.
+    fn lock(&self, path: &str, args: OpLock) -> impl Future<Output = Result<()>> + MaybeSend {
+        let _ = path;
+        let _ = args;
+
+        ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "kv adapter doesn't support this operation",
+        )
+        .with_operation("kv::Adapter::lock")))
+    }
 }
 
 /// Info for this key value accessor.
@@ -117,6 +117,41 @@ impl<S: Adapter> Access for Backend<S> {
         }
     }
 
+    async fn stat_many(&self, args: Vec<(String, OpStat)>) -> Result<RpStatMany> {
+        let root = build_abs_path(&self.root, "");
+        let paths: Vec<_> = args
+            .iter()
+            .map(|(path, _)| build_abs_path(&self.root, path))
+            .filter(|p| p != &root)
+            .collect();
+        let mut sizes = if paths.is_empty() {
+            Vec::new()
+        } else {
+            self.kv.size_many(&paths).await?
+        }
+        .into_iter();
+
+        let results = args
+            .iter()
+            .map(|(path, _)| {
+                if build_abs_path(&self.root, path) == root {
+                    return Ok(Metadata::new(EntryMode::DIR));
+                }
+                match sizes.next() {
+                    Some(Some(size)) => {
+                        Ok(Metadata::new(EntryMode::FILE).with_content_length(size))
+                    }
+                    Some(None) => Err(Error::new(ErrorKind::NotFound, "kv doesn't have this path")),
+                    None => Err(Error::new(
+                        ErrorKind::Unexpected,
+                        "kv returned less sizes than expected",
+                    )),
+                }
+            })
+            .collect();
+        Ok(RpStatMany::new(results))
+    }
+
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
         let p = build_abs_path(&self.root, path);
         let bs = match self.kv.get(&p).await? {
@@ -132,6 +167,12 @@ impl<S: Adapter> Access for Backend<S> {
         Ok((RpWrite::new(), KvWriter::new(self.kv.clone(), p)))
     }
 
+    async fn lock(&self, path: &str, args: OpLock) -> Result<RpLock> {
+        let p = build_abs_path(&self.root, path);
+        self.kv.lock(&p, args).await?;
+        Ok(RpLock::default())
+    }
+
     async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
         Ok((
             RpDelete::default(),
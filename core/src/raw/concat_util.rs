@@ -0,0 +1,164 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use crate::raw::*;
+
+/// ConcatPart is a part of the object built by server side concat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConcatPart {
+    /// Copy the range of the source on the server side.
+    Copy(String, BytesRange),
+    /// Download the ranges of sources and upload them as one part.
+    Upload(Vec<(String, BytesRange)>),
+}
+
+/// Plan the parts of a multipart upload that concatenates given sources.
+///
+/// - `sources` are paths along with their sizes.
+/// - Every part except the last one will be at least `min_part_size`, ranges that are
+///   smaller than it will be merged with their neighbours and uploaded.
+/// - Every copied part will be at most `max_copy_size`.
+///
+/// Returns an empty plan if all sources are empty.
+pub fn plan_concat_parts(
+    sources: &[(String, u64)],
+    min_part_size: u64,
+    max_copy_size: u64,
+) -> Vec<ConcatPart> {
+    debug_assert!(min_part_size <= max_copy_size && max_copy_size > 0);
+
+    let mut parts = vec![];
+    let mut pending = vec![];
+    let mut pending_size = 0;
+
+    for (idx, (path, size)) in sources.iter().enumerate() {
+        let (size, is_last) = (*size, idx + 1 == sources.len());
+        let mut offset = 0;
+
+        // Fill the pending part first so that it could reach the min part size.
+        if pending_size > 0 {
+            let n = (min_part_size - pending_size).min(size);
+            if n > 0 {
+                pending.push((path.clone(), BytesRange::new(0, Some(n))));
+                pending_size += n;
+                offset = n;
+            }
+            if pending_size >= min_part_size {
+                parts.push(ConcatPart::Upload(std::mem::take(&mut pending)));
+                pending_size = 0;
+            }
+        }
+
+        while offset < size {
+            let remaining = size - offset;
+            // Only the last part is allowed to be smaller than the min part size.
+            if remaining < min_part_size && !is_last {
+                pending.push((path.clone(), BytesRange::new(offset, Some(remaining))));
+                pending_size += remaining;
+                break;
+            }
+
+            let n = remaining.min(max_copy_size);
+            parts.push(ConcatPart::Copy(
+                path.clone(),
+                BytesRange::new(offset, Some(n)),
+            ));
+            offset += n;
+        }
+    }
+
+    if !pending.is_empty() {
+        parts.push(ConcatPart::Upload(pending));
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn copy(path: &str, offset: u64, size: u64) -> ConcatPart {
+        ConcatPart::Copy(path.to_string(), BytesRange::new(offset, Some(size)))
+    }
+
+    fn upload(ranges: &[(&str, u64, u64)]) -> ConcatPart {
+        ConcatPart::Upload(
+            ranges
+                .iter()
+                .map(|(p, o, s)| (p.to_string(), BytesRange::new(*o, Some(*s))))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_plan_concat_parts() {
+        let cases = vec![
+            ("empty sources", 4, vec![("a", 0), ("b", 0)], vec![]),
+            (
+                "large sources",
+                4,
+                vec![("a", 10), ("b", 25)],
+                vec![
+                    copy("a", 0, 10),
+                    copy("b", 0, 10),
+                    copy("b", 10, 10),
+                    copy("b", 20, 5),
+                ],
+            ),
+            (
+                "small sources are merged",
+                4,
+                vec![("a", 2), ("b", 3), ("c", 7), ("d", 1)],
+                vec![
+                    upload(&[("a", 0, 2), ("b", 0, 2)]),
+                    upload(&[("b", 2, 1), ("c", 0, 3)]),
+                    copy("c", 3, 4),
+                    copy("d", 0, 1),
+                ],
+            ),
+            (
+                "small tail is merged into next source",
+                4,
+                vec![("a", 12), ("b", 6)],
+                vec![
+                    copy("a", 0, 10),
+                    upload(&[("a", 10, 2), ("b", 0, 2)]),
+                    copy("b", 2, 4),
+                ],
+            ),
+            (
+                "last small source is copied",
+                4,
+                vec![("a", 5), ("b", 1)],
+                vec![copy("a", 0, 5), copy("b", 0, 1)],
+            ),
+            (
+                "no min part size",
+                0,
+                vec![("a", 1), ("b", 12)],
+                vec![copy("a", 0, 1), copy("b", 0, 10), copy("b", 10, 2)],
+            ),
+        ];
+
+        for (name, min, sources, expected) in cases {
+            let sources: Vec<_> = sources
+                .into_iter()
+                .map(|(p, s)| (p.to_string(), s))
+                .collect();
+            assert_eq!(plan_concat_parts(&sources, min, 10), expected, "{name}");
+        }
+    }
+}
@@ -70,6 +70,9 @@ pub use bytes_range::BytesRange;
 mod bytes_content_range;
 pub use bytes_content_range::BytesContentRange;
 
+mod post_policy;
+pub use post_policy::PostPolicy;
+
 mod multipart;
 pub use multipart::FormDataPart;
 pub use multipart::MixedPart;
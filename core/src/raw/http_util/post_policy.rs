@@ -0,0 +1,116 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::time::Duration;
+
+use base64::engine::general_purpose;
+use base64::Engine;
+use chrono::DateTime;
+use chrono::Utc;
+use serde_json::json;
+use serde_json::Value;
+
+use crate::raw::*;
+
+/// PostPolicy builds the policy document of a browser based POST upload.
+///
+/// The document layout is shared by s3 compatible services and gcs, only
+/// the fields used to sign the encoded policy differ between them.
+#[derive(Debug, Clone)]
+pub struct PostPolicy {
+    expiration: DateTime<Utc>,
+    conditions: Vec<Value>,
+}
+
+impl PostPolicy {
+    /// Create a new policy that expires after `expire` since `now`.
+    pub fn new(now: DateTime<Utc>, expire: Duration) -> Self {
+        let expire = chrono::Duration::from_std(expire).unwrap_or(chrono::Duration::MAX);
+        Self {
+            expiration: now
+                .checked_add_signed(expire)
+                .unwrap_or(DateTime::<Utc>::MAX_UTC),
+            conditions: Vec::new(),
+        }
+    }
+
+    /// Require the form field `field` to equal `value`.
+    pub fn push_eq(&mut self, field: &str, value: &str) {
+        self.conditions.push(json!({ field: value }));
+    }
+
+    /// Push the conditions carried by [`OpPresignPost`].
+    pub fn push_conditions(&mut self, args: &OpPresignPost) {
+        if let Some((min, max)) = args.content_length_range() {
+            self.conditions
+                .push(json!(["content-length-range", min, max]));
+        }
+        if let Some(v) = args.content_type() {
+            self.conditions.push(json!(["eq", "$Content-Type", v]));
+        }
+        if let Some(v) = args.content_type_starts_with() {
+            self.conditions
+                .push(json!(["starts-with", "$Content-Type", v]));
+        }
+    }
+
+    /// Return the policy document in json.
+    pub fn document(&self) -> String {
+        json!({
+            "expiration": self.expiration.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+            "conditions": self.conditions,
+        })
+        .to_string()
+    }
+
+    /// Encode the policy document into base64, which is the value of the
+    /// `policy` form field.
+    pub fn encode(&self) -> String {
+        general_purpose::STANDARD.encode(self.document())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_post_policy_encode() {
+        let now = parse_datetime_from_rfc3339("2024-01-02T03:04:05Z").unwrap();
+        let mut policy = PostPolicy::new(now, Duration::from_secs(3600));
+        policy.push_eq("key", "dir/file");
+        policy.push_conditions(
+            &OpPresignPost::new()
+                .with_content_length_range(1, 1024)
+                .with_content_type_starts_with("image/"),
+        );
+
+        let decoded = general_purpose::STANDARD.decode(policy.encode()).unwrap();
+        let doc: Value = serde_json::from_slice(&decoded).unwrap();
+        assert_eq!(
+            doc,
+            json!({
+                "expiration": "2024-01-02T04:04:05.000Z",
+                "conditions": [
+                    {"key": "dir/file"},
+                    ["content-length-range", 1, 1024],
+                    ["starts-with", "$Content-Type", "image/"],
+                ],
+            })
+        );
+    }
+}
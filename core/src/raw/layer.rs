@@ -162,6 +162,49 @@ pub trait LayeredAccess: Send + Sync + Debug + Unpin + 'static {
         self.inner().stat(path, args)
     }
 
+    fn stat_many(
+        &self,
+        args: Vec<(String, OpStat)>,
+    ) -> impl Future<Output = Result<RpStatMany>> + MaybeSend {
+        self.inner().stat_many(args)
+    }
+
+    fn list_uploads(
+        &self,
+        path: &str,
+        args: OpListUploads,
+    ) -> impl Future<Output = Result<RpListUploads>> + MaybeSend {
+        self.inner().list_uploads(path, args)
+    }
+
+    fn abort_upload(
+        &self,
+        path: &str,
+        args: OpAbortUpload,
+    ) -> impl Future<Output = Result<RpAbortUpload>> + MaybeSend {
+        self.inner().abort_upload(path, args)
+    }
+
+    fn lock(&self, path: &str, args: OpLock) -> impl Future<Output = Result<RpLock>> + MaybeSend {
+        self.inner().lock(path, args)
+    }
+
+    fn concat(
+        &self,
+        path: &str,
+        args: OpConcat,
+    ) -> impl Future<Output = Result<RpConcat>> + MaybeSend {
+        self.inner().concat(path, args)
+    }
+
+    fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> impl Future<Output = Result<RpUpdateMetadata>> + MaybeSend {
+        self.inner().update_metadata(path, args)
+    }
+
     fn delete(&self) -> impl Future<Output = Result<(RpDelete, Self::Deleter)>> + MaybeSend;
 
     fn list(
@@ -213,6 +256,34 @@ impl<L: LayeredAccess> Access for L {
         LayeredAccess::stat(self, path, args).await
     }
 
+    async fn stat_many(&self, args: Vec<(String, OpStat)>) -> Result<RpStatMany> {
+        LayeredAccess::stat_many(self, args).await
+    }
+
+    async fn list_uploads(&self, path: &str, args: OpListUploads) -> Result<RpListUploads> {
+        LayeredAccess::list_uploads(self, path, args).await
+    }
+
+    async fn abort_upload(&self, path: &str, args: OpAbortUpload) -> Result<RpAbortUpload> {
+        LayeredAccess::abort_upload(self, path, args).await
+    }
+
+    async fn lock(&self, path: &str, args: OpLock) -> Result<RpLock> {
+        LayeredAccess::lock(self, path, args).await
+    }
+
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        LayeredAccess::concat(self, path, args).await
+    }
+
+    async fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> Result<RpUpdateMetadata> {
+        LayeredAccess::update_metadata(self, path, args).await
+    }
+
     async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
         LayeredAccess::delete(self).await
     }
@@ -88,6 +88,9 @@ pub use futures_util::BoxedStaticFuture;
 pub use futures_util::ConcurrentTasks;
 pub use futures_util::MaybeSend;
 
+mod concat_util;
+pub use concat_util::*;
+
 mod enum_utils;
 pub use enum_utils::*;
 
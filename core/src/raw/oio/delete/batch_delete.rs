@@ -38,6 +38,9 @@ pub trait BatchDelete: Send + Sync + Unpin + 'static {
 
     /// delete_batch delete multiple paths at once.
     ///
+    /// BatchDeleter will never send deletes with preconditions in batch, they will be sent
+    /// via `delete_once` one by one instead.
+    ///
     /// - Implementations should make sure that the length of `batch` equals to the return result's length.
     /// - Implementations should return error no path is deleted.
     fn delete_batch(
@@ -92,6 +95,20 @@ impl<D: BatchDelete> oio::Delete for BatchDeleter<D> {
             self.buffer.clear();
             return Ok(1);
         }
+        // Batch delete APIs don't support preconditions, so conditional deletes
+        // must be sent one by one.
+        if let Some(item) = self
+            .buffer
+            .iter()
+            .find(|(_, args)| args.has_conditions())
+            .cloned()
+        {
+            self.inner
+                .delete_once(item.0.clone(), item.1.clone())
+                .await?;
+            self.buffer.remove(&item);
+            return Ok(1);
+        }
 
         let batch = self.buffer.iter().cloned().collect();
         let result = self.inner.delete_batch(batch).await?;
@@ -0,0 +1,442 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+
+use futures::future::select_all;
+
+use crate::raw::*;
+use crate::*;
+
+/// The max entries that a partition task will fetch before handing back its lister.
+const DEFAULT_BATCH_SIZE: usize = 1000;
+
+/// The max batches that a partition can buffer before the caller consumes them.
+///
+/// Only used in ordered mode. Unordered mode returns entries as soon as they arrive.
+const MAX_BUFFERED_BATCHES: usize = 2;
+
+type PartitionTask = Task<(oio::Lister, Result<(Vec<oio::Entry>, bool)>)>;
+
+/// ConcurrentLister walks a prefix recursively by splitting the keyspace into
+/// partitions and listing them concurrently.
+///
+/// # Architecture
+///
+/// - List the given path once without `recursive` (delimiter fan-out).
+/// - Every file returned is emitted directly.
+/// - Every sub dir returned becomes a partition that will be listed with `recursive`.
+/// - At most `concurrent` partitions are listed at the same time via [`Executor`].
+///
+/// # Ordering
+///
+/// - In ordered mode, entries are returned in the same order of the fan-out listing, with every
+///   partition expanded in place. For services that list in lexicographical order, this is the
+///   same order as a sequential recursive list.
+/// - In unordered mode, entries are returned as soon as any partition yields them.
+pub struct ConcurrentLister {
+    acc: Accessor,
+    executor: Executor,
+    args: OpList,
+    concurrent: usize,
+    ordered: bool,
+    batch_size: usize,
+
+    /// The lister that used to discover partitions, `None` means exhausted.
+    root: Option<oio::Lister>,
+    root_path: String,
+    errored: bool,
+
+    /// Slots in fan-out order, only used by ordered mode.
+    slots: VecDeque<Slot>,
+    /// Entries ready to be returned, only used by unordered mode.
+    ready: VecDeque<oio::Entry>,
+    /// Partitions that have not been started yet.
+    pending: VecDeque<usize>,
+
+    partitions: HashMap<usize, Partition>,
+    tasks: Vec<(usize, PartitionTask)>,
+    next_id: usize,
+}
+
+enum Slot {
+    Entry(Box<oio::Entry>),
+    Partition(usize),
+}
+
+struct Partition {
+    path: String,
+    lister: Option<oio::Lister>,
+    buffered: VecDeque<oio::Entry>,
+    batches: usize,
+    done: bool,
+}
+
+/// # Safety
+///
+/// ConcurrentLister will only be accessed by `&mut Self`
+unsafe impl Sync for ConcurrentLister {}
+
+impl ConcurrentLister {
+    /// Create a new concurrent lister.
+    ///
+    /// `args` will be used to list every partition with `recursive` enabled.
+    pub async fn create(acc: Accessor, path: &str, args: OpList) -> Result<Self> {
+        let executor = acc.info().executor();
+
+        let mut root_args = OpList::new()
+            .with_versions(args.versions())
+            .with_deleted(args.deleted());
+        if let Some(limit) = args.limit() {
+            root_args = root_args.with_limit(limit);
+        }
+        if let Some(start_after) = args.start_after() {
+            root_args = root_args.with_start_after(start_after);
+        }
+        let (_, root) = acc.list(path, root_args).await?;
+
+        Ok(Self {
+            acc,
+            executor,
+            concurrent: args.concurrent().max(1),
+            ordered: args.ordered(),
+            batch_size: args.limit().unwrap_or(DEFAULT_BATCH_SIZE).max(1),
+            args: args.with_recursive(true),
+
+            root: Some(root),
+            root_path: path.to_string(),
+            errored: false,
+
+            slots: VecDeque::new(),
+            ready: VecDeque::new(),
+            pending: VecDeque::new(),
+
+            partitions: HashMap::new(),
+            tasks: Vec::new(),
+            next_id: 0,
+        })
+    }
+
+    /// Pull entries from the root lister until we have enough partitions to run.
+    async fn fill(&mut self) -> Result<()> {
+        // Don't let the buffered root entries grow without limit.
+        let max_slots = self.concurrent * self.batch_size;
+
+        while self.partitions.len() < self.concurrent && self.slots.len() < max_slots {
+            let Some(root) = self.root.as_mut() else {
+                return Ok(());
+            };
+
+            let Some(entry) = root.next_dyn().await? else {
+                self.root = None;
+                return Ok(());
+            };
+
+            if !entry.mode().is_dir() || entry.path() == self.root_path {
+                if self.ordered {
+                    self.slots.push_back(Slot::Entry(Box::new(entry)));
+                } else {
+                    self.ready.push_back(entry);
+                    return Ok(());
+                }
+                continue;
+            }
+
+            let id = self.next_id;
+            self.next_id += 1;
+            self.partitions.insert(
+                id,
+                Partition {
+                    path: entry.path().to_string(),
+                    lister: None,
+                    buffered: VecDeque::new(),
+                    batches: 0,
+                    done: false,
+                },
+            );
+            self.pending.push_back(id);
+            if self.ordered {
+                self.slots.push_back(Slot::Partition(id));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Start pending partitions while there are available slots.
+    async fn start(&mut self) -> Result<()> {
+        while self.tasks.len() < self.concurrent {
+            let Some(id) = self.pending.pop_front() else {
+                return Ok(());
+            };
+            let path = self.partitions[&id].path.clone();
+            let (_, lister) = self.acc.list(&path, self.args.clone()).await?;
+            self.spawn(id, lister);
+        }
+
+        Ok(())
+    }
+
+    fn spawn(&mut self, id: usize, mut lister: oio::Lister) {
+        let batch_size = self.batch_size;
+        let task = self.executor.execute(async move {
+            let mut entries = Vec::with_capacity(batch_size);
+            let res = loop {
+                if entries.len() >= batch_size {
+                    break Ok(false);
+                }
+                match lister.next_dyn().await {
+                    Ok(Some(entry)) => entries.push(entry),
+                    Ok(None) => break Ok(true),
+                    Err(err) => break Err(err),
+                }
+            };
+            (lister, res.map(|done| (entries, done)))
+        });
+        self.tasks.push((id, task));
+    }
+
+    /// Wait for any running partition task and collect its entries.
+    async fn collect(&mut self) -> Result<()> {
+        let ((lister, res), index, _) =
+            select_all(self.tasks.iter_mut().map(|(_, task)| task)).await;
+        let (id, _) = self.tasks.swap_remove(index);
+
+        let (entries, done) = res?;
+        let ordered = self.ordered;
+        let partition = self
+            .partitions
+            .get_mut(&id)
+            .expect("partition of running task must exist");
+        partition.done = done;
+        if ordered {
+            partition.buffered.extend(entries);
+            partition.batches += 1;
+        } else {
+            self.ready.extend(entries);
+        }
+
+        if done {
+            if !ordered {
+                self.partitions.remove(&id);
+            }
+        } else if !ordered || partition.batches < MAX_BUFFERED_BATCHES {
+            self.spawn(id, lister);
+        } else {
+            // Keep the lister until the buffered entries have been consumed.
+            partition.lister = Some(lister);
+        }
+
+        Ok(())
+    }
+
+    /// Return the next entry in fan-out order if it's ready.
+    ///
+    /// Returns `Ok(None)` if the head slot is still waiting for data.
+    fn next_ordered(&mut self) -> Option<oio::Entry> {
+        loop {
+            let id = match self.slots.front()? {
+                Slot::Entry(_) => {
+                    let Some(Slot::Entry(entry)) = self.slots.pop_front() else {
+                        unreachable!("head slot must be entry")
+                    };
+                    return Some(*entry);
+                }
+                Slot::Partition(id) => *id,
+            };
+
+            let partition = self
+                .partitions
+                .get_mut(&id)
+                .expect("partition of slot must exist");
+            if let Some(entry) = partition.buffered.pop_front() {
+                if partition.buffered.is_empty() {
+                    partition.batches = 0;
+                    if let Some(lister) = partition.lister.take() {
+                        self.spawn(id, lister);
+                    }
+                }
+                return Some(entry);
+            }
+            if !partition.done {
+                return None;
+            }
+
+            self.partitions.remove(&id);
+            self.slots.pop_front();
+        }
+    }
+}
+
+impl oio::List for ConcurrentLister {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        if self.errored {
+            return Ok(None);
+        }
+
+        loop {
+            let entry = if self.ordered {
+                self.next_ordered()
+            } else {
+                self.ready.pop_front()
+            };
+            if let Some(entry) = entry {
+                return Ok(Some(entry));
+            }
+
+            let res = async {
+                self.fill().await?;
+                self.start().await?;
+
+                if self.tasks.is_empty() {
+                    return Ok(false);
+                }
+                // The head slot could be a file or a finished partition, check it before waiting.
+                if self.ordered && self.slots.front().is_some_and(|s| self.is_ready(s)) {
+                    return Ok(true);
+                }
+                if !self.ordered && !self.ready.is_empty() {
+                    return Ok(true);
+                }
+                self.collect().await?;
+                Ok(true)
+            }
+            .await;
+
+            match res {
+                Ok(true) => continue,
+                Ok(false) => {
+                    let has_more = if self.ordered {
+                        !self.slots.is_empty()
+                    } else {
+                        !self.ready.is_empty()
+                    };
+                    if has_more || self.root.is_some() {
+                        continue;
+                    }
+                    return Ok(None);
+                }
+                Err(err) => {
+                    // Drop all running tasks, they will be canceled.
+                    self.tasks.clear();
+                    self.errored = true;
+                    return Err(err);
+                }
+            }
+        }
+    }
+}
+
+impl ConcurrentLister {
+    fn is_ready(&self, slot: &Slot) -> bool {
+        match slot {
+            Slot::Entry(_) => true,
+            Slot::Partition(id) => {
+                let p = &self.partitions[id];
+                !p.buffered.is_empty() || p.done
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use futures::TryStreamExt;
+
+    use crate::services::Memory;
+    use crate::*;
+
+    async fn prepare() -> Result<(Operator, Vec<String>)> {
+        let op = Operator::new(Memory::default())?.finish();
+
+        let mut paths = vec![];
+        for dir in ["a", "b", "c", "d"] {
+            for sub in ["x", "y"] {
+                for file in 0..5 {
+                    paths.push(format!("root/{dir}/{sub}/{file}"));
+                }
+            }
+            paths.push(format!("root/{dir}.txt"));
+        }
+        for path in &paths {
+            op.write(path, "hello").await?;
+        }
+        Ok((op, paths))
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_list_unordered() -> Result<()> {
+        let (op, paths) = prepare().await?;
+
+        let expected = op
+            .lister_with("root/")
+            .recursive(true)
+            .await?
+            .map_ok(|e| e.path().to_string())
+            .try_collect::<HashSet<_>>()
+            .await?;
+        let actual = op
+            .lister_with("root/")
+            .recursive(true)
+            .concurrent(3)
+            .limit(2)
+            .await?
+            .map_ok(|e| e.path().to_string())
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        assert_eq!(
+            actual.len(),
+            expected.len(),
+            "no entries should be duplicated"
+        );
+        assert_eq!(actual.into_iter().collect::<HashSet<_>>(), expected);
+        for path in paths {
+            assert!(expected.contains(&path));
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_list_ordered() -> Result<()> {
+        let (op, _) = prepare().await?;
+
+        let expected = op
+            .lister_with("root/")
+            .recursive(true)
+            .await?
+            .map_ok(|e| e.path().to_string())
+            .try_collect::<Vec<_>>()
+            .await?;
+        let actual = op
+            .lister_with("root/")
+            .recursive(true)
+            .concurrent(3)
+            .ordered(true)
+            .limit(2)
+            .await?
+            .map_ok(|e| e.path().to_string())
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+}
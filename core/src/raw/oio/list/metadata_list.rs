@@ -0,0 +1,286 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::VecDeque;
+
+use crate::options::MetadataFields;
+use crate::raw::*;
+use crate::*;
+
+/// The default number of paths in a single `stat_many` request.
+const DEFAULT_STAT_MANY_SIZE: usize = 100;
+
+/// The max entries that a single batch could hold.
+///
+/// Entries that don't need stat will be carried along with the batch to keep the order,
+/// this limit avoids buffering too many of them.
+const MAX_BATCH_ENTRIES: usize = 1000;
+
+/// The input of a fill task.
+struct FillInput {
+    acc: Accessor,
+    fields: MetadataFields,
+    native: bool,
+    entries: Vec<oio::Entry>,
+}
+
+/// MetadataLister will make sure every file entry returned by the inner lister contains
+/// the required metadata fields.
+///
+/// # Architecture
+///
+/// - Entries are pulled from the inner lister in batches.
+/// - For every file entry that is missing any of the required fields, a stat will be sent.
+/// - Stats will be sent via `stat_many` if the service supports it natively, otherwise
+///   they will be sent one by one.
+/// - At most `concurrent` batches will be filled at the same time via [`ConcurrentTasks`].
+///
+/// Entries are returned in the same order as the inner lister. Entries that are not found
+/// while stat (removed after list) will be returned as is.
+pub struct MetadataLister {
+    acc: Accessor,
+    lister: Option<oio::Lister>,
+    fields: MetadataFields,
+    native: bool,
+    batch_size: usize,
+
+    tasks: ConcurrentTasks<FillInput, Vec<oio::Entry>>,
+    ready: VecDeque<oio::Entry>,
+}
+
+/// # Safety
+///
+/// MetadataLister will only be accessed by `&mut Self`
+unsafe impl Sync for MetadataLister {}
+
+impl MetadataLister {
+    /// Create a new metadata lister.
+    pub fn new(acc: Accessor, lister: oio::Lister, args: &OpList) -> Self {
+        let info = acc.info();
+        let cap = info.native_capability();
+        let (native, batch_size) = if cap.stat_many {
+            (
+                true,
+                cap.stat_many_max_size
+                    .unwrap_or(DEFAULT_STAT_MANY_SIZE)
+                    .max(1),
+            )
+        } else {
+            (false, 1)
+        };
+
+        let tasks = ConcurrentTasks::new(
+            info.executor(),
+            args.concurrent().max(1),
+            0,
+            |input: FillInput| {
+                Box::pin(async move {
+                    let res = fill_metadata(&input).await;
+                    (input, res)
+                })
+            },
+        );
+
+        Self {
+            acc,
+            lister: Some(lister),
+            fields: args.metadata(),
+            native,
+            batch_size,
+
+            tasks,
+            ready: VecDeque::new(),
+        }
+    }
+
+    /// Check if given entry needs to be filled by stat.
+    fn need_stat(fields: &MetadataFields, entry: &oio::Entry) -> bool {
+        let meta = entry.metadata();
+        entry.mode().is_file() && !meta.is_deleted() && !meta.contains_fields(fields)
+    }
+
+    /// Pull the next batch from the inner lister.
+    ///
+    /// Returns `None` if the inner lister has been exhausted.
+    async fn next_batch(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        let Some(lister) = self.lister.as_mut() else {
+            return Ok(None);
+        };
+
+        let mut entries = Vec::new();
+        let mut needed = 0;
+        while needed < self.batch_size && entries.len() < MAX_BATCH_ENTRIES {
+            let Some(entry) = lister.next_dyn().await? else {
+                self.lister = None;
+                break;
+            };
+            if Self::need_stat(&self.fields, &entry) {
+                needed += 1;
+            }
+            entries.push(entry);
+        }
+
+        if entries.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(entries))
+    }
+}
+
+impl oio::List for MetadataLister {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        loop {
+            if let Some(entry) = self.ready.pop_front() {
+                return Ok(Some(entry));
+            }
+
+            // Keep sending batches until we have a result or no available slots.
+            while !self.tasks.has_result() && self.tasks.has_remaining() {
+                let Some(entries) = self.next_batch().await? else {
+                    break;
+                };
+                self.tasks
+                    .execute(FillInput {
+                        acc: self.acc.clone(),
+                        fields: self.fields,
+                        native: self.native,
+                        entries,
+                    })
+                    .await?;
+            }
+
+            match self.tasks.next().await {
+                Some(entries) => self.ready.extend(entries?),
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+/// Fill the metadata of entries in given input.
+async fn fill_metadata(input: &FillInput) -> Result<Vec<oio::Entry>> {
+    let mut entries = input.entries.clone();
+
+    let indexes: Vec<usize> = entries
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| MetadataLister::need_stat(&input.fields, e))
+        .map(|(idx, _)| idx)
+        .collect();
+    if indexes.is_empty() {
+        return Ok(entries);
+    }
+
+    let results = if input.native {
+        let args = indexes
+            .iter()
+            .map(|idx| (entries[*idx].path().to_string(), stat_args(&entries[*idx])))
+            .collect();
+        input.acc.stat_many(args).await?.into_results()
+    } else {
+        let mut results = Vec::with_capacity(indexes.len());
+        for idx in &indexes {
+            let entry = &entries[*idx];
+            let res = input.acc.stat(entry.path(), stat_args(entry)).await;
+            results.push(res.map(|rp| rp.into_metadata()));
+        }
+        results
+    };
+
+    if results.len() != indexes.len() {
+        return Err(Error::new(
+            ErrorKind::Unexpected,
+            "stat_many returned unexpected number of results",
+        )
+        .with_context("expected", indexes.len().to_string())
+        .with_context("actual", results.len().to_string()));
+    }
+
+    for (idx, res) in indexes.into_iter().zip(results) {
+        let meta = match res {
+            Ok(meta) => meta,
+            // The entry has been removed after list, return it as is.
+            Err(err) if err.kind() == ErrorKind::NotFound => continue,
+            Err(err) => return Err(err),
+        };
+
+        let old = entries[idx].metadata();
+        let mut meta = meta
+            .with_is_current(old.is_current())
+            .with_is_deleted(old.is_deleted());
+        if let Some(version) = old.version() {
+            meta.set_version(version);
+        }
+        entries[idx] = oio::Entry::new(entries[idx].path(), meta);
+    }
+
+    Ok(entries)
+}
+
+fn stat_args(entry: &oio::Entry) -> OpStat {
+    match entry.metadata().version() {
+        Some(version) => OpStat::new().with_version(version),
+        None => OpStat::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use crate::options::MetadataFields;
+    use crate::services::Memory;
+    use crate::*;
+
+    #[tokio::test]
+    async fn test_metadata_list_keeps_order() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        for i in 0..20 {
+            op.write_with(&format!("dir/{i:02}"), "hello")
+                .content_type("text/plain")
+                .await?;
+        }
+
+        let expected = op
+            .lister_with("dir/")
+            .await?
+            .map_ok(|e| e.path().to_string())
+            .try_collect::<Vec<_>>()
+            .await?;
+        let entries = op
+            .lister_with("dir/")
+            .concurrent(4)
+            .metadata(MetadataFields {
+                content_length: true,
+                content_type: true,
+                ..Default::default()
+            })
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        assert_eq!(
+            entries.iter().map(|e| e.path()).collect::<Vec<_>>(),
+            expected
+        );
+        for entry in entries.iter().filter(|e| e.metadata().is_file()) {
+            assert_eq!(entry.metadata().content_length(), 5);
+            assert_eq!(entry.metadata().content_type(), Some("text/plain"));
+        }
+        Ok(())
+    }
+}
@@ -32,3 +32,9 @@ pub use hierarchy_list::HierarchyLister;
 
 mod prefix_list;
 pub use prefix_list::PrefixLister;
+
+mod concurrent_list;
+pub use concurrent_list::ConcurrentLister;
+
+mod metadata_list;
+pub use metadata_list::MetadataLister;
@@ -98,6 +98,8 @@ struct WriteInput<W: BlockWrite> {
     executor: Executor,
     block_id: Uuid,
     bytes: Buffer,
+    index: usize,
+    tracker: Option<oio::UploadTracker>,
 }
 
 /// BlockWriter will implement [`oio::Write`] based on block
@@ -109,6 +111,8 @@ pub struct BlockWriter<W: BlockWrite> {
     started: bool,
     block_ids: Vec<Uuid>,
     cache: Option<Buffer>,
+    next_index: usize,
+    tracker: Option<oio::UploadTracker>,
     tasks: ConcurrentTasks<WriteInput<W>, Uuid>,
 }
 
@@ -123,6 +127,8 @@ impl<W: BlockWrite> BlockWriter<W> {
             started: false,
             block_ids: Vec::new(),
             cache: None,
+            next_index: 0,
+            tracker: None,
 
             tasks: ConcurrentTasks::new(executor, concurrent, 8192, |input| {
                 Box::pin(async move {
@@ -134,13 +140,10 @@ impl<W: BlockWrite> BlockWriter<W> {
                             input.bytes.clone(),
                         )
                         .map_ok(|_| input.block_id);
-                    match input.executor.timeout() {
-                        None => {
-                            let result = fut.await;
-                            (input, result)
-                        }
+                    let result = match input.executor.timeout() {
+                        None => fut.await,
                         Some(timeout) => {
-                            let result = select! {
+                            select! {
                                 result = fut.fuse() => {
                                     result
                                 }
@@ -150,15 +153,56 @@ impl<W: BlockWrite> BlockWriter<W> {
                                                 .with_context("block_id", input.block_id.to_string())
                                                 .set_temporary())
                                 }
-                            };
-                            (input, result)
+                            }
                         }
+                    };
+                    if let (Ok(block_id), Some(tracker)) = (&result, &input.tracker) {
+                        tracker.record(UploadPart {
+                            part_number: input.index,
+                            etag: block_id.to_string(),
+                            checksum: None,
+                            size: input.bytes.len() as u64,
+                        });
                     }
+                    (input, result)
                 })
             }),
         }
     }
 
+    /// Set up the writer to track and resume uploads based on given [`OpWrite`].
+    ///
+    /// - Progress will be recorded into [`OpWrite::upload_tracker`] if exists.
+    /// - Upload will be resumed from [`OpWrite::resume`] if exists, the etag of parts
+    ///   must be the block id.
+    pub fn with_resume(mut self, args: &OpWrite) -> Result<Self> {
+        self.tracker = args.upload_tracker().cloned();
+
+        if let Some(state) = args.resume() {
+            self.block_ids = state
+                .parts()
+                .iter()
+                .map(|p| {
+                    Uuid::parse_str(&p.etag).map_err(|err| {
+                        Error::new(ErrorKind::Unexpected, "resumed block id is invalid")
+                            .with_context("block_id", &p.etag)
+                            .set_source(err)
+                    })
+                })
+                .collect::<Result<_>>()?;
+            self.next_index = self.block_ids.len();
+            self.started = true;
+
+            if let Some(tracker) = &self.tracker {
+                tracker.start(state.upload_id());
+                for part in state.parts() {
+                    tracker.record(part.clone());
+                }
+            }
+        }
+        Ok(self)
+    }
+
     fn fill_cache(&mut self, bs: Buffer) -> usize {
         let size = bs.len();
         assert!(self.cache.is_none());
@@ -172,13 +216,20 @@ where
     W: BlockWrite,
 {
     async fn write(&mut self, bs: Buffer) -> Result<()> {
-        if !self.started && self.cache.is_none() {
+        // Resumed writer could have been started without cache.
+        if self.cache.is_none() {
             self.fill_cache(bs);
             return Ok(());
         }
 
         // The block upload process has been started.
-        self.started = true;
+        if !self.started {
+            self.started = true;
+            if let Some(tracker) = &self.tracker {
+                // Block uploads don't have upload id.
+                tracker.start("");
+            }
+        }
 
         let bytes = self.cache.clone().expect("pending write must exist");
         self.tasks
@@ -187,9 +238,12 @@ where
                 executor: self.executor.clone(),
                 block_id: Uuid::new_v4(),
                 bytes,
+                index: self.next_index,
+                tracker: self.tracker.clone(),
             })
             .await?;
         self.cache = None;
+        self.next_index += 1;
         self.fill_cache(bs);
         Ok(())
     }
@@ -213,9 +267,12 @@ where
                     executor: self.executor.clone(),
                     block_id: Uuid::new_v4(),
                     bytes: cache,
+                    index: self.next_index,
+                    tracker: self.tracker.clone(),
                 })
                 .await?;
             self.cache = None;
+            self.next_index += 1;
         }
 
         loop {
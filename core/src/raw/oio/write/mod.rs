@@ -39,3 +39,6 @@ pub use block_write::BlockWriter;
 mod position_write;
 pub use position_write::PositionWrite;
 pub use position_write::PositionWriter;
+
+mod upload_tracker;
+pub use upload_tracker::UploadTracker;
@@ -126,6 +126,7 @@ struct WriteInput<W: MultipartWrite> {
     upload_id: Arc<String>,
     part_number: usize,
     bytes: Buffer,
+    tracker: Option<oio::UploadTracker>,
 }
 
 /// MultipartWriter will implement [`oio::Write`] based on multipart
@@ -138,6 +139,7 @@ pub struct MultipartWriter<W: MultipartWrite> {
     parts: Vec<MultipartPart>,
     cache: Option<Buffer>,
     next_part_number: usize,
+    tracker: Option<oio::UploadTracker>,
 
     tasks: ConcurrentTasks<WriteInput<W>, MultipartPart>,
 }
@@ -157,6 +159,7 @@ impl<W: MultipartWrite> MultipartWriter<W> {
             parts: Vec::new(),
             cache: None,
             next_part_number: 0,
+            tracker: None,
 
             tasks: ConcurrentTasks::new(executor, concurrent, 8192, |input| {
                 Box::pin({
@@ -167,13 +170,10 @@ impl<W: MultipartWrite> MultipartWriter<W> {
                             input.bytes.len() as u64,
                             input.bytes.clone(),
                         );
-                        match input.executor.timeout() {
-                            None => {
-                                let result = fut.await;
-                                (input, result)
-                            }
+                        let result = match input.executor.timeout() {
+                            None => fut.await,
                             Some(timeout) => {
-                                let result = select! {
+                                select! {
                                     result = fut.fuse() => {
                                         result
                                     }
@@ -184,16 +184,54 @@ impl<W: MultipartWrite> MultipartWriter<W> {
                                                 .with_context("part_number", input.part_number.to_string())
                                                 .set_temporary())
                                     }
-                                };
-                                (input, result)
+                                }
                             }
+                        };
+                        if let (Ok(part), Some(tracker)) = (&result, &input.tracker) {
+                            tracker.record(UploadPart {
+                                part_number: part.part_number,
+                                etag: part.etag.clone(),
+                                checksum: part.checksum.clone(),
+                                size: input.bytes.len() as u64,
+                            });
                         }
+                        (input, result)
                     }
                 })
             }),
         }
     }
 
+    /// Set up the writer to track and resume uploads based on given [`OpWrite`].
+    ///
+    /// - Progress will be recorded into [`OpWrite::upload_tracker`] if exists.
+    /// - Upload will be resumed from [`OpWrite::resume`] if exists.
+    pub fn with_resume(mut self, args: &OpWrite) -> Self {
+        self.tracker = args.upload_tracker().cloned();
+
+        if let Some(state) = args.resume() {
+            self.upload_id = Some(Arc::new(state.upload_id().to_string()));
+            self.parts = state
+                .parts()
+                .iter()
+                .map(|p| MultipartPart {
+                    part_number: p.part_number,
+                    etag: p.etag.clone(),
+                    checksum: p.checksum.clone(),
+                })
+                .collect();
+            self.next_part_number = self.parts.len();
+
+            if let Some(tracker) = &self.tracker {
+                tracker.start(state.upload_id());
+                for part in state.parts() {
+                    tracker.record(part.clone());
+                }
+            }
+        }
+        self
+    }
+
     fn fill_cache(&mut self, bs: Buffer) -> usize {
         let size = bs.len();
         assert!(self.cache.is_none());
@@ -207,16 +245,21 @@ where
     W: MultipartWrite,
 {
     async fn write(&mut self, bs: Buffer) -> Result<()> {
+        // Fill cache with the first write.
+        //
+        // Resumed writer could have upload_id without cache.
+        if self.cache.is_none() {
+            self.fill_cache(bs);
+            return Ok(());
+        }
+
         let upload_id = match self.upload_id.clone() {
             Some(v) => v,
             None => {
-                // Fill cache with the first write.
-                if self.cache.is_none() {
-                    self.fill_cache(bs);
-                    return Ok(());
-                }
-
                 let upload_id = self.w.initiate_part().await?;
+                if let Some(tracker) = &self.tracker {
+                    tracker.start(&upload_id);
+                }
                 let upload_id = Arc::new(upload_id);
                 self.upload_id = Some(upload_id.clone());
                 upload_id
@@ -233,6 +276,7 @@ where
                 upload_id: upload_id.clone(),
                 part_number,
                 bytes,
+                tracker: self.tracker.clone(),
             })
             .await?;
         self.cache = None;
@@ -268,6 +312,7 @@ where
                     upload_id: upload_id.clone(),
                     part_number,
                     bytes: cache,
+                    tracker: self.tracker.clone(),
                 })
                 .await?;
             self.cache = None;
@@ -515,4 +560,41 @@ mod tests {
             assert_eq!(inner.content.clone().unwrap().to_bytes(), bs);
         }
     }
+
+    #[tokio::test]
+    async fn test_multipart_writer_resume() {
+        let mut rng = thread_rng();
+        let inner = TestWrite::new();
+        let tracker = oio::UploadTracker::new();
+        let args = OpWrite::new().with_upload_tracker(tracker.clone());
+
+        let chunks: Vec<Vec<u8>> = (0..4)
+            .map(|_| {
+                let mut bs = vec![0; rng.gen_range(1..1024)];
+                rng.fill_bytes(&mut bs);
+                bs
+            })
+            .collect();
+
+        let mut w = MultipartWriter::new(Arc::default(), inner.clone(), 1).with_resume(&args);
+        for bs in &chunks[..3] {
+            while w.write(bs.clone().into()).await.is_err() {}
+        }
+        // The last chunk is still cached, only the first two parts could be resumed.
+        let state = tracker.state().expect("upload must be started");
+        assert_eq!(state.parts().len(), 2);
+        assert_eq!(state.offset(), (chunks[0].len() + chunks[1].len()) as u64);
+
+        let args = OpWrite::new().with_resume(state);
+        let mut w = MultipartWriter::new(Arc::default(), inner.clone(), 1).with_resume(&args);
+        for bs in &chunks[2..] {
+            while w.write(bs.clone().into()).await.is_err() {}
+        }
+        while w.close().await.is_err() {}
+
+        let actual_parts: Vec<_> = w.parts.iter().map(|v| v.part_number).collect();
+        assert_eq!(actual_parts, vec![0, 1, 2, 3]);
+        let total_size: usize = chunks.iter().map(|v| v.len()).sum();
+        assert_eq!(inner.lock().await.length, total_size as u64);
+    }
 }
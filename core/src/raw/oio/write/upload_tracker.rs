@@ -0,0 +1,105 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::*;
+
+/// UploadTracker records the progress of a multipart upload so that it could be exported
+/// as [`UploadState`] and resumed later.
+///
+/// It's shared between [`Writer`] and the underlying writers like [`oio::MultipartWriter`]
+/// via [`OpWrite::upload_tracker`](crate::raw::OpWrite::upload_tracker).
+#[derive(Debug, Clone, Default)]
+pub struct UploadTracker {
+    inner: Arc<Mutex<TrackerState>>,
+}
+
+#[derive(Debug, Default)]
+struct TrackerState {
+    upload_id: Option<String>,
+    parts: BTreeMap<usize, UploadPart>,
+}
+
+impl UploadTracker {
+    /// Create a new upload tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark the upload as started with given upload id.
+    pub fn start(&self, upload_id: &str) {
+        let mut state = self.inner.lock().expect("lock must be valid");
+        state.upload_id = Some(upload_id.to_string());
+    }
+
+    /// Record an uploaded part.
+    pub fn record(&self, part: UploadPart) {
+        let mut state = self.inner.lock().expect("lock must be valid");
+        state.parts.insert(part.part_number, part);
+    }
+
+    /// Export the current state.
+    ///
+    /// Returns `None` if the upload has not been started yet.
+    pub fn state(&self) -> Option<UploadState> {
+        let state = self.inner.lock().expect("lock must be valid");
+        let upload_id = state.upload_id.clone()?;
+
+        // Only parts that are contiguous from the beginning could be resumed.
+        let parts = state
+            .parts
+            .values()
+            .enumerate()
+            .take_while(|(idx, part)| *idx == part.part_number)
+            .map(|(_, part)| part.clone())
+            .collect();
+        Some(UploadState::new(upload_id, parts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part(part_number: usize) -> UploadPart {
+        UploadPart {
+            part_number,
+            etag: format!("etag-{part_number}"),
+            checksum: None,
+            size: 10,
+        }
+    }
+
+    #[test]
+    fn test_state_only_contains_contiguous_parts() {
+        let tracker = UploadTracker::new();
+        assert_eq!(tracker.state(), None);
+
+        tracker.start("upload");
+        tracker.record(part(1));
+        tracker.record(part(0));
+        tracker.record(part(3));
+
+        let state = tracker.state().unwrap();
+        assert_eq!(state.upload_id(), "upload");
+        assert_eq!(state.parts(), &[part(0), part(1)]);
+        assert_eq!(state.offset(), 20);
+    }
+}
@@ -49,6 +49,16 @@ pub enum Operation {
     List,
     /// Operation to generate a presigned URL.
     Presign,
+    /// Operation to list incomplete multipart uploads.
+    ListUploads,
+    /// Operation to abort an incomplete multipart upload.
+    AbortUpload,
+    /// Operation to acquire, renew or release a lock.
+    Lock,
+    /// Operation to concatenate files into one.
+    Concat,
+    /// Operation to update metadata of a file in place.
+    UpdateMetadata,
 }
 
 impl Operation {
@@ -77,6 +87,11 @@ impl From<Operation> for &'static str {
             Operation::Delete => "delete",
             Operation::List => "list",
             Operation::Presign => "presign",
+            Operation::ListUploads => "list_uploads",
+            Operation::AbortUpload => "abort_upload",
+            Operation::Lock => "lock",
+            Operation::Concat => "concat",
+            Operation::UpdateMetadata => "update_metadata",
         }
     }
 }
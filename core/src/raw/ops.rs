@@ -27,6 +27,8 @@ use chrono::Utc;
 
 use crate::options;
 use crate::raw::*;
+use crate::Metadata;
+use crate::UploadState;
 
 /// Args for `create` operation.
 ///
@@ -41,12 +43,301 @@ impl OpCreateDir {
     }
 }
 
+/// Args for `list_uploads` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpListUploads {}
+
+impl OpListUploads {
+    /// Create a new `OpListUploads`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Args for `abort_upload` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpAbortUpload {
+    upload_id: String,
+}
+
+impl OpAbortUpload {
+    /// Create a new `OpAbortUpload`.
+    pub fn new(upload_id: &str) -> Self {
+        Self {
+            upload_id: upload_id.to_string(),
+        }
+    }
+
+    /// Get the upload id of this operation.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+}
+
+/// The action of `lock` operation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LockAction {
+    /// Acquire the lock, fails if the lock is held by others and not expired.
+    #[default]
+    Acquire,
+    /// Renew the lock held by the token.
+    Renew,
+    /// Release the lock held by the token.
+    Release,
+}
+
+/// Args for `lock` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpLock {
+    action: LockAction,
+    token: String,
+    ttl: Duration,
+}
+
+impl OpLock {
+    /// Create a new `OpLock`.
+    pub fn new(action: LockAction, token: &str, ttl: Duration) -> Self {
+        Self {
+            action,
+            token: token.to_string(),
+            ttl,
+        }
+    }
+
+    /// Get the action of this operation.
+    pub fn action(&self) -> LockAction {
+        self.action
+    }
+
+    /// Get the token that identifies the holder of the lock.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Get the ttl of the lock.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+}
+
+/// Args for `concat` operation.
+///
+/// The path and sources must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpConcat {
+    sources: Vec<String>,
+    if_match: Option<String>,
+    if_not_exists: bool,
+    write_args: OpWrite,
+}
+
+impl OpConcat {
+    /// Create a new `OpConcat` that concatenates `sources` in order.
+    pub fn new(sources: Vec<String>) -> Self {
+        Self {
+            sources,
+            ..Default::default()
+        }
+    }
+
+    /// Get the sources of this operation.
+    pub fn sources(&self) -> &[String] {
+        &self.sources
+    }
+
+    /// Set the sources of this operation.
+    pub fn with_sources(mut self, sources: Vec<String>) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    /// Set the If-Match of the target, the etag must be the one returned by `stat`.
+    pub fn with_if_match(mut self, if_match: &str) -> Self {
+        self.if_match = Some(if_match.to_string());
+        self
+    }
+
+    /// Get If-Match from option
+    pub fn if_match(&self) -> Option<&str> {
+        self.if_match.as_deref()
+    }
+
+    /// Set the If-Not-Exist of the target.
+    pub fn with_if_not_exists(mut self, if_not_exists: bool) -> Self {
+        self.if_not_exists = if_not_exists;
+        self
+    }
+
+    /// Get If-Not-Exist from option
+    pub fn if_not_exists(&self) -> bool {
+        self.if_not_exists
+    }
+
+    /// Set the args of the target object like content type and user metadata.
+    ///
+    /// Only the metadata fields of [`OpWrite`] will be used.
+    pub fn with_write_args(mut self, args: OpWrite) -> Self {
+        self.write_args = args;
+        self
+    }
+
+    /// Get the args of the target object.
+    pub fn write_args(&self) -> &OpWrite {
+        &self.write_args
+    }
+}
+
+/// Args for `update_metadata` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpUpdateMetadata {
+    content_type: Option<String>,
+    cache_control: Option<String>,
+    content_disposition: Option<String>,
+    user_metadata: HashMap<String, Option<String>>,
+    tags: Option<HashMap<String, String>>,
+}
+
+impl OpUpdateMetadata {
+    /// Create a new `OpUpdateMetadata`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the content type to update.
+    pub fn with_content_type(mut self, content_type: &str) -> Self {
+        self.content_type = Some(content_type.to_string());
+        self
+    }
+
+    /// Get the content type to update.
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Set the cache control to update.
+    pub fn with_cache_control(mut self, cache_control: &str) -> Self {
+        self.cache_control = Some(cache_control.to_string());
+        self
+    }
+
+    /// Get the cache control to update.
+    pub fn cache_control(&self) -> Option<&str> {
+        self.cache_control.as_deref()
+    }
+
+    /// Set the content disposition to update.
+    pub fn with_content_disposition(mut self, content_disposition: &str) -> Self {
+        self.content_disposition = Some(content_disposition.to_string());
+        self
+    }
+
+    /// Get the content disposition to update.
+    pub fn content_disposition(&self) -> Option<&str> {
+        self.content_disposition.as_deref()
+    }
+
+    /// Set the user metadata key to the value, or remove it if the value is `None`.
+    pub fn with_user_metadata(mut self, key: &str, value: Option<&str>) -> Self {
+        self.user_metadata
+            .insert(key.to_string(), value.map(|v| v.to_string()));
+        self
+    }
+
+    /// Get the changes of user metadata.
+    pub fn user_metadata(&self) -> &HashMap<String, Option<String>> {
+        &self.user_metadata
+    }
+
+    /// Set the tags to replace all current tags with.
+    pub fn with_tags(mut self, tags: HashMap<String, String>) -> Self {
+        self.tags = Some(tags);
+        self
+    }
+
+    /// Get the tags to replace all current tags with.
+    pub fn tags(&self) -> Option<&HashMap<String, String>> {
+        self.tags.as_ref()
+    }
+
+    /// Check if any metadata other than tags will be changed.
+    pub fn has_metadata_changes(&self) -> bool {
+        self.has_content_changes() || !self.user_metadata.is_empty()
+    }
+
+    /// Check if content type, cache control or content disposition will be changed.
+    pub fn has_content_changes(&self) -> bool {
+        self.content_type.is_some()
+            || self.cache_control.is_some()
+            || self.content_disposition.is_some()
+    }
+
+    /// Apply the changes of user metadata to the current user metadata.
+    pub fn apply_user_metadata(
+        &self,
+        current: Option<&HashMap<String, String>>,
+    ) -> HashMap<String, String> {
+        let mut user_metadata = current.cloned().unwrap_or_default();
+        for (key, value) in &self.user_metadata {
+            match value {
+                Some(v) => user_metadata.insert(key.clone(), v.clone()),
+                None => user_metadata.remove(key),
+            };
+        }
+        user_metadata
+    }
+
+    /// Apply the changes to the current metadata, and build the args to rewrite it.
+    ///
+    /// This is used by services that replace all metadata at once, like copy with
+    /// `REPLACE` directive.
+    pub fn apply(&self, current: &Metadata) -> OpWrite {
+        let mut args =
+            OpWrite::new().with_user_metadata(self.apply_user_metadata(current.user_metadata()));
+        if let Some(v) = self.content_type().or(current.content_type()) {
+            args = args.with_content_type(v);
+        }
+        if let Some(v) = self.cache_control().or(current.cache_control()) {
+            args = args.with_cache_control(v);
+        }
+        if let Some(v) = self.content_disposition().or(current.content_disposition()) {
+            args = args.with_content_disposition(v);
+        }
+        if let Some(v) = current.content_encoding() {
+            args = args.with_content_encoding(v);
+        }
+        args
+    }
+}
+
+impl From<options::UpdateMetadataOptions> for OpUpdateMetadata {
+    fn from(value: options::UpdateMetadataOptions) -> Self {
+        Self {
+            content_type: value.content_type,
+            cache_control: value.cache_control,
+            content_disposition: value.content_disposition,
+            user_metadata: value.user_metadata,
+            tags: value.tags,
+        }
+    }
+}
+
 /// Args for `delete` operation.
 ///
 /// The path must be normalized.
 #[derive(Debug, Clone, Default, Eq, Hash, PartialEq)]
 pub struct OpDelete {
     version: Option<String>,
+    if_match: Option<String>,
+    if_none_match: Option<String>,
+    if_unmodified_since: Option<DateTime<Utc>>,
 }
 
 impl OpDelete {
@@ -67,12 +358,55 @@ impl OpDelete {
     pub fn version(&self) -> Option<&str> {
         self.version.as_deref()
     }
+
+    /// Set the If-Match of this delete operation.
+    pub fn with_if_match(mut self, if_match: &str) -> Self {
+        self.if_match = Some(if_match.into());
+        self
+    }
+
+    /// Get If-Match from this delete operation.
+    pub fn if_match(&self) -> Option<&str> {
+        self.if_match.as_deref()
+    }
+
+    /// Set the If-None-Match of this delete operation.
+    pub fn with_if_none_match(mut self, if_none_match: &str) -> Self {
+        self.if_none_match = Some(if_none_match.into());
+        self
+    }
+
+    /// Get If-None-Match from this delete operation.
+    pub fn if_none_match(&self) -> Option<&str> {
+        self.if_none_match.as_deref()
+    }
+
+    /// Set the If-Unmodified-Since of this delete operation.
+    pub fn with_if_unmodified_since(mut self, v: DateTime<Utc>) -> Self {
+        self.if_unmodified_since = Some(v);
+        self
+    }
+
+    /// Get If-Unmodified-Since from this delete operation.
+    pub fn if_unmodified_since(&self) -> Option<DateTime<Utc>> {
+        self.if_unmodified_since
+    }
+
+    /// Check if this delete operation carries any preconditions.
+    pub fn has_conditions(&self) -> bool {
+        self.if_match.is_some()
+            || self.if_none_match.is_some()
+            || self.if_unmodified_since.is_some()
+    }
 }
 
 impl From<options::DeleteOptions> for OpDelete {
     fn from(value: options::DeleteOptions) -> Self {
         Self {
             version: value.version,
+            if_match: value.if_match,
+            if_none_match: value.if_none_match,
+            if_unmodified_since: value.if_unmodified_since,
         }
     }
 }
@@ -124,6 +458,15 @@ pub struct OpList {
     ///
     /// Default to `false`
     deleted: bool,
+    /// The concurrent is used to control how many partitions could be listed at the same time.
+    ///
+    /// Only takes effect while `recursive` is `true`.
+    concurrent: usize,
+    /// The ordered is used to control whether the concurrent list should return entries in the
+    /// same order as a sequential list.
+    ordered: bool,
+    /// The metadata fields that must be present in returned entries.
+    metadata: options::MetadataFields,
 }
 
 impl OpList {
@@ -173,16 +516,36 @@ impl OpList {
     /// Change the concurrent of this list operation.
     ///
     /// The default concurrent is 1.
-    #[deprecated(since = "0.53.2", note = "concurrent in list is no-op")]
-    pub fn with_concurrent(self, concurrent: usize) -> Self {
-        let _ = concurrent;
+    pub fn with_concurrent(mut self, concurrent: usize) -> Self {
+        self.concurrent = concurrent;
         self
     }
 
     /// Get the concurrent of list operation.
-    #[deprecated(since = "0.53.2", note = "concurrent in list is no-op")]
     pub fn concurrent(&self) -> usize {
-        0
+        self.concurrent
+    }
+
+    /// Change the ordered of this list operation.
+    pub fn with_ordered(mut self, ordered: bool) -> Self {
+        self.ordered = ordered;
+        self
+    }
+
+    /// Get the ordered of list operation.
+    pub fn ordered(&self) -> bool {
+        self.ordered
+    }
+
+    /// Change the metadata fields that must be present in returned entries.
+    pub fn with_metadata(mut self, metadata: options::MetadataFields) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Get the metadata fields that must be present in returned entries.
+    pub fn metadata(&self) -> options::MetadataFields {
+        self.metadata
     }
 
     /// Change the version of this list operation
@@ -229,6 +592,10 @@ impl From<options::ListOptions> for OpList {
             recursive: value.recursive,
             versions: value.versions,
             deleted: value.deleted,
+            // Ensure concurrent is at least 1
+            concurrent: value.concurrent.max(1),
+            ordered: value.ordered,
+            metadata: value.metadata,
         }
     }
 }
@@ -280,6 +647,14 @@ pub enum PresignOperation {
     Write(OpWrite),
     /// Presign a delete operation.
     Delete(OpDelete),
+    /// Presign a browser based POST upload with policy conditions.
+    Post(OpPresignPost),
+    /// Presign the request that initiates a multipart upload.
+    CreateMultipart(OpWrite),
+    /// Presign the request that uploads one part of a multipart upload.
+    WritePart(OpPresignPart),
+    /// Presign the request that completes a multipart upload.
+    CompleteMultipart(OpPresignComplete),
 }
 
 impl From<OpStat> for PresignOperation {
@@ -306,6 +681,134 @@ impl From<OpDelete> for PresignOperation {
     }
 }
 
+impl From<OpPresignPost> for PresignOperation {
+    fn from(v: OpPresignPost) -> Self {
+        Self::Post(v)
+    }
+}
+
+impl From<OpPresignPart> for PresignOperation {
+    fn from(v: OpPresignPart) -> Self {
+        Self::WritePart(v)
+    }
+}
+
+impl From<OpPresignComplete> for PresignOperation {
+    fn from(v: OpPresignComplete) -> Self {
+        Self::CompleteMultipart(v)
+    }
+}
+
+/// Args for presigning a POST upload.
+///
+/// Every condition set here is embedded into the signed policy, the storage
+/// service will reject uploads that don't match them.
+#[derive(Debug, Clone, Default)]
+pub struct OpPresignPost {
+    content_length_range: Option<(u64, u64)>,
+    content_type: Option<String>,
+    content_type_starts_with: Option<String>,
+}
+
+impl OpPresignPost {
+    /// Create a new `OpPresignPost`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit the size of the uploaded content to `min..=max` bytes.
+    pub fn with_content_length_range(mut self, min: u64, max: u64) -> Self {
+        self.content_length_range = Some((min, max));
+        self
+    }
+
+    /// Get the content length range.
+    pub fn content_length_range(&self) -> Option<(u64, u64)> {
+        self.content_length_range
+    }
+
+    /// Require the uploaded content type to be exactly `content_type`.
+    pub fn with_content_type(mut self, content_type: &str) -> Self {
+        self.content_type = Some(content_type.to_string());
+        self
+    }
+
+    /// Get the required content type.
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Require the uploaded content type to start with `prefix`.
+    pub fn with_content_type_starts_with(mut self, prefix: &str) -> Self {
+        self.content_type_starts_with = Some(prefix.to_string());
+        self
+    }
+
+    /// Get the required content type prefix.
+    pub fn content_type_starts_with(&self) -> Option<&str> {
+        self.content_type_starts_with.as_deref()
+    }
+}
+
+impl From<options::PresignPostOptions> for OpPresignPost {
+    fn from(value: options::PresignPostOptions) -> Self {
+        Self {
+            content_length_range: value.content_length_range,
+            content_type: value.content_type,
+            content_type_starts_with: value.content_type_starts_with,
+        }
+    }
+}
+
+/// Args for presigning a part upload of a multipart upload.
+#[derive(Debug, Clone)]
+pub struct OpPresignPart {
+    upload_id: String,
+    part_number: usize,
+}
+
+impl OpPresignPart {
+    /// Create a new `OpPresignPart`.
+    ///
+    /// `part_number` starts from 1 as required by the storage services.
+    pub fn new(upload_id: &str, part_number: usize) -> Self {
+        Self {
+            upload_id: upload_id.to_string(),
+            part_number,
+        }
+    }
+
+    /// Get the upload id.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+
+    /// Get the part number.
+    pub fn part_number(&self) -> usize {
+        self.part_number
+    }
+}
+
+/// Args for presigning the completion of a multipart upload.
+#[derive(Debug, Clone)]
+pub struct OpPresignComplete {
+    upload_id: String,
+}
+
+impl OpPresignComplete {
+    /// Create a new `OpPresignComplete`.
+    pub fn new(upload_id: &str) -> Self {
+        Self {
+            upload_id: upload_id.to_string(),
+        }
+    }
+
+    /// Get the upload id.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+}
+
 /// Args for `read` operation.
 #[derive(Debug, Clone, Default)]
 pub struct OpRead {
@@ -694,6 +1197,8 @@ pub struct OpWrite {
     if_none_match: Option<String>,
     if_not_exists: bool,
     user_metadata: Option<HashMap<String, String>>,
+    resume: Option<UploadState>,
+    upload_tracker: Option<oio::UploadTracker>,
 }
 
 impl OpWrite {
@@ -821,6 +1326,28 @@ impl OpWrite {
     pub fn user_metadata(&self) -> Option<&HashMap<String, String>> {
         self.user_metadata.as_ref()
     }
+
+    /// Set the upload state to resume from.
+    pub fn with_resume(mut self, state: UploadState) -> Self {
+        self.resume = Some(state);
+        self
+    }
+
+    /// Get the upload state to resume from.
+    pub fn resume(&self) -> Option<&UploadState> {
+        self.resume.as_ref()
+    }
+
+    /// Set the tracker that records the progress of multipart uploads.
+    pub fn with_upload_tracker(mut self, tracker: oio::UploadTracker) -> Self {
+        self.upload_tracker = Some(tracker);
+        self
+    }
+
+    /// Get the tracker that records the progress of multipart uploads.
+    pub fn upload_tracker(&self) -> Option<&oio::UploadTracker> {
+        self.upload_tracker.as_ref()
+    }
 }
 
 /// Args for `writer` operation.
@@ -872,6 +1399,8 @@ impl From<options::WriteOptions> for (OpWrite, OpWriter) {
                 if_none_match: value.if_none_match,
                 if_not_exists: value.if_not_exists,
                 user_metadata: value.user_metadata,
+                resume: value.resume,
+                upload_tracker: None,
             },
             OpWriter { chunk: value.chunk },
         )
@@ -882,6 +1411,11 @@ impl From<options::WriteOptions> for (OpWrite, OpWriter) {
 #[derive(Debug, Clone, Default)]
 pub struct OpCopy {
     if_not_exists: bool,
+    if_match: Option<String>,
+    source_if_match: Option<String>,
+    source_if_none_match: Option<String>,
+    source_if_unmodified_since: Option<DateTime<Utc>>,
+    source_version: Option<String>,
 }
 
 impl OpCopy {
@@ -903,15 +1437,159 @@ impl OpCopy {
     pub fn if_not_exists(&self) -> bool {
         self.if_not_exists
     }
+
+    /// Set the If-Match of the target for the operation.
+    pub fn with_if_match(mut self, if_match: &str) -> Self {
+        self.if_match = Some(if_match.into());
+        self
+    }
+
+    /// Get If-Match of the target.
+    pub fn if_match(&self) -> Option<&str> {
+        self.if_match.as_deref()
+    }
+
+    /// Set the If-Match of the source for the operation.
+    pub fn with_source_if_match(mut self, if_match: &str) -> Self {
+        self.source_if_match = Some(if_match.into());
+        self
+    }
+
+    /// Get If-Match of the source.
+    pub fn source_if_match(&self) -> Option<&str> {
+        self.source_if_match.as_deref()
+    }
+
+    /// Set the If-None-Match of the source for the operation.
+    pub fn with_source_if_none_match(mut self, if_none_match: &str) -> Self {
+        self.source_if_none_match = Some(if_none_match.into());
+        self
+    }
+
+    /// Get If-None-Match of the source.
+    pub fn source_if_none_match(&self) -> Option<&str> {
+        self.source_if_none_match.as_deref()
+    }
+
+    /// Set the If-Unmodified-Since of the source for the operation.
+    pub fn with_source_if_unmodified_since(mut self, v: DateTime<Utc>) -> Self {
+        self.source_if_unmodified_since = Some(v);
+        self
+    }
+
+    /// Get If-Unmodified-Since of the source.
+    pub fn source_if_unmodified_since(&self) -> Option<DateTime<Utc>> {
+        self.source_if_unmodified_since
+    }
+
+    /// Set the version of the source for the operation.
+    pub fn with_source_version(mut self, version: &str) -> Self {
+        self.source_version = Some(version.into());
+        self
+    }
+
+    /// Get the version of the source.
+    pub fn source_version(&self) -> Option<&str> {
+        self.source_version.as_deref()
+    }
+}
+
+impl From<options::CopyOptions> for OpCopy {
+    fn from(value: options::CopyOptions) -> Self {
+        Self {
+            if_not_exists: value.if_not_exists,
+            if_match: value.if_match,
+            source_if_match: value.source_if_match,
+            source_if_none_match: value.source_if_none_match,
+            source_if_unmodified_since: value.source_if_unmodified_since,
+            source_version: value.source_version,
+        }
+    }
 }
 
 /// Args for `rename` operation.
 #[derive(Debug, Clone, Default)]
-pub struct OpRename {}
+pub struct OpRename {
+    if_not_exists: bool,
+    if_match: Option<String>,
+    source_if_match: Option<String>,
+    source_if_none_match: Option<String>,
+    source_if_unmodified_since: Option<DateTime<Utc>>,
+}
 
 impl OpRename {
     /// Create a new `OpMove`.
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Set the if_not_exists flag for the operation.
+    ///
+    /// When set to true, the rename operation will only proceed if the destination
+    /// doesn't already exist.
+    pub fn with_if_not_exists(mut self, if_not_exists: bool) -> Self {
+        self.if_not_exists = if_not_exists;
+        self
+    }
+
+    /// Get if_not_exists flag.
+    pub fn if_not_exists(&self) -> bool {
+        self.if_not_exists
+    }
+
+    /// Set the If-Match of the target for the operation.
+    pub fn with_if_match(mut self, if_match: &str) -> Self {
+        self.if_match = Some(if_match.into());
+        self
+    }
+
+    /// Get If-Match of the target.
+    pub fn if_match(&self) -> Option<&str> {
+        self.if_match.as_deref()
+    }
+
+    /// Set the If-Match of the source for the operation.
+    pub fn with_source_if_match(mut self, if_match: &str) -> Self {
+        self.source_if_match = Some(if_match.into());
+        self
+    }
+
+    /// Get If-Match of the source.
+    pub fn source_if_match(&self) -> Option<&str> {
+        self.source_if_match.as_deref()
+    }
+
+    /// Set the If-None-Match of the source for the operation.
+    pub fn with_source_if_none_match(mut self, if_none_match: &str) -> Self {
+        self.source_if_none_match = Some(if_none_match.into());
+        self
+    }
+
+    /// Get If-None-Match of the source.
+    pub fn source_if_none_match(&self) -> Option<&str> {
+        self.source_if_none_match.as_deref()
+    }
+
+    /// Set the If-Unmodified-Since of the source for the operation.
+    pub fn with_source_if_unmodified_since(mut self, v: DateTime<Utc>) -> Self {
+        self.source_if_unmodified_since = Some(v);
+        self
+    }
+
+    /// Get If-Unmodified-Since of the source.
+    pub fn source_if_unmodified_since(&self) -> Option<DateTime<Utc>> {
+        self.source_if_unmodified_since
+    }
+}
+
+impl From<options::RenameOptions> for OpRename {
+    fn from(value: options::RenameOptions) -> Self {
+        Self {
+            if_not_exists: value.if_not_exists,
+            if_match: value.if_match,
+            source_if_match: value.source_if_match,
+            source_if_none_match: value.source_if_none_match,
+            source_if_unmodified_since: value.source_if_unmodified_since,
+        }
+    }
 }
@@ -17,6 +17,7 @@
 
 use http::Request;
 
+use crate::layers::ReadCoalesceSaved;
 use crate::raw::*;
 use crate::*;
 
@@ -36,18 +37,63 @@ pub struct RpList {}
 #[derive(Debug, Clone)]
 pub struct RpPresign {
     req: PresignedRequest,
+    fields: Vec<(String, String)>,
 }
 
 impl RpPresign {
     /// Create a new reply for `presign`.
     pub fn new(req: PresignedRequest) -> Self {
-        RpPresign { req }
+        RpPresign {
+            req,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Set the form fields that must be sent along with a presigned POST.
+    pub fn with_form_fields(mut self, fields: Vec<(String, String)>) -> Self {
+        self.fields = fields;
+        self
     }
 
     /// Consume reply to build a presigned request.
     pub fn into_presigned_request(self) -> PresignedRequest {
         self.req
     }
+
+    /// Consume reply to build a presigned POST form.
+    pub fn into_presigned_post(self) -> PresignedPost {
+        PresignedPost {
+            uri: self.req.uri,
+            fields: self.fields,
+        }
+    }
+}
+
+/// PresignedPost is a presigned POST form returned by `presign_post`.
+///
+/// Clients upload by sending a `multipart/form-data` POST to `uri` with all
+/// `fields` in order, followed by the `file` field carrying the content.
+#[derive(Debug, Clone)]
+pub struct PresignedPost {
+    uri: http::Uri,
+    fields: Vec<(String, String)>,
+}
+
+impl PresignedPost {
+    /// Create a new PresignedPost
+    pub fn new(uri: http::Uri, fields: Vec<(String, String)>) -> Self {
+        Self { uri, fields }
+    }
+
+    /// Return the uri that form should be posted to.
+    pub fn uri(&self) -> &http::Uri {
+        &self.uri
+    }
+
+    /// Return the form fields that must be sent before the file.
+    pub fn fields(&self) -> &[(String, String)] {
+        &self.fields
+    }
 }
 
 /// PresignedRequest is a presigned request return by `presign`.
@@ -116,6 +162,8 @@ pub struct RpRead {
     /// It's ok to leave range as empty, but it's recommended to set range if possible. We will use
     /// this range as hint to do some optimization like avoid an extra stat or read.
     range: Option<BytesContentRange>,
+    /// Coalesced is the requests and bytes saved by serving other reads with this read.
+    coalesced: Option<ReadCoalesceSaved>,
 }
 
 impl RpRead {
@@ -151,6 +199,20 @@ impl RpRead {
         self.range = range;
         self
     }
+
+    /// Got the requests and bytes saved by serving other reads with this read.
+    ///
+    /// Only set by [`ReadCoalesceLayer`](crate::layers::ReadCoalesceLayer) for the
+    /// coalesced read.
+    pub fn coalesced(&self) -> Option<ReadCoalesceSaved> {
+        self.coalesced
+    }
+
+    /// Set the requests and bytes saved by serving other reads with this read.
+    pub fn with_coalesced(mut self, coalesced: Option<ReadCoalesceSaved>) -> Self {
+        self.coalesced = coalesced;
+        self
+    }
 }
 
 /// Reply for `stat` operation.
@@ -177,6 +239,70 @@ impl RpStat {
     }
 }
 
+/// Reply for `stat_many` operation.
+#[derive(Debug, Default)]
+pub struct RpStatMany {
+    results: Vec<Result<Metadata>>,
+}
+
+impl RpStatMany {
+    /// Create a new reply for `stat_many`.
+    ///
+    /// The results must be in the same order as the input paths.
+    pub fn new(results: Vec<Result<Metadata>>) -> Self {
+        RpStatMany { results }
+    }
+
+    /// Get the results in the same order as the input paths.
+    pub fn results(&self) -> &[Result<Metadata>] {
+        &self.results
+    }
+
+    /// Consume RpStatMany to get the inner results.
+    pub fn into_results(self) -> Vec<Result<Metadata>> {
+        self.results
+    }
+}
+
+/// Reply for `list_uploads` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpListUploads {
+    uploads: Vec<Upload>,
+}
+
+impl RpListUploads {
+    /// Create a new reply for `list_uploads`.
+    pub fn new(uploads: Vec<Upload>) -> Self {
+        RpListUploads { uploads }
+    }
+
+    /// Get the uploads.
+    pub fn uploads(&self) -> &[Upload] {
+        &self.uploads
+    }
+
+    /// Consume RpListUploads to get the inner uploads.
+    pub fn into_uploads(self) -> Vec<Upload> {
+        self.uploads
+    }
+}
+
+/// Reply for `abort_upload` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpAbortUpload {}
+
+/// Reply for `lock` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpLock {}
+
+/// Reply for `concat` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpConcat {}
+
+/// Reply for `update_metadata` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpUpdateMetadata {}
+
 /// Reply for `write` operation.
 #[derive(Debug, Clone, Default)]
 pub struct RpWrite {}
@@ -0,0 +1,153 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use anyhow::Result;
+use sha2::Digest;
+use sha2::Sha256;
+
+use super::*;
+
+pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
+    let cap = op.info().full_capability();
+
+    if cap.read && cap.write {
+        tests.extend(async_trials!(
+            op,
+            test_concat,
+            test_concat_with_empty_source,
+            test_concat_non_existing_source,
+            test_concat_source_dir
+        ))
+    }
+
+    // Keep the number of sources small enough to run against real services.
+    if cap.read && cap.write && cap.concat && cap.concat_max_sources.is_some_and(|v| v <= 64) {
+        tests.extend(async_trials!(op, test_concat_exceed_max_sources))
+    }
+}
+
+/// Concat files and the content must be concatenated in order.
+pub async fn test_concat(op: Operator) -> Result<()> {
+    let mut sources = vec![];
+    let mut expected = vec![];
+    for _ in 0..3 {
+        let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+        op.write(&path, content.clone()).await?;
+        sources.push(path);
+        expected.extend(content);
+    }
+
+    let target = TEST_FIXTURE.new_file_path();
+    op.concat(&sources, &target).await?;
+
+    let content = op.read(&target).await?.to_vec();
+    assert_eq!(content.len(), expected.len(), "concat size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&content)),
+        format!("{:x}", Sha256::digest(&expected)),
+        "concat content"
+    );
+    Ok(())
+}
+
+/// Concat files with an empty one must succeed.
+pub async fn test_concat_with_empty_source(op: Operator) -> Result<()> {
+    let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+    op.write(&path, content.clone()).await?;
+    let empty = TEST_FIXTURE.new_file_path();
+    op.write(&empty, "").await?;
+
+    let target = TEST_FIXTURE.new_file_path();
+    op.concat([empty.as_str(), path.as_str()], &target).await?;
+
+    assert_eq!(op.read(&target).await?.to_vec(), content);
+    Ok(())
+}
+
+/// Concat with a non-existing source must fail.
+pub async fn test_concat_non_existing_source(op: Operator) -> Result<()> {
+    let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+    op.write(&path, content).await?;
+    let missing = TEST_FIXTURE.new_file_path();
+
+    let target = TEST_FIXTURE.new_file_path();
+    let err = op
+        .concat([path.as_str(), missing.as_str()], &target)
+        .await
+        .expect_err("concat with missing source must fail");
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+    Ok(())
+}
+
+/// Concat with a dir source must fail.
+pub async fn test_concat_source_dir(op: Operator) -> Result<()> {
+    let target = TEST_FIXTURE.new_file_path();
+    let err = op
+        .concat(["dir/"], &target)
+        .await
+        .expect_err("concat with dir source must fail");
+    assert_eq!(err.kind(), ErrorKind::IsADirectory);
+    Ok(())
+}
+
+/// Concat more sources than `concat_max_sources` must be done in batches, and `to` can be
+/// any of the sources.
+pub async fn test_concat_exceed_max_sources(op: Operator) -> Result<()> {
+    let max = op
+        .info()
+        .full_capability()
+        .concat_max_sources
+        .unwrap_or_default()
+        .max(2);
+
+    let target = TEST_FIXTURE.new_file_path();
+    op.write(&target, "origin").await?;
+
+    let mut sources = vec![];
+    let mut expected = vec![];
+    for i in 0..max + 2 {
+        // Put the target in a later batch.
+        if i == max + 1 {
+            sources.push(target.clone());
+            expected.extend_from_slice(b"origin");
+        }
+        let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+        op.write(&path, content.clone()).await?;
+        sources.push(path);
+        expected.extend(content);
+    }
+
+    op.concat(&sources, &target).await?;
+
+    let content = op.read(&target).await?.to_vec();
+    assert_eq!(content.len(), expected.len(), "concat size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&content)),
+        format!("{:x}", Sha256::digest(&expected)),
+        "concat content"
+    );
+
+    // The temporary file must be cleaned up.
+    let parent = raw::get_parent(&target);
+    let entries = op.list(parent).await?;
+    assert!(
+        entries
+            .iter()
+            .all(|v| !v.path().starts_with(&format!("{target}."))),
+        "temporary file must be removed"
+    );
+    Ok(())
+}
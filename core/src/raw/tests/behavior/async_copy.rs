@@ -19,7 +19,7 @@ use anyhow::Result;
 use sha2::Digest;
 use sha2::Sha256;
 
-use crate::*;
+use super::*;
 
 pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
     let cap = op.info().full_capability();
@@ -38,6 +38,18 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
         ))
     }
 
+    if cap.read && cap.write && cap.stat && cap.delete {
+        tests.extend(async_trials!(
+            op,
+            test_copy_to_other_operator,
+            test_move_to_other_operator
+        ))
+    }
+
+    if cap.read && cap.write && cap.write_with_resume && cap.stat && cap.delete {
+        tests.extend(async_trials!(op, test_copy_to_with_resume))
+    }
+
     if cap.read && cap.write && cap.copy && cap.copy_with_if_not_exists {
         tests.extend(async_trials!(
             op,
@@ -45,6 +57,106 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_copy_with_if_not_exists_to_existing_file
         ))
     }
+
+    if cap.read && cap.write && cap.stat && cap.copy && cap.copy_with_source_if_match {
+        tests.extend(async_trials!(op, test_copy_with_source_if_match))
+    }
+
+    if cap.read && cap.write && cap.stat && cap.copy && cap.copy_with_source_if_unmodified_since {
+        tests.extend(async_trials!(op, test_copy_with_source_if_unmodified_since))
+    }
+}
+
+/// Copy a file into another operator and back.
+pub async fn test_copy_to_other_operator(op: Operator) -> Result<()> {
+    let other = Operator::new(services::Memory::default())?.finish();
+
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let (source_content, size) = gen_bytes(op.info().full_capability());
+    op.write(&source_path, source_content.clone()).await?;
+
+    let progress = TransferProgress::default();
+    let meta = op
+        .copy_to_options(
+            &other,
+            &source_path,
+            "copied",
+            options::CopyToOptions {
+                concurrent: 2,
+                progress: Some(progress.clone()),
+                ..Default::default()
+            },
+        )
+        .await?;
+    assert_eq!(meta.content_length(), size as u64);
+    assert_eq!(progress.bytes(), size as u64);
+    assert_eq!(other.read("copied").await?.to_bytes(), source_content);
+
+    // Copy it back to make sure the operator under test works as target too.
+    let target_path = uuid::Uuid::new_v4().to_string();
+    other.copy_to(&op, "copied", &target_path).await?;
+    let target_content = op.read(&target_path).await?.to_bytes();
+    assert_eq!(
+        format!("{:x}", Sha256::digest(target_content)),
+        format!("{:x}", Sha256::digest(&source_content)),
+    );
+
+    op.delete(&source_path).await.expect("delete must succeed");
+    op.delete(&target_path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Move a file into another operator should remove the source.
+pub async fn test_move_to_other_operator(op: Operator) -> Result<()> {
+    let other = Operator::new(services::Memory::default())?.finish();
+
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let (source_content, _) = gen_bytes(op.info().full_capability());
+    op.write(&source_path, source_content.clone()).await?;
+
+    op.move_to(&other, &source_path, "moved").await?;
+    assert!(!op.exists(&source_path).await?);
+    assert_eq!(other.read("moved").await?.to_bytes(), source_content);
+    Ok(())
+}
+
+/// Resume a copy into the operator under test with the state of a previous upload.
+pub async fn test_copy_to_with_resume(op: Operator) -> Result<()> {
+    let other = Operator::new(services::Memory::default())?.finish();
+    let chunk = 5 * 1024 * 1024;
+    let content = gen_fixed_bytes(3 * chunk);
+    other.write("source", content.clone()).await?;
+
+    // Upload the first parts and drop the writer to simulate a failed copy.
+    let target_path = uuid::Uuid::new_v4().to_string();
+    let mut w = op.writer_with(&target_path).chunk(chunk).await?;
+    for bs in content.chunks(chunk).take(2) {
+        w.write(bs.to_vec()).await?;
+    }
+    let state = w.upload_state().expect("upload must be started");
+    drop(w);
+
+    let meta = other
+        .copy_to_options(
+            &op,
+            "source",
+            &target_path,
+            options::CopyToOptions {
+                chunk: Some(chunk),
+                resume: Some(state),
+                ..Default::default()
+            },
+        )
+        .await?;
+    assert_eq!(meta.content_length(), content.len() as u64);
+    let target_content = op.read(&target_path).await?.to_bytes();
+    assert_eq!(
+        format!("{:x}", Sha256::digest(target_content)),
+        format!("{:x}", Sha256::digest(&content)),
+    );
+
+    op.delete(&target_path).await.expect("delete must succeed");
+    Ok(())
 }
 
 /// Copy a file with ascii name and test contents.
@@ -312,3 +424,79 @@ pub async fn test_copy_with_if_not_exists_to_existing_file(op: Operator) -> Resu
     op.delete(&target_path).await.expect("delete must succeed");
     Ok(())
 }
+
+/// Copy with source_if_match should match, otherwise, a ConditionNotMatch error will be returned.
+pub async fn test_copy_with_source_if_match(op: Operator) -> Result<()> {
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let (source_content, _) = gen_bytes(op.info().full_capability());
+
+    op.write(&source_path, source_content.clone()).await?;
+    let etag = op.stat(&source_path).await?.etag().unwrap().to_string();
+
+    let target_path = uuid::Uuid::new_v4().to_string();
+
+    let err = op
+        .copy_with(&source_path, &target_path)
+        .source_if_match("\"invalid_etag\"")
+        .await
+        .expect_err("copy must fail");
+    assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+    assert!(!op.exists(&target_path).await?);
+
+    op.copy_with(&source_path, &target_path)
+        .source_if_match(&etag)
+        .await?;
+
+    let target_content = op
+        .read(&target_path)
+        .await
+        .expect("read must succeed")
+        .to_bytes();
+    assert_eq!(
+        format!("{:x}", Sha256::digest(target_content)),
+        format!("{:x}", Sha256::digest(&source_content)),
+    );
+
+    op.delete(&source_path).await.expect("delete must succeed");
+    op.delete(&target_path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Copy with source_if_unmodified_since should match, otherwise, a ConditionNotMatch error will be returned.
+pub async fn test_copy_with_source_if_unmodified_since(op: Operator) -> Result<()> {
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let (source_content, _) = gen_bytes(op.info().full_capability());
+
+    op.write(&source_path, source_content.clone()).await?;
+    let last_modified_time = op.stat(&source_path).await?.last_modified().unwrap();
+
+    let target_path = uuid::Uuid::new_v4().to_string();
+
+    let since = last_modified_time - chrono::Duration::seconds(1);
+    let err = op
+        .copy_with(&source_path, &target_path)
+        .source_if_unmodified_since(since)
+        .await
+        .expect_err("copy must fail");
+    assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+    assert!(!op.exists(&target_path).await?);
+
+    let since = last_modified_time + chrono::Duration::seconds(1);
+    op.copy_with(&source_path, &target_path)
+        .source_if_unmodified_since(since)
+        .await?;
+
+    let target_content = op
+        .read(&target_path)
+        .await
+        .expect("read must succeed")
+        .to_bytes();
+    assert_eq!(
+        format!("{:x}", Sha256::digest(target_content)),
+        format!("{:x}", Sha256::digest(&source_content)),
+    );
+
+    op.delete(&source_path).await.expect("delete must succeed");
+    op.delete(&target_path).await.expect("delete must succeed");
+    Ok(())
+}
@@ -15,13 +15,13 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::raw::Access;
+use crate::raw::OpDelete;
 use anyhow::Result;
 use futures::TryStreamExt;
 use log::warn;
-use opendal::raw::Access;
-use opendal::raw::OpDelete;
 
-use crate::*;
+use super::*;
 
 pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
     let cap = op.info().full_capability();
@@ -40,6 +40,12 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_batch_delete,
             test_batch_delete_with_version
         ));
+        if cap.delete_with_if_match {
+            tests.extend(async_trials!(op, test_delete_with_if_match));
+        }
+        if cap.delete_with_if_unmodified_since {
+            tests.extend(async_trials!(op, test_delete_with_if_unmodified_since));
+        }
         if cap.list_with_recursive {
             tests.extend(async_trials!(op, test_remove_all_basic));
             if !cap.create_dir {
@@ -81,7 +87,7 @@ pub async fn test_delete_empty_dir(op: Operator) -> Result<()> {
 /// Delete file with special chars should succeed.
 pub async fn test_delete_with_special_chars(op: Operator) -> Result<()> {
     // Ignore test for atomicserver until https://github.com/atomicdata-dev/atomic-server/issues/663 addressed.
-    if op.info().scheme() == opendal::Scheme::Atomicserver {
+    if op.info().scheme() == crate::Scheme::Atomicserver {
         warn!("ignore test for atomicserver until https://github.com/atomicdata-dev/atomic-server/issues/663 is resolved");
         return Ok(());
     }
@@ -352,3 +358,47 @@ pub async fn test_batch_delete_with_version(op: Operator) -> Result<()> {
 
     Ok(())
 }
+
+/// Delete with if_unmodified_since should match, otherwise, a ConditionNotMatch error will be returned.
+pub async fn test_delete_with_if_unmodified_since(op: Operator) -> Result<()> {
+    let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+
+    op.write(&path, content).await.expect("write must succeed");
+    let last_modified_time = op.stat(&path).await?.last_modified().unwrap();
+
+    let since = last_modified_time - chrono::Duration::seconds(1);
+    let err = op
+        .delete_with(&path)
+        .if_unmodified_since(since)
+        .await
+        .expect_err("delete must fail");
+    assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+    assert!(op.exists(&path).await?);
+
+    let since = last_modified_time + chrono::Duration::seconds(1);
+    op.delete_with(&path).if_unmodified_since(since).await?;
+    assert!(!op.exists(&path).await?);
+
+    Ok(())
+}
+
+/// Delete with if_match should match, otherwise, a ConditionNotMatch error will be returned.
+pub async fn test_delete_with_if_match(op: Operator) -> Result<()> {
+    let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+
+    op.write(&path, content).await.expect("write must succeed");
+    let etag = op.stat(&path).await?.etag().unwrap().to_string();
+
+    let err = op
+        .delete_with(&path)
+        .if_match("\"invalid_etag\"")
+        .await
+        .expect_err("delete must fail");
+    assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+    assert!(op.exists(&path).await?);
+
+    op.delete_with(&path).if_match(&etag).await?;
+    assert!(!op.exists(&path).await?);
+
+    Ok(())
+}
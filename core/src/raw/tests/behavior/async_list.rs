@@ -24,7 +24,7 @@ use futures::StreamExt;
 use futures::TryStreamExt;
 use log::debug;
 
-use crate::*;
+use super::*;
 
 pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
     let cap = op.info().full_capability();
@@ -45,13 +45,17 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_list_non_exist_dir_with_recursive,
             test_list_dir_with_recursive,
             test_list_dir_with_recursive_no_trailing_slash,
+            test_list_dir_with_recursive_concurrent,
             test_list_file_with_recursive,
             test_list_root_with_recursive,
             test_remove_all,
             test_list_files_with_versions,
             test_list_with_versions_and_limit,
             test_list_with_versions_and_start_after,
-            test_list_files_with_deleted
+            test_list_files_with_deleted,
+            test_restore_version,
+            test_restore_version_of_delete_marker,
+            test_purge_versions
         ))
     }
 
@@ -471,6 +475,48 @@ pub async fn test_list_dir_with_recursive(op: Operator) -> Result<()> {
     Ok(())
 }
 
+// Concurrent list should output the same entries as sequential list
+pub async fn test_list_dir_with_recursive_concurrent(op: Operator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+
+    let paths = [
+        "x/", "x/x/", "x/x/x/", "x/x/x/x/", "x/x/x/y", "x/x/y", "x/y", "x/yy", "x/z/", "x/z/y",
+    ];
+    for path in paths.iter() {
+        if path.ends_with('/') {
+            op.create_dir(&format!("{parent}/{path}")).await?;
+        } else {
+            op.write(&format!("{parent}/{path}"), "test_scan").await?;
+        }
+    }
+
+    for ordered in [false, true] {
+        let w = op
+            .lister_with(&format!("{parent}/x/"))
+            .recursive(true)
+            .concurrent(4)
+            .ordered(ordered)
+            .await?;
+        let mut actual = w
+            .try_collect::<Vec<_>>()
+            .await?
+            .into_iter()
+            .map(|v| {
+                v.path()
+                    .strip_prefix(&format!("{parent}/"))
+                    .unwrap()
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        actual.sort();
+
+        assert_eq!(actual, paths.to_vec(), "ordered: {ordered}");
+    }
+
+    op.remove_all(&format!("{parent}/")).await?;
+    Ok(())
+}
+
 // same as test_list_dir_with_recursive except listing 'x' instead of 'x/'
 pub async fn test_list_dir_with_recursive_no_trailing_slash(op: Operator) -> Result<()> {
     let parent = uuid::Uuid::new_v4().to_string();
@@ -649,6 +695,90 @@ pub async fn test_list_files_with_deleted(op: Operator) -> Result<()> {
     Ok(())
 }
 
+pub async fn test_restore_version(op: Operator) -> Result<()> {
+    let cap = op.info().full_capability();
+    if !(cap.list_with_versions && cap.copy_with_source_version) {
+        return Ok(());
+    }
+
+    let parent = TEST_FIXTURE.new_dir_path();
+    let file_path = format!("{parent}{}", TEST_FIXTURE.new_file_path());
+    op.write(file_path.as_str(), "1").await?;
+    op.write(file_path.as_str(), "2").await?;
+
+    let ds = op.list_with(&file_path).versions(true).await?;
+    let first = ds
+        .iter()
+        .filter(|de| de.path() == file_path)
+        .min_by_key(|de| de.metadata().last_modified())
+        .and_then(|de| de.metadata().version())
+        .expect("first version must exist")
+        .to_string();
+
+    op.restore_version(&file_path, &first).await?;
+    assert_eq!(op.read(&file_path).await?.to_vec(), b"1");
+
+    // Restore creates a new version instead of removing others.
+    let ds = op.list_with(&file_path).versions(true).await?;
+    assert_eq!(ds.iter().filter(|de| de.path() == file_path).count(), 3);
+
+    op.remove_all(&parent).await?;
+    Ok(())
+}
+
+pub async fn test_restore_version_of_delete_marker(op: Operator) -> Result<()> {
+    let cap = op.info().full_capability();
+    if !(cap.list_with_deleted && cap.delete_with_version) {
+        return Ok(());
+    }
+
+    let parent = TEST_FIXTURE.new_dir_path();
+    let file_path = format!("{parent}{}", TEST_FIXTURE.new_file_path());
+    op.write(file_path.as_str(), "1").await?;
+    op.delete(file_path.as_str()).await?;
+    assert!(!op.exists(&file_path).await?);
+
+    let ds = op.list_with(&file_path).deleted(true).await?;
+    let marker = ds
+        .iter()
+        .find(|de| de.path() == file_path && de.metadata().is_deleted())
+        .and_then(|de| de.metadata().version())
+        .expect("delete marker must exist")
+        .to_string();
+
+    op.restore_version(&file_path, &marker).await?;
+    assert_eq!(op.read(&file_path).await?.to_vec(), b"1");
+
+    op.remove_all(&parent).await?;
+    Ok(())
+}
+
+pub async fn test_purge_versions(op: Operator) -> Result<()> {
+    let cap = op.info().full_capability();
+    if !(cap.list_with_versions && cap.delete_with_version) {
+        return Ok(());
+    }
+
+    let parent = TEST_FIXTURE.new_dir_path();
+    let file_path = format!("{parent}{}", TEST_FIXTURE.new_file_path());
+    for content in ["1", "2", "3", "4"] {
+        op.write(file_path.as_str(), content).await?;
+    }
+
+    let purged = op.purge_versions(&parent, 1).await?;
+    assert_eq!(purged, 2, "only the latest non-current version is kept");
+
+    let ds = op.list_with(&file_path).versions(true).await?;
+    let ds: Vec<_> = ds.iter().filter(|de| de.path() == file_path).collect();
+    assert_eq!(ds.len(), 2);
+    assert_eq!(op.read(&file_path).await?.to_vec(), b"4");
+
+    assert_eq!(op.purge_versions(&parent, 0).await?, 1);
+
+    op.remove_all(&parent).await?;
+    Ok(())
+}
+
 // listing a directory with version, which contains more object versions than a page can take
 pub async fn test_list_with_versions_and_limit(op: Operator) -> Result<()> {
     // Gdrive think that this test is an abuse of their service and redirect us
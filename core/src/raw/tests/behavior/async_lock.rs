@@ -0,0 +1,69 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::time::sleep;
+
+use super::*;
+
+pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
+    let cap = op.info().full_capability();
+
+    if cap.lock || (cap.read && cap.stat && cap.write_with_if_not_exists && cap.write_with_if_match)
+    {
+        tests.extend(async_trials!(op, test_lock, test_lock_held_by_others));
+    }
+}
+
+/// Lock, renew and release must succeed, and the lock can be acquired again.
+pub async fn test_lock(op: Operator) -> Result<()> {
+    let path = TEST_FIXTURE.new_file_path();
+
+    let mut lease = op.lock(&path, Duration::from_secs(30)).await?;
+    assert!(!lease.is_expired());
+    lease.renew().await?;
+    lease.release().await?;
+
+    let lease = op.lock(&path, Duration::from_secs(30)).await?;
+    lease.release().await?;
+    Ok(())
+}
+
+/// Lock held by others must not be acquired until it has expired.
+pub async fn test_lock_held_by_others(op: Operator) -> Result<()> {
+    let path = TEST_FIXTURE.new_file_path();
+
+    let lease = op.lock(&path, Duration::from_secs(2)).await?;
+    let err = op
+        .lock(&path, Duration::from_secs(30))
+        .await
+        .expect_err("lock held by others must fail");
+    assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+
+    sleep(Duration::from_secs(3)).await;
+    let other = op.lock(&path, Duration::from_secs(30)).await?;
+
+    let err = lease
+        .release()
+        .await
+        .expect_err("release lock taken over by others must fail");
+    assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+    other.release().await?;
+    Ok(())
+}
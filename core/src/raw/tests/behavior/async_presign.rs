@@ -18,15 +18,15 @@
 use std::str::FromStr;
 use std::time::Duration;
 
+use crate::raw;
 use anyhow::Result;
 use http::header;
 use log::debug;
-use opendal::raw;
 use reqwest::Url;
 use sha2::Digest;
 use sha2::Sha256;
 
-use crate::*;
+use super::*;
 
 pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
     let cap = op.info().full_capability();
@@ -37,7 +37,9 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_presign_write,
             test_presign_read,
             test_presign_stat,
-            test_presign_delete
+            test_presign_delete,
+            test_presign_post,
+            test_presign_multipart
         ))
     }
 }
@@ -173,3 +175,120 @@ pub async fn test_presign_delete(op: Operator) -> Result<()> {
     assert!(!op.exists(&path).await.expect("delete must succeed"));
     Ok(())
 }
+
+/// Presign post should upload content via form.
+pub async fn test_presign_post(op: Operator) -> Result<()> {
+    let cap = op.info().full_capability();
+    if !cap.presign_post {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {path}");
+    let (content, size) = gen_bytes(op.info().full_capability());
+
+    let post = op
+        .presign_post(
+            &path,
+            options::PresignPostOptions {
+                content_length_range: Some((0, size as u64)),
+                ..Default::default()
+            },
+            Duration::from_secs(3600),
+        )
+        .await?;
+    debug!("Generated post: {post:?}");
+
+    let mut form = raw::Multipart::new();
+    for (k, v) in post.fields() {
+        form = form.part(raw::FormDataPart::new(k).content(v.clone()));
+    }
+    form = form.part(raw::FormDataPart::new("file").content(content));
+    let req = form.apply(http::Request::post(post.uri().to_string()))?;
+    let (parts, body) = req.into_parts();
+
+    let client = reqwest::Client::new();
+    let resp = client
+        .post(Url::from_str(&post.uri().to_string()).expect("must be valid url"))
+        .headers(parts.headers)
+        .body(body.to_bytes())
+        .send()
+        .await
+        .expect("send request must succeed");
+    assert!(resp.status().is_success(), "post status: {}", resp.status());
+
+    let meta = op.stat(&path).await.expect("stat must succeed");
+    assert_eq!(meta.content_length(), size as u64);
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Presign multipart should upload content via create, part and complete requests.
+pub async fn test_presign_multipart(op: Operator) -> Result<()> {
+    let cap = op.info().full_capability();
+    if !cap.presign_multipart {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {path}");
+    let (content, size) = gen_bytes(op.info().full_capability());
+    let client = reqwest::Client::new();
+    let send = |signed_req: raw::PresignedRequest, body: Vec<u8>| {
+        let mut req = client.request(
+            signed_req.method().clone(),
+            Url::from_str(&signed_req.uri().to_string()).expect("must be valid url"),
+        );
+        for (k, v) in signed_req.header() {
+            req = req.header(k, v);
+        }
+        req.header(header::CONTENT_LENGTH, body.len())
+            .body(body)
+            .send()
+    };
+
+    let signed_req = op
+        .presign_multipart_create(&path, Duration::from_secs(3600))
+        .await?;
+    let resp = send(signed_req, vec![]).await?;
+    assert!(
+        resp.status().is_success(),
+        "create status: {}",
+        resp.status()
+    );
+    let text = resp.text().await?;
+    let upload_id = text
+        .split_once("<UploadId>")
+        .and_then(|(_, v)| v.split_once("</UploadId>"))
+        .map(|(v, _)| v.to_string())
+        .expect("upload id must be returned");
+
+    let signed_req = op
+        .presign_part(&path, &upload_id, 1, Duration::from_secs(3600))
+        .await?;
+    let resp = send(signed_req, content).await?;
+    assert!(resp.status().is_success(), "part status: {}", resp.status());
+    let etag = raw::parse_etag(resp.headers())?
+        .expect("etag must be returned")
+        .to_string();
+
+    let signed_req = op
+        .presign_multipart_complete(&path, &upload_id, Duration::from_secs(3600))
+        .await?;
+    let body = format!(
+        "<CompleteMultipartUpload><Part><PartNumber>1</PartNumber><ETag>{etag}</ETag></Part></CompleteMultipartUpload>"
+    );
+    let resp = send(signed_req, body.into_bytes()).await?;
+    assert!(
+        resp.status().is_success(),
+        "complete status: {}",
+        resp.status()
+    );
+
+    let meta = op.stat(&path).await.expect("stat must succeed");
+    assert_eq!(meta.content_length(), size as u64);
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
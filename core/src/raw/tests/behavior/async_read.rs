@@ -27,7 +27,7 @@ use sha2::Digest;
 use sha2::Sha256;
 use tokio::time::sleep;
 
-use crate::*;
+use super::*;
 
 pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
     let cap = op.info().full_capability();
@@ -385,7 +385,7 @@ pub async fn test_read_with_dir_path(op: Operator) -> anyhow::Result<()> {
 /// Read file with special chars should succeed.
 pub async fn test_read_with_special_chars(op: Operator) -> anyhow::Result<()> {
     // Ignore test for atomicserver until https://github.com/atomicdata-dev/atomic-server/issues/663 addressed.
-    if op.info().scheme() == opendal::Scheme::Atomicserver {
+    if op.info().scheme() == crate::Scheme::Atomicserver {
         warn!("ignore test for atomicserver until https://github.com/atomicdata-dev/atomic-server/issues/663 is resolved");
         return Ok(());
     }
@@ -19,7 +19,7 @@ use anyhow::Result;
 use sha2::Digest;
 use sha2::Sha256;
 
-use crate::*;
+use super::*;
 
 pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
     let cap = op.info().full_capability();
@@ -36,6 +36,13 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_rename_overwrite
         ))
     }
+
+    if cap.read && cap.write && cap.rename && cap.rename_with_if_not_exists {
+        tests.extend(async_trials!(
+            op,
+            test_rename_with_if_not_exists_to_existing_file
+        ))
+    }
 }
 
 /// Rename a file and test with stat.
@@ -208,3 +215,40 @@ pub async fn test_rename_overwrite(op: Operator) -> Result<()> {
     op.delete(&target_path).await.expect("delete must succeed");
     Ok(())
 }
+
+/// Rename with if_not_exists to an existing file should fail.
+pub async fn test_rename_with_if_not_exists_to_existing_file(op: Operator) -> Result<()> {
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let (source_content, _) = gen_bytes(op.info().full_capability());
+
+    op.write(&source_path, source_content.clone()).await?;
+
+    let target_path = uuid::Uuid::new_v4().to_string();
+    let (target_content, _) = gen_bytes(op.info().full_capability());
+    assert_ne!(source_content, target_content);
+
+    op.write(&target_path, target_content.clone()).await?;
+
+    let err = op
+        .rename_with(&source_path, &target_path)
+        .if_not_exists(true)
+        .await
+        .expect_err("rename must fail");
+    assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+
+    // Both files should be unchanged.
+    let current_content = op
+        .read(&target_path)
+        .await
+        .expect("read must succeed")
+        .to_bytes();
+    assert_eq!(
+        format!("{:x}", Sha256::digest(current_content)),
+        format!("{:x}", Sha256::digest(&target_content)),
+    );
+    assert!(op.exists(&source_path).await?);
+
+    op.delete(&source_path).await.expect("delete must succeed");
+    op.delete(&target_path).await.expect("delete must succeed");
+    Ok(())
+}
@@ -24,7 +24,7 @@ use log::warn;
 use reqwest::Url;
 use tokio::time::sleep;
 
-use crate::*;
+use super::*;
 
 pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
     let cap = op.info().full_capability();
@@ -38,6 +38,7 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_stat_with_special_chars,
             test_stat_not_cleaned_path,
             test_stat_not_exist,
+            test_stat_many,
             test_stat_with_if_match,
             test_stat_with_if_none_match,
             test_stat_with_if_modified_since,
@@ -132,7 +133,7 @@ pub async fn test_stat_nested_parent_dir(op: Operator) -> Result<()> {
 /// Stat existing file with special chars should return metadata
 pub async fn test_stat_with_special_chars(op: Operator) -> Result<()> {
     // Ignore test for atomicserver until https://github.com/atomicdata-dev/atomic-server/issues/663 addressed.
-    if op.info().scheme() == opendal::Scheme::Atomicserver {
+    if op.info().scheme() == crate::Scheme::Atomicserver {
         warn!("ignore test for atomicserver until https://github.com/atomicdata-dev/atomic-server/issues/663 is resolved");
         return Ok(());
     }
@@ -181,6 +182,40 @@ pub async fn test_stat_not_exist(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Stat many should return results in the same order as given paths.
+pub async fn test_stat_many(op: Operator) -> Result<()> {
+    let (path_a, content_a, size_a) = TEST_FIXTURE.new_file(op.clone());
+    let (path_b, content_b, size_b) = TEST_FIXTURE.new_file(op.clone());
+    let not_exist = uuid::Uuid::new_v4().to_string();
+
+    op.write(&path_a, content_a)
+        .await
+        .expect("write must succeed");
+    op.write(&path_b, content_b)
+        .await
+        .expect("write must succeed");
+
+    let results = op
+        .stat_many_options(
+            vec![path_a.as_str(), not_exist.as_str(), path_b.as_str()],
+            options::StatManyOptions { concurrent: 2 },
+        )
+        .await?;
+    assert_eq!(results.len(), 3);
+
+    let mut results = results.into_iter();
+    let meta = results.next().unwrap()?;
+    assert_eq!(meta.content_length(), size_a as u64);
+    assert_eq!(
+        results.next().unwrap().unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+    let meta = results.next().unwrap()?;
+    assert_eq!(meta.content_length(), size_b as u64);
+
+    Ok(())
+}
+
 /// Stat with if_match should succeed, else get a ConditionNotMatch error.
 pub async fn test_stat_with_if_match(op: Operator) -> Result<()> {
     if !op.info().full_capability().stat_with_if_match {
@@ -0,0 +1,139 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use anyhow::Result;
+
+use super::*;
+
+pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
+    let cap = op.info().full_capability();
+
+    if cap.read
+        && cap.stat
+        && cap.write
+        && cap.delete
+        && cap.list
+        && (cap.write_with_if_not_exists || cap.rename_with_if_not_exists)
+    {
+        tests.extend(async_trials!(
+            op,
+            test_transaction_commit,
+            test_transaction_conflict,
+            test_transaction_abort,
+            test_transaction_write_reserved_like_path
+        ));
+    }
+}
+
+/// Committed files must be resolved through the latest manifest.
+pub async fn test_transaction_commit(op: Operator) -> Result<()> {
+    let root = TEST_FIXTURE.new_dir_path();
+    let (_, content, _) = TEST_FIXTURE.new_file(op.clone());
+
+    let mut txn = op.transaction(&root).await?;
+    assert_eq!(txn.base().version(), 0);
+    txn.write("data/a", content.clone()).await?;
+    txn.write("data/b", content.clone()).await?;
+    assert_eq!(txn.read("data/a").await?.to_vec(), content);
+    assert!(
+        op.manifest(&root).await?.resolve("data/a").is_none(),
+        "staged files must not be visible before commit"
+    );
+    let manifest = txn.commit().await?;
+    assert_eq!(manifest.version(), 1);
+
+    let loaded = op.manifest(&root).await?;
+    assert_eq!(loaded, manifest);
+    let path = loaded.resolve("data/a").expect("committed file must exist");
+    assert_eq!(op.read(path).await?.to_vec(), content);
+
+    let mut txn = op.transaction(&root).await?;
+    txn.delete("data/a")?;
+    let manifest = txn.commit().await?;
+    assert_eq!(manifest.version(), 2);
+    assert_eq!(manifest.paths().collect::<Vec<_>>(), vec!["data/b"]);
+
+    op.remove_all(&root).await?;
+    Ok(())
+}
+
+/// Concurrent commits based on the same version must conflict.
+pub async fn test_transaction_conflict(op: Operator) -> Result<()> {
+    let root = TEST_FIXTURE.new_dir_path();
+
+    let mut first = op.transaction(&root).await?;
+    let mut second = op.transaction(&root).await?;
+    first.write("data/a", "first").await?;
+    second.write("data/a", "second").await?;
+
+    first.commit().await?;
+    let err = second
+        .commit()
+        .await
+        .expect_err("conflicting commit must fail");
+    assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+
+    let manifest = op.manifest(&root).await?;
+    assert_eq!(manifest.version(), 1);
+    let path = manifest
+        .resolve("data/a")
+        .expect("committed file must exist");
+    assert_eq!(op.read(path).await?.to_vec(), b"first");
+
+    op.remove_all(&root).await?;
+    Ok(())
+}
+
+/// Abort must remove staged files without changing the manifest.
+pub async fn test_transaction_abort(op: Operator) -> Result<()> {
+    let root = TEST_FIXTURE.new_dir_path();
+
+    let mut txn = op.transaction(&root).await?;
+    txn.write("data/a", "content").await?;
+    txn.abort().await?;
+
+    assert_eq!(op.manifest(&root).await?.version(), 0);
+    let entries = op
+        .list_with(&format!("{root}_staging/"))
+        .recursive(true)
+        .await?;
+    assert!(
+        entries.iter().all(|e| e.metadata().is_dir()),
+        "staged files must be removed"
+    );
+
+    op.remove_all(&root).await?;
+    Ok(())
+}
+
+/// Files named like the staged manifest must keep their own content.
+pub async fn test_transaction_write_reserved_like_path(op: Operator) -> Result<()> {
+    let root = TEST_FIXTURE.new_dir_path();
+    let (_, content, _) = TEST_FIXTURE.new_file(op.clone());
+
+    let mut txn = op.transaction(&root).await?;
+    txn.write("_manifest.json", content.clone()).await?;
+    let manifest = txn.commit().await?;
+
+    let path = manifest
+        .resolve("_manifest.json")
+        .expect("committed file must exist");
+    assert_eq!(op.read(path).await?.to_vec(), content);
+
+    op.remove_all(&root).await?;
+    Ok(())
+}
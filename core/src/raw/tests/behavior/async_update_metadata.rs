@@ -0,0 +1,149 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+use super::*;
+
+pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
+    let cap = op.info().full_capability();
+
+    if cap.stat && cap.write && cap.update_metadata {
+        tests.extend(async_trials!(
+            op,
+            test_update_metadata_content_type,
+            test_update_metadata_user_metadata,
+            test_update_metadata_not_exist
+        ))
+    }
+
+    if cap.stat && cap.write && cap.update_metadata && cap.update_metadata_with_tags {
+        tests.extend(async_trials!(op, test_update_metadata_tags))
+    }
+}
+
+/// Update content type must keep the content unchanged.
+pub async fn test_update_metadata_content_type(op: Operator) -> Result<()> {
+    let (path, content, size) = TEST_FIXTURE.new_file(op.clone());
+    op.write(&path, content.clone()).await?;
+
+    op.update_metadata(
+        &path,
+        options::UpdateMetadataOptions {
+            content_type: Some("text/plain".to_string()),
+            cache_control: Some("no-cache".to_string()),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    let meta = op.stat(&path).await?;
+    assert_eq!(meta.content_type(), Some("text/plain"));
+    assert_eq!(meta.cache_control(), Some("no-cache"));
+    assert_eq!(meta.content_length(), size as u64);
+    assert_eq!(op.read(&path).await?.to_vec(), content);
+    Ok(())
+}
+
+/// Update user metadata must merge with the existing keys.
+pub async fn test_update_metadata_user_metadata(op: Operator) -> Result<()> {
+    if !op.info().full_capability().write_with_user_metadata {
+        return Ok(());
+    }
+
+    let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+    op.write_with(&path, content)
+        .user_metadata([
+            ("keep".to_string(), "a".to_string()),
+            ("remove".to_string(), "b".to_string()),
+        ])
+        .await?;
+
+    let mut changes = HashMap::new();
+    changes.insert("remove".to_string(), None);
+    changes.insert("added".to_string(), Some("c".to_string()));
+    op.update_metadata(
+        &path,
+        options::UpdateMetadataOptions {
+            user_metadata: changes,
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    let meta = op.stat(&path).await?;
+    let expected: HashMap<String, String> = [
+        ("keep".to_string(), "a".to_string()),
+        ("added".to_string(), "c".to_string()),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(meta.user_metadata(), Some(&expected));
+    Ok(())
+}
+
+/// Update metadata of a non-existing file must fail with NotFound.
+pub async fn test_update_metadata_not_exist(op: Operator) -> Result<()> {
+    let path = TEST_FIXTURE.new_file_path();
+
+    let err = op
+        .update_metadata(
+            &path,
+            options::UpdateMetadataOptions {
+                content_type: Some("text/plain".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect_err("update metadata of a non-existing file must fail");
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+    Ok(())
+}
+
+/// Update tags along with content type must keep the content unchanged.
+pub async fn test_update_metadata_tags(op: Operator) -> Result<()> {
+    let (path, content, size) = TEST_FIXTURE.new_file(op.clone());
+    op.write(&path, content.clone()).await?;
+
+    let tags: HashMap<String, String> = [("project".to_string(), "opendal".to_string())]
+        .into_iter()
+        .collect();
+    op.update_metadata(
+        &path,
+        options::UpdateMetadataOptions {
+            tags: Some(tags.clone()),
+            ..Default::default()
+        },
+    )
+    .await?;
+    op.update_metadata(
+        &path,
+        options::UpdateMetadataOptions {
+            content_type: Some("text/plain".to_string()),
+            tags: Some(tags),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    let meta = op.stat(&path).await?;
+    assert_eq!(meta.content_type(), Some("text/plain"));
+    assert_eq!(meta.content_length(), size as u64);
+    assert_eq!(op.read(&path).await?.to_vec(), content);
+    Ok(())
+}
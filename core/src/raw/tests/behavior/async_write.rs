@@ -29,7 +29,7 @@ use log::warn;
 use sha2::Digest;
 use sha2::Sha256;
 
-use crate::*;
+use super::*;
 
 pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
     let cap = op.info().full_capability();
@@ -71,6 +71,14 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_writer_with_append
         ))
     }
+
+    if cap.read && cap.write && cap.write_with_resume && cap.stat {
+        tests.extend(async_trials!(op, test_writer_with_resume))
+    }
+
+    if cap.write && cap.write_can_multi && cap.list_uploads && cap.abort_upload {
+        tests.extend(async_trials!(op, test_abort_uploads))
+    }
 }
 
 /// Write a single file and test with stat.
@@ -115,12 +123,12 @@ pub async fn test_write_with_dir_path(op: Operator) -> Result<()> {
 /// Write a single file with special chars should succeed.
 pub async fn test_write_with_special_chars(op: Operator) -> Result<()> {
     // Ignore test for atomicserver until https://github.com/atomicdata-dev/atomic-server/issues/663 addressed.
-    if op.info().scheme() == opendal::Scheme::Atomicserver {
+    if op.info().scheme() == crate::Scheme::Atomicserver {
         warn!("ignore test for atomicserver until https://github.com/atomicdata-dev/atomic-server/issues/663 is resolved");
         return Ok(());
     }
     // Ignore test for vercel blob https://github.com/apache/opendal/pull/4103.
-    if op.info().scheme() == opendal::Scheme::VercelBlob {
+    if op.info().scheme() == crate::Scheme::VercelBlob {
         warn!("ignore test for vercel blob https://github.com/apache/opendal/pull/4103");
         return Ok(());
     }
@@ -817,3 +825,56 @@ pub async fn test_write_with_if_match(op: Operator) -> Result<()> {
 
     Ok(())
 }
+
+/// Resume a multipart upload with the state exported by a previous writer.
+pub async fn test_writer_with_resume(op: Operator) -> Result<()> {
+    let path = TEST_FIXTURE.new_file_path();
+    let chunk = 5 * 1024 * 1024;
+    let content = gen_fixed_bytes(4 * chunk);
+
+    let mut w = op.writer_with(&path).chunk(chunk).await?;
+    for bs in content.chunks(chunk).take(3) {
+        w.write(bs.to_vec()).await?;
+    }
+    let state = w.upload_state().expect("upload must be started");
+    // Drop the writer without close to simulate a crash.
+    drop(w);
+
+    let offset = state.offset() as usize;
+    assert!(offset > 0, "some parts must have been uploaded");
+    let mut w = op.writer_with(&path).chunk(chunk).resume(state).await?;
+    w.write(content[offset..].to_vec()).await?;
+    w.close().await?;
+
+    let bs = op.read(&path).await?.to_bytes();
+    assert_eq!(bs.len(), content.len(), "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content)),
+        "read content"
+    );
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Abort incomplete multipart uploads left by writers.
+pub async fn test_abort_uploads(op: Operator) -> Result<()> {
+    let dir = format!("{}/", uuid::Uuid::new_v4());
+    let path = format!("{dir}{}", uuid::Uuid::new_v4());
+    let chunk = 5 * 1024 * 1024;
+    let content = gen_fixed_bytes(3 * chunk);
+
+    let mut w = op.writer_with(&path).chunk(chunk).await?;
+    w.write(content).await?;
+    // Drop the writer without close to leave an incomplete upload.
+    drop(w);
+
+    let uploads = op.list_uploads(&dir).await?;
+    assert_eq!(uploads.len(), 1);
+    assert_eq!(uploads[0].path(), path);
+
+    assert_eq!(op.abort_uploads(&dir).await?, 1);
+    assert!(op.list_uploads(&dir).await?.is_empty());
+    Ok(())
+}
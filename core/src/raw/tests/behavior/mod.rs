@@ -0,0 +1,230 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+//! Behavior tests that every service and layer should pass.
+//!
+//! The behavior tests take an [`Operator`] and return a list of
+//! [`libtest_mimic::Trial`], tests will be selected by the full capability
+//! of the operator. Users can certify their own [`Access`] implementations
+//! and layers against the same contract that all services in OpenDAL follow:
+//!
+//! ```no_run
+//! use libtest_mimic::Arguments;
+//! use opendal::raw::tests::behavior;
+//! use opendal::raw::tests::TEST_RUNTIME;
+//! use opendal::services;
+//! use opendal::Capability;
+//! use opendal::Operator;
+//!
+//! fn main() -> anyhow::Result<()> {
+//!     let op = Operator::new(services::Memory::default())?.finish();
+//!
+//!     let expected = Capability {
+//!         read: true,
+//!         write: true,
+//!         list: true,
+//!         ..Default::default()
+//!     };
+//!     let trials = behavior::tests_with_capability(&op, expected);
+//!     let conclusion = libtest_mimic::run(&Arguments::from_args(), trials);
+//!
+//!     TEST_RUNTIME.block_on(behavior::TEST_FIXTURE.cleanup(op));
+//!     conclusion.exit()
+//! }
+//! ```
+
+#[macro_use]
+mod utils;
+pub use utils::*;
+
+mod async_concat;
+mod async_copy;
+mod async_create_dir;
+mod async_delete;
+mod async_list;
+mod async_lock;
+mod async_presign;
+mod async_read;
+mod async_rename;
+mod async_stat;
+mod async_transaction;
+mod async_update_metadata;
+mod async_write;
+
+use libtest_mimic::Failed;
+use libtest_mimic::Trial;
+
+use crate::*;
+
+/// Build all behavior tests for given operator.
+///
+/// Tests that are not supported by the full capability of the operator
+/// will be skipped.
+pub fn tests(op: &Operator) -> Vec<Trial> {
+    let mut tests = Vec::new();
+
+    async_concat::tests(op, &mut tests);
+    async_copy::tests(op, &mut tests);
+    async_create_dir::tests(op, &mut tests);
+    async_delete::tests(op, &mut tests);
+    async_list::tests(op, &mut tests);
+    async_lock::tests(op, &mut tests);
+    async_presign::tests(op, &mut tests);
+    async_read::tests(op, &mut tests);
+    async_rename::tests(op, &mut tests);
+    async_stat::tests(op, &mut tests);
+    async_transaction::tests(op, &mut tests);
+    async_update_metadata::tests(op, &mut tests);
+    async_write::tests(op, &mut tests);
+
+    tests
+}
+
+/// Build all behavior tests for given operator, along with a test that
+/// checks the operator's full capability against the expected one.
+///
+/// The capability test fails if any capability that is expected is not
+/// supported by the operator, so that the behavior tests will not be
+/// skipped silently.
+pub fn tests_with_capability(op: &Operator, expected: Capability) -> Vec<Trial> {
+    let actual = op.info().full_capability();
+    let mut tests = vec![Trial::test("behavior::test_capability", move || {
+        let missing = missing_capabilities(expected, actual);
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(Failed::from(format!(
+                "expected capabilities are not supported: {}",
+                missing.join(", ")
+            )))
+        }
+    })];
+    tests.extend(self::tests(op));
+    tests
+}
+
+macro_rules! missing_capabilities {
+    ($expected:ident, $actual:ident, $($field:ident),* $(,)?) => {{
+        let mut missing = Vec::new();
+        $(
+            if $expected.$field && !$actual.$field {
+                missing.push(stringify!($field));
+            }
+        )*
+        missing
+    }};
+}
+
+/// Returns the capabilities that are expected but not supported.
+fn missing_capabilities(expected: Capability, actual: Capability) -> Vec<&'static str> {
+    missing_capabilities!(
+        expected,
+        actual,
+        stat,
+        stat_with_if_match,
+        stat_with_if_none_match,
+        stat_with_if_modified_since,
+        stat_with_if_unmodified_since,
+        stat_with_override_cache_control,
+        stat_with_override_content_disposition,
+        stat_with_override_content_type,
+        stat_with_version,
+        stat_many,
+        read,
+        read_with_if_match,
+        read_with_if_none_match,
+        read_with_if_modified_since,
+        read_with_if_unmodified_since,
+        read_with_override_cache_control,
+        read_with_override_content_disposition,
+        read_with_override_content_type,
+        read_with_version,
+        write,
+        write_can_multi,
+        write_can_empty,
+        write_can_append,
+        write_with_content_type,
+        write_with_content_disposition,
+        write_with_content_encoding,
+        write_with_cache_control,
+        write_with_if_match,
+        write_with_if_none_match,
+        write_with_if_not_exists,
+        write_with_user_metadata,
+        write_with_resume,
+        create_dir,
+        delete,
+        delete_with_version,
+        delete_with_if_match,
+        delete_with_if_none_match,
+        delete_with_if_unmodified_since,
+        copy,
+        copy_with_if_not_exists,
+        copy_with_if_match,
+        copy_with_source_if_match,
+        copy_with_source_if_none_match,
+        copy_with_source_if_unmodified_since,
+        copy_with_source_version,
+        rename,
+        rename_with_if_not_exists,
+        rename_with_if_match,
+        rename_with_source_if_match,
+        rename_with_source_if_none_match,
+        rename_with_source_if_unmodified_since,
+        list,
+        list_with_limit,
+        list_with_start_after,
+        list_with_recursive,
+        list_with_versions,
+        list_with_deleted,
+        list_uploads,
+        abort_upload,
+        lock,
+        concat,
+        update_metadata,
+        update_metadata_with_tags,
+        presign,
+        presign_read,
+        presign_stat,
+        presign_write,
+        presign_delete,
+        presign_post,
+        presign_multipart,
+        shared,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_capabilities() {
+        let expected = Capability {
+            read: true,
+            write: true,
+            list: true,
+            ..Default::default()
+        };
+        let actual = Capability {
+            read: true,
+            write: true,
+            ..Default::default()
+        };
+        assert_eq!(missing_capabilities(expected, actual), vec!["list"]);
+        assert!(missing_capabilities(actual, expected).is_empty());
+    }
+}
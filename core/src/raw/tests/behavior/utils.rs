@@ -21,12 +21,20 @@ use std::sync::Mutex;
 use futures::Future;
 use libtest_mimic::Failed;
 use libtest_mimic::Trial;
-use opendal::raw::tests::TEST_RUNTIME;
-use opendal::raw::*;
-use opendal::*;
 use rand::distributions::uniform::SampleRange;
 use rand::prelude::*;
 
+use crate::raw::tests::TEST_RUNTIME;
+use crate::raw::*;
+use crate::*;
+
+/// The fixture shared by all behavior tests.
+///
+/// Paths created by tests will be recorded here, call [`Fixture::cleanup`]
+/// after all tests finished to remove them.
+pub static TEST_FIXTURE: Fixture = Fixture::new();
+
+/// Generate random bytes with size in given range.
 pub fn gen_bytes_with_range(range: impl SampleRange<usize>) -> (Vec<u8>, usize) {
     let mut rng = thread_rng();
 
@@ -37,17 +45,20 @@ pub fn gen_bytes_with_range(range: impl SampleRange<usize>) -> (Vec<u8>, usize)
     (content, size)
 }
 
+/// Generate random bytes that could be written by given capability.
 pub fn gen_bytes(cap: Capability) -> (Vec<u8>, usize) {
     let max_size = cap.write_total_max_size.unwrap_or(4 * 1024 * 1024);
     gen_bytes_with_range(1..max_size)
 }
 
+/// Generate random bytes with given size.
 pub fn gen_fixed_bytes(size: usize) -> Vec<u8> {
     let (content, _) = gen_bytes_with_range(size..=size);
 
     content
 }
 
+/// Generate a random range that reads at least one byte of given size.
 pub fn gen_offset_length(size: usize) -> (u64, u64) {
     let mut rng = thread_rng();
 
@@ -74,7 +85,6 @@ where
     })
 }
 
-#[macro_export]
 macro_rules! async_trials {
     ($op:ident, $($test:ident),*) => {
         vec![$(
@@ -83,8 +93,9 @@ macro_rules! async_trials {
     };
 }
 
+/// Fixture records the paths created by behavior tests.
 pub struct Fixture {
-    pub paths: Mutex<Vec<String>>,
+    paths: Mutex<Vec<String>>,
 }
 
 impl Default for Fixture {
@@ -134,6 +145,7 @@ impl Fixture {
         self.new_file_with_range(uuid::Uuid::new_v4().to_string(), 1..max_size)
     }
 
+    /// Create a new file at given path with random content
     pub fn new_file_with_path(
         &self,
         op: impl Into<Operator>,
@@ -28,3 +28,5 @@ pub use write::WriteChecker;
 mod utils;
 pub use utils::init_test_service;
 pub use utils::TEST_RUNTIME;
+
+pub mod behavior;
@@ -29,6 +29,7 @@ use reqsign::AzureStorageLoader;
 use reqsign::AzureStorageSigner;
 use sha2::Digest;
 use sha2::Sha256;
+use uuid::Uuid;
 
 use super::core::constants::X_MS_META_PREFIX;
 use super::core::constants::X_MS_VERSION_ID;
@@ -43,6 +44,8 @@ use crate::raw::*;
 use crate::services::AzblobConfig;
 use crate::*;
 const AZBLOB_BATCH_LIMIT: usize = 256;
+/// The max size of a block staged by Put Block From URL.
+const AZBLOB_MAX_BLOCK_SIZE: u64 = 4000 * 1024 * 1024;
 
 impl From<AzureStorageConfig> for AzblobConfig {
     fn from(value: AzureStorageConfig) -> Self {
@@ -265,6 +268,13 @@ impl AzblobBuilder {
         self
     }
 
+    /// Set blob versioning status for this backend
+    pub fn enable_versioning(mut self, enabled: bool) -> Self {
+        self.config.enable_versioning = enabled;
+
+        self
+    }
+
     /// from_connection_string will make a builder from connection string
     ///
     /// connection string looks like:
@@ -419,15 +429,34 @@ impl Builder for AzblobBuilder {
                             write_with_if_not_exists: true,
                             write_with_if_none_match: true,
                             write_with_user_metadata: true,
+                            write_with_resume: true,
 
                             delete: true,
                             delete_max_size: Some(AZBLOB_BATCH_LIMIT),
+                            delete_with_if_match: true,
+                            delete_with_if_none_match: true,
+                            delete_with_if_unmodified_since: true,
+                            delete_with_version: self.config.enable_versioning,
 
                             copy: true,
                             copy_with_if_not_exists: true,
+                            copy_with_if_match: true,
+                            copy_with_source_if_match: true,
+                            copy_with_source_if_none_match: true,
+                            copy_with_source_if_unmodified_since: true,
+                            copy_with_source_version: self.config.enable_versioning,
+
+                            // Put Block From URL authorizes sources by SAS.
+                            concat: self.config.sas_token.is_some(),
+                            // Azure allows at most 50000 blocks in one blob.
+                            concat_max_sources: Some(50000),
+
+                            update_metadata: true,
+                            update_metadata_with_tags: true,
 
                             list: true,
                             list_with_recursive: true,
+                            list_with_versions: self.config.enable_versioning,
 
                             presign: self.config.sas_token.is_some(),
                             presign_stat: self.config.sas_token.is_some(),
@@ -520,11 +549,10 @@ impl Access for AzblobBackend {
         let w = if args.append() {
             AzblobWriters::Two(oio::AppendWriter::new(w))
         } else {
-            AzblobWriters::One(oio::BlockWriter::new(
-                self.core.info.clone(),
-                w,
-                args.concurrent(),
-            ))
+            AzblobWriters::One(
+                oio::BlockWriter::new(self.core.info.clone(), w, args.concurrent())
+                    .with_resume(&args)?,
+            )
         };
 
         Ok((RpWrite::default(), w))
@@ -543,6 +571,7 @@ impl Access for AzblobBackend {
             path.to_string(),
             args.recursive(),
             args.limit(),
+            args.versions(),
         );
 
         Ok((RpList::default(), oio::PageLister::new(l)))
@@ -559,6 +588,87 @@ impl Access for AzblobBackend {
         }
     }
 
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        let mut sources = Vec::with_capacity(args.sources().len());
+        for source in args.sources() {
+            let resp = self
+                .core
+                .azblob_get_blob_properties(source, &OpStat::new())
+                .await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp));
+            }
+            let size = parse_content_length(resp.headers())?.unwrap_or_default();
+            sources.push((source.clone(), size));
+        }
+
+        // Blocks have no min size, so every part will be copied on the server side.
+        let mut block_ids = vec![];
+        for part in plan_concat_parts(&sources, 0, AZBLOB_MAX_BLOCK_SIZE) {
+            let ConcatPart::Copy(source, range) = part else {
+                unreachable!("blocks without min size must be copied")
+            };
+            let block_id = Uuid::new_v4();
+            let resp = self
+                .core
+                .azblob_put_block_from_url(path, block_id, &source, range)
+                .await?;
+            if resp.status() != StatusCode::CREATED {
+                return Err(parse_error(resp));
+            }
+            block_ids.push(block_id);
+        }
+
+        let resp = self
+            .core
+            .azblob_concat_put_block_list(path, block_ids, &args)
+            .await?;
+        match resp.status() {
+            StatusCode::CREATED | StatusCode::OK => Ok(RpConcat::default()),
+            _ => Err(parse_error(resp)),
+        }
+    }
+
+    async fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> Result<RpUpdateMetadata> {
+        let meta = self.stat(path, OpStat::new()).await?.into_metadata();
+        let op = args.apply(&meta);
+
+        // Both requests change the etag, so carry the new etag to the next request.
+        let mut etag = meta.etag().map(|v| v.to_string());
+        if !args.user_metadata().is_empty() {
+            let user_metadata = op.user_metadata().cloned().unwrap_or_default();
+            let resp = self
+                .core
+                .azblob_set_blob_metadata(path, &user_metadata, etag.as_deref())
+                .await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp));
+            }
+            etag = parse_etag(resp.headers())?.map(|v| v.to_string());
+        }
+        if args.has_content_changes() {
+            let resp = self
+                .core
+                .azblob_set_blob_properties(path, &op, meta.content_md5(), etag.as_deref())
+                .await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp));
+            }
+        }
+        // Tags don't change the etag of the blob, so they are not guarded by it.
+        if let Some(tags) = args.tags() {
+            let resp = self.core.azblob_set_blob_tags(path, tags).await?;
+            if resp.status() != StatusCode::NO_CONTENT {
+                return Err(parse_error(resp));
+            }
+        }
+        Ok(RpUpdateMetadata::default())
+    }
+
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
         let req = match args.operation() {
             PresignOperation::Stat(v) => self.core.azblob_head_blob_request(path, v),
@@ -570,7 +680,11 @@ impl Access for AzblobBackend {
                 self.core
                     .azblob_put_blob_request(path, None, &OpWrite::default(), Buffer::new())
             }
-            PresignOperation::Delete(_) => Err(Error::new(
+            PresignOperation::Delete(_)
+            | PresignOperation::Post(_)
+            | PresignOperation::CreateMultipart(_)
+            | PresignOperation::WritePart(_)
+            | PresignOperation::CompleteMultipart(_) => Err(Error::new(
                 ErrorKind::Unsupported,
                 "operation is not supported",
             )),
@@ -75,6 +75,10 @@ pub struct AzblobConfig {
 
     /// The maximum batch operations of Azblob service backend.
     pub batch_max_operations: Option<usize>,
+
+    /// Is blob versioning enabled for this storage account.
+    #[serde(default)]
+    pub enable_versioning: bool,
 }
 
 impl Debug for AzblobConfig {
@@ -94,6 +98,7 @@ impl Debug for AzblobConfig {
         if self.sas_token.is_some() {
             ds.field("sas_token", &"<redacted>");
         }
+        ds.field("enable_versioning", &self.enable_versioning);
 
         ds.finish()
     }
@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Formatter;
@@ -51,7 +52,15 @@ pub mod constants {
 
     pub const X_MS_BLOB_TYPE: &str = "x-ms-blob-type";
     pub const X_MS_COPY_SOURCE: &str = "x-ms-copy-source";
+    pub const X_MS_SOURCE_IF_MATCH: &str = "x-ms-source-if-match";
+    pub const X_MS_SOURCE_IF_NONE_MATCH: &str = "x-ms-source-if-none-match";
+    pub const X_MS_SOURCE_IF_UNMODIFIED_SINCE: &str = "x-ms-source-if-unmodified-since";
+    pub const X_MS_SOURCE_RANGE: &str = "x-ms-source-range";
     pub const X_MS_BLOB_CACHE_CONTROL: &str = "x-ms-blob-cache-control";
+    pub const X_MS_BLOB_CONTENT_TYPE: &str = "x-ms-blob-content-type";
+    pub const X_MS_BLOB_CONTENT_DISPOSITION: &str = "x-ms-blob-content-disposition";
+    pub const X_MS_BLOB_CONTENT_ENCODING: &str = "x-ms-blob-content-encoding";
+    pub const X_MS_BLOB_CONTENT_MD5: &str = "x-ms-blob-content-md5";
     pub const X_MS_BLOB_CONDITION_APPENDPOS: &str = "x-ms-blob-condition-appendpos";
     pub const X_MS_META_PREFIX: &str = "x-ms-meta-";
 
@@ -527,6 +536,184 @@ impl AzblobCore {
         self.send(req).await
     }
 
+    /// Stage a block by copying a range of an existing blob.
+    ///
+    /// The source blob will be authorized by a SAS url, so this requires `sas_token`.
+    pub async fn azblob_put_block_from_url(
+        &self,
+        path: &str,
+        block_id: Uuid,
+        source: &str,
+        range: BytesRange,
+    ) -> Result<Response<Buffer>> {
+        let mut source_req = Request::get(self.build_path_url(source))
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+        self.sign_query(&mut source_req).await?;
+
+        let url = QueryPairsWriter::new(&self.build_path_url(path))
+            .push("comp", "block")
+            .push(
+                "blockid",
+                &percent_encode_path(&BASE64_STANDARD.encode(block_id.as_bytes())),
+            )
+            .finish();
+
+        let mut req = Request::put(&url)
+            .header(constants::X_MS_COPY_SOURCE, source_req.uri().to_string())
+            .header(constants::X_MS_SOURCE_RANGE, range.to_header())
+            .header(CONTENT_LENGTH, 0)
+            .extension(Operation::Concat)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// Commit staged blocks into the blob with the conditions of concat.
+    pub async fn azblob_concat_put_block_list(
+        &self,
+        path: &str,
+        block_ids: Vec<Uuid>,
+        args: &OpConcat,
+    ) -> Result<Response<Buffer>> {
+        let write_args = args.write_args();
+        let mut req = self.azblob_complete_put_block_list_request(path, block_ids, write_args)?;
+
+        let headers = req.headers_mut();
+        let to_header =
+            |v: &str| HeaderValue::from_str(v).map_err(|err| new_request_build_error(err.into()));
+        if let Some(v) = write_args.content_type() {
+            headers.insert(constants::X_MS_BLOB_CONTENT_TYPE, to_header(v)?);
+        }
+        if let Some(v) = write_args.content_disposition() {
+            headers.insert(constants::X_MS_BLOB_CONTENT_DISPOSITION, to_header(v)?);
+        }
+        if let Some(v) = write_args.content_encoding() {
+            headers.insert(constants::X_MS_BLOB_CONTENT_ENCODING, to_header(v)?);
+        }
+        for (key, value) in write_args.user_metadata().into_iter().flatten() {
+            let name = HeaderName::from_bytes(format!("{X_MS_META_PREFIX}{key}").as_bytes())
+                .map_err(|err| new_request_build_error(err.into()))?;
+            headers.insert(name, to_header(value)?);
+        }
+        if let Some(if_match) = args.if_match() {
+            headers.insert(
+                IF_MATCH,
+                HeaderValue::from_str(if_match)
+                    .map_err(|err| new_request_build_error(err.into()))?,
+            );
+        }
+        if args.if_not_exists() {
+            headers.insert(IF_NONE_MATCH, HeaderValue::from_static("*"));
+        }
+        req.extensions_mut().insert(Operation::Concat);
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// Replace all user metadata of the blob.
+    pub async fn azblob_set_blob_metadata(
+        &self,
+        path: &str,
+        user_metadata: &HashMap<String, String>,
+        if_match: Option<&str>,
+    ) -> Result<Response<Buffer>> {
+        let url = format!("{}?comp=metadata", self.build_path_url(path));
+
+        let mut req = Request::put(&url).header(CONTENT_LENGTH, 0);
+        for (key, value) in user_metadata {
+            req = req.header(format!("{X_MS_META_PREFIX}{key}"), value);
+        }
+        if let Some(if_match) = if_match {
+            req = req.header(IF_MATCH, if_match);
+        }
+
+        let mut req = req
+            .extension(Operation::UpdateMetadata)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// Replace all tags of the blob.
+    pub async fn azblob_set_blob_tags(
+        &self,
+        path: &str,
+        tags: &HashMap<String, String>,
+    ) -> Result<Response<Buffer>> {
+        let url = format!("{}?comp=tags", self.build_path_url(path));
+
+        let mut tag: Vec<_> = tags
+            .iter()
+            .map(|(key, value)| BlobTag {
+                key: key.clone(),
+                value: value.clone(),
+            })
+            .collect();
+        tag.sort_by(|a, b| a.key.cmp(&b.key));
+        let content = quick_xml::se::to_string(&BlobTags {
+            tag_set: BlobTagSet { tag },
+        })
+        .map_err(new_xml_serialize_error)?;
+
+        let mut req = Request::put(&url)
+            .header(CONTENT_LENGTH, content.len())
+            .header(CONTENT_TYPE, "application/xml")
+            .extension(Operation::UpdateMetadata)
+            .body(Buffer::from(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// Replace the content properties of the blob.
+    ///
+    /// Properties that are not set will be cleared by azblob, so all of them must be
+    /// carried in `args` along with `content_md5`.
+    pub async fn azblob_set_blob_properties(
+        &self,
+        path: &str,
+        args: &OpWrite,
+        content_md5: Option<&str>,
+        if_match: Option<&str>,
+    ) -> Result<Response<Buffer>> {
+        let url = format!("{}?comp=properties", self.build_path_url(path));
+
+        let mut req = Request::put(&url).header(CONTENT_LENGTH, 0);
+        if let Some(v) = args.content_type() {
+            req = req.header(constants::X_MS_BLOB_CONTENT_TYPE, v);
+        }
+        if let Some(v) = args.cache_control() {
+            req = req.header(constants::X_MS_BLOB_CACHE_CONTROL, v);
+        }
+        if let Some(v) = args.content_disposition() {
+            req = req.header(constants::X_MS_BLOB_CONTENT_DISPOSITION, v);
+        }
+        if let Some(v) = args.content_encoding() {
+            req = req.header(constants::X_MS_BLOB_CONTENT_ENCODING, v);
+        }
+        if let Some(v) = content_md5 {
+            req = req.header(constants::X_MS_BLOB_CONTENT_MD5, v);
+        }
+        if let Some(if_match) = if_match {
+            req = req.header(IF_MATCH, if_match);
+        }
+
+        let mut req = req
+            .extension(Operation::UpdateMetadata)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
     pub fn azblob_head_blob_request(&self, path: &str, args: &OpStat) -> Result<Request<Buffer>> {
         let mut req = Request::head(self.build_path_url(path));
 
@@ -560,16 +747,37 @@ impl AzblobCore {
         self.send(req).await
     }
 
-    fn azblob_delete_blob_request(&self, path: &str) -> Result<Request<Buffer>> {
-        Request::delete(self.build_path_url(path))
-            .header(CONTENT_LENGTH, 0)
-            .extension(Operation::Delete)
+    fn azblob_delete_blob_request(&self, path: &str, args: &OpDelete) -> Result<Request<Buffer>> {
+        let mut url = self.build_path_url(path);
+        if let Some(version) = args.version() {
+            url.push_str(&format!("?versionid={}", percent_encode_path(version)));
+        }
+        let mut req = Request::delete(url).header(CONTENT_LENGTH, 0);
+
+        if let Some(if_match) = args.if_match() {
+            req = req.header(IF_MATCH, if_match);
+        }
+        if let Some(if_none_match) = args.if_none_match() {
+            req = req.header(IF_NONE_MATCH, if_none_match);
+        }
+        if let Some(if_unmodified_since) = args.if_unmodified_since() {
+            req = req.header(
+                IF_UNMODIFIED_SINCE,
+                format_datetime_into_http_date(if_unmodified_since),
+            );
+        }
+
+        req.extension(Operation::Delete)
             .body(Buffer::new())
             .map_err(new_request_build_error)
     }
 
-    pub async fn azblob_delete_blob(&self, path: &str) -> Result<Response<Buffer>> {
-        let mut req = self.azblob_delete_blob_request(path)?;
+    pub async fn azblob_delete_blob(
+        &self,
+        path: &str,
+        args: &OpDelete,
+    ) -> Result<Response<Buffer>> {
+        let mut req = self.azblob_delete_blob_request(path, args)?;
 
         self.sign(&mut req).await?;
         self.send(req).await
@@ -581,7 +789,10 @@ impl AzblobCore {
         to: &str,
         args: OpCopy,
     ) -> Result<Response<Buffer>> {
-        let source = self.build_path_url(from);
+        let mut source = self.build_path_url(from);
+        if let Some(version) = args.source_version() {
+            source.push_str(&format!("?versionid={}", percent_encode_path(version)));
+        }
         let target = self.build_path_url(to);
 
         let mut req = Request::put(&target)
@@ -592,6 +803,21 @@ impl AzblobCore {
         if args.if_not_exists() {
             req = req.header(IF_NONE_MATCH, "*");
         }
+        if let Some(if_match) = args.if_match() {
+            req = req.header(IF_MATCH, if_match);
+        }
+        if let Some(v) = args.source_if_match() {
+            req = req.header(constants::X_MS_SOURCE_IF_MATCH, v);
+        }
+        if let Some(v) = args.source_if_none_match() {
+            req = req.header(constants::X_MS_SOURCE_IF_NONE_MATCH, v);
+        }
+        if let Some(v) = args.source_if_unmodified_since() {
+            req = req.header(
+                constants::X_MS_SOURCE_IF_UNMODIFIED_SINCE,
+                format_datetime_into_http_date(v),
+            );
+        }
 
         let mut req = req
             .extension(Operation::Copy)
@@ -608,12 +834,17 @@ impl AzblobCore {
         next_marker: &str,
         delimiter: &str,
         limit: Option<usize>,
+        versions: bool,
     ) -> Result<Response<Buffer>> {
         let p = build_abs_path(&self.root, path);
         let mut url = QueryPairsWriter::new(&format!("{}/{}", self.endpoint, self.container))
             .push("restype", "container")
             .push("comp", "list");
 
+        if versions {
+            url = url.push("include", "versions");
+        }
+
         if !p.is_empty() {
             url = url.push("prefix", &percent_encode_path(&p));
         }
@@ -636,7 +867,10 @@ impl AzblobCore {
         self.send(req).await
     }
 
-    pub async fn azblob_batch_delete(&self, paths: &[String]) -> Result<Response<Buffer>> {
+    pub async fn azblob_batch_delete(
+        &self,
+        args: &[(String, OpDelete)],
+    ) -> Result<Response<Buffer>> {
         let url = format!(
             "{}/{}?restype=container&comp=batch",
             self.endpoint, self.container
@@ -644,8 +878,8 @@ impl AzblobCore {
 
         let mut multipart = Multipart::new();
 
-        for (idx, path) in paths.iter().enumerate() {
-            let mut req = self.azblob_delete_blob_request(path)?;
+        for (idx, (path, args)) in args.iter().enumerate() {
+            let mut req = self.azblob_delete_blob_request(path, args)?;
             self.batch_sign(&mut req).await?;
 
             multipart = multipart.part(
@@ -668,6 +902,26 @@ pub struct PutBlockListRequest {
     pub latest: Vec<String>,
 }
 
+/// Request of Set Blob Tags.
+#[derive(Default, Debug, Serialize)]
+#[serde(default, rename = "Tags", rename_all = "PascalCase")]
+pub struct BlobTags {
+    pub tag_set: BlobTagSet,
+}
+
+#[derive(Default, Debug, Serialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct BlobTagSet {
+    pub tag: Vec<BlobTag>,
+}
+
+#[derive(Default, Debug, Serialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct BlobTag {
+    pub key: String,
+    pub value: String,
+}
+
 #[derive(Default, Debug, Deserialize)]
 #[serde(default, rename_all = "PascalCase")]
 pub struct ListBlobsOutput {
@@ -694,6 +948,9 @@ pub struct BlobPrefix {
 pub struct Blob {
     pub properties: Properties,
     pub name: String,
+    /// Only returned while listing with versions.
+    pub version_id: Option<String>,
+    pub is_current_version: Option<bool>,
 }
 
 #[derive(Default, Debug, Deserialize)]
@@ -897,6 +1154,39 @@ mod tests {
         de::from_reader(Bytes::from(bs).reader()).expect("must success")
     }
 
+    /// This example is from https://learn.microsoft.com/en-us/rest/api/storageservices/set-blob-tags
+    #[test]
+    fn test_serialize_blob_tags() {
+        let req = BlobTags {
+            tag_set: BlobTagSet {
+                tag: vec![
+                    BlobTag {
+                        key: "tag-name-1".to_string(),
+                        value: "tag-value-1".to_string(),
+                    },
+                    BlobTag {
+                        key: "tag-name-2".to_string(),
+                        value: "tag-value-2".to_string(),
+                    },
+                ],
+            },
+        };
+
+        let actual = quick_xml::se::to_string(&req).expect("must succeed");
+
+        pretty_assertions::assert_eq!(
+            actual,
+            r#"<Tags>
+                <TagSet>
+                    <Tag><Key>tag-name-1</Key><Value>tag-value-1</Value></Tag>
+                    <Tag><Key>tag-name-2</Key><Value>tag-value-2</Value></Tag>
+                </TagSet>
+            </Tags>"#
+                // Cleanup space and new line
+                .replace([' ', '\n'], "")
+        )
+    }
+
     /// This example is from https://learn.microsoft.com/en-us/rest/api/storageservices/put-block-list?tabs=microsoft-entra-id
     #[test]
     fn test_serialize_put_block_list_request() {
@@ -934,4 +1224,52 @@ mod tests {
             vec!["1".to_string(), "2".to_string(), "3".to_string()]
         );
     }
+
+    #[test]
+    fn test_parse_xml_with_versions() {
+        let bs = bytes::Bytes::from(
+            r#"
+            <?xml version="1.0" encoding="utf-8"?>
+            <EnumerationResults ServiceEndpoint="https://test.blob.core.windows.net/" ContainerName="myazurebucket">
+                <Blobs>
+                    <Blob>
+                        <Name>dir1/file</Name>
+                        <VersionId>2022-03-20T11:29:03.1234567Z</VersionId>
+                        <Properties>
+                            <Last-Modified>Sun, 20 Mar 2022 11:29:03 GMT</Last-Modified>
+                            <Etag>0x8DA0A64D66790C3</Etag>
+                            <Content-Length>3485277</Content-Length>
+                        </Properties>
+                    </Blob>
+                    <Blob>
+                        <Name>dir1/file</Name>
+                        <VersionId>2022-03-29T01:54:07.7654321Z</VersionId>
+                        <IsCurrentVersion>true</IsCurrentVersion>
+                        <Properties>
+                            <Last-Modified>Tue, 29 Mar 2022 01:54:07 GMT</Last-Modified>
+                            <Etag>0x8DA112702D88FE4</Etag>
+                            <Content-Length>2471869</Content-Length>
+                        </Properties>
+                    </Blob>
+                </Blobs>
+                <NextMarker />
+            </EnumerationResults>"#,
+        );
+
+        let out: ListBlobsOutput = de::from_reader(bs.reader()).expect("must success");
+
+        let versions: Vec<_> = out
+            .blobs
+            .blob
+            .iter()
+            .map(|v| (v.version_id.as_deref(), v.is_current_version))
+            .collect();
+        assert_eq!(
+            versions,
+            vec![
+                (Some("2022-03-20T11:29:03.1234567Z"), None),
+                (Some("2022-03-29T01:54:07.7654321Z"), Some(true)),
+            ]
+        );
+    }
 }
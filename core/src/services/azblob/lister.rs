@@ -32,10 +32,17 @@ pub struct AzblobLister {
     path: String,
     delimiter: &'static str,
     limit: Option<usize>,
+    versions: bool,
 }
 
 impl AzblobLister {
-    pub fn new(core: Arc<AzblobCore>, path: String, recursive: bool, limit: Option<usize>) -> Self {
+    pub fn new(
+        core: Arc<AzblobCore>,
+        path: String,
+        recursive: bool,
+        limit: Option<usize>,
+        versions: bool,
+    ) -> Self {
         let delimiter = if recursive { "" } else { "/" };
 
         Self {
@@ -43,6 +50,7 @@ impl AzblobLister {
             path,
             delimiter,
             limit,
+            versions,
         }
     }
 }
@@ -51,7 +59,13 @@ impl oio::PageList for AzblobLister {
     async fn next_page(&self, ctx: &mut oio::PageContext) -> Result<()> {
         let resp = self
             .core
-            .azblob_list_blobs(&self.path, &ctx.token, self.delimiter, self.limit)
+            .azblob_list_blobs(
+                &self.path,
+                &ctx.token,
+                self.delimiter,
+                self.limit,
+                self.versions,
+            )
             .await?;
 
         if resp.status() != http::StatusCode::OK {
@@ -88,7 +102,7 @@ impl oio::PageList for AzblobLister {
                 path = "/".to_string();
             }
 
-            let meta = Metadata::new(EntryMode::from_path(&path))
+            let mut meta = Metadata::new(EntryMode::from_path(&path))
                 // Keep fit with ETag header.
                 .with_etag(format!("\"{}\"", object.properties.etag.as_str()))
                 .with_content_length(object.properties.content_length)
@@ -97,6 +111,10 @@ impl oio::PageList for AzblobLister {
                 .with_last_modified(parse_datetime_from_rfc2822(
                     object.properties.last_modified.as_str(),
                 )?);
+            if let Some(version_id) = &object.version_id {
+                meta.set_version(version_id);
+                meta.set_is_current(object.is_current_version.unwrap_or_default());
+            }
 
             let de = oio::Entry::with(path, meta);
             ctx.entries.push_back(de);
@@ -19,6 +19,8 @@ use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
 
+use bytes::Buf;
+use chrono::DateTime;
 use http::Request;
 use http::Response;
 use http::StatusCode;
@@ -29,6 +31,7 @@ use super::core::constants;
 use super::core::parse_file_info;
 use super::core::B2Core;
 use super::core::B2Signer;
+use super::core::ListUnfinishedLargeFilesResponse;
 use super::delete::B2Deleter;
 use super::error::parse_error;
 use super::lister::B2Lister;
@@ -202,6 +205,7 @@ impl Builder for B2Builder {
                             write_can_empty: true,
                             write_can_multi: true,
                             write_with_content_type: true,
+                            write_with_resume: true,
                             // The min multipart size of b2 is 5 MiB.
                             //
                             // ref: <https://www.backblaze.com/docs/cloud-storage-large-files>
@@ -223,6 +227,9 @@ impl Builder for B2Builder {
                             list_with_start_after: true,
                             list_with_recursive: true,
 
+                            list_uploads: true,
+                            abort_upload: true,
+
                             presign: true,
                             presign_read: true,
                             presign_write: true,
@@ -303,9 +310,10 @@ impl Access for B2Backend {
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
         let concurrent = args.concurrent();
-        let writer = B2Writer::new(self.core.clone(), path, args);
+        let writer = B2Writer::new(self.core.clone(), path, args.clone());
 
-        let w = oio::MultipartWriter::new(self.core.info.clone(), writer, concurrent);
+        let w = oio::MultipartWriter::new(self.core.info.clone(), writer, concurrent)
+            .with_resume(&args);
 
         Ok((RpWrite::default(), w))
     }
@@ -349,6 +357,55 @@ impl Access for B2Backend {
         }
     }
 
+    async fn list_uploads(&self, path: &str, _: OpListUploads) -> Result<RpListUploads> {
+        let mut uploads = Vec::new();
+        let mut start_file_id = String::new();
+
+        loop {
+            let resp = self
+                .core
+                .list_unfinished_large_files(path, &start_file_id)
+                .await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp));
+            }
+
+            let output: ListUnfinishedLargeFilesResponse =
+                serde_json::from_reader(resp.into_body().reader())
+                    .map_err(new_json_deserialize_error)?;
+
+            for file in output.files {
+                let mut u = Upload::new(
+                    &build_rel_path(&self.core.root, &file.file_name),
+                    &file.file_id,
+                );
+                if let Some(initiated) = file
+                    .upload_timestamp
+                    .and_then(DateTime::from_timestamp_millis)
+                {
+                    u = u.with_initiated(initiated);
+                }
+                uploads.push(u);
+            }
+
+            match output.next_file_id {
+                Some(file_id) if !file_id.is_empty() => start_file_id = file_id,
+                _ => break,
+            }
+        }
+
+        Ok(RpListUploads::new(uploads))
+    }
+
+    async fn abort_upload(&self, _: &str, args: OpAbortUpload) -> Result<RpAbortUpload> {
+        let resp = self.core.cancel_large_file(args.upload_id()).await?;
+        match resp.status() {
+            // b2 returns code 200 if abort succeeds.
+            StatusCode::OK | StatusCode::NOT_FOUND => Ok(RpAbortUpload::default()),
+            _ => Err(parse_error(resp)),
+        }
+    }
+
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
         match args.operation() {
             PresignOperation::Stat(_) => {
@@ -425,7 +482,11 @@ impl Access for B2Backend {
                     parts.headers,
                 )))
             }
-            PresignOperation::Delete(_) => Err(Error::new(
+            PresignOperation::Delete(_)
+            | PresignOperation::Post(_)
+            | PresignOperation::CreateMultipart(_)
+            | PresignOperation::WritePart(_)
+            | PresignOperation::CompleteMultipart(_) => Err(Error::new(
                 ErrorKind::Unsupported,
                 "operation is not supported",
             )),
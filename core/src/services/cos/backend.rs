@@ -18,6 +18,10 @@
 use std::fmt::Debug;
 use std::sync::Arc;
 
+use bytes::Buf;
+use http::header::CONTENT_LENGTH;
+use http::HeaderMap;
+use http::Method;
 use http::Response;
 use http::StatusCode;
 use http::Uri;
@@ -260,20 +264,31 @@ impl Builder for CosBuilder {
                                 Some(usize::MAX)
                             },
                             write_with_user_metadata: true,
+                            write_with_resume: true,
 
                             delete: true,
                             delete_with_version: self.config.enable_versioning,
                             copy: true,
 
+                            update_metadata: true,
+                            update_metadata_with_tags: true,
+                            copy_with_source_version: self.config.enable_versioning,
+
                             list: true,
                             list_with_recursive: true,
                             list_with_versions: self.config.enable_versioning,
                             list_with_deleted: self.config.enable_versioning,
 
+                            list_uploads: true,
+
+                            abort_upload: true,
+
                             presign: true,
                             presign_stat: true,
                             presign_read: true,
                             presign_write: true,
+                            presign_post: true,
+                            presign_multipart: true,
 
                             shared: true,
 
@@ -364,11 +379,10 @@ impl Access for CosBackend {
         let w = if args.append() {
             CosWriters::Two(oio::AppendWriter::new(writer))
         } else {
-            CosWriters::One(oio::MultipartWriter::new(
-                self.core.info.clone(),
-                writer,
-                args.concurrent(),
-            ))
+            CosWriters::One(
+                oio::MultipartWriter::new(self.core.info.clone(), writer, args.concurrent())
+                    .with_resume(&args),
+            )
         };
 
         Ok((RpWrite::default(), w))
@@ -400,8 +414,8 @@ impl Access for CosBackend {
         Ok((RpList::default(), l))
     }
 
-    async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
-        let resp = self.core.cos_copy_object(from, to).await?;
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let resp = self.core.cos_copy_object(from, to, &args).await?;
 
         let status = resp.status();
 
@@ -411,6 +425,89 @@ impl Access for CosBackend {
         }
     }
 
+    async fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> Result<RpUpdateMetadata> {
+        if args.has_metadata_changes() {
+            let meta = self.stat(path, OpStat::new()).await?.into_metadata();
+            let op = args.apply(&meta);
+
+            let resp = self
+                .core
+                .cos_replace_object_metadata(path, &op, meta.etag())
+                .await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp));
+            }
+        }
+
+        if let Some(tags) = args.tags() {
+            let resp = self.core.cos_put_object_tagging(path, tags).await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp));
+            }
+        }
+        Ok(RpUpdateMetadata::default())
+    }
+
+    async fn list_uploads(&self, path: &str, _: OpListUploads) -> Result<RpListUploads> {
+        let mut uploads = Vec::new();
+        let (mut key_marker, mut upload_id_marker) = (String::new(), String::new());
+
+        loop {
+            let resp = self
+                .core
+                .cos_list_multipart_uploads(path, &key_marker, &upload_id_marker)
+                .await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp));
+            }
+
+            let output: ListMultipartUploadsResult =
+                quick_xml::de::from_reader(resp.into_body().reader())
+                    .map_err(new_xml_deserialize_error)?;
+
+            for upload in output.upload {
+                let mut u = Upload::new(
+                    &build_rel_path(&self.core.root, &upload.key),
+                    &upload.upload_id,
+                );
+                if let Some(initiated) = upload.initiated {
+                    u = u.with_initiated(parse_datetime_from_rfc3339(&initiated)?);
+                }
+                uploads.push(u);
+            }
+
+            match (
+                output.is_truncated,
+                output.next_key_marker,
+                output.next_upload_id_marker,
+            ) {
+                (Some(true), Some(key), Some(upload_id)) => {
+                    key_marker = key;
+                    upload_id_marker = upload_id;
+                }
+                _ => break,
+            }
+        }
+
+        Ok(RpListUploads::new(uploads))
+    }
+
+    async fn abort_upload(&self, path: &str, args: OpAbortUpload) -> Result<RpAbortUpload> {
+        let resp = self
+            .core
+            .cos_abort_multipart_upload(path, args.upload_id())
+            .await?;
+        match resp.status() {
+            // cos returns code 204 if abort succeeds, and 404 if upload not exists.
+            StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => Ok(RpAbortUpload::default()),
+            _ => Err(parse_error(resp)),
+        }
+    }
+
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
         let req = match args.operation() {
             PresignOperation::Stat(v) => self.core.cos_head_object_request(path, v),
@@ -422,12 +519,39 @@ impl Access for CosBackend {
                 self.core
                     .cos_put_object_request(path, None, v, Buffer::new())
             }
+            PresignOperation::Post(v) => {
+                let fields = self.core.sign_post_policy(path, v, args.expire()).await?;
+                let uri = format!("{}/", self.core.endpoint)
+                    .parse()
+                    .map_err(new_http_uri_invalid_error)?;
+                return Ok(RpPresign::new(PresignedRequest::new(
+                    Method::POST,
+                    uri,
+                    HeaderMap::new(),
+                ))
+                .with_form_fields(fields));
+            }
+            PresignOperation::CreateMultipart(v) => {
+                self.core.cos_initiate_multipart_upload_request(path, v)
+            }
+            PresignOperation::WritePart(v) => self.core.cos_upload_part_request(
+                path,
+                v.upload_id(),
+                v.part_number(),
+                0,
+                Buffer::new(),
+            ),
+            PresignOperation::CompleteMultipart(v) => self
+                .core
+                .cos_complete_multipart_upload_request(path, v.upload_id(), vec![]),
             PresignOperation::Delete(_) => Err(Error::new(
                 ErrorKind::Unsupported,
                 "operation is not supported",
             )),
         };
         let mut req = req?;
+        // Content length is decided by the client who sends the presigned request.
+        req.headers_mut().remove(CONTENT_LENGTH);
         self.core.sign_query(&mut req, args.expire()).await?;
 
         // We don't need this request anymore, consume it directly.
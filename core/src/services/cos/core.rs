@@ -15,12 +15,16 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
 use std::time::Duration;
 
 use bytes::Bytes;
+use chrono::Utc;
+use hmac::Hmac;
+use hmac::Mac;
 use http::header::CACHE_CONTROL;
 use http::header::CONTENT_DISPOSITION;
 use http::header::CONTENT_LENGTH;
@@ -29,6 +33,7 @@ use http::header::IF_MATCH;
 use http::header::IF_MODIFIED_SINCE;
 use http::header::IF_NONE_MATCH;
 use http::header::IF_UNMODIFIED_SINCE;
+use http::HeaderValue;
 use http::Request;
 use http::Response;
 use reqsign::TencentCosCredential;
@@ -36,6 +41,8 @@ use reqsign::TencentCosCredentialLoader;
 use reqsign::TencentCosSigner;
 use serde::Deserialize;
 use serde::Serialize;
+use sha1::Digest;
+use sha1::Sha1;
 
 use crate::raw::*;
 use crate::*;
@@ -46,6 +53,12 @@ pub mod constants {
     pub const X_COS_VERSION_ID: &str = "x-cos-version-id";
 }
 
+fn hex_hmac_sha1(key: &[u8], content: &[u8]) -> String {
+    let mut mac = Hmac::<Sha1>::new_from_slice(key).expect("HMAC can take key of any size");
+    mac.update(content);
+    format!("{:x}", mac.finalize().into_bytes())
+}
+
 pub struct CosCore {
     pub info: Arc<AccessorInfo>,
     pub bucket: String,
@@ -106,6 +119,58 @@ impl CosCore {
             .map_err(new_request_sign_error)
     }
 
+    /// Build the form fields of a presigned POST upload.
+    ///
+    /// Reference: <https://www.tencentcloud.com/document/product/436/14690>
+    pub async fn sign_post_policy(
+        &self,
+        path: &str,
+        args: &OpPresignPost,
+        expire: Duration,
+    ) -> Result<Vec<(String, String)>> {
+        let key = build_abs_path(&self.root, path);
+        let mut fields = vec![("key".to_string(), key.clone())];
+        if let Some(v) = args.content_type() {
+            fields.push(("Content-Type".to_string(), v.to_string()));
+        }
+
+        let Some(cred) = self.load_credential().await? else {
+            return Ok(fields);
+        };
+
+        let now = Utc::now();
+        let key_time = format!(
+            "{};{}",
+            now.timestamp(),
+            now.timestamp() + expire.as_secs() as i64
+        );
+
+        let mut policy = PostPolicy::new(now, expire);
+        policy.push_eq("bucket", &self.bucket);
+        policy.push_eq("key", &key);
+        policy.push_eq("q-sign-algorithm", "sha1");
+        policy.push_eq("q-ak", &cred.secret_id);
+        policy.push_eq("q-sign-time", &key_time);
+        if let Some(token) = &cred.security_token {
+            policy.push_eq("x-cos-security-token", token);
+        }
+        policy.push_conditions(args);
+
+        let sign_key = hex_hmac_sha1(cred.secret_key.as_bytes(), key_time.as_bytes());
+        let string_to_sign = format!("{:x}", Sha1::digest(policy.document().as_bytes()));
+        let signature = hex_hmac_sha1(sign_key.as_bytes(), string_to_sign.as_bytes());
+
+        fields.push(("q-sign-algorithm".to_string(), "sha1".to_string()));
+        fields.push(("q-ak".to_string(), cred.secret_id));
+        fields.push(("q-key-time".to_string(), key_time));
+        if let Some(token) = cred.security_token {
+            fields.push(("x-cos-security-token".to_string(), token));
+        }
+        fields.push(("policy".to_string(), policy.encode()));
+        fields.push(("q-signature".to_string(), signature));
+        Ok(fields)
+    }
+
     #[inline]
     pub async fn send(&self, req: Request<Buffer>) -> Result<Response<Buffer>> {
         self.info.http_client().send(req).await
@@ -343,11 +408,19 @@ impl CosCore {
         Ok(req)
     }
 
-    pub async fn cos_copy_object(&self, from: &str, to: &str) -> Result<Response<Buffer>> {
+    pub async fn cos_copy_object(
+        &self,
+        from: &str,
+        to: &str,
+        args: &OpCopy,
+    ) -> Result<Response<Buffer>> {
         let source = build_abs_path(&self.root, from);
         let target = build_abs_path(&self.root, to);
 
-        let source = format!("/{}/{}", self.bucket, percent_encode_path(&source));
+        let mut source = format!("/{}/{}", self.bucket, percent_encode_path(&source));
+        if let Some(version) = args.source_version() {
+            source.push_str(&format!("?versionId={}", percent_encode_path(version)));
+        }
         let url = format!("{}/{}", self.endpoint, percent_encode_path(&target));
 
         let mut req = Request::put(&url)
@@ -361,6 +434,75 @@ impl CosCore {
         self.send(req).await
     }
 
+    /// Replace the metadata of the object by copying it onto itself.
+    ///
+    /// CopyObject only supports objects up to 5GiB.
+    pub async fn cos_replace_object_metadata(
+        &self,
+        path: &str,
+        args: &OpWrite,
+        source_if_match: Option<&str>,
+    ) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+        let source = format!("/{}/{}", self.bucket, percent_encode_path(&p));
+
+        let mut req = self.cos_put_object_request(path, Some(0), args, Buffer::new())?;
+        let headers = req.headers_mut();
+        headers.insert(
+            "x-cos-copy-source",
+            HeaderValue::from_str(&source).map_err(|err| new_request_build_error(err.into()))?,
+        );
+        headers.insert(
+            "x-cos-metadata-directive",
+            HeaderValue::from_static("Replaced"),
+        );
+        if let Some(v) = source_if_match {
+            headers.insert(
+                "x-cos-copy-source-if-match",
+                HeaderValue::from_str(v).map_err(|err| new_request_build_error(err.into()))?,
+            );
+        }
+        req.extensions_mut().insert(Operation::UpdateMetadata);
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
+    pub async fn cos_put_object_tagging(
+        &self,
+        path: &str,
+        tags: &HashMap<String, String>,
+    ) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+        let url = format!("{}/{}?tagging", self.endpoint, percent_encode_path(&p));
+
+        let mut tag: Vec<_> = tags
+            .iter()
+            .map(|(key, value)| Tag {
+                key: key.clone(),
+                value: value.clone(),
+            })
+            .collect();
+        tag.sort_by(|a, b| a.key.cmp(&b.key));
+        let content = quick_xml::se::to_string(&Tagging {
+            tag_set: TagSet { tag },
+        })
+        .map_err(new_xml_serialize_error)?;
+
+        let mut req = Request::put(&url)
+            .header(CONTENT_LENGTH, content.len())
+            .header(CONTENT_TYPE, "application/xml")
+            .header("CONTENT-MD5", format_content_md5(content.as_bytes()))
+            .extension(Operation::UpdateMetadata)
+            .body(Buffer::from(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
     pub async fn cos_list_objects(
         &self,
         path: &str,
@@ -400,6 +542,18 @@ impl CosCore {
         path: &str,
         args: &OpWrite,
     ) -> Result<Response<Buffer>> {
+        let mut req = self.cos_initiate_multipart_upload_request(path, args)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
+    pub fn cos_initiate_multipart_upload_request(
+        &self,
+        path: &str,
+        args: &OpWrite,
+    ) -> Result<Request<Buffer>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!("{}/{}?uploads", self.endpoint, percent_encode_path(&p));
@@ -427,21 +581,34 @@ impl CosCore {
 
         let req = req.extension(Operation::Write);
 
-        let mut req = req.body(Buffer::new()).map_err(new_request_build_error)?;
+        let req = req.body(Buffer::new()).map_err(new_request_build_error)?;
+
+        Ok(req)
+    }
+
+    pub async fn cos_upload_part(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number: usize,
+        size: u64,
+        body: Buffer,
+    ) -> Result<Response<Buffer>> {
+        let mut req = self.cos_upload_part_request(path, upload_id, part_number, size, body)?;
 
         self.sign(&mut req).await?;
 
         self.send(req).await
     }
 
-    pub async fn cos_upload_part_request(
+    pub fn cos_upload_part_request(
         &self,
         path: &str,
         upload_id: &str,
         part_number: usize,
         size: u64,
         body: Buffer,
-    ) -> Result<Response<Buffer>> {
+    ) -> Result<Request<Buffer>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!(
@@ -458,19 +625,30 @@ impl CosCore {
         let req = req.extension(Operation::Write);
 
         // Set body
-        let mut req = req.body(body).map_err(new_request_build_error)?;
+        let req = req.body(body).map_err(new_request_build_error)?;
+
+        Ok(req)
+    }
+
+    pub async fn cos_complete_multipart_upload(
+        &self,
+        path: &str,
+        upload_id: &str,
+        parts: Vec<CompleteMultipartUploadRequestPart>,
+    ) -> Result<Response<Buffer>> {
+        let mut req = self.cos_complete_multipart_upload_request(path, upload_id, parts)?;
 
         self.sign(&mut req).await?;
 
         self.send(req).await
     }
 
-    pub async fn cos_complete_multipart_upload(
+    pub fn cos_complete_multipart_upload_request(
         &self,
         path: &str,
         upload_id: &str,
         parts: Vec<CompleteMultipartUploadRequestPart>,
-    ) -> Result<Response<Buffer>> {
+    ) -> Result<Request<Buffer>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!(
@@ -491,16 +669,42 @@ impl CosCore {
 
         let req = req.extension(Operation::Write);
 
-        let mut req = req
+        let req = req
             .body(Buffer::from(Bytes::from(content)))
             .map_err(new_request_build_error)?;
 
-        self.sign(&mut req).await?;
+        Ok(req)
+    }
 
+    /// Abort an on-going multipart upload.
+    pub async fn cos_list_multipart_uploads(
+        &self,
+        path: &str,
+        key_marker: &str,
+        upload_id_marker: &str,
+    ) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = QueryPairsWriter::new(&self.endpoint).push("uploads", "");
+
+        if !p.is_empty() {
+            url = url.push("prefix", &percent_encode_path(&p));
+        }
+        if !key_marker.is_empty() {
+            url = url.push("key-marker", &percent_encode_path(key_marker));
+        }
+        if !upload_id_marker.is_empty() {
+            url = url.push("upload-id-marker", &percent_encode_path(upload_id_marker));
+        }
+
+        let mut req = Request::get(url.finish())
+            .extension(Operation::ListUploads)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
         self.send(req).await
     }
 
-    /// Abort an on-going multipart upload.
     pub async fn cos_abort_multipart_upload(
         &self,
         path: &str,
@@ -563,6 +767,24 @@ impl CosCore {
     }
 }
 
+/// Output of ListMultipartUploads.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct ListMultipartUploadsResult {
+    pub is_truncated: Option<bool>,
+    pub next_key_marker: Option<String>,
+    pub next_upload_id_marker: Option<String>,
+    pub upload: Vec<ListMultipartUploadsResultUpload>,
+}
+
+#[derive(Default, Debug, Eq, PartialEq, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct ListMultipartUploadsResultUpload {
+    pub key: String,
+    pub upload_id: String,
+    pub initiated: Option<String>,
+}
+
 /// Result of CreateMultipartUpload
 #[derive(Default, Debug, Deserialize)]
 #[serde(default, rename_all = "PascalCase")]
@@ -570,6 +792,26 @@ pub struct InitiateMultipartUploadResult {
     pub upload_id: String,
 }
 
+/// Request of PutObjectTagging.
+#[derive(Default, Debug, Serialize)]
+#[serde(default, rename = "Tagging", rename_all = "PascalCase")]
+pub struct Tagging {
+    pub tag_set: TagSet,
+}
+
+#[derive(Default, Debug, Serialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct TagSet {
+    pub tag: Vec<Tag>,
+}
+
+#[derive(Default, Debug, Serialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct Tag {
+    pub key: String,
+    pub value: String,
+}
+
 /// Request of CompleteMultipartUploadRequest
 #[derive(Default, Debug, Serialize)]
 #[serde(default, rename = "CompleteMultipartUpload", rename_all = "PascalCase")]
@@ -217,6 +217,7 @@ impl EtcdAccessor {
             stat: true,
             list: true,
 
+            lock: true,
             shared: true,
 
             ..Default::default()
@@ -324,6 +325,12 @@ impl Access for EtcdAccessor {
         Ok((RpWrite::new(), writer))
     }
 
+    async fn lock(&self, path: &str, args: OpLock) -> Result<RpLock> {
+        let abs_path = build_abs_path(&self.info.root(), path);
+        self.core.lock(&abs_path, &args).await?;
+        Ok(RpLock::default())
+    }
+
     async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
         let deleter = oio::OneShotDeleter::new(EtcdDeleter::new(
             self.core.clone(),
@@ -21,9 +21,16 @@ use std::fmt::Formatter;
 use bb8::PooledConnection;
 use bb8::RunError;
 use etcd_client::Client;
+use etcd_client::Compare;
+use etcd_client::CompareOp;
 use etcd_client::ConnectOptions;
+use etcd_client::PutOptions;
+use etcd_client::Txn;
+use etcd_client::TxnOp;
 use tokio::sync::OnceCell;
 
+use crate::raw::LockAction;
+use crate::raw::OpLock;
 use crate::services::etcd::error::format_etcd_error;
 use crate::{Buffer, Error, ErrorKind, Result};
 
@@ -140,4 +147,74 @@ impl EtcdCore {
         let _ = client.delete(key, None).await.map_err(format_etcd_error)?;
         Ok(())
     }
+
+    /// Lock the key with etcd lease, the key will be removed by etcd after
+    /// the lease expired.
+    pub async fn lock(&self, key: &str, args: &OpLock) -> Result<()> {
+        let mut client = self.conn().await?;
+
+        let held = match args.action() {
+            LockAction::Acquire => {
+                // Round up so that the lease never expires before the ttl.
+                let ttl = args.ttl();
+                let ttl = (ttl.as_secs() + u64::from(ttl.subsec_nanos() > 0)).max(1) as i64;
+                let lease = client
+                    .lease_grant(ttl, None)
+                    .await
+                    .map_err(format_etcd_error)?;
+                let txn = Txn::new()
+                    .when([Compare::create_revision(key, CompareOp::Equal, 0)])
+                    .and_then([TxnOp::put(
+                        key,
+                        args.token(),
+                        Some(PutOptions::new().with_lease(lease.id())),
+                    )]);
+                let resp = client.txn(txn).await.map_err(format_etcd_error)?;
+                if !resp.succeeded() {
+                    let _ = client.lease_revoke(lease.id()).await;
+                }
+                resp.succeeded()
+            }
+            LockAction::Renew => {
+                let resp = client.get(key, None).await.map_err(format_etcd_error)?;
+                match resp.kvs().first() {
+                    Some(kv) if kv.value() == args.token().as_bytes() => {
+                        let (mut keeper, mut stream) = client
+                            .lease_keep_alive(kv.lease())
+                            .await
+                            .map_err(format_etcd_error)?;
+                        keeper.keep_alive().await.map_err(format_etcd_error)?;
+                        let resp = stream.message().await.map_err(format_etcd_error)?;
+                        resp.is_some_and(|resp| resp.ttl() > 0)
+                    }
+                    _ => false,
+                }
+            }
+            LockAction::Release => {
+                let resp = client.get(key, None).await.map_err(format_etcd_error)?;
+                match resp.kvs().first() {
+                    Some(kv) if kv.value() == args.token().as_bytes() => {
+                        let txn = Txn::new()
+                            .when([Compare::value(key, CompareOp::Equal, args.token())])
+                            .and_then([TxnOp::delete(key, None)]);
+                        let resp = client.txn(txn).await.map_err(format_etcd_error)?;
+                        if resp.succeeded() {
+                            let _ = client.lease_revoke(kv.lease()).await;
+                        }
+                        resp.succeeded()
+                    }
+                    _ => false,
+                }
+            }
+        };
+
+        if held {
+            Ok(())
+        } else {
+            Err(Error::new(
+                ErrorKind::ConditionNotMatch,
+                "lock is held by others or has expired",
+            ))
+        }
+    }
 }
@@ -159,11 +159,18 @@ impl Builder for FsBuilder {
 
                             create_dir: true,
                             delete: true,
+                            delete_with_if_unmodified_since: true,
 
                             list: true,
 
                             copy: true,
+                            // Conditional copy and rename are done with hard links,
+                            // which are only available for files on most filesystems.
+                            copy_with_if_not_exists: true,
+                            copy_with_source_if_unmodified_since: true,
                             rename: true,
+                            rename_with_if_not_exists: true,
+                            rename_with_source_if_unmodified_since: true,
 
                             shared: true,
 
@@ -175,6 +182,7 @@ impl Builder for FsBuilder {
                 root,
                 atomic_write_dir,
                 buf_pool: oio::PooledBuf::new(16).with_initial_capacity(256 * 1024),
+                condition_lock: tokio::sync::Mutex::new(()),
             }),
         })
     }
@@ -261,13 +269,13 @@ impl Access for FsBackend {
         }
     }
 
-    async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
-        self.core.fs_copy(from, to).await?;
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.core.fs_copy(from, to, &args).await?;
         Ok(RpCopy::default())
     }
 
-    async fn rename(&self, from: &str, to: &str, _args: OpRename) -> Result<RpRename> {
-        self.core.fs_rename(from, to).await?;
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.core.fs_rename(from, to, &args).await?;
         Ok(RpRename::default())
     }
 }
@@ -21,6 +21,8 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use chrono::DateTime;
+use chrono::Utc;
+use log::warn;
 
 use super::error::*;
 use crate::raw::*;
@@ -32,6 +34,9 @@ pub struct FsCore {
     pub root: PathBuf,
     pub atomic_write_dir: Option<PathBuf>,
     pub buf_pool: oio::PooledBuf,
+    /// Conditional operations on time compare and act under this lock, so they
+    /// won't interleave with each other inside the same process.
+    pub condition_lock: tokio::sync::Mutex<()>,
 }
 
 impl FsCore {
@@ -198,26 +203,99 @@ impl FsCore {
         }
     }
 
-    pub async fn fs_copy(&self, from: &str, to: &str) -> Result<()> {
+    pub async fn fs_delete(&self, path: &str, args: &OpDelete) -> Result<()> {
+        let p = self.root.join(path.trim_end_matches('/'));
+
+        let _guard = match args.if_unmodified_since() {
+            Some(_) => Some(self.condition_lock.lock().await),
+            None => None,
+        };
+
+        let meta = match tokio::fs::metadata(&p).await {
+            Ok(meta) => meta,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(new_std_io_error(err)),
+        };
+        check_unmodified_since(&meta, args.if_unmodified_since())?;
+
+        if meta.is_dir() {
+            tokio::fs::remove_dir(&p).await.map_err(new_std_io_error)?;
+        } else {
+            tokio::fs::remove_file(&p).await.map_err(new_std_io_error)?;
+        }
+        Ok(())
+    }
+
+    pub async fn fs_copy(&self, from: &str, to: &str, args: &OpCopy) -> Result<()> {
         let from = self.root.join(from.trim_end_matches('/'));
+
+        let _guard = match args.source_if_unmodified_since() {
+            Some(_) => Some(self.condition_lock.lock().await),
+            None => None,
+        };
+
         // try to get the metadata of the source file to ensure it exists
-        tokio::fs::metadata(&from).await.map_err(new_std_io_error)?;
+        let meta = tokio::fs::metadata(&from).await.map_err(new_std_io_error)?;
+        check_unmodified_since(&meta, args.source_if_unmodified_since())?;
 
         let to = self
             .ensure_write_abs_path(&self.root, to.trim_end_matches('/'))
             .await?;
+        if args.if_not_exists() {
+            // Copy into a temp file next to the target first, so that a failed
+            // copy won't leave a truncated target behind. `link` fails if the
+            // target exists, which is atomic across processes.
+            let tmp = to.with_file_name(build_tmp_path_of(&to.to_string_lossy()));
+            let res = match tokio::fs::copy(&from, &tmp).await {
+                Ok(_) => tokio::fs::hard_link(&tmp, &to)
+                    .await
+                    .map_err(|err| new_link_error(err, &to)),
+                Err(err) => Err(new_std_io_error(err)),
+            };
+            if let Err(err) = tokio::fs::remove_file(&tmp).await {
+                if err.kind() != std::io::ErrorKind::NotFound {
+                    warn!("remove temp file {} failed: {err}", tmp.display());
+                }
+            }
+            return res;
+        }
 
         tokio::fs::copy(from, to).await.map_err(new_std_io_error)?;
         Ok(())
     }
 
-    pub async fn fs_rename(&self, from: &str, to: &str) -> Result<()> {
+    pub async fn fs_rename(&self, from: &str, to: &str, args: &OpRename) -> Result<()> {
         let from = self.root.join(from.trim_end_matches('/'));
-        tokio::fs::metadata(&from).await.map_err(new_std_io_error)?;
+
+        let _guard = match args.source_if_unmodified_since() {
+            Some(_) => Some(self.condition_lock.lock().await),
+            None => None,
+        };
+
+        let meta = tokio::fs::metadata(&from).await.map_err(new_std_io_error)?;
+        check_unmodified_since(&meta, args.source_if_unmodified_since())?;
 
         let to = self
             .ensure_write_abs_path(&self.root, to.trim_end_matches('/'))
             .await?;
+        if args.if_not_exists() {
+            // `link` fails if the target exists, which is atomic across processes
+            // while `rename` will replace the target silently.
+            if meta.is_dir() {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "rename with if_not_exists is not supported for dirs",
+                )
+                .with_context("path", from.to_string_lossy()));
+            }
+            tokio::fs::hard_link(&from, &to)
+                .await
+                .map_err(|err| new_link_error(err, &to))?;
+            tokio::fs::remove_file(&from)
+                .await
+                .map_err(new_std_io_error)?;
+            return Ok(());
+        }
 
         tokio::fs::rename(from, to)
             .await
@@ -225,3 +303,39 @@ impl FsCore {
         Ok(())
     }
 }
+
+fn check_unmodified_since(meta: &std::fs::Metadata, since: Option<DateTime<Utc>>) -> Result<()> {
+    let Some(since) = since else {
+        return Ok(());
+    };
+
+    let modified: DateTime<Utc> = meta
+        .modified()
+        .map(DateTime::from)
+        .map_err(new_std_io_error)?;
+    if modified > since {
+        return Err(Error::new(
+            ErrorKind::ConditionNotMatch,
+            "file has been modified since the given time",
+        )
+        .with_context("last_modified", modified.to_rfc3339())
+        .with_context("if_unmodified_since", since.to_rfc3339()));
+    }
+    Ok(())
+}
+
+fn new_link_error(err: std::io::Error, path: &Path) -> Error {
+    match err.kind() {
+        std::io::ErrorKind::AlreadyExists => {
+            Error::new(ErrorKind::ConditionNotMatch, "target already exists")
+                .with_context("path", path.to_string_lossy())
+        }
+        std::io::ErrorKind::Unsupported => Error::new(
+            ErrorKind::Unsupported,
+            "if_not_exists requires hard links which are not supported here",
+        )
+        .with_context("path", path.to_string_lossy())
+        .set_source(err),
+        _ => new_std_io_error(err),
+    }
+}
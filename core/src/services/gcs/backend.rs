@@ -19,6 +19,10 @@ use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
 
+use bytes::Buf;
+use http::header::CONTENT_LENGTH;
+use http::HeaderMap;
+use http::Method;
 use http::Response;
 use http::StatusCode;
 use log::debug;
@@ -302,6 +306,11 @@ impl Builder for GcsBuilder {
         }
 
         let signer = GoogleSigner::new("storage");
+        let service_account = GcsServiceAccount::load(
+            self.config.credential.as_deref(),
+            self.config.credential_path.as_deref(),
+            self.config.disable_config_load,
+        );
 
         let backend = GcsBackend {
             core: Arc::new(GcsCore {
@@ -327,6 +336,7 @@ impl Builder for GcsBuilder {
                             write_with_content_type: true,
                             write_with_content_encoding: true,
                             write_with_user_metadata: true,
+                            write_with_resume: true,
                             write_with_if_not_exists: true,
 
                             // The min multipart size of Gcs is 5 MiB.
@@ -342,19 +352,44 @@ impl Builder for GcsBuilder {
                                 Some(usize::MAX)
                             },
 
+                            stat_many: true,
+                            stat_many_max_size: Some(100),
+
                             delete: true,
                             delete_max_size: Some(100),
+                            delete_with_if_match: true,
+                            delete_with_if_none_match: true,
+                            delete_with_version: true,
                             copy: true,
+                            copy_with_if_not_exists: true,
+                            copy_with_if_match: true,
+                            copy_with_source_if_match: true,
+                            copy_with_source_if_none_match: true,
+                            copy_with_source_version: true,
+
+                            concat: true,
+                            // GCS compose accepts at most 32 source objects.
+                            concat_max_sources: Some(32),
+
+                            update_metadata: true,
 
                             list: true,
                             list_with_limit: true,
                             list_with_start_after: true,
                             list_with_recursive: true,
+                            list_with_versions: true,
+
+                            list_uploads: true,
+
+                            abort_upload: true,
 
                             presign: true,
                             presign_stat: true,
                             presign_read: true,
                             presign_write: true,
+                            presign_multipart: true,
+                            // POST policies can only be signed by service account keys.
+                            presign_post: service_account.is_some(),
 
                             shared: true,
 
@@ -380,6 +415,7 @@ impl Builder for GcsBuilder {
                 predefined_acl: self.config.predefined_acl.clone(),
                 default_storage_class: self.config.default_storage_class.clone(),
                 allow_anonymous: self.config.allow_anonymous,
+                service_account,
             }),
         };
 
@@ -416,6 +452,48 @@ impl Access for GcsBackend {
         Ok(RpStat::new(m))
     }
 
+    async fn stat_many(&self, args: Vec<(String, OpStat)>) -> Result<RpStatMany> {
+        let resp = self.core.gcs_get_objects_metadata(&args).await?;
+
+        // Cloud Storage returns a 200 status code, even if some or all of the sub-requests fail.
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp));
+        }
+
+        let boundary = parse_multipart_boundary(resp.headers())?.ok_or_else(|| {
+            Error::new(
+                ErrorKind::Unexpected,
+                "gcs batch stat response content type is empty",
+            )
+        })?;
+        let multipart: Multipart<MixedPart> = Multipart::new()
+            .with_boundary(boundary)
+            .parse(resp.into_body().to_bytes())?;
+        let parts = multipart.into_parts();
+        if parts.len() != args.len() {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "gcs batch stat response parts count mismatch",
+            )
+            .with_context("expected", args.len().to_string())
+            .with_context("actual", parts.len().to_string()));
+        }
+
+        let results = parts
+            .into_iter()
+            .zip(args)
+            .map(|(part, (path, _))| {
+                let resp = part.into_response();
+                if !resp.status().is_success() {
+                    return Err(parse_error(resp));
+                }
+                GcsCore::build_metadata_from_object_response(&path, resp.into_body())
+            })
+            .collect();
+
+        Ok(RpStatMany::new(results))
+    }
+
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
         let resp = self.core.gcs_get_object(path, args.range(), &args).await?;
 
@@ -435,8 +513,8 @@ impl Access for GcsBackend {
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
         let concurrent = args.concurrent();
-        let w = GcsWriter::new(self.core.clone(), path, args);
-        let w = oio::MultipartWriter::new(self.core.info.clone(), w, concurrent);
+        let w = GcsWriter::new(self.core.clone(), path, args.clone());
+        let w = oio::MultipartWriter::new(self.core.info.clone(), w, concurrent).with_resume(&args);
 
         Ok((RpWrite::default(), w))
     }
@@ -455,13 +533,35 @@ impl Access for GcsBackend {
             args.recursive(),
             args.limit(),
             args.start_after(),
+            args.versions(),
         );
 
         Ok((RpList::default(), oio::PageLister::new(l)))
     }
 
-    async fn copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
-        let resp = self.core.gcs_copy_object(from, to).await?;
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let generation = match GcsCore::etag_precondition(args.if_match(), None) {
+            Some(cond) if !args.if_not_exists() => {
+                Some(self.core.gcs_resolve_generation(to, &cond).await?)
+            }
+            _ => None,
+        };
+        let source_generation =
+            match GcsCore::etag_precondition(args.source_if_match(), args.source_if_none_match()) {
+                Some(cond) => Some(self.core.gcs_resolve_generation(from, &cond).await?),
+                None => None,
+            };
+
+        let resp = self
+            .core
+            .gcs_copy_object(
+                from,
+                to,
+                &args,
+                generation.as_deref(),
+                source_generation.as_deref(),
+            )
+            .await?;
 
         if resp.status().is_success() {
             Ok(RpCopy::default())
@@ -470,6 +570,104 @@ impl Access for GcsBackend {
         }
     }
 
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        // Preconditions of gcs compose are based on the generation of the object, so we
+        // need to resolve the generation that matches the given etag first.
+        let generation = if args.if_not_exists() {
+            Some("0".to_string())
+        } else if let Some(cond) = GcsCore::etag_precondition(args.if_match(), None) {
+            Some(self.core.gcs_resolve_generation(path, &cond).await?)
+        } else {
+            None
+        };
+
+        let resp = self
+            .core
+            .gcs_compose_object(
+                path,
+                args.sources(),
+                args.write_args(),
+                generation.as_deref(),
+            )
+            .await?;
+
+        if resp.status().is_success() {
+            Ok(RpConcat::default())
+        } else {
+            Err(parse_error(resp))
+        }
+    }
+
+    async fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> Result<RpUpdateMetadata> {
+        let resp = self.core.gcs_patch_object(path, &args).await?;
+
+        if resp.status().is_success() {
+            Ok(RpUpdateMetadata::default())
+        } else {
+            Err(parse_error(resp))
+        }
+    }
+
+    async fn list_uploads(&self, path: &str, _: OpListUploads) -> Result<RpListUploads> {
+        let mut uploads = Vec::new();
+        let (mut key_marker, mut upload_id_marker) = (String::new(), String::new());
+
+        loop {
+            let resp = self
+                .core
+                .gcs_list_multipart_uploads(path, &key_marker, &upload_id_marker)
+                .await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp));
+            }
+
+            let output: ListMultipartUploadsResult =
+                quick_xml::de::from_reader(resp.into_body().reader())
+                    .map_err(new_xml_deserialize_error)?;
+
+            for upload in output.upload {
+                let mut u = Upload::new(
+                    &build_rel_path(&self.core.root, &upload.key),
+                    &upload.upload_id,
+                );
+                if let Some(initiated) = upload.initiated {
+                    u = u.with_initiated(parse_datetime_from_rfc3339(&initiated)?);
+                }
+                uploads.push(u);
+            }
+
+            match (
+                output.is_truncated,
+                output.next_key_marker,
+                output.next_upload_id_marker,
+            ) {
+                (Some(true), Some(key), Some(upload_id)) => {
+                    key_marker = key;
+                    upload_id_marker = upload_id;
+                }
+                _ => break,
+            }
+        }
+
+        Ok(RpListUploads::new(uploads))
+    }
+
+    async fn abort_upload(&self, path: &str, args: OpAbortUpload) -> Result<RpAbortUpload> {
+        let resp = self
+            .core
+            .gcs_abort_multipart_upload(path, args.upload_id())
+            .await?;
+        match resp.status() {
+            // gcs returns code 204 if abort succeeds, and 404 if upload not exists.
+            StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => Ok(RpAbortUpload::default()),
+            _ => Err(parse_error(resp)),
+        }
+    }
+
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
         // We will not send this request out, just for signing.
         let req = match args.operation() {
@@ -479,12 +677,39 @@ impl Access for GcsBackend {
                 self.core
                     .gcs_insert_object_xml_request(path, v, Buffer::new())
             }
+            PresignOperation::CreateMultipart(v) => {
+                self.core.gcs_initiate_multipart_upload_request(path, v)
+            }
+            PresignOperation::WritePart(v) => self.core.gcs_upload_part_request(
+                path,
+                v.upload_id(),
+                v.part_number(),
+                0,
+                Buffer::new(),
+            ),
+            PresignOperation::CompleteMultipart(v) => self
+                .core
+                .gcs_complete_multipart_upload_request(path, v.upload_id(), vec![]),
+            PresignOperation::Post(v) => {
+                let fields = self.core.sign_post_policy(path, v, args.expire())?;
+                let uri = format!("{}/{}", self.core.endpoint, self.core.bucket)
+                    .parse()
+                    .map_err(new_http_uri_invalid_error)?;
+                return Ok(RpPresign::new(PresignedRequest::new(
+                    Method::POST,
+                    uri,
+                    HeaderMap::new(),
+                ))
+                .with_form_fields(fields));
+            }
             PresignOperation::Delete(_) => Err(Error::new(
                 ErrorKind::Unsupported,
                 "operation is not supported",
             )),
         };
         let mut req = req?;
+        // Content length is decided by the client who sends the presigned request.
+        req.headers_mut().remove(CONTENT_LENGTH);
         self.core.sign_query(&mut req, args.expire())?;
 
         // We don't need this request anymore, consume it directly.
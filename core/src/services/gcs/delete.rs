@@ -33,11 +33,29 @@ impl GcsDeleter {
     pub fn new(core: Arc<GcsCore>) -> Self {
         Self { core }
     }
+
+    /// Resolve the generation to pin for the etag preconditions of `args`.
+    async fn resolve_generation(&self, path: &str, args: &OpDelete) -> Result<Option<String>> {
+        match GcsCore::etag_precondition(args.if_match(), args.if_none_match()) {
+            Some(cond) => Ok(Some(self.core.gcs_resolve_generation(path, &cond).await?)),
+            None => Ok(None),
+        }
+    }
 }
 
 impl oio::BatchDelete for GcsDeleter {
-    async fn delete_once(&self, path: String, _: OpDelete) -> Result<()> {
-        let resp = self.core.gcs_delete_object(&path).await?;
+    async fn delete_once(&self, path: String, args: OpDelete) -> Result<()> {
+        let generation = match self.resolve_generation(&path, &args).await {
+            Ok(generation) => generation,
+            // deleting not existing objects is ok
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err),
+        };
+
+        let resp = self
+            .core
+            .gcs_delete_object(&path, &args, generation.as_deref())
+            .await?;
 
         // deleting not existing objects is ok
         if resp.status().is_success() || resp.status() == StatusCode::NOT_FOUND {
@@ -48,8 +66,30 @@ impl oio::BatchDelete for GcsDeleter {
     }
 
     async fn delete_batch(&self, batch: Vec<(String, OpDelete)>) -> Result<BatchDeleteResult> {
-        let paths: Vec<String> = batch.into_iter().map(|(p, _)| p).collect();
-        let resp = self.core.gcs_delete_objects(paths.clone()).await?;
+        let mut batched_result = BatchDeleteResult::default();
+
+        // Resolve etag preconditions before sending the batch, objects that fail the
+        // check are reported as failed without being sent.
+        let mut resolved = Vec::with_capacity(batch.len());
+        for (path, args) in batch {
+            match self.resolve_generation(&path, &args).await {
+                Ok(generation) => resolved.push((path, args, generation)),
+                // deleting not existing objects is ok
+                Err(err) if err.kind() == ErrorKind::NotFound => {
+                    batched_result.succeeded.push((path, args))
+                }
+                Err(err) => batched_result.failed.push((path, args, err)),
+            }
+        }
+        if resolved.is_empty() {
+            if batched_result.succeeded.is_empty() {
+                let err = batched_result.failed.remove(0).2;
+                return Err(err);
+            }
+            return Ok(batched_result);
+        }
+
+        let resp = self.core.gcs_delete_objects(&resolved).await?;
 
         let status = resp.status();
 
@@ -70,20 +110,16 @@ impl oio::BatchDelete for GcsDeleter {
             .parse(resp.into_body().to_bytes())?;
         let parts = multipart.into_parts();
 
-        let mut batched_result = BatchDeleteResult::default();
-
         for (i, part) in parts.into_iter().enumerate() {
             let resp = part.into_response();
             // TODO: maybe we can take it directly?
-            let path = paths[i].clone();
+            let (path, args, _) = resolved[i].clone();
 
             // deleting not existing objects is ok
             if resp.status().is_success() || resp.status() == StatusCode::NOT_FOUND {
-                batched_result.succeeded.push((path, OpDelete::default()));
+                batched_result.succeeded.push((path, args));
             } else {
-                batched_result
-                    .failed
-                    .push((path, OpDelete::default(), parse_error(resp)));
+                batched_result.failed.push((path, args, parse_error(resp)));
             }
         }
 
@@ -33,6 +33,7 @@ pub struct GcsLister {
     path: String,
     delimiter: &'static str,
     limit: Option<usize>,
+    versions: bool,
 
     /// Filter results to objects whose names are lexicographically
     /// **equal to or after** startOffset
@@ -47,6 +48,7 @@ impl GcsLister {
         recursive: bool,
         limit: Option<usize>,
         start_after: Option<&str>,
+        versions: bool,
     ) -> Self {
         let delimiter = if recursive { "" } else { "/" };
         Self {
@@ -55,6 +57,7 @@ impl GcsLister {
             path: path.to_string(),
             delimiter,
             limit,
+            versions,
             start_after: start_after.map(String::from),
         }
     }
@@ -74,6 +77,7 @@ impl oio::PageList for GcsLister {
                 } else {
                     None
                 },
+                self.versions,
             )
             .await?;
 
@@ -125,6 +129,10 @@ impl oio::PageList for GcsLister {
             }
 
             meta.set_last_modified(parse_datetime_from_rfc3339(object.updated.as_str())?);
+            if self.versions {
+                meta.set_version(&object.generation);
+                meta.set_is_current(object.time_deleted.is_none());
+            }
 
             let de = oio::Entry::with(path, meta);
 
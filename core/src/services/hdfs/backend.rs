@@ -189,6 +189,9 @@ impl Builder for HdfsBuilder {
                         list: true,
 
                         rename: true,
+                        // Rename of hdfs never overwrites an existing target, so
+                        // it's atomic for `if_not_exists`.
+                        rename_with_if_not_exists: true,
 
                         shared: true,
 
@@ -364,7 +367,7 @@ impl Access for HdfsBackend {
         Ok((RpList::default(), Some(rd)))
     }
 
-    async fn rename(&self, from: &str, to: &str, _args: OpRename) -> Result<RpRename> {
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
         let from_path = build_rooted_abs_path(&self.root, from);
         self.client.metadata(&from_path).map_err(new_std_io_error)?;
 
@@ -392,6 +395,13 @@ impl Access for HdfsBackend {
                     .create_dir(&parent.to_string_lossy())
                     .map_err(new_std_io_error)?;
             }
+            Ok(_) if args.if_not_exists() => {
+                return Err(Error::new(
+                    ErrorKind::ConditionNotMatch,
+                    "target already exists, doesn't match the condition if_not_exists",
+                )
+                .with_context("input", &to_path));
+            }
             Ok(metadata) => {
                 if metadata.is_file() {
                     self.client
@@ -404,9 +414,18 @@ impl Access for HdfsBackend {
             }
         }
 
-        self.client
-            .rename_file(&from_path, &to_path)
-            .map_err(new_std_io_error)?;
+        if let Err(err) = self.client.rename_file(&from_path, &to_path) {
+            // The target is created by others after our check.
+            if args.if_not_exists() && self.client.metadata(&to_path).is_ok() {
+                return Err(Error::new(
+                    ErrorKind::ConditionNotMatch,
+                    "target already exists, doesn't match the condition if_not_exists",
+                )
+                .with_context("input", &to_path)
+                .set_source(err));
+            }
+            return Err(new_std_io_error(err));
+        }
 
         Ok(RpRename::new())
     }
@@ -24,7 +24,9 @@ use http::StatusCode;
 use log::debug;
 
 use super::core::HttpCore;
+use super::core::ListFormat;
 use super::error::parse_error;
+use super::lister::HttpLister;
 use super::DEFAULT_SCHEME;
 use crate::raw::*;
 use crate::services::HttpConfig;
@@ -114,6 +116,24 @@ impl HttpBuilder {
         self
     }
 
+    /// Set the format of directory listing pages served by the server.
+    ///
+    /// Available values are `auto`, `html` and `json`, default to `auto`.
+    ///
+    /// - `auto`: Detect the format from the `Content-Type` of response.
+    /// - `html`: Parse anchors in HTML index pages like nginx/Apache autoindex, caddy browse
+    ///   and python `http.server`.
+    /// - `json`: Parse JSON index output of nginx (`autoindex_format json`) and caddy.
+    pub fn list_format(mut self, format: &str) -> Self {
+        self.config.list_format = if format.is_empty() {
+            None
+        } else {
+            Some(format.to_string())
+        };
+
+        self
+    }
+
     /// Specify the http client that used by this service.
     ///
     /// # Notes
@@ -145,6 +165,11 @@ impl Builder for HttpBuilder {
         let root = normalize_root(&self.config.root.unwrap_or_default());
         debug!("backend use root {root}");
 
+        let list_format = match &self.config.list_format {
+            Some(v) => v.parse::<ListFormat>()?,
+            None => ListFormat::Auto,
+        };
+
         let mut auth = None;
         if let Some(username) = &self.config.username {
             auth = Some(format_authorization_by_basic(
@@ -169,6 +194,8 @@ impl Builder for HttpBuilder {
                 read_with_if_match: true,
                 read_with_if_none_match: true,
 
+                list: true,
+
                 presign: auth.is_none(),
                 presign_read: auth.is_none(),
                 presign_stat: auth.is_none(),
@@ -191,6 +218,7 @@ impl Builder for HttpBuilder {
             endpoint: endpoint.to_string(),
             root,
             authorization: auth,
+            list_format,
         });
 
         Ok(HttpBackend { core })
@@ -214,7 +242,7 @@ impl Debug for HttpBackend {
 impl Access for HttpBackend {
     type Reader = HttpBody;
     type Writer = ();
-    type Lister = ();
+    type Lister = oio::PageLister<HttpLister>;
     type Deleter = ();
 
     fn info(&self) -> Arc<AccessorInfo> {
@@ -259,6 +287,11 @@ impl Access for HttpBackend {
         }
     }
 
+    async fn list(&self, path: &str, _: OpList) -> Result<(RpList, Self::Lister)> {
+        let l = HttpLister::new(self.core.clone(), path);
+        Ok((RpList::default(), oio::PageLister::new(l)))
+    }
+
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
         if self.core.has_authorization() {
             return Err(Error::new(
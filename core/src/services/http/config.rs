@@ -36,6 +36,13 @@ pub struct HttpConfig {
     pub token: Option<String>,
     /// root of this backend
     pub root: Option<String>,
+    /// The format of directory listing pages served by the server.
+    ///
+    /// - `auto`: Detect the format from the response, this is the default.
+    /// - `html`: Parse anchors in HTML index pages like nginx/Apache autoindex, caddy browse
+    ///   and python `http.server`.
+    /// - `json`: Parse JSON index output of nginx (`autoindex_format json`) and caddy.
+    pub list_format: Option<String>,
 }
 
 impl Debug for HttpConfig {
@@ -43,6 +50,7 @@ impl Debug for HttpConfig {
         let mut de = f.debug_struct("HttpConfig");
         de.field("endpoint", &self.endpoint);
         de.field("root", &self.root);
+        de.field("list_format", &self.list_format);
 
         de.finish_non_exhaustive()
     }
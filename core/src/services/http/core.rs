@@ -17,9 +17,11 @@
 
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use http::header;
+use http::header::ACCEPT;
 use http::header::IF_MATCH;
 use http::header::IF_NONE_MATCH;
 use http::Request;
@@ -35,6 +37,7 @@ pub struct HttpCore {
     pub root: String,
 
     pub authorization: Option<String>,
+    pub list_format: ListFormat,
 }
 
 impl Debug for HttpCore {
@@ -42,6 +45,7 @@ impl Debug for HttpCore {
         f.debug_struct("HttpCore")
             .field("endpoint", &self.endpoint)
             .field("root", &self.root)
+            .field("list_format", &self.list_format)
             .finish()
     }
 }
@@ -122,4 +126,62 @@ impl HttpCore {
         let req = self.http_head_request(path, args)?;
         self.info.http_client().send(req).await
     }
+
+    pub async fn http_list(&self, path: &str) -> Result<Response<Buffer>> {
+        let p = build_rooted_abs_path(&self.root, path);
+
+        let url = format!("{}{}", self.endpoint, percent_encode_path(&p));
+
+        let mut req = Request::get(&url);
+
+        // Servers like caddy will return JSON output if it's accepted.
+        req = req.header(
+            ACCEPT,
+            match self.list_format {
+                ListFormat::Auto => "application/json, text/html;q=0.9, */*;q=0.8",
+                ListFormat::Html => "text/html",
+                ListFormat::Json => "application/json",
+            },
+        );
+
+        if let Some(auth) = &self.authorization {
+            req = req.header(header::AUTHORIZATION, auth.clone())
+        }
+
+        let req = req
+            .extension(Operation::List)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.info.http_client().send(req).await
+    }
+}
+
+/// The format of directory listing pages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ListFormat {
+    /// Detect the format by the `Content-Type` of response.
+    #[default]
+    Auto,
+    /// HTML index pages.
+    Html,
+    /// JSON index output of nginx and caddy.
+    Json,
+}
+
+impl FromStr for ListFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "auto" => Ok(ListFormat::Auto),
+            "html" => Ok(ListFormat::Html),
+            "json" => Ok(ListFormat::Json),
+            _ => Err(
+                Error::new(ErrorKind::ConfigInvalid, "list_format is invalid")
+                    .with_context("service", Scheme::Http)
+                    .with_context("list_format", s),
+            ),
+        }
+    }
 }
@@ -0,0 +1,640 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use bytes::Buf;
+use chrono::NaiveDateTime;
+use http::header::CONTENT_TYPE;
+use http::StatusCode;
+use serde::Deserialize;
+
+use super::core::HttpCore;
+use super::core::ListFormat;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+/// Date formats used by autoindex pages.
+///
+/// - nginx: `18-Oct-2026 10:20`
+/// - Apache: `2026-10-18 10:20` or `18-Oct-2026 10:20`
+const HTML_DATE_FORMATS: [&str; 3] = ["%d-%b-%Y %H:%M", "%Y-%m-%d %H:%M", "%Y-%m-%d %H:%M:%S"];
+
+pub struct HttpLister {
+    core: Arc<HttpCore>,
+
+    path: String,
+}
+
+impl HttpLister {
+    pub fn new(core: Arc<HttpCore>, path: &str) -> Self {
+        Self {
+            core,
+            path: path.to_string(),
+        }
+    }
+}
+
+impl oio::PageList for HttpLister {
+    async fn next_page(&self, ctx: &mut oio::PageContext) -> Result<()> {
+        let resp = self.core.http_list(&self.path).await?;
+
+        match resp.status() {
+            StatusCode::OK => {}
+            StatusCode::NOT_FOUND if self.path.ends_with('/') => {
+                ctx.done = true;
+                return Ok(());
+            }
+            _ => return Err(parse_error(resp)),
+        }
+
+        let format = match self.core.list_format {
+            ListFormat::Auto => {
+                let is_json = resp
+                    .headers()
+                    .get(CONTENT_TYPE)
+                    .and_then(|v| v.to_str().ok())
+                    .is_some_and(|v| v.contains("json"));
+                if is_json {
+                    ListFormat::Json
+                } else {
+                    ListFormat::Html
+                }
+            }
+            v => v,
+        };
+
+        let bs = resp.into_body();
+        let entries = match format {
+            ListFormat::Json => parse_json(bs)?,
+            _ => {
+                let base = build_rooted_abs_path(&self.core.root, &self.path);
+                parse_html(&base, &String::from_utf8_lossy(&bs.to_bytes()))
+            }
+        };
+
+        ctx.entries
+            .push_back(oio::Entry::new(&self.path, Metadata::new(EntryMode::DIR)));
+        for (name, meta) in entries {
+            let path = if self.path == "/" {
+                name
+            } else {
+                format!("{}{name}", self.path)
+            };
+            ctx.entries.push_back(oio::Entry::new(&path, meta));
+        }
+        ctx.done = true;
+
+        Ok(())
+    }
+}
+
+/// Entry of JSON index output.
+///
+/// nginx and caddy share the same shape except the field names:
+///
+/// - nginx: `{"name":"a.txt", "type":"file", "mtime":"Sun, 18 Oct 2026 10:20:00 GMT", "size":12}`
+/// - caddy: `{"name":"a.txt", "is_dir":false, "mod_time":"2026-10-18T10:20:00Z", "size":12}`
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct JsonEntry {
+    name: String,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    is_dir: Option<bool>,
+    size: Option<u64>,
+    mtime: Option<String>,
+    mod_time: Option<String>,
+}
+
+/// Parse JSON index output into entry names and metadata.
+///
+/// Names of dirs will end with `/`.
+fn parse_json(bs: Buffer) -> Result<Vec<(String, Metadata)>> {
+    let output: Vec<JsonEntry> =
+        serde_json::from_reader(bs.reader()).map_err(new_json_deserialize_error)?;
+
+    let mut entries = Vec::with_capacity(output.len());
+    for entry in output {
+        let name = entry.name.trim_end_matches('/');
+        if name.is_empty() || name == "." || name == ".." {
+            continue;
+        }
+
+        let is_dir = entry.is_dir.unwrap_or_default()
+            || entry.kind.as_deref() == Some("directory")
+            || entry.name.ends_with('/');
+        if is_dir {
+            entries.push((format!("{name}/"), Metadata::new(EntryMode::DIR)));
+            continue;
+        }
+
+        let mut meta = Metadata::new(EntryMode::FILE);
+        if let Some(size) = entry.size {
+            meta.set_content_length(size);
+        }
+        // Servers may render mtime in other formats, skip it instead of failing the page.
+        let mtime = entry
+            .mod_time
+            .as_deref()
+            .and_then(|v| parse_datetime_from_rfc3339(v).ok())
+            .or_else(|| {
+                entry
+                    .mtime
+                    .as_deref()
+                    .and_then(|v| parse_datetime_from_rfc2822(v).ok())
+            });
+        if let Some(v) = mtime {
+            meta.set_last_modified(v);
+        }
+        entries.push((name.to_string(), meta));
+    }
+    Ok(entries)
+}
+
+/// Parse HTML index pages into entry names and metadata.
+///
+/// Every anchor that links to a direct child of `base` is treated as an entry, links to
+/// parents, other sites or sort options are ignored. Size and mtime are extracted from
+/// the text following the anchor on the same line which is how nginx and Apache render
+/// their index pages. Names of dirs will end with `/`.
+fn parse_html(base: &str, html: &str) -> Vec<(String, Metadata)> {
+    // Tags are case-insensitive, ascii lowercase keeps the byte offsets.
+    let lower = html.to_ascii_lowercase();
+
+    let mut seen = HashSet::new();
+    let mut entries = Vec::new();
+    let mut pos = 0;
+    while let Some(start) = lower[pos..].find("<a ").map(|v| v + pos) {
+        let Some(tag_end) = lower[start..].find('>').map(|v| v + start) else {
+            break;
+        };
+        pos = tag_end;
+
+        let Some(href) = parse_href(&html[start..tag_end]) else {
+            continue;
+        };
+        let Some(name) = resolve_href(base, &href) else {
+            continue;
+        };
+
+        let anchor_end = lower[tag_end..]
+            .find("</a>")
+            .map(|v| v + tag_end + 4)
+            .unwrap_or(tag_end);
+        let line_end = ["<a ", "\n", "</tr>"]
+            .iter()
+            .filter_map(|p| lower[anchor_end..].find(p))
+            .min()
+            .map(|v| v + anchor_end)
+            .unwrap_or(html.len());
+        pos = anchor_end;
+
+        if !seen.insert(name.clone()) {
+            continue;
+        }
+
+        if name.ends_with('/') {
+            entries.push((name, Metadata::new(EntryMode::DIR)));
+            continue;
+        }
+        let mut meta = Metadata::new(EntryMode::FILE);
+        parse_html_details(&strip_tags(&html[anchor_end..line_end]), &mut meta);
+        entries.push((name, meta));
+    }
+    entries
+}
+
+/// Extract the value of `href` attribute from an anchor tag.
+fn parse_href(tag: &str) -> Option<String> {
+    let idx = tag.to_ascii_lowercase().find("href=")?;
+    let value = &tag[idx + 5..];
+
+    let value = match value.chars().next()? {
+        quote @ ('"' | '\'') => value[1..].split(quote).next()?,
+        _ => value
+            .split(|c: char| c.is_whitespace() || c == '>')
+            .next()?,
+    };
+    Some(unescape_html(value))
+}
+
+/// Resolve href into the name of a direct child of `base`.
+fn resolve_href(base: &str, href: &str) -> Option<String> {
+    if href.starts_with('?') || href.starts_with('#') {
+        return None;
+    }
+    // Skip links with scheme like `https://` and `mailto:`.
+    if let Some(idx) = href.find(':') {
+        if !href[..idx].contains('/') {
+            return None;
+        }
+    }
+    // Decode before comparing since `base` is not encoded.
+    let href = percent_decode_path(href.split(['?', '#']).next()?);
+
+    let name = if href.starts_with('/') {
+        href.strip_prefix(base)?
+    } else {
+        href.strip_prefix("./").unwrap_or(&href)
+    };
+
+    let trimmed = name.strip_suffix('/').unwrap_or(name);
+    if trimmed.is_empty() || trimmed == "." || trimmed == ".." || trimmed.contains('/') {
+        return None;
+    }
+    Some(name.to_string())
+}
+
+/// Parse mtime and size from the text after an anchor like `18-Oct-2026 10:20   12`.
+fn parse_html_details(text: &str, meta: &mut Metadata) {
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+
+    for idx in 0..tokens.len().saturating_sub(1) {
+        let s = format!("{} {}", tokens[idx], tokens[idx + 1]);
+        let Some(mtime) = HTML_DATE_FORMATS
+            .iter()
+            .find_map(|f| NaiveDateTime::parse_from_str(&s, f).ok())
+        else {
+            continue;
+        };
+        meta.set_last_modified(mtime.and_utc());
+
+        // Size could be rendered in human-readable format like `1.2K`, only exact
+        // sizes will be used.
+        if let Some(size) = tokens.get(idx + 2).and_then(|v| v.parse::<u64>().ok()) {
+            meta.set_content_length(size);
+        }
+        return;
+    }
+}
+
+/// Remove all tags in given html.
+fn strip_tags(html: &str) -> String {
+    let mut s = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => {
+                in_tag = false;
+                s.push(' ');
+            }
+            _ if !in_tag => s.push(c),
+            _ => {}
+        }
+    }
+    unescape_html(&s)
+}
+
+/// Unescape html entities that could appear in index pages.
+fn unescape_html(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+
+    s.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+    use chrono::Utc;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn summary(entries: Vec<(String, Metadata)>) -> Vec<(String, Option<u64>, Option<String>)> {
+        entries
+            .into_iter()
+            .map(|(name, meta)| {
+                let size = meta.is_file().then(|| meta.content_length());
+                let mtime = meta.last_modified().map(|v| v.to_rfc3339());
+                (name, size, mtime)
+            })
+            .collect()
+    }
+
+    fn mtime(s: &str) -> Option<String> {
+        Some(s.parse::<DateTime<Utc>>().unwrap().to_rfc3339())
+    }
+
+    #[test]
+    fn test_parse_nginx_html() {
+        let html = r#"<html>
+<head><title>Index of /data/</title></head>
+<body>
+<h1>Index of /data/</h1><hr><pre><a href="../">../</a>
+<a href="normal_dir/">normal_dir/</a>                                        18-Oct-2026 10:20                   -
+<a href="special_dir%20%20!@%23$%25%5E&amp;()_+-=;',/">special_dir  !@#$%^&amp;()_+-=;',/</a>                  18-Oct-2026 10:20                   -
+<a href="normal_file.txt">normal_file.txt</a>                                    18-Oct-2026 10:21                  12
+<a href="a-very-long-file-name-that-will-be-truncated.txt">a-very-long-file-name-that-will-be-trunc..&gt;</a> 18-Oct-2026 10:22                 3
+</pre><hr></body>
+</html>"#;
+
+        assert_eq!(
+            summary(parse_html("/data/", html)),
+            vec![
+                ("normal_dir/".to_string(), None, None),
+                ("special_dir  !@#$%^&()_+-=;',/".to_string(), None, None),
+                (
+                    "normal_file.txt".to_string(),
+                    Some(12),
+                    mtime("2026-10-18T10:21:00Z")
+                ),
+                (
+                    "a-very-long-file-name-that-will-be-truncated.txt".to_string(),
+                    Some(3),
+                    mtime("2026-10-18T10:22:00Z")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_apache_html() {
+        let html = r#"<!DOCTYPE HTML PUBLIC "-//W3C//DTD HTML 3.2 Final//EN">
+<html>
+ <head>
+  <title>Index of /data</title>
+ </head>
+ <body>
+<h1>Index of /data</h1>
+  <table>
+   <tr><th valign="top"><img src="/icons/blank.gif" alt="[ICO]"></th><th><a href="?C=N;O=D">Name</a></th><th><a href="?C=M;O=A">Last modified</a></th><th><a href="?C=S;O=A">Size</a></th><th><a href="?C=D;O=A">Description</a></th></tr>
+   <tr><th colspan="5"><hr></th></tr>
+<tr><td valign="top"><img src="/icons/back.gif" alt="[PARENTDIR]"></td><td><a href="/">Parent Directory</a></td><td>&nbsp;</td><td align="right">  - </td><td>&nbsp;</td></tr>
+<tr><td valign="top"><img src="/icons/folder.gif" alt="[DIR]"></td><td><a href="normal_dir/">normal_dir/</a></td><td align="right">2026-10-18 10:20  </td><td align="right">  - </td><td>&nbsp;</td></tr>
+<tr><td valign="top"><img src="/icons/text.gif" alt="[TXT]"></td><td><a href="normal_file.txt">normal_file.txt</a></td><td align="right">2026-10-18 10:21  </td><td align="right"> 12 </td><td>&nbsp;</td></tr>
+<tr><td valign="top"><img src="/icons/unknown.gif" alt="[   ]"></td><td><a href="large.bin">large.bin</a></td><td align="right">2026-10-18 10:22  </td><td align="right">1.2M</td><td>&nbsp;</td></tr>
+   <tr><th colspan="5"><hr></th></tr>
+</table>
+</body></html>"#;
+
+        assert_eq!(
+            summary(parse_html("/data/", html)),
+            vec![
+                ("normal_dir/".to_string(), None, None),
+                (
+                    "normal_file.txt".to_string(),
+                    Some(12),
+                    mtime("2026-10-18T10:21:00Z")
+                ),
+                (
+                    "large.bin".to_string(),
+                    Some(0),
+                    mtime("2026-10-18T10:22:00Z")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_python_html() {
+        let html = r#"<!DOCTYPE HTML>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Directory listing for /</title>
+</head>
+<body>
+<h1>Directory listing for /</h1>
+<hr>
+<ul>
+<li><a href="normal_dir/">normal_dir/</a></li>
+<li><a href="normal_file.txt">normal_file.txt</a></li>
+<li><a href="special_file%20%20%21%40%23%24%25%5E%26%28%29_%2B-%3D%3B%27%2C.txt">special_file  !@#$%^&amp;()_+-=;&#x27;,.txt</a></li>
+</ul>
+<hr>
+</body>
+</html>"#;
+
+        assert_eq!(
+            summary(parse_html("/", html)),
+            vec![
+                ("normal_dir/".to_string(), None, None),
+                ("normal_file.txt".to_string(), Some(0), None),
+                (
+                    "special_file  !@#$%^&()_+-=;',.txt".to_string(),
+                    Some(0),
+                    None
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_html_with_absolute_href() {
+        let html = r#"<a href="/data/">Up</a>
+<a href="/data/sub/file.txt">file.txt</a>
+<a HREF='/data/sub/dir/'>dir/</A>
+<a href="https://example.com/">example</a>
+<a href="./file.txt">file.txt</a>"#;
+
+        assert_eq!(
+            summary(parse_html("/data/sub/", html)),
+            vec![
+                ("file.txt".to_string(), Some(0), None),
+                ("dir/".to_string(), None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_html_with_encoded_absolute_href() {
+        let html = r#"<a href="/my%20data/">Up</a>
+<a href="/my%20data/sub/normal%20file.txt">normal file.txt</a>
+<a href="/my%20data/sub/special%20%21%23/">special !#/</a>"#;
+
+        assert_eq!(
+            summary(parse_html("/my data/sub/", html)),
+            vec![
+                ("normal file.txt".to_string(), Some(0), None),
+                ("special !#/".to_string(), None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_nginx_json() {
+        let bs = Buffer::from(
+            r#"[
+{ "name":"normal_dir", "type":"directory", "mtime":"Sun, 18 Oct 2026 10:20:00 GMT" },
+{ "name":"normal_file.txt", "type":"file", "mtime":"Sun, 18 Oct 2026 10:21:00 GMT", "size":12 }
+]"#,
+        );
+
+        assert_eq!(
+            summary(parse_json(bs).unwrap()),
+            vec![
+                ("normal_dir/".to_string(), None, None),
+                (
+                    "normal_file.txt".to_string(),
+                    Some(12),
+                    mtime("2026-10-18T10:21:00Z")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_json_with_invalid_mtime() {
+        let bs = Buffer::from(
+            r#"[
+{ "name":"normal_file.txt", "type":"file", "mtime":"2026/10/18 10:21", "size":12 },
+{ "name":"other_file.txt", "type":"file", "mtime":"Sun, 18 Oct 2026 10:22:00 GMT", "size":3 }
+]"#,
+        );
+
+        assert_eq!(
+            summary(parse_json(bs).unwrap()),
+            vec![
+                ("normal_file.txt".to_string(), Some(12), None),
+                (
+                    "other_file.txt".to_string(),
+                    Some(3),
+                    mtime("2026-10-18T10:22:00Z")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_caddy_json() {
+        let bs = Buffer::from(
+            r#"[
+{"name":"normal_dir/","size":4096,"url":"./normal_dir/","mod_time":"2026-10-18T10:20:00.123456789+08:00","mode":2147484141,"is_dir":true,"is_symlink":false},
+{"name":"normal_file.txt","size":12,"url":"./normal_file.txt","mod_time":"2026-10-18T10:21:00Z","mode":420,"is_dir":false,"is_symlink":false}
+]"#,
+        );
+
+        assert_eq!(
+            summary(parse_json(bs).unwrap()),
+            vec![
+                ("normal_dir/".to_string(), None, None),
+                (
+                    "normal_file.txt".to_string(),
+                    Some(12),
+                    mtime("2026-10-18T10:21:00Z")
+                ),
+            ]
+        );
+    }
+
+    /// Serve autoindex pages like nginx does on a local port, responding `404` for
+    /// pages that are not in `pages`.
+    fn serve(pages: Vec<(&'static str, &'static str, &'static str)>) -> String {
+        use std::io::BufRead;
+        use std::io::BufReader;
+        use std::io::Write;
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let path = line
+                    .split_whitespace()
+                    .nth(1)
+                    .unwrap_or_default()
+                    .to_string();
+                // Drain headers of the request.
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+
+                let resp = match pages.iter().find(|(p, _, _)| *p == path) {
+                    Some((_, content_type, body)) => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    ),
+                    None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+                };
+                let _ = stream.write_all(resp.as_bytes());
+            }
+        });
+        endpoint
+    }
+
+    #[tokio::test]
+    async fn test_list_served_autoindex() -> Result<()> {
+        let endpoint = serve(vec![
+            (
+                "/my%20data/",
+                "text/html",
+                r#"<html>
+<head><title>Index of /my data/</title></head>
+<body>
+<h1>Index of /my data/</h1><hr><pre><a href="../">../</a>
+<a href="/my%20data/normal%20dir/">normal dir/</a>                                  18-Oct-2026 10:20                   -
+<a href="/my%20data/normal%20file.txt">normal file.txt</a>                         18-Oct-2026 10:21                  12
+</pre><hr></body>
+</html>"#,
+            ),
+            (
+                "/my%20data/normal%20dir/",
+                "application/json",
+                r#"[{"name":"nested.txt","size":3,"mod_time":"not a time","is_dir":false}]"#,
+            ),
+        ]);
+
+        let op = Operator::new(
+            crate::services::Http::default()
+                .endpoint(&endpoint)
+                .root("/my data/"),
+        )?
+        .finish();
+
+        let mut entries: Vec<_> = op
+            .list_with("")
+            .recursive(true)
+            .await?
+            .into_iter()
+            .map(|e| {
+                let meta = e.metadata();
+                let size = meta.is_file().then(|| meta.content_length());
+                (e.path().to_string(), size)
+            })
+            .collect();
+        entries.sort();
+        assert_eq!(
+            entries,
+            vec![
+                ("/".to_string(), None),
+                ("normal dir/".to_string(), None),
+                ("normal dir/nested.txt".to_string(), Some(3)),
+                ("normal file.txt".to_string(), Some(12)),
+            ]
+        );
+
+        // Missing dirs are listed as empty.
+        assert!(op.list("missing/").await?.is_empty());
+        Ok(())
+    }
+}
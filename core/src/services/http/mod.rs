@@ -26,6 +26,8 @@ mod backend;
 #[cfg(feature = "services-http")]
 mod core;
 #[cfg(feature = "services-http")]
+mod lister;
+#[cfg(feature = "services-http")]
 pub use backend::HttpBuilder as Http;
 
 mod config;
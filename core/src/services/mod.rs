@@ -157,6 +157,9 @@ pub use self::redb::*;
 mod redis;
 pub use self::redis::*;
 
+mod replay;
+pub use self::replay::*;
+
 mod rocksdb;
 pub use self::rocksdb::*;
 
@@ -20,7 +20,12 @@ use std::fmt::Formatter;
 use std::sync::Arc;
 use std::time::Duration;
 
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
 use bytes::Bytes;
+use chrono::Utc;
+use hmac::Hmac;
+use hmac::Mac;
 use http::header::CACHE_CONTROL;
 use http::header::CONTENT_DISPOSITION;
 use http::header::CONTENT_LENGTH;
@@ -34,6 +39,7 @@ use reqsign::HuaweicloudObsCredentialLoader;
 use reqsign::HuaweicloudObsSigner;
 use serde::Deserialize;
 use serde::Serialize;
+use sha1::Sha1;
 
 use crate::raw::*;
 use crate::*;
@@ -100,6 +106,48 @@ impl ObsCore {
             .map_err(new_request_sign_error)
     }
 
+    /// Build the form fields of a presigned POST upload.
+    ///
+    /// Reference: <https://support.huaweicloud.com/intl/en-us/api-obs/obs_04_0012.html>
+    pub async fn sign_post_policy(
+        &self,
+        path: &str,
+        args: &OpPresignPost,
+        expire: Duration,
+    ) -> Result<Vec<(String, String)>> {
+        let key = build_abs_path(&self.root, path);
+        let mut fields = vec![("key".to_string(), key.clone())];
+        if let Some(v) = args.content_type() {
+            fields.push(("Content-Type".to_string(), v.to_string()));
+        }
+
+        let Some(cred) = self.load_credential().await? else {
+            return Ok(fields);
+        };
+
+        let mut policy = PostPolicy::new(Utc::now(), expire);
+        policy.push_eq("bucket", &self.bucket);
+        policy.push_eq("key", &key);
+        if let Some(token) = &cred.security_token {
+            policy.push_eq("x-obs-security-token", token);
+        }
+        policy.push_conditions(args);
+        let policy = policy.encode();
+
+        let mut mac = Hmac::<Sha1>::new_from_slice(cred.secret_access_key.as_bytes())
+            .expect("HMAC can take key of any size");
+        mac.update(policy.as_bytes());
+        let signature = BASE64_STANDARD.encode(mac.finalize().into_bytes());
+
+        fields.push(("AccessKeyId".to_string(), cred.access_key_id));
+        if let Some(token) = cred.security_token {
+            fields.push(("x-obs-security-token".to_string(), token));
+        }
+        fields.push(("policy".to_string(), policy));
+        fields.push(("signature".to_string(), signature));
+        Ok(fields)
+    }
+
     #[inline]
     pub async fn send(&self, req: Request<Buffer>) -> Result<Response<Buffer>> {
         self.info.http_client().send(req).await
@@ -337,6 +385,18 @@ impl ObsCore {
         path: &str,
         content_type: Option<&str>,
     ) -> Result<Response<Buffer>> {
+        let mut req = self.obs_initiate_multipart_upload_request(path, content_type)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
+    pub fn obs_initiate_multipart_upload_request(
+        &self,
+        path: &str,
+        content_type: Option<&str>,
+    ) -> Result<Request<Buffer>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!("{}/{}?uploads", self.endpoint, percent_encode_path(&p));
@@ -346,23 +406,37 @@ impl ObsCore {
             req = req.header(CONTENT_TYPE, mime)
         }
 
-        let mut req = req
+        let req = req
             .extension(Operation::Write)
             .body(Buffer::new())
             .map_err(new_request_build_error)?;
 
+        Ok(req)
+    }
+
+    pub async fn obs_upload_part(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number: usize,
+        size: Option<u64>,
+        body: Buffer,
+    ) -> Result<Response<Buffer>> {
+        let mut req = self.obs_upload_part_request(path, upload_id, part_number, size, body)?;
+
         self.sign(&mut req).await?;
 
         self.send(req).await
     }
-    pub async fn obs_upload_part_request(
+
+    pub fn obs_upload_part_request(
         &self,
         path: &str,
         upload_id: &str,
         part_number: usize,
         size: Option<u64>,
         body: Buffer,
-    ) -> Result<Response<Buffer>> {
+    ) -> Result<Request<Buffer>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!(
@@ -379,15 +453,13 @@ impl ObsCore {
             req = req.header(CONTENT_LENGTH, size);
         }
 
-        let mut req = req
+        let req = req
             .extension(Operation::Write)
             // Set body
             .body(body)
             .map_err(new_request_build_error)?;
 
-        self.sign(&mut req).await?;
-
-        self.send(req).await
+        Ok(req)
     }
 
     pub async fn obs_complete_multipart_upload(
@@ -396,6 +468,18 @@ impl ObsCore {
         upload_id: &str,
         parts: Vec<CompleteMultipartUploadRequestPart>,
     ) -> Result<Response<Buffer>> {
+        let mut req = self.obs_complete_multipart_upload_request(path, upload_id, parts)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    pub fn obs_complete_multipart_upload_request(
+        &self,
+        path: &str,
+        upload_id: &str,
+        parts: Vec<CompleteMultipartUploadRequestPart>,
+    ) -> Result<Request<Buffer>> {
         let p = build_abs_path(&self.root, path);
         let url = format!(
             "{}/{}?uploadId={}",
@@ -415,16 +499,43 @@ impl ObsCore {
         // Set content-type to `application/xml` to avoid mixed with form post.
         let req = req.header(CONTENT_TYPE, "application/xml");
 
-        let mut req = req
+        let req = req
             .extension(Operation::Write)
             .body(Buffer::from(Bytes::from(content)))
             .map_err(new_request_build_error)?;
 
+        Ok(req)
+    }
+
+    /// Abort an on-going multipart upload.
+    pub async fn obs_list_multipart_uploads(
+        &self,
+        path: &str,
+        key_marker: &str,
+        upload_id_marker: &str,
+    ) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = QueryPairsWriter::new(&self.endpoint).push("uploads", "");
+
+        if !p.is_empty() {
+            url = url.push("prefix", &percent_encode_path(&p));
+        }
+        if !key_marker.is_empty() {
+            url = url.push("key-marker", &percent_encode_path(key_marker));
+        }
+        if !upload_id_marker.is_empty() {
+            url = url.push("upload-id-marker", &percent_encode_path(upload_id_marker));
+        }
+
+        let mut req = Request::get(url.finish())
+            .extension(Operation::ListUploads)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
         self.sign(&mut req).await?;
         self.send(req).await
     }
 
-    /// Abort an on-going multipart upload.
     pub async fn obs_abort_multipart_upload(
         &self,
         path: &str,
@@ -449,6 +560,24 @@ impl ObsCore {
     }
 }
 
+/// Output of ListMultipartUploads.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct ListMultipartUploadsResult {
+    pub is_truncated: Option<bool>,
+    pub next_key_marker: Option<String>,
+    pub next_upload_id_marker: Option<String>,
+    pub upload: Vec<ListMultipartUploadsResultUpload>,
+}
+
+#[derive(Default, Debug, Eq, PartialEq, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct ListMultipartUploadsResultUpload {
+    pub key: String,
+    pub upload_id: String,
+    pub initiated: Option<String>,
+}
+
 /// Result of CreateMultipartUpload
 #[derive(Default, Debug, Deserialize)]
 #[serde(default, rename_all = "PascalCase")]
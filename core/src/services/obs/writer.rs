@@ -116,7 +116,7 @@ impl oio::MultipartWrite for ObsWriter {
 
         let resp = self
             .core
-            .obs_upload_part_request(&self.path, upload_id, part_number, Some(size), body)
+            .obs_upload_part(&self.path, upload_id, part_number, Some(size), body)
             .await?;
 
         let status = resp.status();
@@ -15,10 +15,15 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
 
+use bytes::Buf;
+use http::header::CONTENT_LENGTH;
+use http::HeaderMap;
+use http::Method;
 use http::Response;
 use http::StatusCode;
 use http::Uri;
@@ -27,6 +32,7 @@ use reqsign::AliyunConfig;
 use reqsign::AliyunLoader;
 use reqsign::AliyunOssSigner;
 
+use super::concat::OssConcatenator;
 use super::core::*;
 use super::delete::OssDeleter;
 use super::error::parse_error;
@@ -40,6 +46,8 @@ use crate::raw::*;
 use crate::services::OssConfig;
 use crate::*;
 const DEFAULT_BATCH_MAX_OPERATIONS: usize = 1000;
+/// The max size of objects that could be copied by a single CopyObject.
+const MAX_COPY_OBJECT_SIZE: u64 = 1024 * 1024 * 1024;
 
 impl Configurator for OssConfig {
     type Builder = OssBuilder;
@@ -466,12 +474,21 @@ impl Builder for OssBuilder {
                                 Some(usize::MAX)
                             },
                             write_with_user_metadata: true,
+                            write_with_resume: true,
 
                             delete: true,
                             delete_with_version: self.config.enable_versioning,
                             delete_max_size: Some(delete_max_size),
 
                             copy: true,
+                            copy_with_source_version: self.config.enable_versioning,
+
+                            concat: true,
+                            // OSS allows at most 10000 parts in one multipart upload.
+                            concat_max_sources: Some(10000),
+
+                            update_metadata: true,
+                            update_metadata_with_tags: true,
 
                             list: true,
                             list_with_limit: true,
@@ -480,10 +497,16 @@ impl Builder for OssBuilder {
                             list_with_versions: self.config.enable_versioning,
                             list_with_deleted: self.config.enable_versioning,
 
+                            list_uploads: true,
+
+                            abort_upload: true,
+
                             presign: true,
                             presign_stat: true,
                             presign_read: true,
                             presign_write: true,
+                            presign_post: true,
+                            presign_multipart: true,
 
                             shared: true,
 
@@ -572,11 +595,10 @@ impl Access for OssBackend {
         let w = if args.append() {
             OssWriters::Two(oio::AppendWriter::new(writer))
         } else {
-            OssWriters::One(oio::MultipartWriter::new(
-                self.core.info.clone(),
-                writer,
-                args.concurrent(),
-            ))
+            OssWriters::One(
+                oio::MultipartWriter::new(self.core.info.clone(), writer, args.concurrent())
+                    .with_resume(&args),
+            )
         };
 
         Ok((RpWrite::default(), w))
@@ -609,8 +631,8 @@ impl Access for OssBackend {
         Ok((RpList::default(), l))
     }
 
-    async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
-        let resp = self.core.oss_copy_object(from, to).await?;
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let resp = self.core.oss_copy_object(from, to, &args).await?;
         let status = resp.status();
 
         match status {
@@ -619,6 +641,112 @@ impl Access for OssBackend {
         }
     }
 
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        let op = args.write_args().clone();
+        OssConcatenator::concat(self.core.clone(), path, args, &op).await
+    }
+
+    async fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> Result<RpUpdateMetadata> {
+        if args.has_metadata_changes() {
+            let meta = self.stat(path, OpStat::new()).await?.into_metadata();
+            let op = args.apply(&meta);
+
+            if meta.content_length() <= MAX_COPY_OBJECT_SIZE {
+                let resp = self
+                    .core
+                    .oss_replace_object_metadata(path, &op, meta.etag())
+                    .await?;
+                if resp.status() != StatusCode::OK {
+                    return Err(parse_error(resp));
+                }
+            } else {
+                // Unlike CopyObject, multipart upload doesn't keep the tags.
+                let tags = match args.tags() {
+                    Some(_) => None,
+                    None => Some(self.get_tags(path).await?),
+                };
+
+                // CopyObject only supports objects up to 1GiB, copy larger ones part by part.
+                // OSS doesn't support if_match on complete, so the etag is not checked here.
+                OssConcatenator::concat(
+                    self.core.clone(),
+                    path,
+                    OpConcat::new(vec![path.to_string()]),
+                    &op,
+                )
+                .await?;
+                if let Some(tags) = tags.filter(|v| !v.is_empty()) {
+                    self.put_tags(path, &tags).await?;
+                }
+            }
+        }
+
+        if let Some(tags) = args.tags() {
+            self.put_tags(path, tags).await?;
+        }
+        Ok(RpUpdateMetadata::default())
+    }
+
+    async fn list_uploads(&self, path: &str, _: OpListUploads) -> Result<RpListUploads> {
+        let mut uploads = Vec::new();
+        let (mut key_marker, mut upload_id_marker) = (String::new(), String::new());
+
+        loop {
+            let resp = self
+                .core
+                .oss_list_multipart_uploads(path, &key_marker, &upload_id_marker)
+                .await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp));
+            }
+
+            let output: ListMultipartUploadsResult =
+                quick_xml::de::from_reader(resp.into_body().reader())
+                    .map_err(new_xml_deserialize_error)?;
+
+            for upload in output.upload {
+                let mut u = Upload::new(
+                    &build_rel_path(&self.core.root, &upload.key),
+                    &upload.upload_id,
+                );
+                if let Some(initiated) = upload.initiated {
+                    u = u.with_initiated(parse_datetime_from_rfc3339(&initiated)?);
+                }
+                uploads.push(u);
+            }
+
+            match (
+                output.is_truncated,
+                output.next_key_marker,
+                output.next_upload_id_marker,
+            ) {
+                (Some(true), Some(key), Some(upload_id)) => {
+                    key_marker = key;
+                    upload_id_marker = upload_id;
+                }
+                _ => break,
+            }
+        }
+
+        Ok(RpListUploads::new(uploads))
+    }
+
+    async fn abort_upload(&self, path: &str, args: OpAbortUpload) -> Result<RpAbortUpload> {
+        let resp = self
+            .core
+            .oss_abort_multipart_upload(path, args.upload_id())
+            .await?;
+        match resp.status() {
+            // oss returns code 204 if abort succeeds, and 404 if upload not exists.
+            StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => Ok(RpAbortUpload::default()),
+            _ => Err(parse_error(resp)),
+        }
+    }
+
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
         // We will not send this request out, just for signing.
         let req = match args.operation() {
@@ -628,12 +756,44 @@ impl Access for OssBackend {
                 self.core
                     .oss_put_object_request(path, None, v, Buffer::new(), true)
             }
+            PresignOperation::Post(v) => {
+                let fields = self.core.sign_post_policy(path, v, args.expire()).await?;
+                let uri = format!("{}/", self.core.presign_endpoint)
+                    .parse()
+                    .map_err(new_http_uri_invalid_error)?;
+                return Ok(RpPresign::new(PresignedRequest::new(
+                    Method::POST,
+                    uri,
+                    HeaderMap::new(),
+                ))
+                .with_form_fields(fields));
+            }
+            PresignOperation::CreateMultipart(v) => self.core.oss_initiate_upload_request(
+                path,
+                v.content_type(),
+                v.content_disposition(),
+                v.cache_control(),
+                true,
+            ),
+            PresignOperation::WritePart(v) => self.core.oss_upload_part_request(
+                path,
+                v.upload_id(),
+                v.part_number(),
+                true,
+                0,
+                Buffer::new(),
+            ),
+            PresignOperation::CompleteMultipart(v) => self
+                .core
+                .oss_complete_multipart_upload_request(path, v.upload_id(), true, vec![]),
             PresignOperation::Delete(_) => Err(Error::new(
                 ErrorKind::Unsupported,
                 "operation is not supported",
             )),
         };
         let mut req = req?;
+        // Content length is decided by the client who sends the presigned request.
+        req.headers_mut().remove(CONTENT_LENGTH);
 
         self.core.sign_query(&mut req, args.expire()).await?;
 
@@ -647,3 +807,29 @@ impl Access for OssBackend {
         )))
     }
 }
+
+impl OssBackend {
+    async fn get_tags(&self, path: &str) -> Result<HashMap<String, String>> {
+        let resp = self.core.oss_get_object_tagging(path).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp));
+        }
+
+        let output: Tagging = quick_xml::de::from_reader(resp.into_body().reader())
+            .map_err(new_xml_deserialize_error)?;
+        Ok(output
+            .tag_set
+            .tag
+            .into_iter()
+            .map(|v| (v.key, v.value))
+            .collect())
+    }
+
+    async fn put_tags(&self, path: &str, tags: &HashMap<String, String>) -> Result<()> {
+        let resp = self.core.oss_put_object_tagging(path, tags).await?;
+        match resp.status() {
+            StatusCode::OK => Ok(()),
+            _ => Err(parse_error(resp)),
+        }
+    }
+}
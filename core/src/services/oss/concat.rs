@@ -0,0 +1,213 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::sync::Arc;
+
+use bytes::Buf;
+use http::HeaderValue;
+use http::Response;
+use http::StatusCode;
+
+use super::core::constants::X_OSS_FORBID_OVERWRITE;
+use super::core::*;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+/// The min size of every part except the last one.
+const MIN_PART_SIZE: u64 = 100 * 1024;
+/// The max size of a part copied by `UploadPartCopy`.
+const MAX_COPY_PART_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+
+/// OssConcatenator concatenates objects into a new object via multipart upload.
+///
+/// Ranges of sources are copied on the server side by `UploadPartCopy`. Ranges that are
+/// smaller than the min part size are downloaded and merged into their neighbours
+/// before uploading.
+pub struct OssConcatenator {
+    core: Arc<OssCore>,
+    path: String,
+    upload_id: String,
+
+    parts: Vec<MultipartUploadPart>,
+}
+
+impl OssConcatenator {
+    /// Concat sources into `path`, the new object will be created with the metadata in `op`.
+    pub async fn concat(
+        core: Arc<OssCore>,
+        path: &str,
+        args: OpConcat,
+        op: &OpWrite,
+    ) -> Result<RpConcat> {
+        if args.if_match().is_some() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "oss doesn't support concat with if_match",
+            ));
+        }
+
+        let mut sources = Vec::with_capacity(args.sources().len());
+        for source in args.sources() {
+            let size = stat_size(&core, source).await?;
+            sources.push((source.clone(), size));
+        }
+        let plan = plan_concat_parts(&sources, MIN_PART_SIZE, MAX_COPY_PART_SIZE);
+
+        let resp = core.oss_initiate_upload_with_metadata(path, op).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp));
+        }
+        let result: InitiateMultipartUploadResult =
+            quick_xml::de::from_reader(resp.into_body().reader())
+                .map_err(new_xml_deserialize_error)?;
+
+        let mut c = OssConcatenator {
+            core: core.clone(),
+            path: path.to_string(),
+            upload_id: result.upload_id,
+            parts: vec![],
+        };
+
+        match c.run(plan, &args).await {
+            Ok(()) => Ok(RpConcat::default()),
+            Err(err) => {
+                // Abort the upload as best effort, the error of concat matters more.
+                let _ = core.oss_abort_multipart_upload(path, &c.upload_id).await;
+                Err(err)
+            }
+        }
+    }
+
+    async fn run(&mut self, plan: Vec<ConcatPart>, args: &OpConcat) -> Result<()> {
+        // Multipart upload requires at least one part.
+        if plan.is_empty() {
+            self.upload_part(Buffer::new()).await?;
+        }
+
+        for part in plan {
+            match part {
+                ConcatPart::Copy(source, range) => self.copy_part(&source, range).await?,
+                ConcatPart::Upload(ranges) => {
+                    let mut bufs = Vec::with_capacity(ranges.len());
+                    for (source, range) in ranges {
+                        bufs.push(self.read_range(&source, range).await?);
+                    }
+                    self.upload_part(bufs.into_iter().flatten().collect())
+                        .await?;
+                }
+            }
+        }
+
+        self.complete(args).await
+    }
+
+    async fn read_range(&self, path: &str, range: BytesRange) -> Result<Buffer> {
+        let resp = self
+            .core
+            .oss_get_object(path, &OpRead::new().with_range(range))
+            .await?;
+
+        let (part, mut body) = resp.into_parts();
+        let buf = body.to_buffer().await?;
+        match part.status {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => Ok(buf),
+            _ => Err(parse_error(Response::from_parts(part, buf))),
+        }
+    }
+
+    async fn upload_part(&mut self, body: Buffer) -> Result<()> {
+        let part_number = self.parts.len() + 1;
+        let resp = self
+            .core
+            .oss_upload_part(
+                &self.path,
+                &self.upload_id,
+                part_number,
+                false,
+                body.len() as u64,
+                body,
+            )
+            .await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp));
+        }
+
+        let etag = parse_etag(resp.headers())?
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::Unexpected,
+                    "ETag not present in returning response",
+                )
+            })?
+            .to_string();
+        self.parts.push(MultipartUploadPart { part_number, etag });
+        Ok(())
+    }
+
+    async fn copy_part(&mut self, source: &str, range: BytesRange) -> Result<()> {
+        let part_number = self.parts.len() + 1;
+        let resp = self
+            .core
+            .oss_upload_part_copy(&self.path, &self.upload_id, part_number, source, range)
+            .await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp));
+        }
+
+        let result: CopyPartResult = quick_xml::de::from_reader(resp.into_body().reader())
+            .map_err(new_xml_deserialize_error)?;
+        self.parts.push(MultipartUploadPart {
+            part_number,
+            etag: result.etag,
+        });
+        Ok(())
+    }
+
+    async fn complete(&mut self, args: &OpConcat) -> Result<()> {
+        let mut req = self.core.oss_complete_multipart_upload_request(
+            &self.path,
+            &self.upload_id,
+            false,
+            std::mem::take(&mut self.parts),
+        )?;
+        if args.if_not_exists() {
+            req.headers_mut()
+                .insert(X_OSS_FORBID_OVERWRITE, HeaderValue::from_static("true"));
+        }
+        req.extensions_mut().insert(Operation::Concat);
+
+        self.core.sign(&mut req).await?;
+        let resp = self.core.send(req).await?;
+        match resp.status() {
+            StatusCode::OK => Ok(()),
+            _ => Err(parse_error(resp)),
+        }
+    }
+}
+
+async fn stat_size(core: &OssCore, path: &str) -> Result<u64> {
+    let resp = core.oss_head_object(path, &OpStat::new()).await?;
+    if resp.status() != StatusCode::OK {
+        return Err(parse_error(resp));
+    }
+    parse_content_length(resp.headers())?.ok_or_else(|| {
+        Error::new(
+            ErrorKind::Unexpected,
+            "content length not present in returning response",
+        )
+    })
+}
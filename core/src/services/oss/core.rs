@@ -15,13 +15,19 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
 use std::time::Duration;
 
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
 use bytes::Bytes;
+use chrono::Utc;
 use constants::X_OSS_META_PREFIX;
+use hmac::Hmac;
+use hmac::Mac;
 use http::header::CACHE_CONTROL;
 use http::header::CONTENT_DISPOSITION;
 use http::header::CONTENT_LENGTH;
@@ -41,6 +47,7 @@ use reqsign::AliyunLoader;
 use reqsign::AliyunOssSigner;
 use serde::Deserialize;
 use serde::Serialize;
+use sha1::Sha1;
 
 use crate::raw::*;
 use crate::services::oss::core::constants::X_OSS_FORBID_OVERWRITE;
@@ -53,6 +60,14 @@ pub mod constants {
 
     pub const X_OSS_FORBID_OVERWRITE: &str = "x-oss-forbid-overwrite";
 
+    pub const X_OSS_COPY_SOURCE: &str = "x-oss-copy-source";
+
+    pub const X_OSS_COPY_SOURCE_RANGE: &str = "x-oss-copy-source-range";
+
+    pub const X_OSS_COPY_SOURCE_IF_MATCH: &str = "x-oss-copy-source-if-match";
+
+    pub const X_OSS_METADATA_DIRECTIVE: &str = "x-oss-metadata-directive";
+
     pub const X_OSS_VERSION_ID: &str = "x-oss-version-id";
 
     pub const RESPONSE_CONTENT_DISPOSITION: &str = "response-content-disposition";
@@ -138,6 +153,48 @@ impl OssCore {
             .map_err(new_request_sign_error)
     }
 
+    /// Build the form fields of a presigned POST upload.
+    ///
+    /// Reference: <https://www.alibabacloud.com/help/en/oss/developer-reference/postobject>
+    pub async fn sign_post_policy(
+        &self,
+        path: &str,
+        args: &OpPresignPost,
+        expire: Duration,
+    ) -> Result<Vec<(String, String)>> {
+        let key = build_abs_path(&self.root, path);
+        let mut fields = vec![("key".to_string(), key.clone())];
+        if let Some(v) = args.content_type() {
+            fields.push(("Content-Type".to_string(), v.to_string()));
+        }
+
+        let Some(cred) = self.load_credential().await? else {
+            return Ok(fields);
+        };
+
+        let mut policy = PostPolicy::new(Utc::now(), expire);
+        policy.push_eq("bucket", &self.bucket);
+        policy.push_eq("key", &key);
+        if let Some(token) = &cred.security_token {
+            policy.push_eq("x-oss-security-token", token);
+        }
+        policy.push_conditions(args);
+        let policy = policy.encode();
+
+        let mut mac = Hmac::<Sha1>::new_from_slice(cred.access_key_secret.as_bytes())
+            .expect("HMAC can take key of any size");
+        mac.update(policy.as_bytes());
+        let signature = BASE64_STANDARD.encode(mac.finalize().into_bytes());
+
+        fields.push(("OSSAccessKeyId".to_string(), cred.access_key_id));
+        if let Some(token) = cred.security_token {
+            fields.push(("x-oss-security-token".to_string(), token));
+        }
+        fields.push(("policy".to_string(), policy));
+        fields.push(("Signature".to_string(), signature));
+        Ok(fields)
+    }
+
     #[inline]
     pub async fn send(&self, req: Request<Buffer>) -> Result<Response<Buffer>> {
         self.info.http_client().send(req).await
@@ -495,7 +552,12 @@ impl OssCore {
         self.send(req).await
     }
 
-    pub async fn oss_copy_object(&self, from: &str, to: &str) -> Result<Response<Buffer>> {
+    pub async fn oss_copy_object(
+        &self,
+        from: &str,
+        to: &str,
+        args: &OpCopy,
+    ) -> Result<Response<Buffer>> {
         let source = build_abs_path(&self.root, from);
         let target = build_abs_path(&self.root, to);
 
@@ -504,7 +566,10 @@ impl OssCore {
             self.get_endpoint(false),
             percent_encode_path(&target)
         );
-        let source = format!("/{}/{}", self.bucket, percent_encode_path(&source));
+        let mut source = format!("/{}/{}", self.bucket, percent_encode_path(&source));
+        if let Some(version) = args.source_version() {
+            source.push_str(&format!("?versionId={}", percent_encode_path(version)));
+        }
 
         let mut req = Request::put(&url);
 
@@ -520,6 +585,127 @@ impl OssCore {
         self.send(req).await
     }
 
+    /// Replace the metadata of the object by copying it onto itself.
+    ///
+    /// CopyObject only supports objects up to 1GiB.
+    pub async fn oss_replace_object_metadata(
+        &self,
+        path: &str,
+        args: &OpWrite,
+        source_if_match: Option<&str>,
+    ) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}", self.get_endpoint(false), percent_encode_path(&p));
+
+        let mut req = Request::put(&url);
+        req = self.insert_metadata_headers(req, None, args)?;
+        req = self.insert_sse_headers(req);
+        req = req
+            .header(
+                constants::X_OSS_COPY_SOURCE,
+                format!("/{}/{}", self.bucket, percent_encode_path(&p)),
+            )
+            .header(constants::X_OSS_METADATA_DIRECTIVE, "REPLACE");
+        if let Some(v) = source_if_match {
+            req = req.header(constants::X_OSS_COPY_SOURCE_IF_MATCH, v);
+        }
+
+        let mut req = req
+            .extension(Operation::UpdateMetadata)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    pub async fn oss_put_object_tagging(
+        &self,
+        path: &str,
+        tags: &HashMap<String, String>,
+    ) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+        let url = format!(
+            "{}/{}?tagging",
+            self.get_endpoint(false),
+            percent_encode_path(&p)
+        );
+
+        let mut tag: Vec<_> = tags
+            .iter()
+            .map(|(key, value)| Tag {
+                key: key.clone(),
+                value: value.clone(),
+            })
+            .collect();
+        tag.sort_by(|a, b| a.key.cmp(&b.key));
+        let content = quick_xml::se::to_string(&Tagging {
+            tag_set: TagSet { tag },
+        })
+        .map_err(new_xml_serialize_error)?;
+
+        let mut req = Request::put(&url)
+            .header(CONTENT_LENGTH, content.len())
+            .header(CONTENT_TYPE, "application/xml")
+            .extension(Operation::UpdateMetadata)
+            .body(Buffer::from(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    pub async fn oss_get_object_tagging(&self, path: &str) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+        let url = format!(
+            "{}/{}?tagging",
+            self.get_endpoint(false),
+            percent_encode_path(&p)
+        );
+
+        let mut req = Request::get(&url)
+            .extension(Operation::UpdateMetadata)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// Initiate a multipart upload with the metadata in args.
+    pub async fn oss_initiate_upload_with_metadata(
+        &self,
+        path: &str,
+        args: &OpWrite,
+    ) -> Result<Response<Buffer>> {
+        let mut req = self.oss_initiate_upload_request(
+            path,
+            args.content_type(),
+            args.content_disposition(),
+            args.cache_control(),
+            false,
+        )?;
+        if let Some(user_metadata) = args.user_metadata() {
+            for (key, value) in user_metadata {
+                if !self.check_user_metadata_key(key) {
+                    return Err(Error::new(
+                        ErrorKind::Unsupported,
+                        "the format of the user metadata key is invalid, please refer the document",
+                    ));
+                }
+                let name = HeaderName::from_bytes(format!("{X_OSS_META_PREFIX}{key}").as_bytes())
+                    .map_err(|err| new_request_build_error(err.into()))?;
+                let value = HeaderValue::from_str(value)
+                    .map_err(|err| new_request_build_error(err.into()))?;
+                req.headers_mut().insert(name, value);
+            }
+        }
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
     pub async fn oss_list_object(
         &self,
         path: &str,
@@ -633,6 +819,25 @@ impl OssCore {
         cache_control: Option<&str>,
         is_presign: bool,
     ) -> Result<Response<Buffer>> {
+        let mut req = self.oss_initiate_upload_request(
+            path,
+            content_type,
+            content_disposition,
+            cache_control,
+            is_presign,
+        )?;
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    pub fn oss_initiate_upload_request(
+        &self,
+        path: &str,
+        content_type: Option<&str>,
+        content_disposition: Option<&str>,
+        cache_control: Option<&str>,
+        is_presign: bool,
+    ) -> Result<Request<Buffer>> {
         let path = build_abs_path(&self.root, path);
         let endpoint = self.get_endpoint(is_presign);
         let url = format!("{}/{}?uploads", endpoint, percent_encode_path(&path));
@@ -650,13 +855,28 @@ impl OssCore {
 
         let req = req.extension(Operation::Write);
 
-        let mut req = req.body(Buffer::new()).map_err(new_request_build_error)?;
+        let req = req.body(Buffer::new()).map_err(new_request_build_error)?;
+        Ok(req)
+    }
+
+    /// Upload a part
+    pub async fn oss_upload_part(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number: usize,
+        is_presign: bool,
+        size: u64,
+        body: Buffer,
+    ) -> Result<Response<Buffer>> {
+        let mut req =
+            self.oss_upload_part_request(path, upload_id, part_number, is_presign, size, body)?;
         self.sign(&mut req).await?;
         self.send(req).await
     }
 
     /// Creates a request to upload a part
-    pub async fn oss_upload_part_request(
+    pub fn oss_upload_part_request(
         &self,
         path: &str,
         upload_id: &str,
@@ -664,7 +884,7 @@ impl OssCore {
         is_presign: bool,
         size: u64,
         body: Buffer,
-    ) -> Result<Response<Buffer>> {
+    ) -> Result<Request<Buffer>> {
         let p = build_abs_path(&self.root, path);
         let endpoint = self.get_endpoint(is_presign);
 
@@ -681,18 +901,64 @@ impl OssCore {
 
         let req = req.extension(Operation::Write);
 
-        let mut req = req.body(body).map_err(new_request_build_error)?;
+        let req = req.body(body).map_err(new_request_build_error)?;
+        Ok(req)
+    }
+
+    /// Upload a part by copying a range of an existing object.
+    pub async fn oss_upload_part_copy(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number: usize,
+        source: &str,
+        range: BytesRange,
+    ) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+        let source = build_abs_path(&self.root, source);
+
+        let url = format!(
+            "{}/{}?partNumber={}&uploadId={}",
+            self.endpoint,
+            percent_encode_path(&p),
+            part_number,
+            percent_encode_path(upload_id)
+        );
+
+        let mut req = Request::put(&url)
+            .header(
+                constants::X_OSS_COPY_SOURCE,
+                format!("/{}/{}", self.bucket, percent_encode_path(&source)),
+            )
+            .header(constants::X_OSS_COPY_SOURCE_RANGE, range.to_header())
+            .extension(Operation::Concat)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
         self.sign(&mut req).await?;
         self.send(req).await
     }
 
-    pub async fn oss_complete_multipart_upload_request(
+    pub async fn oss_complete_multipart_upload(
         &self,
         path: &str,
         upload_id: &str,
         is_presign: bool,
         parts: Vec<MultipartUploadPart>,
     ) -> Result<Response<Buffer>> {
+        let mut req =
+            self.oss_complete_multipart_upload_request(path, upload_id, is_presign, parts)?;
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    pub fn oss_complete_multipart_upload_request(
+        &self,
+        path: &str,
+        upload_id: &str,
+        is_presign: bool,
+        parts: Vec<MultipartUploadPart>,
+    ) -> Result<Request<Buffer>> {
         let p = build_abs_path(&self.root, path);
         let endpoint = self.get_endpoint(is_presign);
         let url = format!(
@@ -715,16 +981,43 @@ impl OssCore {
 
         let req = req.extension(Operation::Write);
 
-        let mut req = req
+        let req = req
             .body(Buffer::from(Bytes::from(content)))
             .map_err(new_request_build_error)?;
 
-        self.sign(&mut req).await?;
-        self.send(req).await
+        Ok(req)
     }
 
     /// Abort an ongoing multipart upload.
     /// reference docs https://www.alibabacloud.com/help/zh/oss/developer-reference/abortmultipartupload
+    pub async fn oss_list_multipart_uploads(
+        &self,
+        path: &str,
+        key_marker: &str,
+        upload_id_marker: &str,
+    ) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = QueryPairsWriter::new(&self.endpoint).push("uploads", "");
+
+        if !p.is_empty() {
+            url = url.push("prefix", &percent_encode_path(&p));
+        }
+        if !key_marker.is_empty() {
+            url = url.push("key-marker", &percent_encode_path(key_marker));
+        }
+        if !upload_id_marker.is_empty() {
+            url = url.push("upload-id-marker", &percent_encode_path(upload_id_marker));
+        }
+
+        let mut req = Request::get(url.finish())
+            .extension(Operation::ListUploads)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
     pub async fn oss_abort_multipart_upload(
         &self,
         path: &str,
@@ -748,6 +1041,26 @@ impl OssCore {
     }
 }
 
+/// Request of PutObjectTagging and result of GetObjectTagging.
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename = "Tagging", rename_all = "PascalCase")]
+pub struct Tagging {
+    pub tag_set: TagSet,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct TagSet {
+    pub tag: Vec<Tag>,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct Tag {
+    pub key: String,
+    pub value: String,
+}
+
 /// Request of DeleteObjects.
 #[derive(Default, Debug, Serialize)]
 #[serde(default, rename = "Delete", rename_all = "PascalCase")]
@@ -785,6 +1098,24 @@ pub struct DeleteObjectsResultError {
     pub message: String,
 }
 
+/// Output of ListMultipartUploads.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct ListMultipartUploadsResult {
+    pub is_truncated: Option<bool>,
+    pub next_key_marker: Option<String>,
+    pub next_upload_id_marker: Option<String>,
+    pub upload: Vec<ListMultipartUploadsResultUpload>,
+}
+
+#[derive(Default, Debug, Eq, PartialEq, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct ListMultipartUploadsResultUpload {
+    pub key: String,
+    pub upload_id: String,
+    pub initiated: Option<String>,
+}
+
 #[derive(Default, Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct InitiateMultipartUploadResult {
@@ -795,6 +1126,14 @@ pub struct InitiateMultipartUploadResult {
     pub upload_id: String,
 }
 
+/// Result of UploadPartCopy
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct CopyPartResult {
+    #[serde(rename = "ETag")]
+    pub etag: String,
+}
+
 #[derive(Clone, Default, Debug, Serialize)]
 #[serde(default, rename_all = "PascalCase")]
 pub struct MultipartUploadPart {
@@ -19,6 +19,8 @@
 #[cfg(feature = "services-oss")]
 pub(super) const DEFAULT_SCHEME: &str = "oss";
 #[cfg(feature = "services-oss")]
+mod concat;
+#[cfg(feature = "services-oss")]
 mod core;
 #[cfg(feature = "services-oss")]
 mod delete;
@@ -119,7 +119,7 @@ impl oio::MultipartWrite for OssWriter {
 
         let resp = self
             .core
-            .oss_upload_part_request(&self.path, upload_id, part_number, false, size, body)
+            .oss_upload_part(&self.path, upload_id, part_number, false, size, body)
             .await?;
 
         let status = resp.status();
@@ -160,7 +160,7 @@ impl oio::MultipartWrite for OssWriter {
 
         let resp = self
             .core
-            .oss_complete_multipart_upload_request(&self.path, upload_id, false, parts)
+            .oss_complete_multipart_upload(&self.path, upload_id, false, parts)
             .await?;
 
         let meta = Self::parse_metadata(resp.headers())?;
@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::str::FromStr;
@@ -195,6 +196,8 @@ impl kv::Adapter for Adapter {
             Capability {
                 read: true,
                 write: true,
+                stat_many: true,
+                stat_many_max_size: Some(1000),
                 shared: true,
                 ..Default::default()
             },
@@ -216,6 +219,25 @@ impl kv::Adapter for Adapter {
         Ok(value.map(Buffer::from))
     }
 
+    async fn size_many(&self, paths: &[String]) -> Result<Vec<Option<u64>>> {
+        let pool = self.get_client().await?;
+
+        let rows: Vec<(String, i64)> = sqlx::query_as(&format!(
+            r#"SELECT "{}", octet_length("{}")::BIGINT FROM "{}" WHERE "{}" = ANY($1)"#,
+            self.key_field, self.value_field, self.table, self.key_field
+        ))
+        .bind(paths)
+        .fetch_all(pool)
+        .await
+        .map_err(parse_postgres_error)?;
+
+        let sizes: HashMap<_, _> = rows.into_iter().collect();
+        Ok(paths
+            .iter()
+            .map(|p| sizes.get(p).map(|v| *v as u64))
+            .collect())
+    }
+
     async fn set(&self, path: &str, value: Buffer) -> Result<()> {
         let pool = self.get_client().await?;
 
@@ -286,6 +286,7 @@ impl RedisAccessor {
             delete: true,
             stat: true,
             write_can_empty: true,
+            lock: true,
             shared: true,
             ..Default::default()
         });
@@ -369,6 +370,12 @@ impl Access for RedisAccessor {
         ))
     }
 
+    async fn lock(&self, path: &str, args: OpLock) -> Result<RpLock> {
+        let p = build_abs_path(&self.root, path);
+        self.core.lock(&p, &args).await?;
+        Ok(RpLock::default())
+    }
+
     async fn list(&self, path: &str, _: OpList) -> Result<(RpList, Self::Lister)> {
         let _ = build_abs_path(&self.root, path);
         // Redis doesn't support listing keys, return empty list
@@ -31,9 +31,11 @@ use redis::Cmd;
 use redis::Pipeline;
 use redis::RedisError;
 use redis::RedisFuture;
+use redis::Script;
 use redis::Value;
 use tokio::sync::OnceCell;
 
+use crate::raw::*;
 use crate::*;
 
 #[derive(Clone)]
@@ -200,8 +202,74 @@ impl RedisCore {
         let _: () = conn.del(key).await.map_err(format_redis_error)?;
         Ok(())
     }
+
+    /// Lock the key with `SET NX PX` and check the token via lua scripts
+    /// while renewing or releasing, so that only the holder can touch it.
+    pub async fn lock(&self, key: &str, args: &OpLock) -> Result<()> {
+        let mut conn = self.conn().await?;
+        let ttl = args.ttl().as_millis().max(1) as u64;
+
+        let held: bool = match args.action() {
+            LockAction::Acquire => {
+                let res: Option<String> = redis::cmd("SET")
+                    .arg(key)
+                    .arg(args.token())
+                    .arg("NX")
+                    .arg("PX")
+                    .arg(ttl)
+                    .query_async(&mut *conn)
+                    .await
+                    .map_err(format_redis_error)?;
+                res.is_some()
+            }
+            LockAction::Renew => {
+                let res: i64 = Script::new(REDIS_LOCK_RENEW_SCRIPT)
+                    .key(key)
+                    .arg(args.token())
+                    .arg(ttl)
+                    .invoke_async(&mut *conn)
+                    .await
+                    .map_err(format_redis_error)?;
+                res == 1
+            }
+            LockAction::Release => {
+                let res: i64 = Script::new(REDIS_LOCK_RELEASE_SCRIPT)
+                    .key(key)
+                    .arg(args.token())
+                    .invoke_async(&mut *conn)
+                    .await
+                    .map_err(format_redis_error)?;
+                res == 1
+            }
+        };
+
+        if held {
+            Ok(())
+        } else {
+            Err(Error::new(
+                ErrorKind::ConditionNotMatch,
+                "lock is held by others or has expired",
+            ))
+        }
+    }
 }
 
+const REDIS_LOCK_RENEW_SCRIPT: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+    return redis.call("PEXPIRE", KEYS[1], ARGV[2])
+else
+    return 0
+end
+"#;
+
+const REDIS_LOCK_RELEASE_SCRIPT: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+    return redis.call("DEL", KEYS[1])
+else
+    return 0
+end
+"#;
+
 pub fn format_redis_error(e: RedisError) -> Error {
     Error::new(ErrorKind::Unexpected, e.category())
         .set_source(e)
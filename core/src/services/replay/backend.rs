@@ -0,0 +1,315 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::path::Path;
+use std::sync::Arc;
+
+use super::core::*;
+use super::delete::ReplayDeleter;
+use super::lister::ReplayLister;
+use super::reader::ReplayReader;
+use super::writer::ReplayWriter;
+use super::DEFAULT_SCHEME;
+use crate::layers::abort_upload_args;
+use crate::layers::concat_args;
+use crate::layers::copy_args;
+use crate::layers::list_args;
+use crate::layers::load_records;
+use crate::layers::lock_args;
+use crate::layers::read_args;
+use crate::layers::rename_args;
+use crate::layers::stat_args;
+use crate::layers::update_metadata_args;
+use crate::layers::write_args;
+use crate::raw::*;
+use crate::services::ReplayConfig;
+use crate::*;
+
+impl Configurator for ReplayConfig {
+    type Builder = ReplayBuilder;
+    fn into_builder(self) -> Self::Builder {
+        ReplayBuilder { config: self }
+    }
+}
+
+/// Replay service support which serves the recording of `RecordLayer`.
+#[doc = include_str!("docs.md")]
+#[derive(Default, Debug)]
+pub struct ReplayBuilder {
+    config: ReplayConfig,
+}
+
+impl ReplayBuilder {
+    /// Set the path of the recording.
+    pub fn path(mut self, path: &str) -> Self {
+        if !path.is_empty() {
+            self.config.path = Some(path.to_string());
+        }
+        self
+    }
+}
+
+impl Builder for ReplayBuilder {
+    type Config = ReplayConfig;
+
+    fn build(self) -> Result<impl Access> {
+        let path = match &self.config.path {
+            Some(path) => path,
+            None => {
+                return Err(Error::new(ErrorKind::ConfigInvalid, "path is empty")
+                    .with_operation("Builder::build")
+                    .with_context("service", Scheme::Replay))
+            }
+        };
+
+        let records = load_records(Path::new(path)).map_err(|err| {
+            err.with_operation("Builder::build")
+                .with_context("service", Scheme::Replay)
+        })?;
+
+        Ok(ReplayBackend::new(ReplayCore::new(records), path))
+    }
+}
+
+/// ReplayBackend serves the recorded calls.
+#[derive(Debug, Clone)]
+pub struct ReplayBackend {
+    core: Arc<ReplayCore>,
+    info: Arc<AccessorInfo>,
+}
+
+impl ReplayBackend {
+    fn new(core: ReplayCore, path: &str) -> Self {
+        let info = AccessorInfo::default();
+        info.set_scheme(DEFAULT_SCHEME);
+        info.set_name(path);
+        info.set_root("/");
+        // Replay claims all capabilities that could be served from a
+        // recording, calls that are not recorded will be rejected later.
+        //
+        // `lock` and `concat` are only claimed if they are recorded, since
+        // operator emulates them with other calls otherwise.
+        info.set_native_capability(Capability {
+            stat: true,
+            stat_with_if_match: true,
+            stat_with_if_none_match: true,
+            stat_with_if_modified_since: true,
+            stat_with_if_unmodified_since: true,
+            stat_with_version: true,
+
+            read: true,
+            read_with_if_match: true,
+            read_with_if_none_match: true,
+            read_with_if_modified_since: true,
+            read_with_if_unmodified_since: true,
+            read_with_version: true,
+
+            write: true,
+            write_can_multi: true,
+            write_can_empty: true,
+            write_can_append: true,
+            write_with_content_type: true,
+            write_with_content_disposition: true,
+            write_with_content_encoding: true,
+            write_with_cache_control: true,
+            write_with_if_match: true,
+            write_with_if_none_match: true,
+            write_with_if_not_exists: true,
+            write_with_user_metadata: true,
+
+            create_dir: true,
+            delete: true,
+            delete_with_version: true,
+            copy: true,
+            copy_with_if_not_exists: true,
+            rename: true,
+
+            list: true,
+            list_with_limit: true,
+            list_with_start_after: true,
+            list_with_recursive: true,
+            list_with_versions: true,
+            list_with_deleted: true,
+
+            list_uploads: true,
+            abort_upload: true,
+            lock: core.contains(Operation::Lock),
+            concat: core.contains(Operation::Concat),
+            update_metadata: true,
+            update_metadata_with_tags: true,
+
+            shared: true,
+            ..Default::default()
+        });
+
+        Self {
+            core: Arc::new(core),
+            info: Arc::new(info),
+        }
+    }
+}
+
+impl Access for ReplayBackend {
+    type Reader = ReplayReader;
+    type Writer = ReplayWriter;
+    type Lister = ReplayLister;
+    type Deleter = ReplayDeleter;
+
+    fn info(&self) -> Arc<AccessorInfo> {
+        self.info.clone()
+    }
+
+    async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        self.core
+            .take_ok(Operation::CreateDir, path, BTreeMap::new())?;
+        Ok(RpCreateDir::default())
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let record = self.core.take_ok(Operation::Stat, path, stat_args(&args))?;
+        let meta = parse_record_metadata(&record.metadata.unwrap_or_default())?;
+        Ok(RpStat::new(meta))
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let record = self.core.take(Operation::Read, path, read_args(&args))?;
+        let err = record.error.as_ref().map(parse_record_error);
+        let reader = match (record.payload, err) {
+            (Some(payload), err) => ReplayReader::new(parse_record_payload(&payload)?, err),
+            (None, Some(err)) => return Err(err),
+            (None, None) => ReplayReader::new(Buffer::new(), None),
+        };
+        Ok((RpRead::new(), reader))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let record = self.core.take(Operation::Write, path, write_args(&args))?;
+        // Errors without payload are returned while starting the write.
+        if let (Some(err), None) = (&record.error, &record.payload) {
+            return Err(parse_record_error(err));
+        }
+        Ok((RpWrite::new(), ReplayWriter::new(record)))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.core
+            .take_ok(Operation::Copy, from, copy_args(to, &args))?;
+        Ok(RpCopy::default())
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.core
+            .take_ok(Operation::Rename, from, rename_args(to, &args))?;
+        Ok(RpRename::default())
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let record = self.core.take(Operation::List, path, list_args(&args))?;
+        let err = record.error.as_ref().map(parse_record_error);
+        match (record.entries.is_empty(), err) {
+            (true, Some(err)) => Err(err),
+            (_, err) => Ok((RpList::default(), ReplayLister::new(record.entries, err))),
+        }
+    }
+
+    async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        Ok((RpDelete::default(), ReplayDeleter::new(self.core.clone())))
+    }
+
+    async fn list_uploads(&self, path: &str, _: OpListUploads) -> Result<RpListUploads> {
+        let record = self
+            .core
+            .take_ok(Operation::ListUploads, path, BTreeMap::new())?;
+        let uploads = record
+            .uploads
+            .iter()
+            .map(parse_record_upload)
+            .collect::<Result<_>>()?;
+        Ok(RpListUploads::new(uploads))
+    }
+
+    async fn abort_upload(&self, path: &str, args: OpAbortUpload) -> Result<RpAbortUpload> {
+        self.core
+            .take_ok(Operation::AbortUpload, path, abort_upload_args(&args))?;
+        Ok(RpAbortUpload::default())
+    }
+
+    async fn lock(&self, path: &str, args: OpLock) -> Result<RpLock> {
+        self.core.take_ok(Operation::Lock, path, lock_args(&args))?;
+        Ok(RpLock::default())
+    }
+
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        self.core
+            .take_ok(Operation::Concat, path, concat_args(&args))?;
+        Ok(RpConcat::default())
+    }
+
+    async fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> Result<RpUpdateMetadata> {
+        self.core
+            .take_ok(Operation::UpdateMetadata, path, update_metadata_args(&args))?;
+        Ok(RpUpdateMetadata::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layers::RecordLayer;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_replay() -> Result<()> {
+        let path =
+            std::env::temp_dir().join(format!("opendal-replay-{}.jsonl", uuid::Uuid::new_v4()));
+
+        let layer = RecordLayer::new(&path)?;
+        let op = Operator::new(Memory::default())?
+            .layer(layer.clone())
+            .finish();
+        op.write("dir/file", "Hello, World!").await?;
+        let meta = op.stat("dir/file").await?;
+        assert_eq!(op.read("dir/file").await?.to_vec(), b"Hello, World!");
+        assert_eq!(op.list("dir/").await?.len(), 1);
+        op.delete("dir/file").await?;
+        assert!(!op.exists("dir/file").await?);
+        layer.flush().await?;
+
+        let op = Operator::new(ReplayBuilder::default().path(path.to_str().unwrap()))?.finish();
+        op.write("dir/file", "Hello, World!").await?;
+        assert_eq!(op.stat("dir/file").await?, meta);
+        assert_eq!(op.read("dir/file").await?.to_vec(), b"Hello, World!");
+        assert_eq!(op.list("dir/").await?.len(), 1);
+        op.delete("dir/file").await?;
+        assert!(!op.exists("dir/file").await?);
+
+        // Calls that were not recorded should fail.
+        let err = op.read("dir/other").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        // Writes with different content should fail.
+        let op = Operator::new(ReplayBuilder::default().path(path.to_str().unwrap()))?.finish();
+        assert!(op.write("dir/file", "Hello, OpenDAL!").await.is_err());
+
+        std::fs::remove_file(path).unwrap();
+        Ok(())
+    }
+}
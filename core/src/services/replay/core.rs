@@ -0,0 +1,197 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Mutex;
+
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+
+use crate::layers::Record;
+use crate::layers::RecordError;
+use crate::layers::RecordMetadata;
+use crate::layers::RecordPayload;
+use crate::layers::RecordUpload;
+use crate::raw::*;
+use crate::*;
+
+/// The key used to match a call with its records.
+pub type RecordKey = (String, String, BTreeMap<String, String>);
+
+pub fn record_key(record: &Record) -> RecordKey {
+    (record.op.clone(), record.path.clone(), record.args.clone())
+}
+
+pub fn parse_record_error(err: &RecordError) -> Error {
+    let kind = [
+        ErrorKind::Unsupported,
+        ErrorKind::ConfigInvalid,
+        ErrorKind::NotFound,
+        ErrorKind::PermissionDenied,
+        ErrorKind::IsADirectory,
+        ErrorKind::NotADirectory,
+        ErrorKind::AlreadyExists,
+        ErrorKind::RateLimited,
+        ErrorKind::IsSameFile,
+        ErrorKind::ConditionNotMatch,
+        ErrorKind::RangeNotSatisfied,
+    ]
+    .into_iter()
+    .find(|kind| kind.into_static() == err.kind)
+    .unwrap_or(ErrorKind::Unexpected);
+
+    Error::new(kind, err.message.clone())
+        .with_operation("replay")
+        .with_temporary(err.temporary)
+}
+
+pub fn parse_record_metadata(meta: &RecordMetadata) -> Result<Metadata> {
+    let mode = match meta.mode.as_str() {
+        "file" => EntryMode::FILE,
+        "dir" => EntryMode::DIR,
+        _ => EntryMode::Unknown,
+    };
+
+    let mut m = Metadata::new(mode)
+        .with_content_length(meta.content_length)
+        .with_is_current(meta.is_current)
+        .with_is_deleted(meta.is_deleted);
+    if let Some(v) = &meta.content_type {
+        m.set_content_type(v);
+    }
+    if let Some(v) = &meta.content_disposition {
+        m.set_content_disposition(v);
+    }
+    if let Some(v) = &meta.content_encoding {
+        m.set_content_encoding(v);
+    }
+    if let Some(v) = &meta.content_md5 {
+        m.set_content_md5(v);
+    }
+    if let Some(v) = &meta.cache_control {
+        m.set_cache_control(v);
+    }
+    if let Some(v) = &meta.etag {
+        m.set_etag(v);
+    }
+    if let Some(v) = &meta.last_modified {
+        m.set_last_modified(parse_datetime_from_rfc3339(v)?);
+    }
+    if let Some(v) = &meta.version {
+        m.set_version(v);
+    }
+    if let Some(v) = &meta.user_metadata {
+        m = m.with_user_metadata(v.clone());
+    }
+    Ok(m)
+}
+
+pub fn parse_record_payload(payload: &RecordPayload) -> Result<Buffer> {
+    let data = payload.data.as_deref().unwrap_or_default();
+    let bs = BASE64_STANDARD.decode(data).map_err(|err| {
+        Error::new(ErrorKind::Unexpected, "recorded payload is invalid").set_source(err)
+    })?;
+    Ok(Buffer::from(bs))
+}
+
+pub fn parse_record_upload(upload: &RecordUpload) -> Result<Upload> {
+    let mut u = Upload::new(&upload.path, &upload.upload_id);
+    if let Some(v) = &upload.initiated {
+        u = u.with_initiated(parse_datetime_from_rfc3339(v)?);
+    }
+    Ok(u)
+}
+
+pub struct ReplayCore {
+    records: Mutex<HashMap<RecordKey, VecDeque<Record>>>,
+}
+
+impl Debug for ReplayCore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReplayCore").finish_non_exhaustive()
+    }
+}
+
+impl ReplayCore {
+    pub fn new(records: Vec<Record>) -> Self {
+        let mut map: HashMap<_, VecDeque<_>> = HashMap::new();
+        for record in records {
+            map.entry(record_key(&record))
+                .or_default()
+                .push_back(record);
+        }
+        Self {
+            records: Mutex::new(map),
+        }
+    }
+
+    /// Check whether the recording contains any call of given operation.
+    pub fn contains(&self, op: Operation) -> bool {
+        self.records
+            .lock()
+            .unwrap()
+            .keys()
+            .any(|(v, _, _)| v == op.into_static())
+    }
+
+    /// Take the next record of given call.
+    ///
+    /// Returns an error if the call was not recorded or all its records have
+    /// been consumed.
+    pub fn take(
+        &self,
+        op: Operation,
+        path: &str,
+        args: BTreeMap<String, String>,
+    ) -> Result<Record> {
+        let args_str = serde_json::to_string(&args).unwrap_or_default();
+        let key = (op.into_static().to_string(), path.to_string(), args);
+
+        self.take_by_key(&key).ok_or_else(|| {
+            Error::new(ErrorKind::Unexpected, "call is not recorded")
+                .with_operation(op)
+                .with_context("path", path)
+                .with_context("args", args_str)
+        })
+    }
+
+    /// Take the next record of given key.
+    pub fn take_by_key(&self, key: &RecordKey) -> Option<Record> {
+        self.records
+            .lock()
+            .unwrap()
+            .get_mut(key)
+            .and_then(|v| v.pop_front())
+    }
+
+    /// Take the next record of given call and return the recorded error if any.
+    pub fn take_ok(
+        &self,
+        op: Operation,
+        path: &str,
+        args: BTreeMap<String, String>,
+    ) -> Result<Record> {
+        let record = self.take(op, path, args)?;
+        match &record.error {
+            Some(err) => Err(parse_record_error(err)),
+            None => Ok(record),
+        }
+    }
+}
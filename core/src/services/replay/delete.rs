@@ -0,0 +1,72 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use super::core::*;
+use crate::layers::delete_args;
+use crate::layers::Record;
+use crate::raw::*;
+use crate::*;
+
+pub struct ReplayDeleter {
+    core: Arc<ReplayCore>,
+    queue: VecDeque<Record>,
+}
+
+impl ReplayDeleter {
+    pub fn new(core: Arc<ReplayCore>) -> Self {
+        Self {
+            core,
+            queue: VecDeque::new(),
+        }
+    }
+}
+
+impl oio::Delete for ReplayDeleter {
+    fn delete(&mut self, path: &str, args: OpDelete) -> Result<()> {
+        let record = self
+            .core
+            .take(Operation::Delete, path, delete_args(&args))?;
+        self.queue.push_back(record);
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<usize> {
+        let mut deleted = 0;
+        while let Some(record) = self.queue.front() {
+            if let Some(err) = &record.error {
+                // Return the succeeded ones first so that the failed path
+                // could be retried.
+                if deleted > 0 {
+                    break;
+                }
+                let err = parse_record_error(err);
+                // The failed path will be retried, serve it with its next record.
+                let key = record_key(record);
+                self.queue.pop_front();
+                if let Some(next) = self.core.take_by_key(&key) {
+                    self.queue.push_front(next);
+                }
+                return Err(err);
+            }
+            self.queue.pop_front();
+            deleted += 1;
+        }
+        Ok(deleted)
+    }
+}
@@ -0,0 +1,52 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::vec::IntoIter;
+
+use super::core::*;
+use crate::layers::RecordEntry;
+use crate::raw::*;
+use crate::*;
+
+/// ReplayLister returns the recorded entries and then the recorded error if any.
+pub struct ReplayLister {
+    entries: IntoIter<RecordEntry>,
+    error: Option<Error>,
+}
+
+impl ReplayLister {
+    pub fn new(entries: Vec<RecordEntry>, error: Option<Error>) -> Self {
+        Self {
+            entries: entries.into_iter(),
+            error,
+        }
+    }
+}
+
+impl oio::List for ReplayLister {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        match self.entries.next() {
+            Some(entry) => Ok(Some(oio::Entry::new(
+                &entry.path,
+                parse_record_metadata(&entry.metadata)?,
+            ))),
+            None => match self.error.take() {
+                Some(err) => Err(err),
+                None => Ok(None),
+            },
+        }
+    }
+}
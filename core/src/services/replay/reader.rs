@@ -0,0 +1,47 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use crate::raw::*;
+use crate::*;
+
+/// ReplayReader returns the recorded data and then the recorded error if any.
+pub struct ReplayReader {
+    data: Option<Buffer>,
+    error: Option<Error>,
+}
+
+impl ReplayReader {
+    pub fn new(data: Buffer, error: Option<Error>) -> Self {
+        Self {
+            data: Some(data),
+            error,
+        }
+    }
+}
+
+impl oio::Read for ReplayReader {
+    async fn read(&mut self) -> Result<Buffer> {
+        if let Some(bs) = self.data.take() {
+            if !bs.is_empty() {
+                return Ok(bs);
+            }
+        }
+        match self.error.take() {
+            Some(err) => Err(err),
+            None => Ok(Buffer::new()),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use super::core::*;
+use crate::layers::PayloadHasher;
+use crate::layers::Record;
+use crate::raw::*;
+use crate::*;
+
+/// ReplayWriter checks the written data against the recording.
+pub struct ReplayWriter {
+    record: Record,
+    hasher: PayloadHasher,
+}
+
+impl ReplayWriter {
+    pub fn new(record: Record) -> Self {
+        Self {
+            record,
+            hasher: PayloadHasher::default(),
+        }
+    }
+}
+
+impl oio::Write for ReplayWriter {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        self.hasher.update(&bs);
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        let actual = std::mem::take(&mut self.hasher).finish();
+        if let Some(expected) = &self.record.payload {
+            if expected.size != actual.size || expected.sha256 != actual.sha256 {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "written content doesn't match the recording",
+                )
+                .with_operation(Operation::Write)
+                .with_context("path", &self.record.path)
+                .with_context("expected_size", expected.size)
+                .with_context("actual_size", actual.size));
+            }
+        }
+
+        if let Some(err) = &self.record.error {
+            return Err(parse_record_error(err));
+        }
+        match &self.record.metadata {
+            Some(meta) => parse_record_metadata(meta),
+            None => Ok(Metadata::new(EntryMode::FILE).with_content_length(actual.size)),
+        }
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
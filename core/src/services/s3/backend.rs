@@ -26,8 +26,12 @@ use std::sync::LazyLock;
 
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
+use bytes::Buf;
 use constants::X_AMZ_META_PREFIX;
 use constants::X_AMZ_VERSION_ID;
+use http::header::CONTENT_LENGTH;
+use http::HeaderMap;
+use http::Method;
 use http::Response;
 use http::StatusCode;
 use log::debug;
@@ -41,6 +45,7 @@ use reqsign::AwsDefaultLoader;
 use reqsign::AwsV4Signer;
 use reqwest::Url;
 
+use super::concat::S3Concatenator;
 use super::core::*;
 use super::delete::S3Deleter;
 use super::error::parse_error;
@@ -68,6 +73,8 @@ static ENDPOINT_TEMPLATES: LazyLock<HashMap<&'static str, &'static str>> = LazyL
 });
 
 const DEFAULT_BATCH_MAX_OPERATIONS: usize = 1000;
+/// The max size of objects that could be copied by a single CopyObject.
+const MAX_COPY_OBJECT_SIZE: u64 = 5 * 1024 * 1024 * 1024;
 
 impl Configurator for S3Config {
     type Builder = S3Builder;
@@ -945,6 +952,7 @@ impl Builder for S3Builder {
                             write_with_if_match: !self.config.disable_write_with_if_match,
                             write_with_if_not_exists: true,
                             write_with_user_metadata: true,
+                            write_with_resume: true,
 
                             // The min multipart size of S3 is 5 MiB.
                             //
@@ -962,8 +970,19 @@ impl Builder for S3Builder {
                             delete: true,
                             delete_max_size: Some(delete_max_size),
                             delete_with_version: self.config.enable_versioning,
+                            delete_with_if_match: true,
 
                             copy: true,
+                            concat: true,
+                            // S3 allows at most 10000 parts in one multipart upload.
+                            concat_max_sources: Some(10000),
+
+                            update_metadata: true,
+                            update_metadata_with_tags: true,
+                            copy_with_source_if_match: true,
+                            copy_with_source_if_none_match: true,
+                            copy_with_source_if_unmodified_since: true,
+                            copy_with_source_version: self.config.enable_versioning,
 
                             list: true,
                             list_with_limit: true,
@@ -972,10 +991,15 @@ impl Builder for S3Builder {
                             list_with_versions: self.config.enable_versioning,
                             list_with_deleted: self.config.enable_versioning,
 
+                            list_uploads: true,
+                            abort_upload: true,
+
                             presign: true,
                             presign_stat: true,
                             presign_read: true,
                             presign_write: true,
+                            presign_post: true,
+                            presign_multipart: true,
 
                             shared: true,
 
@@ -1033,21 +1057,7 @@ impl Access for S3Backend {
         let status = resp.status();
 
         match status {
-            StatusCode::OK => {
-                let headers = resp.headers();
-                let mut meta = parse_into_metadata(path, headers)?;
-
-                let user_meta = parse_prefixed_headers(headers, X_AMZ_META_PREFIX);
-                if !user_meta.is_empty() {
-                    meta = meta.with_user_metadata(user_meta);
-                }
-
-                if let Some(v) = parse_header_to_str(headers, X_AMZ_VERSION_ID)? {
-                    meta.set_version(v);
-                }
-
-                Ok(RpStat::new(meta))
-            }
+            StatusCode::OK => parse_object_metadata(path, resp.headers()).map(RpStat::new),
             _ => Err(parse_error(resp)),
         }
     }
@@ -1074,11 +1084,10 @@ impl Access for S3Backend {
         let w = if args.append() {
             S3Writers::Two(oio::AppendWriter::new(writer))
         } else {
-            S3Writers::One(oio::MultipartWriter::new(
-                self.core.info.clone(),
-                writer,
-                args.concurrent(),
-            ))
+            S3Writers::One(
+                oio::MultipartWriter::new(self.core.info.clone(), writer, args.concurrent())
+                    .with_resume(&args),
+            )
         };
 
         Ok((RpWrite::default(), w))
@@ -1115,8 +1124,8 @@ impl Access for S3Backend {
         Ok((RpList::default(), l))
     }
 
-    async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
-        let resp = self.core.s3_copy_object(from, to).await?;
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let resp = self.core.s3_copy_object(from, to, &args).await?;
 
         let status = resp.status();
 
@@ -1126,6 +1135,114 @@ impl Access for S3Backend {
         }
     }
 
+    async fn concat(&self, path: &str, args: OpConcat) -> Result<RpConcat> {
+        let op = args.write_args().clone();
+        S3Concatenator::concat(self.core.clone(), path, args, &op, None).await
+    }
+
+    async fn update_metadata(
+        &self,
+        path: &str,
+        args: OpUpdateMetadata,
+    ) -> Result<RpUpdateMetadata> {
+        let resp = self.core.s3_head_object(path, OpStat::new()).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp));
+        }
+        let source = resp.headers();
+        let meta = parse_object_metadata(path, source)?;
+
+        if args.has_metadata_changes() {
+            let op = args.apply(&meta);
+            if meta.content_length() <= MAX_COPY_OBJECT_SIZE {
+                let resp = self
+                    .core
+                    .s3_replace_object_metadata(path, &op, meta.etag(), source)
+                    .await?;
+                if resp.status() != StatusCode::OK {
+                    return Err(parse_error(resp));
+                }
+            } else {
+                // Unlike CopyObject, multipart upload doesn't keep the tags.
+                let tags = match args.tags() {
+                    Some(_) => None,
+                    None => Some(self.get_tags(path).await?),
+                };
+
+                // CopyObject only supports objects up to 5GiB, copy larger ones part by part.
+                let mut concat = OpConcat::new(vec![path.to_string()]);
+                if let Some(etag) = meta.etag() {
+                    concat = concat.with_if_match(etag);
+                }
+                S3Concatenator::concat(self.core.clone(), path, concat, &op, Some(source)).await?;
+                if let Some(tags) = tags.filter(|v| !v.is_empty()) {
+                    self.put_tags(path, &tags).await?;
+                }
+            }
+        }
+
+        if let Some(tags) = args.tags() {
+            self.put_tags(path, tags).await?;
+        }
+        Ok(RpUpdateMetadata::default())
+    }
+
+    async fn list_uploads(&self, path: &str, _: OpListUploads) -> Result<RpListUploads> {
+        let mut uploads = Vec::new();
+        let (mut key_marker, mut upload_id_marker) = (String::new(), String::new());
+
+        loop {
+            let resp = self
+                .core
+                .s3_list_multipart_uploads(path, &key_marker, &upload_id_marker)
+                .await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp));
+            }
+
+            let output: ListMultipartUploadsResult =
+                quick_xml::de::from_reader(resp.into_body().reader())
+                    .map_err(new_xml_deserialize_error)?;
+
+            for upload in output.upload {
+                let mut u = Upload::new(
+                    &build_rel_path(&self.core.root, &upload.key),
+                    &upload.upload_id,
+                );
+                if let Some(initiated) = upload.initiated {
+                    u = u.with_initiated(parse_datetime_from_rfc3339(&initiated)?);
+                }
+                uploads.push(u);
+            }
+
+            match (
+                output.is_truncated,
+                output.next_key_marker,
+                output.next_upload_id_marker,
+            ) {
+                (Some(true), Some(key), Some(upload_id)) => {
+                    key_marker = key;
+                    upload_id_marker = upload_id;
+                }
+                _ => break,
+            }
+        }
+
+        Ok(RpListUploads::new(uploads))
+    }
+
+    async fn abort_upload(&self, path: &str, args: OpAbortUpload) -> Result<RpAbortUpload> {
+        let resp = self
+            .core
+            .s3_abort_multipart_upload(path, args.upload_id())
+            .await?;
+        match resp.status() {
+            // s3 returns code 204 if abort succeeds, and 404 if upload not exists.
+            StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => Ok(RpAbortUpload::default()),
+            _ => Err(parse_error(resp)),
+        }
+    }
+
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
         let (expire, op) = args.into_parts();
         // We will not send this request out, just for signing.
@@ -1139,12 +1256,40 @@ impl Access for S3Backend {
                 self.core
                     .s3_put_object_request(path, None, &OpWrite::default(), Buffer::new())
             }
+            PresignOperation::Post(v) => {
+                let fields = self.core.sign_post_policy(path, &v, expire).await?;
+                let uri = format!("{}/", self.core.endpoint)
+                    .parse()
+                    .map_err(new_http_uri_invalid_error)?;
+                return Ok(RpPresign::new(PresignedRequest::new(
+                    Method::POST,
+                    uri,
+                    HeaderMap::new(),
+                ))
+                .with_form_fields(fields));
+            }
+            PresignOperation::CreateMultipart(v) => {
+                self.core.s3_initiate_multipart_upload_request(path, &v)
+            }
+            PresignOperation::WritePart(v) => self.core.s3_upload_part_request(
+                path,
+                v.upload_id(),
+                v.part_number(),
+                0,
+                Buffer::new(),
+                None,
+            ),
+            PresignOperation::CompleteMultipart(v) => self
+                .core
+                .s3_complete_multipart_upload_request(path, v.upload_id(), vec![]),
             PresignOperation::Delete(_) => Err(Error::new(
                 ErrorKind::Unsupported,
                 "operation is not supported",
             )),
         };
         let mut req = req?;
+        // Content length is decided by the client who sends the presigned request.
+        req.headers_mut().remove(CONTENT_LENGTH);
 
         self.core.sign_query(&mut req, expire).await?;
 
@@ -1159,6 +1304,47 @@ impl Access for S3Backend {
     }
 }
 
+impl S3Backend {
+    async fn get_tags(&self, path: &str) -> Result<HashMap<String, String>> {
+        let resp = self.core.s3_get_object_tagging(path).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp));
+        }
+
+        let output: Tagging = quick_xml::de::from_reader(resp.into_body().reader())
+            .map_err(new_xml_deserialize_error)?;
+        Ok(output
+            .tag_set
+            .tag
+            .into_iter()
+            .map(|v| (v.key, v.value))
+            .collect())
+    }
+
+    async fn put_tags(&self, path: &str, tags: &HashMap<String, String>) -> Result<()> {
+        let resp = self.core.s3_put_object_tagging(path, tags).await?;
+        match resp.status() {
+            StatusCode::OK => Ok(()),
+            _ => Err(parse_error(resp)),
+        }
+    }
+}
+
+fn parse_object_metadata(path: &str, headers: &HeaderMap) -> Result<Metadata> {
+    let mut meta = parse_into_metadata(path, headers)?;
+
+    let user_meta = parse_prefixed_headers(headers, X_AMZ_META_PREFIX);
+    if !user_meta.is_empty() {
+        meta = meta.with_user_metadata(user_meta);
+    }
+
+    if let Some(v) = parse_header_to_str(headers, X_AMZ_VERSION_ID)? {
+        meta.set_version(v);
+    }
+
+    Ok(meta)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
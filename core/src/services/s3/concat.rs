@@ -0,0 +1,245 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::sync::Arc;
+
+use bytes::Buf;
+use http::header::IF_MATCH;
+use http::header::IF_NONE_MATCH;
+use http::HeaderMap;
+use http::HeaderValue;
+use http::Response;
+use http::StatusCode;
+
+use super::core::*;
+use super::error::from_s3_error;
+use super::error::parse_error;
+use super::error::S3Error;
+use crate::raw::*;
+use crate::*;
+
+/// The min size of every part except the last one.
+const MIN_PART_SIZE: u64 = 5 * 1024 * 1024;
+/// The max size of a part copied by `UploadPartCopy`.
+const MAX_COPY_PART_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+
+/// S3Concatenator concatenates objects into a new object via multipart upload.
+///
+/// Ranges of sources are copied on the server side by `UploadPartCopy`. Ranges that are
+/// smaller than the min part size are downloaded and merged into their neighbours
+/// before uploading.
+pub struct S3Concatenator {
+    core: Arc<S3Core>,
+    path: String,
+    upload_id: String,
+
+    parts: Vec<CompleteMultipartUploadRequestPart>,
+}
+
+impl S3Concatenator {
+    /// Concat sources into `path`, the new object will be created with the metadata in `op`.
+    ///
+    /// The storage class and server side encryption will be kept from the `source` headers
+    /// if given, which is used to rewrite an object onto itself.
+    pub async fn concat(
+        core: Arc<S3Core>,
+        path: &str,
+        args: OpConcat,
+        op: &OpWrite,
+        source: Option<&HeaderMap>,
+    ) -> Result<RpConcat> {
+        let mut sources = Vec::with_capacity(args.sources().len());
+        for source in args.sources() {
+            let size = stat_size(&core, source).await?;
+            sources.push((source.clone(), size));
+        }
+        let plan = plan_concat_parts(&sources, MIN_PART_SIZE, MAX_COPY_PART_SIZE);
+
+        let mut req = core.s3_initiate_multipart_upload_request(path, op)?;
+        if let Some(source) = source {
+            keep_source_object_headers(req.headers_mut(), source);
+        }
+        core.sign(&mut req).await?;
+        let resp = core.send(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp));
+        }
+        let result: InitiateMultipartUploadResult =
+            quick_xml::de::from_reader(resp.into_body().reader())
+                .map_err(new_xml_deserialize_error)?;
+
+        let mut c = S3Concatenator {
+            core: core.clone(),
+            path: path.to_string(),
+            upload_id: result.upload_id,
+            parts: vec![],
+        };
+
+        match c.run(plan, &args).await {
+            Ok(()) => Ok(RpConcat::default()),
+            Err(err) => {
+                // Abort the upload as best effort, the error of concat matters more.
+                let _ = core.s3_abort_multipart_upload(path, &c.upload_id).await;
+                Err(err)
+            }
+        }
+    }
+
+    async fn run(&mut self, plan: Vec<ConcatPart>, args: &OpConcat) -> Result<()> {
+        // Multipart upload requires at least one part.
+        if plan.is_empty() {
+            self.upload_part(Buffer::new()).await?;
+        }
+
+        for part in plan {
+            match part {
+                ConcatPart::Copy(source, range) => self.copy_part(&source, range).await?,
+                ConcatPart::Upload(ranges) => {
+                    let mut bufs = Vec::with_capacity(ranges.len());
+                    for (source, range) in ranges {
+                        bufs.push(self.read_range(&source, range).await?);
+                    }
+                    self.upload_part(bufs.into_iter().flatten().collect())
+                        .await?;
+                }
+            }
+        }
+
+        self.complete(args).await
+    }
+
+    async fn read_range(&self, path: &str, range: BytesRange) -> Result<Buffer> {
+        let resp = self
+            .core
+            .s3_get_object(path, range, &OpRead::default())
+            .await?;
+
+        let (part, mut body) = resp.into_parts();
+        let buf = body.to_buffer().await?;
+        match part.status {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => Ok(buf),
+            _ => Err(parse_error(Response::from_parts(part, buf))),
+        }
+    }
+
+    async fn upload_part(&mut self, body: Buffer) -> Result<()> {
+        let part_number = self.parts.len() + 1;
+        let checksum = self.core.calculate_checksum(&body);
+        let mut req = self.core.s3_upload_part_request(
+            &self.path,
+            &self.upload_id,
+            part_number,
+            body.len() as u64,
+            body,
+            checksum.clone(),
+        )?;
+        self.core.sign(&mut req).await?;
+        let resp = self.core.send(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp));
+        }
+
+        let etag = parse_etag(resp.headers())?
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::Unexpected,
+                    "ETag not present in returning response",
+                )
+            })?
+            .to_string();
+        self.parts.push(CompleteMultipartUploadRequestPart {
+            part_number,
+            etag,
+            checksum_crc32c: checksum,
+        });
+        Ok(())
+    }
+
+    async fn copy_part(&mut self, source: &str, range: BytesRange) -> Result<()> {
+        let part_number = self.parts.len() + 1;
+        let resp = self
+            .core
+            .s3_upload_part_copy(&self.path, &self.upload_id, part_number, source, range)
+            .await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp));
+        }
+
+        let result: CopyPartResult = quick_xml::de::from_reader(resp.into_body().reader())
+            .map_err(new_xml_deserialize_error)?;
+        self.parts.push(CompleteMultipartUploadRequestPart {
+            part_number,
+            etag: result.etag,
+            checksum_crc32c: result.checksum_crc32c,
+        });
+        Ok(())
+    }
+
+    async fn complete(&mut self, args: &OpConcat) -> Result<()> {
+        let mut req = self.core.s3_complete_multipart_upload_request(
+            &self.path,
+            &self.upload_id,
+            std::mem::take(&mut self.parts),
+        )?;
+
+        let headers = req.headers_mut();
+        if let Some(etag) = args.if_match() {
+            let v =
+                HeaderValue::from_str(etag).map_err(|err| new_request_build_error(err.into()))?;
+            headers.insert(IF_MATCH, v);
+        }
+        if args.if_not_exists() {
+            headers.insert(IF_NONE_MATCH, HeaderValue::from_static("*"));
+        }
+        req.extensions_mut().insert(Operation::Concat);
+
+        self.core.sign(&mut req).await?;
+        let resp = self.core.send(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp));
+        }
+
+        // S3 might return error for status code 200, check the body as well.
+        let (parts, body) = resp.into_parts();
+        let ret: CompleteMultipartUploadResult =
+            quick_xml::de::from_reader(body.reader()).map_err(new_xml_deserialize_error)?;
+        if !ret.code.is_empty() {
+            return Err(from_s3_error(
+                S3Error {
+                    code: ret.code,
+                    message: ret.message,
+                    resource: "".to_string(),
+                    request_id: ret.request_id,
+                },
+                parts,
+            ));
+        }
+        Ok(())
+    }
+}
+
+async fn stat_size(core: &S3Core, path: &str) -> Result<u64> {
+    let resp = core.s3_head_object(path, OpStat::new()).await?;
+    if resp.status() != StatusCode::OK {
+        return Err(parse_error(resp));
+    }
+    parse_content_length(resp.headers())?.ok_or_else(|| {
+        Error::new(
+            ErrorKind::Unexpected,
+            "content length not present in returning response",
+        )
+    })
+}
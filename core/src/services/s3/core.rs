@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Display;
@@ -28,7 +29,10 @@ use std::time::Duration;
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
 use bytes::Bytes;
+use chrono::Utc;
 use constants::X_AMZ_META_PREFIX;
+use hmac::Hmac;
+use hmac::Mac;
 use http::header::HeaderName;
 use http::header::CACHE_CONTROL;
 use http::header::CONTENT_DISPOSITION;
@@ -40,6 +44,7 @@ use http::header::IF_MATCH;
 use http::header::IF_MODIFIED_SINCE;
 use http::header::IF_NONE_MATCH;
 use http::header::IF_UNMODIFIED_SINCE;
+use http::HeaderMap;
 use http::HeaderValue;
 use http::Request;
 use http::Response;
@@ -48,12 +53,18 @@ use reqsign::AwsCredentialLoad;
 use reqsign::AwsV4Signer;
 use serde::Deserialize;
 use serde::Serialize;
+use sha2::Sha256;
 
 use crate::raw::*;
 use crate::*;
 
 pub mod constants {
     pub const X_AMZ_COPY_SOURCE: &str = "x-amz-copy-source";
+    pub const X_AMZ_COPY_SOURCE_IF_MATCH: &str = "x-amz-copy-source-if-match";
+    pub const X_AMZ_COPY_SOURCE_IF_NONE_MATCH: &str = "x-amz-copy-source-if-none-match";
+    pub const X_AMZ_COPY_SOURCE_IF_UNMODIFIED_SINCE: &str = "x-amz-copy-source-if-unmodified-since";
+    pub const X_AMZ_COPY_SOURCE_RANGE: &str = "x-amz-copy-source-range";
+    pub const X_AMZ_METADATA_DIRECTIVE: &str = "x-amz-metadata-directive";
 
     pub const X_AMZ_SERVER_SIDE_ENCRYPTION: &str = "x-amz-server-side-encryption";
     pub const X_AMZ_SERVER_REQUEST_PAYER: (&str, &str) = ("x-amz-request-payer", "requester");
@@ -88,6 +99,12 @@ pub mod constants {
     pub const S3_QUERY_VERSION_ID: &str = "versionId";
 }
 
+fn hmac_sha256(key: &[u8], content: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC can take key of any size");
+    mac.update(content);
+    mac.finalize().into_bytes().to_vec()
+}
+
 pub struct S3Core {
     pub info: Arc<AccessorInfo>,
 
@@ -201,6 +218,69 @@ impl S3Core {
         Ok(())
     }
 
+    /// Build the form fields of a presigned POST upload.
+    ///
+    /// Reference: <https://docs.aws.amazon.com/AmazonS3/latest/API/sigv4-HTTPPOSTConstructPolicy.html>
+    pub async fn sign_post_policy(
+        &self,
+        path: &str,
+        args: &OpPresignPost,
+        expire: Duration,
+    ) -> Result<Vec<(String, String)>> {
+        let key = build_abs_path(&self.root, path);
+        let mut fields = vec![("key".to_string(), key.clone())];
+        if let Some(v) = args.content_type() {
+            fields.push(("Content-Type".to_string(), v.to_string()));
+        }
+
+        let Some(cred) = self.load_credential().await? else {
+            return Ok(fields);
+        };
+
+        let now = Utc::now();
+        let date = now.format("%Y%m%d").to_string();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let region = self.signer.region();
+        let credential = format!("{}/{date}/{region}/s3/aws4_request", cred.access_key_id);
+
+        let mut policy = PostPolicy::new(now, expire);
+        policy.push_eq("bucket", &self.bucket);
+        policy.push_eq("key", &key);
+        policy.push_eq("x-amz-algorithm", "AWS4-HMAC-SHA256");
+        policy.push_eq("x-amz-credential", &credential);
+        policy.push_eq("x-amz-date", &amz_date);
+        if let Some(token) = &cred.session_token {
+            policy.push_eq("x-amz-security-token", token);
+        }
+        policy.push_conditions(args);
+        let policy = policy.encode();
+
+        let signing_key = [date.as_str(), region, "s3", "aws4_request"].iter().fold(
+            format!("AWS4{}", cred.secret_access_key).into_bytes(),
+            |key, v| hmac_sha256(&key, v.as_bytes()),
+        );
+        let signature =
+            hmac_sha256(&signing_key, policy.as_bytes())
+                .iter()
+                .fold(String::new(), |mut s, b| {
+                    let _ = write!(s, "{b:02x}");
+                    s
+                });
+
+        fields.push((
+            "x-amz-algorithm".to_string(),
+            "AWS4-HMAC-SHA256".to_string(),
+        ));
+        fields.push(("x-amz-credential".to_string(), credential));
+        fields.push(("x-amz-date".to_string(), amz_date));
+        if let Some(token) = cred.session_token {
+            fields.push(("x-amz-security-token".to_string(), token));
+        }
+        fields.push(("policy".to_string(), policy));
+        fields.push(("x-amz-signature".to_string(), signature));
+        Ok(fields)
+    }
+
     #[inline]
     pub async fn send(&self, req: Request<Buffer>) -> Result<Response<Buffer>> {
         self.info.http_client().send(req).await
@@ -638,6 +718,10 @@ impl S3Core {
 
         let mut req = Request::delete(&url);
 
+        if let Some(if_match) = args.if_match() {
+            req = req.header(IF_MATCH, if_match);
+        }
+
         // Set request payer header if enabled.
         req = self.insert_request_payer_header(req);
 
@@ -652,18 +736,11 @@ impl S3Core {
         self.send(req).await
     }
 
-    pub async fn s3_copy_object(&self, from: &str, to: &str) -> Result<Response<Buffer>> {
-        let from = build_abs_path(&self.root, from);
-        let to = build_abs_path(&self.root, to);
-
-        let source = format!("{}/{}", self.bucket, percent_encode_path(&from));
-        let target = format!("{}/{}", self.endpoint, percent_encode_path(&to));
-
-        let mut req = Request::put(&target);
-
-        // Set SSE headers.
-        req = self.insert_sse_headers(req, true);
-
+    /// Insert the SSE-C headers of the copy source.
+    pub fn insert_copy_source_sse_headers(
+        &self,
+        mut req: http::request::Builder,
+    ) -> http::request::Builder {
         if let Some(v) = &self.server_side_encryption_customer_algorithm {
             let mut v = v.clone();
             v.set_sensitive(true);
@@ -700,6 +777,153 @@ impl S3Core {
             )
         }
 
+        req
+    }
+
+    /// Replace the metadata of the object by copying it onto itself.
+    ///
+    /// CopyObject only supports objects up to 5GiB.
+    pub async fn s3_replace_object_metadata(
+        &self,
+        path: &str,
+        args: &OpWrite,
+        source_if_match: Option<&str>,
+        source: &HeaderMap,
+    ) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+        let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+
+        let mut req = Request::put(&url);
+
+        req = self.insert_metadata_headers(req, None, args);
+        req = req.header(constants::X_AMZ_METADATA_DIRECTIVE, "REPLACE");
+        if let Some(v) = source_if_match {
+            req = req.header(constants::X_AMZ_COPY_SOURCE_IF_MATCH, v);
+        }
+
+        // Set SSE headers.
+        req = self.insert_sse_headers(req, true);
+        req = self.insert_copy_source_sse_headers(req);
+
+        // Set request payer header if enabled.
+        req = self.insert_request_payer_header(req);
+
+        let mut req = req
+            // Inject operation to the request.
+            .extension(Operation::UpdateMetadata)
+            .header(
+                constants::X_AMZ_COPY_SOURCE,
+                format!("{}/{}", self.bucket, percent_encode_path(&p)),
+            )
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+        keep_source_object_headers(req.headers_mut(), source);
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
+    pub async fn s3_put_object_tagging(
+        &self,
+        path: &str,
+        tags: &HashMap<String, String>,
+    ) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+        let url = format!("{}/{}?tagging", self.endpoint, percent_encode_path(&p));
+
+        let mut req = Request::put(&url);
+
+        let mut tag: Vec<_> = tags
+            .iter()
+            .map(|(key, value)| Tag {
+                key: key.clone(),
+                value: value.clone(),
+            })
+            .collect();
+        tag.sort_by(|a, b| a.key.cmp(&b.key));
+        let content = quick_xml::se::to_string(&Tagging {
+            tag_set: TagSet { tag },
+        })
+        .map_err(new_xml_serialize_error)?;
+
+        req = req.header(CONTENT_LENGTH, content.len());
+        req = req.header(CONTENT_TYPE, "application/xml");
+        // Set content-md5 as required by API.
+        req = req.header("CONTENT-MD5", format_content_md5(content.as_bytes()));
+
+        // Set request payer header if enabled.
+        req = self.insert_request_payer_header(req);
+
+        // Inject operation to the request.
+        req = req.extension(Operation::UpdateMetadata);
+
+        let mut req = req
+            .body(Buffer::from(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
+    pub async fn s3_get_object_tagging(&self, path: &str) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+        let url = format!("{}/{}?tagging", self.endpoint, percent_encode_path(&p));
+
+        let mut req = Request::get(&url);
+
+        // Set request payer header if enabled.
+        req = self.insert_request_payer_header(req);
+
+        // Inject operation to the request.
+        req = req.extension(Operation::UpdateMetadata);
+
+        let mut req = req.body(Buffer::new()).map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
+    pub async fn s3_copy_object(
+        &self,
+        from: &str,
+        to: &str,
+        args: &OpCopy,
+    ) -> Result<Response<Buffer>> {
+        let from = build_abs_path(&self.root, from);
+        let to = build_abs_path(&self.root, to);
+
+        let mut source = format!("{}/{}", self.bucket, percent_encode_path(&from));
+        if let Some(version) = args.source_version() {
+            source.push_str(&format!(
+                "?{}={}",
+                constants::S3_QUERY_VERSION_ID,
+                percent_encode_path(version)
+            ));
+        }
+        let target = format!("{}/{}", self.endpoint, percent_encode_path(&to));
+
+        let mut req = Request::put(&target);
+
+        // Set SSE headers.
+        req = self.insert_sse_headers(req, true);
+        req = self.insert_copy_source_sse_headers(req);
+
+        if let Some(v) = args.source_if_match() {
+            req = req.header(constants::X_AMZ_COPY_SOURCE_IF_MATCH, v);
+        }
+        if let Some(v) = args.source_if_none_match() {
+            req = req.header(constants::X_AMZ_COPY_SOURCE_IF_NONE_MATCH, v);
+        }
+        if let Some(v) = args.source_if_unmodified_since() {
+            req = req.header(
+                constants::X_AMZ_COPY_SOURCE_IF_UNMODIFIED_SINCE,
+                format_datetime_into_http_date(v),
+            );
+        }
+
         // Set request payer header if enabled.
         req = self.insert_request_payer_header(req);
 
@@ -812,6 +1036,18 @@ impl S3Core {
         path: &str,
         args: &OpWrite,
     ) -> Result<Response<Buffer>> {
+        let mut req = self.s3_initiate_multipart_upload_request(path, args)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
+    pub fn s3_initiate_multipart_upload_request(
+        &self,
+        path: &str,
+        args: &OpWrite,
+    ) -> Result<Request<Buffer>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!("{}/{}?uploads", self.endpoint, percent_encode_path(&p));
@@ -854,11 +1090,9 @@ impl S3Core {
         // Inject operation to the request.
         req = req.extension(Operation::Write);
 
-        let mut req = req.body(Buffer::new()).map_err(new_request_build_error)?;
-
-        self.sign(&mut req).await?;
+        let req = req.body(Buffer::new()).map_err(new_request_build_error)?;
 
-        self.send(req).await
+        Ok(req)
     }
 
     pub fn s3_upload_part_request(
@@ -904,12 +1138,69 @@ impl S3Core {
         Ok(req)
     }
 
+    /// Upload a part by copying a range of an existing object.
+    pub async fn s3_upload_part_copy(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number: usize,
+        source: &str,
+        range: BytesRange,
+    ) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+        let source = build_abs_path(&self.root, source);
+
+        let url = format!(
+            "{}/{}?partNumber={}&uploadId={}",
+            self.endpoint,
+            percent_encode_path(&p),
+            part_number,
+            percent_encode_path(upload_id)
+        );
+
+        let mut req = Request::put(&url);
+
+        // Set request payer header if enabled.
+        req = self.insert_request_payer_header(req);
+
+        // Set SSE headers.
+        req = self.insert_sse_headers(req, true);
+
+        let mut req = req
+            // Inject operation to the request.
+            .extension(Operation::Concat)
+            .header(
+                constants::X_AMZ_COPY_SOURCE,
+                format!("{}/{}", self.bucket, percent_encode_path(&source)),
+            )
+            .header(constants::X_AMZ_COPY_SOURCE_RANGE, range.to_header())
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
     pub async fn s3_complete_multipart_upload(
         &self,
         path: &str,
         upload_id: &str,
         parts: Vec<CompleteMultipartUploadRequestPart>,
     ) -> Result<Response<Buffer>> {
+        let mut req = self.s3_complete_multipart_upload_request(path, upload_id, parts)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
+    pub fn s3_complete_multipart_upload_request(
+        &self,
+        path: &str,
+        upload_id: &str,
+        parts: Vec<CompleteMultipartUploadRequestPart>,
+    ) -> Result<Request<Buffer>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!(
@@ -937,13 +1228,11 @@ impl S3Core {
         // Inject operation to the request.
         req = req.extension(Operation::Write);
 
-        let mut req = req
+        let req = req
             .body(Buffer::from(Bytes::from(content)))
             .map_err(new_request_build_error)?;
 
-        self.sign(&mut req).await?;
-
-        self.send(req).await
+        Ok(req)
     }
 
     /// Abort an on-going multipart upload.
@@ -976,6 +1265,42 @@ impl S3Core {
         self.send(req).await
     }
 
+    pub async fn s3_list_multipart_uploads(
+        &self,
+        path: &str,
+        key_marker: &str,
+        upload_id_marker: &str,
+    ) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = QueryPairsWriter::new(&self.endpoint).push("uploads", "");
+
+        if !p.is_empty() {
+            url = url.push("prefix", &percent_encode_path(&p));
+        }
+        if !key_marker.is_empty() {
+            url = url.push("key-marker", &percent_encode_path(key_marker));
+        }
+        if !upload_id_marker.is_empty() {
+            url = url.push("upload-id-marker", &percent_encode_path(upload_id_marker));
+        }
+
+        let mut req = Request::get(url.finish());
+
+        // Set request payer header if enabled.
+        req = self.insert_request_payer_header(req);
+
+        let mut req = req
+            // Inject operation to the request.
+            .extension(Operation::ListUploads)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
     pub async fn s3_delete_objects(
         &self,
         paths: Vec<(String, OpDelete)>,
@@ -1076,6 +1401,16 @@ pub struct InitiateMultipartUploadResult {
     pub upload_id: String,
 }
 
+/// Result of UploadPartCopy
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct CopyPartResult {
+    #[serde(rename = "ETag")]
+    pub etag: String,
+    #[serde(rename = "ChecksumCRC32C")]
+    pub checksum_crc32c: Option<String>,
+}
+
 /// Request of CompleteMultipartUploadRequest
 #[derive(Default, Debug, Serialize)]
 #[serde(default, rename = "CompleteMultipartUpload", rename_all = "PascalCase")]
@@ -1136,6 +1471,41 @@ pub struct CompleteMultipartUploadResult {
     pub request_id: String,
 }
 
+/// Keep the storage class and server side encryption of the source object while rewriting
+/// it onto itself, otherwise they will be reset to the defaults of the bucket or backend.
+pub fn keep_source_object_headers(headers: &mut HeaderMap, source: &HeaderMap) {
+    for name in [
+        constants::X_AMZ_STORAGE_CLASS,
+        constants::X_AMZ_SERVER_SIDE_ENCRYPTION,
+        constants::X_AMZ_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID,
+    ] {
+        match source.get(name) {
+            Some(v) => headers.insert(name, v.clone()),
+            None => headers.remove(name),
+        };
+    }
+}
+
+/// Request of PutObjectTagging and result of GetObjectTagging.
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename = "Tagging", rename_all = "PascalCase")]
+pub struct Tagging {
+    pub tag_set: TagSet,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct TagSet {
+    pub tag: Vec<Tag>,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct Tag {
+    pub key: String,
+    pub value: String,
+}
+
 /// Request of DeleteObjects.
 #[derive(Default, Debug, Serialize)]
 #[serde(default, rename = "Delete", rename_all = "PascalCase")]
@@ -1175,6 +1545,24 @@ pub struct DeleteObjectsResultError {
     pub version_id: Option<String>,
 }
 
+/// Output of ListMultipartUploads.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct ListMultipartUploadsResult {
+    pub is_truncated: Option<bool>,
+    pub next_key_marker: Option<String>,
+    pub next_upload_id_marker: Option<String>,
+    pub upload: Vec<ListMultipartUploadsResultUpload>,
+}
+
+#[derive(Default, Debug, Eq, PartialEq, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct ListMultipartUploadsResultUpload {
+    pub key: String,
+    pub upload_id: String,
+    pub initiated: Option<String>,
+}
+
 /// Output of ListBucket/ListObjects (a.k.a ListObjectsV1).
 #[derive(Default, Debug, Deserialize)]
 #[serde(default, rename_all = "PascalCase")]
@@ -1382,6 +1770,61 @@ mod tests {
         assert_eq!(out.etag, "\"3858f62230ac3c915f300c664312c11f-9\"");
     }
 
+    /// This example is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_ListMultipartUploads.html#API_ListMultipartUploads_Examples
+    #[test]
+    fn test_deserialize_list_multipart_uploads_result() {
+        let bs = Bytes::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <ListMultipartUploadsResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+              <Bucket>bucket</Bucket>
+              <KeyMarker></KeyMarker>
+              <UploadIdMarker></UploadIdMarker>
+              <NextKeyMarker>my-movie.m2ts</NextKeyMarker>
+              <NextUploadIdMarker>YW55IGlkZWEgd2h5IGVsdmluZydzIHVwbG9hZCBmYWlsZWQ</NextUploadIdMarker>
+              <MaxUploads>3</MaxUploads>
+              <IsTruncated>true</IsTruncated>
+              <Upload>
+                <Key>my-divisor</Key>
+                <UploadId>XMgbGlrZSBlbHZpbmcncyBub3QgaGF2aW5nIG11Y2ggbHVjaw</UploadId>
+                <StorageClass>STANDARD</StorageClass>
+                <Initiated>2010-11-10T20:48:33.000Z</Initiated>
+              </Upload>
+              <Upload>
+                <Key>my-movie.m2ts</Key>
+                <UploadId>VXBsb2FkIElEIGZvciBlbHZpbmcncyBteS1tb3ZpZS5tMnRzIHVwbG9hZA</UploadId>
+                <StorageClass>STANDARD</StorageClass>
+                <Initiated>2010-11-10T20:48:33.000Z</Initiated>
+              </Upload>
+            </ListMultipartUploadsResult>"#,
+        );
+
+        let out: ListMultipartUploadsResult =
+            quick_xml::de::from_reader(bs.reader()).expect("must success");
+
+        assert_eq!(out.is_truncated, Some(true));
+        assert_eq!(out.next_key_marker, Some("my-movie.m2ts".to_string()));
+        assert_eq!(
+            out.next_upload_id_marker,
+            Some("YW55IGlkZWEgd2h5IGVsdmluZydzIHVwbG9hZCBmYWlsZWQ".to_string())
+        );
+        assert_eq!(
+            out.upload,
+            vec![
+                ListMultipartUploadsResultUpload {
+                    key: "my-divisor".to_string(),
+                    upload_id: "XMgbGlrZSBlbHZpbmcncyBub3QgaGF2aW5nIG11Y2ggbHVjaw".to_string(),
+                    initiated: Some("2010-11-10T20:48:33.000Z".to_string()),
+                },
+                ListMultipartUploadsResultUpload {
+                    key: "my-movie.m2ts".to_string(),
+                    upload_id: "VXBsb2FkIElEIGZvciBlbHZpbmcncyBteS1tb3ZpZS5tMnRzIHVwbG9hZA"
+                        .to_string(),
+                    initiated: Some("2010-11-10T20:48:33.000Z".to_string()),
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_deserialize_complete_multipart_upload_result_when_return_error() {
         let bs = Bytes::from(
@@ -1440,6 +1883,80 @@ mod tests {
         )
     }
 
+    /// This example is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_GetObjectTagging.html#API_GetObjectTagging_Examples
+    #[test]
+    fn test_serde_tagging() {
+        let bs = Bytes::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <Tagging xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+              <TagSet>
+                 <Tag>
+                   <Key>tag1</Key>
+                   <Value>val1</Value>
+                 </Tag>
+                 <Tag>
+                   <Key>tag2</Key>
+                   <Value>val2</Value>
+                 </Tag>
+              </TagSet>
+            </Tagging>"#,
+        );
+
+        let out: Tagging = quick_xml::de::from_reader(bs.reader()).expect("must success");
+        assert_eq!(out.tag_set.tag.len(), 2);
+        assert_eq!(out.tag_set.tag[1].key, "tag2");
+        assert_eq!(out.tag_set.tag[1].value, "val2");
+
+        let actual = quick_xml::se::to_string(&out).expect("must succeed");
+        pretty_assertions::assert_eq!(
+            actual,
+            r#"<Tagging>
+              <TagSet>
+                <Tag><Key>tag1</Key><Value>val1</Value></Tag>
+                <Tag><Key>tag2</Key><Value>val2</Value></Tag>
+              </TagSet>
+            </Tagging>"#
+                // Cleanup space and new line
+                .replace([' ', '\n'], "")
+        );
+
+        let empty = quick_xml::se::to_string(&Tagging::default()).expect("must succeed");
+        assert_eq!(empty, "<Tagging><TagSet/></Tagging>");
+    }
+
+    #[test]
+    fn test_keep_source_object_headers() {
+        let mut source = HeaderMap::new();
+        source.insert(
+            constants::X_AMZ_STORAGE_CLASS,
+            HeaderValue::from_static("GLACIER_IR"),
+        );
+        source.insert(
+            constants::X_AMZ_SERVER_SIDE_ENCRYPTION,
+            HeaderValue::from_static("aws:kms"),
+        );
+        source.insert(
+            constants::X_AMZ_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID,
+            HeaderValue::from_static("key-of-object"),
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            constants::X_AMZ_STORAGE_CLASS,
+            HeaderValue::from_static("STANDARD_IA"),
+        );
+        headers.insert(
+            constants::X_AMZ_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID,
+            HeaderValue::from_static("key-of-backend"),
+        );
+        keep_source_object_headers(&mut headers, &source);
+        assert_eq!(headers, source);
+
+        // Objects in the STANDARD class don't return the storage class.
+        keep_source_object_headers(&mut headers, &HeaderMap::new());
+        assert!(headers.is_empty());
+    }
+
     /// This example is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_DeleteObjects.html#API_DeleteObjects_Examples
     #[test]
     fn test_deserialize_delete_objects_result() {
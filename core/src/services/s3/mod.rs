@@ -19,6 +19,8 @@
 #[cfg(feature = "services-s3")]
 pub(super) const DEFAULT_SCHEME: &str = "s3";
 #[cfg(feature = "services-s3")]
+mod concat;
+#[cfg(feature = "services-s3")]
 mod core;
 #[cfg(feature = "services-s3")]
 mod delete;
@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::pin::Pin;
@@ -231,6 +232,8 @@ impl kv::Adapter for Adapter {
                 read: true,
                 write: true,
                 delete: true,
+                stat_many: true,
+                stat_many_max_size: Some(500),
                 list: true,
                 shared: false,
                 ..Default::default()
@@ -253,6 +256,27 @@ impl kv::Adapter for Adapter {
         Ok(value.map(Buffer::from))
     }
 
+    async fn size_many(&self, paths: &[String]) -> Result<Vec<Option<u64>>> {
+        let pool = self.get_client().await?;
+
+        let placeholders = vec!["?"; paths.len()].join(", ");
+        let sql = format!(
+            "SELECT `{}`, length(`{}`) FROM `{}` WHERE `{}` IN ({placeholders})",
+            self.key_field, self.value_field, self.table, self.key_field
+        );
+        let mut query = sqlx::query_as(&sql);
+        for path in paths {
+            query = query.bind(path);
+        }
+        let rows: Vec<(String, i64)> = query.fetch_all(pool).await.map_err(parse_sqlite_error)?;
+
+        let sizes: HashMap<_, _> = rows.into_iter().collect();
+        Ok(paths
+            .iter()
+            .map(|p| sizes.get(p).map(|v| *v as u64))
+            .collect())
+    }
+
     async fn set(&self, path: &str, value: Buffer) -> Result<()> {
         let pool = self.get_client().await?;
 
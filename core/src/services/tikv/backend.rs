@@ -24,11 +24,14 @@ use tokio::sync::OnceCell;
 
 use crate::raw::adapters::kv;
 use crate::raw::Access;
+use crate::raw::LockAction;
+use crate::raw::OpLock;
 use crate::services::TikvConfig;
 use crate::Builder;
 use crate::Capability;
 use crate::Error;
 use crate::ErrorKind;
+use crate::LockRecord;
 use crate::Scheme;
 use crate::*;
 
@@ -193,6 +196,7 @@ impl kv::Adapter for Adapter {
             Capability {
                 read: true,
                 write: true,
+                lock: true,
                 shared: true,
                 ..Default::default()
             },
@@ -224,6 +228,57 @@ impl kv::Adapter for Adapter {
             .await
             .map_err(parse_tikv_error)
     }
+
+    /// TiKV has no native lease, so lock records are swapped atomically
+    /// with `compare_and_swap` and expire by their own timestamps.
+    ///
+    /// Released locks are swapped into an empty record instead of being
+    /// deleted, since raw kv can't delete a key conditionally.
+    async fn lock(&self, path: &str, args: OpLock) -> Result<()> {
+        let client = self.get_connection().await?.with_atomic_for_cas();
+        let previous = client
+            .get(path.to_owned())
+            .await
+            .map_err(parse_tikv_error)?;
+        let record = previous.as_deref().map(LockRecord::decode).transpose()?;
+
+        let next = match args.action() {
+            LockAction::Acquire => {
+                if record.is_some_and(|r| !r.is_expired()) {
+                    return Err(Error::new(
+                        ErrorKind::ConditionNotMatch,
+                        "lock is held by others",
+                    ));
+                }
+                LockRecord::new(args.token(), args.ttl())
+            }
+            LockAction::Renew | LockAction::Release => {
+                if !record.is_some_and(|r| r.token == args.token() && !r.is_expired()) {
+                    return Err(Error::new(
+                        ErrorKind::ConditionNotMatch,
+                        "lock is not held by this token",
+                    ));
+                }
+                if args.action() == LockAction::Renew {
+                    LockRecord::new(args.token(), args.ttl())
+                } else {
+                    LockRecord::default()
+                }
+            }
+        };
+
+        let (_, swapped) = client
+            .compare_and_swap(path.to_owned(), previous, next.encode()?)
+            .await
+            .map_err(parse_tikv_error)?;
+        if !swapped {
+            return Err(Error::new(
+                ErrorKind::ConditionNotMatch,
+                "lock has been changed concurrently",
+            ));
+        }
+        Ok(())
+    }
 }
 
 fn parse_tikv_error(e: tikv_client::Error) -> Error {
@@ -82,6 +82,10 @@ pub struct Capability {
     pub stat_with_override_content_type: bool,
     /// Indicates if versions stat operations are supported.
     pub stat_with_version: bool,
+    /// Indicates if stat operations on multiple paths in one request are supported.
+    pub stat_many: bool,
+    /// Maximum number of paths supported for a single batch stat operation.
+    pub stat_many_max_size: Option<usize>,
 
     /// Indicates if the operator supports read operations.
     pub read: bool,
@@ -126,6 +130,8 @@ pub struct Capability {
     pub write_with_if_not_exists: bool,
     /// Indicates if custom user metadata can be attached during write operations.
     pub write_with_user_metadata: bool,
+    /// Indicates if multipart uploads can be resumed from an exported upload state.
+    pub write_with_resume: bool,
     /// Maximum size supported for multipart uploads.
     /// For example, AWS S3 supports up to 5GiB per part in multipart uploads.
     pub write_multi_max_size: Option<usize>,
@@ -145,14 +151,40 @@ pub struct Capability {
     pub delete_with_version: bool,
     /// Maximum size supported for single delete operations.
     pub delete_max_size: Option<usize>,
+    /// Indicates if conditional delete operations with if-match are supported.
+    pub delete_with_if_match: bool,
+    /// Indicates if conditional delete operations with if-none-match are supported.
+    pub delete_with_if_none_match: bool,
+    /// Indicates if conditional delete operations with if-unmodified-since are supported.
+    pub delete_with_if_unmodified_since: bool,
 
     /// Indicates if copy operations are supported.
     pub copy: bool,
     /// Indicates if conditional copy operations with if-not-exists are supported.
     pub copy_with_if_not_exists: bool,
+    /// Indicates if conditional copy operations with target if-match are supported.
+    pub copy_with_if_match: bool,
+    /// Indicates if conditional copy operations with source if-match are supported.
+    pub copy_with_source_if_match: bool,
+    /// Indicates if conditional copy operations with source if-none-match are supported.
+    pub copy_with_source_if_none_match: bool,
+    /// Indicates if conditional copy operations with source if-unmodified-since are supported.
+    pub copy_with_source_if_unmodified_since: bool,
+    /// Indicates if copy operations from a specific source version are supported.
+    pub copy_with_source_version: bool,
 
     /// Indicates if rename operations are supported.
     pub rename: bool,
+    /// Indicates if conditional rename operations with if-not-exists are supported.
+    pub rename_with_if_not_exists: bool,
+    /// Indicates if conditional rename operations with target if-match are supported.
+    pub rename_with_if_match: bool,
+    /// Indicates if conditional rename operations with source if-match are supported.
+    pub rename_with_source_if_match: bool,
+    /// Indicates if conditional rename operations with source if-none-match are supported.
+    pub rename_with_source_if_none_match: bool,
+    /// Indicates if conditional rename operations with source if-unmodified-since are supported.
+    pub rename_with_source_if_unmodified_since: bool,
 
     /// Indicates if list operations are supported.
     pub list: bool,
@@ -170,6 +202,24 @@ pub struct Capability {
     /// Indicates if listing with deleted files included is supported.
     pub list_with_deleted: bool,
 
+    /// Indicates if incomplete multipart uploads can be listed.
+    pub list_uploads: bool,
+    /// Indicates if incomplete multipart uploads can be aborted.
+    pub abort_upload: bool,
+
+    /// Indicates if native lock (lease) is supported.
+    pub lock: bool,
+
+    /// Indicates if concatenating files into one on the server side is supported.
+    pub concat: bool,
+    /// Maximum number of sources supported for single concat operations.
+    pub concat_max_sources: Option<usize>,
+
+    /// Indicates if updating metadata without rewriting the content is supported.
+    pub update_metadata: bool,
+    /// Indicates if tags can be replaced by update_metadata operations.
+    pub update_metadata_with_tags: bool,
+
     /// Indicates if presigned URL generation is supported.
     pub presign: bool,
     /// Indicates if presigned URLs for read operations are supported.
@@ -180,6 +230,10 @@ pub struct Capability {
     pub presign_write: bool,
     /// Indicates if presigned URLs for delete operations are supported.
     pub presign_delete: bool,
+    /// Indicates if presigned POST forms with policy conditions are supported.
+    pub presign_post: bool,
+    /// Indicates if presigned URLs for multipart uploads are supported.
+    pub presign_multipart: bool,
 
     /// Indicate if the operator supports shared access.
     pub shared: bool,
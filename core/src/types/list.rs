@@ -45,7 +45,14 @@ unsafe impl Sync for Lister {}
 impl Lister {
     /// Create a new lister.
     pub(crate) async fn create(acc: Accessor, path: &str, args: OpList) -> Result<Self> {
-        let (_, lister) = acc.list(path, args).await?;
+        let mut lister: oio::Lister = if args.recursive() && args.concurrent() > 1 {
+            Box::new(oio::ConcurrentLister::create(acc.clone(), path, args.clone()).await?)
+        } else {
+            acc.list(path, args.clone()).await?.1
+        };
+        if !args.metadata().is_empty() {
+            lister = Box::new(oio::MetadataLister::new(acc, lister, &args));
+        }
 
         Ok(Self {
             lister: Some(lister),
@@ -0,0 +1,315 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::raw::*;
+use crate::*;
+
+/// Lease is the guard of a lock acquired by [`Operator::lock`].
+///
+/// The lock is held until the lease is released or expired. Users should
+/// call [`Lease::renew`] before [`Lease::expires_at`] to keep holding it.
+///
+/// Dropping a lease without calling [`Lease::release`] won't release the
+/// lock, others can only acquire it after the ttl expires.
+pub struct Lease {
+    op: Operator,
+    path: String,
+    token: String,
+    ttl: Duration,
+    expires_at: DateTime<Utc>,
+    /// The etag of the lock record, `None` if the lock is native.
+    etag: Option<String>,
+    released: bool,
+}
+
+impl std::fmt::Debug for Lease {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Lease")
+            .field("path", &self.path)
+            .field("token", &self.token)
+            .field("ttl", &self.ttl)
+            .field("expires_at", &self.expires_at)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Lease {
+    /// Acquire the lock of path.
+    ///
+    /// Services with [`Capability::lock`] will use their native lock,
+    /// others will write a lock record with `if_not_exists`, take over
+    /// expired records with `if_match` and release it by deleting with
+    /// `if_match`.
+    pub(crate) async fn acquire(op: Operator, path: &str, ttl: Duration) -> Result<Lease> {
+        let cap = op.info().full_capability();
+        let token = uuid::Uuid::new_v4().to_string();
+
+        let mut lease = Lease {
+            op,
+            path: path.to_string(),
+            token,
+            ttl,
+            expires_at: Utc::now(),
+            etag: None,
+            released: false,
+        };
+
+        if cap.lock {
+            lease.call_native(LockAction::Acquire).await?;
+        } else if cap.read
+            && cap.stat
+            && cap.write_with_if_not_exists
+            && cap.write_with_if_match
+            && cap.delete_with_if_match
+        {
+            lease.acquire_emulated().await?;
+        } else {
+            // Mark as released so that drop won't try to release it.
+            lease.released = true;
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "lock requires native lock or write with if_not_exists, if_match and delete with if_match",
+            )
+            .with_operation(Operation::Lock)
+            .with_context("service", lease.op.info().scheme())
+            .with_context("path", path));
+        }
+        Ok(lease)
+    }
+
+    /// Get the path of this lease.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Get the token that identifies the holder of this lease.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Get the ttl of this lease.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Get the time when this lease will expire if not renewed.
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        self.expires_at
+    }
+
+    /// Check if this lease has expired.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
+    }
+
+    /// Renew this lease with its ttl.
+    ///
+    /// Returns [`ErrorKind::ConditionNotMatch`] if the lock is not held by
+    /// this lease anymore.
+    pub async fn renew(&mut self) -> Result<()> {
+        if self.etag.is_none() {
+            return self.call_native(LockAction::Renew).await;
+        }
+
+        if self.is_expired() {
+            return Err(
+                Error::new(ErrorKind::ConditionNotMatch, "lease has expired")
+                    .with_operation(Operation::Lock)
+                    .with_context("path", &self.path),
+            );
+        }
+        let etag = self.etag.clone().unwrap_or_default();
+        self.write_record(Some(&etag)).await
+    }
+
+    /// Release this lease.
+    ///
+    /// Returns [`ErrorKind::ConditionNotMatch`] if the lock is not held by
+    /// this lease anymore.
+    pub async fn release(mut self) -> Result<()> {
+        self.released = true;
+        release(&self.op, &self.path, &self.token, self.etag.take()).await
+    }
+
+    async fn call_native(&mut self, action: LockAction) -> Result<()> {
+        let expires_at = Utc::now() + self.ttl;
+        self.op
+            .inner()
+            .lock(&self.path, OpLock::new(action, &self.token, self.ttl))
+            .await?;
+        self.expires_at = expires_at;
+        Ok(())
+    }
+
+    async fn acquire_emulated(&mut self) -> Result<()> {
+        let err = match self.write_record(None).await {
+            Ok(()) => return Ok(()),
+            Err(err) if err.kind() == ErrorKind::ConditionNotMatch => err,
+            Err(err) => return Err(err),
+        };
+
+        // The record exists, take it over if it has expired. The record read
+        // here may be newer than the etag, but the takeover will fail then.
+        let meta = self.op.stat(&self.path).await?;
+        let etag = meta.etag().ok_or_else(|| missing_etag(&self.path))?;
+        let record = LockRecord::decode(&self.op.read(&self.path).await?.to_vec())?;
+        if !record.is_expired() {
+            return Err(err.with_context("reason", "lock is held by others"));
+        }
+        self.write_record(Some(etag)).await
+    }
+
+    /// Write the lock record with `if_not_exists` or `if_match`.
+    async fn write_record(&mut self, etag: Option<&str>) -> Result<()> {
+        let record = LockRecord::new(&self.token, self.ttl);
+        let mut fut = self.op.write_with(&self.path, record.encode()?);
+        fut = match etag {
+            Some(etag) => fut.if_match(etag),
+            None => fut.if_not_exists(true),
+        };
+        let meta = fut.await?;
+
+        let etag = match meta.etag() {
+            Some(etag) => etag.to_string(),
+            None => self
+                .op
+                .stat(&self.path)
+                .await?
+                .etag()
+                .ok_or_else(|| missing_etag(&self.path))?
+                .to_string(),
+        };
+        self.etag = Some(etag);
+        self.expires_at = record.expires_at();
+        Ok(())
+    }
+}
+
+impl Drop for Lease {
+    fn drop(&mut self) {
+        if self.released {
+            return;
+        }
+
+        // Drop could happen outside of a runtime, so we can't release it here.
+        log::warn!(
+            "lease of {} dropped without release, the lock will be held until {}",
+            self.path,
+            self.expires_at
+        );
+    }
+}
+
+async fn release(op: &Operator, path: &str, token: &str, etag: Option<String>) -> Result<()> {
+    let Some(etag) = etag else {
+        op.inner()
+            .lock(
+                path,
+                OpLock::new(LockAction::Release, token, Duration::ZERO),
+            )
+            .await?;
+        return Ok(());
+    };
+
+    op.delete_with(path).if_match(&etag).await
+}
+
+fn missing_etag(path: &str) -> Error {
+    Error::new(
+        ErrorKind::Unsupported,
+        "lock requires etag of the lock record but service doesn't return it",
+    )
+    .with_operation(Operation::Lock)
+    .with_context("path", path)
+}
+
+/// LockRecord is the content of lock records written by services without
+/// native lease support.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct LockRecord {
+    pub token: String,
+    /// Expire time in milliseconds since unix epoch.
+    pub expires_at: i64,
+}
+
+impl LockRecord {
+    /// Create a new lock record that expires after ttl.
+    pub fn new(token: &str, ttl: Duration) -> Self {
+        let expires_at = Utc::now() + ttl;
+        Self {
+            token: token.to_string(),
+            expires_at: expires_at.timestamp_millis(),
+        }
+    }
+
+    /// Get the expire time of this record.
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp_millis(self.expires_at).unwrap_or_default()
+    }
+
+    /// Check if this record has expired.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now().timestamp_millis()
+    }
+
+    pub fn decode(bs: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bs).map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "lock record is invalid").set_source(err)
+        })
+    }
+
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self).map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "lock record can't be encoded").set_source(err)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_lock_unsupported() {
+        let op = Operator::new(Memory::default()).unwrap().finish();
+
+        let err = op
+            .lock("lock", Duration::from_secs(10))
+            .await
+            .expect_err("memory doesn't support lock");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn test_lock_record() {
+        let record = LockRecord::new("token", Duration::from_secs(10));
+        assert!(!record.is_expired());
+        assert_eq!(
+            LockRecord::decode(&record.encode().unwrap()).unwrap(),
+            record
+        );
+        assert!(LockRecord::default().is_expired());
+    }
+}
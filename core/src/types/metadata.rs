@@ -19,6 +19,7 @@ use std::collections::HashMap;
 
 use chrono::prelude::*;
 
+use crate::options::MetadataFields;
 use crate::raw::*;
 use crate::*;
 
@@ -433,4 +434,18 @@ impl Metadata {
         self.user_metadata = Some(data);
         self
     }
+
+    /// Check if all given fields have been set in this metadata.
+    pub(crate) fn contains_fields(&self, fields: &MetadataFields) -> bool {
+        (!fields.content_length || self.content_length.is_some())
+            && (!fields.content_md5 || self.content_md5.is_some())
+            && (!fields.content_type || self.content_type.is_some())
+            && (!fields.content_encoding || self.content_encoding.is_some())
+            && (!fields.content_disposition || self.content_disposition.is_some())
+            && (!fields.cache_control || self.cache_control.is_some())
+            && (!fields.etag || self.etag.is_some())
+            && (!fields.last_modified || self.last_modified.is_some())
+            && (!fields.version || self.version.is_some())
+            && (!fields.user_metadata || self.user_metadata.is_some())
+    }
 }
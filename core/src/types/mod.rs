@@ -39,9 +39,23 @@ pub use list::Lister;
 mod delete;
 pub use delete::*;
 
+mod transfer;
+pub use transfer::Transfer;
+pub use transfer::TransferProgress;
+pub use transfer::TransferReport;
+
 mod execute;
 pub use execute::*;
 
+mod lock;
+pub use lock::Lease;
+
+mod transaction;
+#[cfg(feature = "services-tikv")]
+pub(crate) use lock::LockRecord;
+pub use transaction::Manifest;
+pub use transaction::Transaction;
+
 mod operator;
 pub use operator::operator_futures;
 pub use operator::Operator;
@@ -250,6 +250,8 @@ impl Operator {
             Scheme::Persy => Self::from_iter::<services::Persy>(iter)?.finish(),
             #[cfg(feature = "services-redis")]
             Scheme::Redis => Self::from_iter::<services::Redis>(iter)?.finish(),
+            #[cfg(feature = "services-replay")]
+            Scheme::Replay => Self::from_iter::<services::Replay>(iter)?.finish(),
             #[cfg(feature = "services-rocksdb")]
             Scheme::Rocksdb => Self::from_iter::<services::Rocksdb>(iter)?.finish(),
             #[cfg(feature = "services-s3")]
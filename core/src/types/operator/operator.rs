@@ -26,6 +26,7 @@ use crate::operator_futures::*;
 use crate::raw::oio::DeleteDyn;
 use crate::raw::*;
 use crate::types::delete::Deleter;
+use crate::types::transfer::cancelled_error;
 use crate::*;
 
 /// The `Operator` serves as the entry point for all public asynchronous APIs.
@@ -408,6 +409,138 @@ impl Operator {
         Ok(rp.into_metadata())
     }
 
+    /// Retrieve the metadata of multiple paths.
+    ///
+    /// # Notes
+    ///
+    /// - Results are returned in the same order as the given paths.
+    /// - The returned `Err` means the whole operation failed, while every path could
+    ///   still fail individually, for example, with [`ErrorKind::NotFound`].
+    /// - If the service supports [`Capability::stat_many`], paths will be sent in batches.
+    ///   Otherwise, every path will be sent as a separate stat request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let results = op.stat_many(vec!["a.txt", "b.txt"]).await?;
+    /// for res in results {
+    ///     match res {
+    ///         Ok(meta) => println!("size: {}", meta.content_length()),
+    ///         Err(err) => println!("stat failed: {err}"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn stat_many(
+        &self,
+        paths: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Vec<Result<Metadata>>> {
+        self.stat_many_options(paths, options::StatManyOptions::default())
+            .await
+    }
+
+    /// Retrieve the metadata of multiple paths with additional options.
+    ///
+    /// # Options
+    ///
+    /// Check [`options::StatManyOptions`] for all available options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// use opendal::options;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let results = op
+    ///     .stat_many_options(vec!["a.txt", "b.txt"], options::StatManyOptions {
+    ///         concurrent: 8,
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn stat_many_options(
+        &self,
+        paths: impl IntoIterator<Item = impl AsRef<str>>,
+        opts: options::StatManyOptions,
+    ) -> Result<Vec<Result<Metadata>>> {
+        let paths: Vec<String> = paths
+            .into_iter()
+            .map(|p| normalize_path(p.as_ref()))
+            .collect();
+        if paths.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let info = self.accessor.info();
+        let cap = info.native_capability();
+        let (native, batch_size) = if cap.stat_many {
+            (true, cap.stat_many_max_size.unwrap_or(paths.len()).max(1))
+        } else {
+            (false, 1)
+        };
+
+        let mut tasks = ConcurrentTasks::new(
+            info.executor(),
+            opts.concurrent.max(1),
+            0,
+            |(acc, paths, native): (Accessor, Vec<String>, bool)| {
+                Box::pin(async move {
+                    let res = Self::stat_many_inner(&acc, &paths, native).await;
+                    ((acc, paths, native), res)
+                })
+            },
+        );
+
+        let mut results = Vec::with_capacity(paths.len());
+        for chunk in paths.chunks(batch_size) {
+            if tasks.has_result() {
+                results.extend(tasks.next().await.expect("result must be available")?);
+            }
+            tasks
+                .execute((self.accessor.clone(), chunk.to_vec(), native))
+                .await?;
+        }
+        while let Some(res) = tasks.next().await {
+            results.extend(res?);
+        }
+
+        Ok(results)
+    }
+
+    async fn stat_many_inner(
+        acc: &Accessor,
+        paths: &[String],
+        native: bool,
+    ) -> Result<Vec<Result<Metadata>>> {
+        if !native {
+            let mut results = Vec::with_capacity(paths.len());
+            for path in paths {
+                let res = acc.stat(path, OpStat::new()).await;
+                results.push(res.map(|rp| rp.into_metadata()));
+            }
+            return Ok(results);
+        }
+
+        let args = paths.iter().map(|p| (p.clone(), OpStat::new())).collect();
+        let results = acc.stat_many(args).await?.into_results();
+        if results.len() != paths.len() {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "stat_many returned unexpected number of results",
+            )
+            .with_context("expected", paths.len().to_string())
+            .with_context("actual", results.len().to_string()));
+        }
+        Ok(results)
+    }
+
     /// Check whether this path exists.
     ///
     /// # Example
@@ -1009,7 +1142,8 @@ impl Operator {
             );
         }
 
-        let (args, opts) = opts.into();
+        let (args, opts): (OpWrite, OpWriter) = opts.into();
+        let args = args.with_upload_tracker(oio::UploadTracker::new());
         let context = WriteContext::new(acc, path, args, opts);
         let w = Writer::new(context).await?;
         Ok(w)
@@ -1153,220 +1287,880 @@ impl Operator {
         Self::copy_inner(self.inner().clone(), from, (opts, to)).await
     }
 
-    async fn copy_inner(
-        acc: Accessor,
-        from: String,
-        (opts, to): (options::CopyOptions, String),
-    ) -> Result<()> {
-        if !validate_path(&from, EntryMode::FILE) {
-            return Err(
-                Error::new(ErrorKind::IsADirectory, "from path is a directory")
-                    .with_operation("Operator::copy")
-                    .with_context("service", acc.info().scheme())
-                    .with_context("from", from),
-            );
+    /// Concatenate `sources` into one file at `to` in order.
+    ///
+    /// # Notes
+    ///
+    /// - `sources` and `to` must be files, `to` will be overwritten if it exists.
+    /// - If the service supports [`Capability::concat`] (like gcs compose and s3
+    ///   `UploadPartCopy`), sources will be concatenated on the server side without
+    ///   downloading them. Sources that exceed [`Capability::concat_max_sources`] will be
+    ///   concatenated in batches into a temporary file beside `to`, which is renamed (or
+    ///   copied) to `to` at the end. `to` is only replaced once all batches succeed.
+    /// - Otherwise, sources will be streamed from reader to writer, and `to` must not be
+    ///   one of the sources.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.concat(["day/part-0.parquet", "day/part-1.parquet"], "day.parquet")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn concat<I, S>(&self, sources: I, to: &str) -> Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let to = normalize_path(to);
+        let sources: Vec<String> = sources
+            .into_iter()
+            .map(|v| normalize_path(v.as_ref()))
+            .collect();
+
+        let new_error = |kind, message| {
+            Error::new(kind, message)
+                .with_operation("Operator::concat")
+                .with_context("service", self.info().scheme())
+                .with_context("to", &to)
+        };
+        if sources.is_empty() {
+            return Err(new_error(ErrorKind::Unexpected, "sources are empty"));
         }
-
-        if !validate_path(&to, EntryMode::FILE) {
-            return Err(
-                Error::new(ErrorKind::IsADirectory, "to path is a directory")
-                    .with_operation("Operator::copy")
-                    .with_context("service", acc.info().scheme())
-                    .with_context("to", to),
-            );
+        if !validate_path(&to, EntryMode::FILE)
+            || sources.iter().any(|v| !validate_path(v, EntryMode::FILE))
+        {
+            return Err(new_error(
+                ErrorKind::IsADirectory,
+                "sources or to path is a directory",
+            ));
         }
 
-        if from == to {
-            return Err(
-                Error::new(ErrorKind::IsSameFile, "from and to paths are same")
-                    .with_operation("Operator::copy")
-                    .with_context("service", acc.info().scheme())
-                    .with_context("from", &from)
-                    .with_context("to", &to),
-            );
+        let cap = self.info().full_capability();
+        if !cap.concat {
+            if sources.contains(&to) {
+                return Err(new_error(
+                    ErrorKind::IsSameFile,
+                    "to path is one of the sources",
+                ));
+            }
+            return self.concat_by_stream(&sources, &to).await;
         }
 
-        let mut op = OpCopy::new();
-        if opts.if_not_exists {
-            op = op.with_if_not_exists(true);
+        // Keep at least 2 sources in every batch so that the batches make progress.
+        let max = cap.concat_max_sources.unwrap_or(usize::MAX).max(2);
+        if sources.len() <= max {
+            self.inner().concat(&to, OpConcat::new(sources)).await?;
+            return Ok(());
+        }
+        if !cap.rename && !cap.copy {
+            return Err(new_error(
+                ErrorKind::Unsupported,
+                "sources exceed concat_max_sources but service can't rename or copy",
+            ));
         }
 
-        acc.copy(&from, &to, op).await.map(|_| ())
+        // Build the result in a temporary file beside `to` so that readers of `to` never
+        // observe a partial result, and `to` stays intact if any batch fails.
+        let tmp = format!("{to}.{}.concat", uuid::Uuid::new_v4());
+        let res = self.concat_batches(&sources, &tmp, max).await;
+        let res = match res {
+            Ok(()) if cap.rename => self
+                .inner()
+                .rename(&tmp, &to, OpRename::new())
+                .await
+                .map(|_| ()),
+            Ok(()) => self
+                .inner()
+                .copy(&tmp, &to, OpCopy::new())
+                .await
+                .map(|_| ()),
+            Err(err) => Err(err),
+        };
+        // Ignore the cleanup error since `to` has been published or the error is returned.
+        if res.is_err() || !cap.rename {
+            let _ = self.delete(&tmp).await;
+        }
+        res
+    }
+
+    /// Concat the first batch into `to`, then append the following batches to it.
+    async fn concat_batches(&self, sources: &[String], to: &str, max: usize) -> Result<()> {
+        let (first, rest) = sources.split_at(max);
+        self.inner()
+            .concat(to, OpConcat::new(first.to_vec()))
+            .await?;
+        for batch in rest.chunks(max - 1) {
+            let mut batch_sources = Vec::with_capacity(batch.len() + 1);
+            batch_sources.push(to.to_string());
+            batch_sources.extend_from_slice(batch);
+            self.inner()
+                .concat(to, OpConcat::new(batch_sources))
+                .await?;
+        }
+        Ok(())
     }
 
-    /// Rename a file from `from` to `to`.
+    /// Update the metadata of the file at `path` without rewriting its content.
     ///
     /// # Notes
     ///
-    /// - `from` and `to` must be a file.
-    /// - `to` will be overwritten if it exists.
-    /// - If `from` and `to` are the same, an `IsSameFile` error will occur.
+    /// - Only the given changes will be applied, other metadata will be kept.
+    /// - Tags are replaced as a whole if given, check [`Capability::update_metadata_with_tags`]
+    ///   before setting them.
+    /// - Services like gcs and azblob update metadata in place, while s3 like services
+    ///   copy the file onto itself with the new metadata on the server side.
+    ///
+    /// # Capability
+    ///
+    /// Check [`Capability::update_metadata`] before using this operation.
     ///
     /// # Examples
     ///
     /// ```
     /// # use opendal::Result;
     /// # use opendal::Operator;
+    /// # use opendal::options::UpdateMetadataOptions;
     ///
     /// # async fn test(op: Operator) -> Result<()> {
-    /// op.rename("path/to/file", "path/to/file2").await?;
+    /// let mut changes = UpdateMetadataOptions::default();
+    /// changes.content_type = Some("application/parquet".to_string());
+    /// changes
+    ///     .user_metadata
+    ///     .insert("owner".to_string(), Some("alice".to_string()));
+    /// // Remove the `draft` key from user metadata.
+    /// changes.user_metadata.insert("draft".to_string(), None);
+    /// op.update_metadata("path/to/file", changes).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn rename(&self, from: &str, to: &str) -> Result<()> {
-        let from = normalize_path(from);
+    pub async fn update_metadata(
+        &self,
+        path: &str,
+        changes: impl Into<options::UpdateMetadataOptions>,
+    ) -> Result<()> {
+        let path = normalize_path(path);
 
-        if !validate_path(&from, EntryMode::FILE) {
-            return Err(
-                Error::new(ErrorKind::IsADirectory, "from path is a directory")
-                    .with_operation("Operator::move_")
-                    .with_context("service", self.info().scheme())
-                    .with_context("from", from),
-            );
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(Error::new(ErrorKind::IsADirectory, "path is a directory")
+                .with_operation("Operator::update_metadata")
+                .with_context("service", self.info().scheme())
+                .with_context("path", path));
         }
 
-        let to = normalize_path(to);
+        self.inner()
+            .update_metadata(&path, OpUpdateMetadata::from(changes.into()))
+            .await?;
+        Ok(())
+    }
 
-        if !validate_path(&to, EntryMode::FILE) {
-            return Err(
-                Error::new(ErrorKind::IsADirectory, "to path is a directory")
-                    .with_operation("Operator::move_")
-                    .with_context("service", self.info().scheme())
-                    .with_context("to", to),
-            );
+    async fn concat_by_stream(&self, sources: &[String], to: &str) -> Result<()> {
+        let mut writer = self.writer(to).await?;
+        let res = async {
+            for source in sources {
+                let mut stream = self.reader(source).await?.into_stream(..).await?;
+                while let Some(bs) = stream.try_next().await? {
+                    writer.write(bs).await?;
+                }
+            }
+            writer.close().await?;
+            Ok(())
         }
+        .await;
 
-        if from == to {
-            return Err(
-                Error::new(ErrorKind::IsSameFile, "from and to paths are same")
-                    .with_operation("Operator::move_")
-                    .with_context("service", self.info().scheme())
-                    .with_context("from", from)
-                    .with_context("to", to),
-            );
+        if res.is_err() {
+            // Ignore the abort error, the original error is more important.
+            let _ = writer.abort().await;
         }
-
-        self.inner().rename(&from, &to, OpRename::new()).await?;
-
-        Ok(())
+        res
     }
 
-    /// Delete the given path.
+    /// Copy all files under `from` into `to`.
+    ///
+    /// Returns a [`Transfer`] handle which could be used to observe the progress, cancel
+    /// the operation and get the final [`TransferReport`].
     ///
     /// # Notes
     ///
-    /// - Deleting a file that does not exist won't return errors.
+    /// - If `from` is a file, it will be copied to `to` directly.
+    /// - If `from` is a dir, `to` must be a dir too. Every file under `from` will be copied
+    ///   to the same relative path under `to`.
+    /// - If the service supports [`Capability::copy`], files will be copied server-side.
+    ///   Otherwise, they will be streamed from reader to writer.
+    /// - Failures of single files will not stop the operation, check
+    ///   [`TransferReport::failures`] for them.
     ///
     /// # Examples
     ///
     /// ```
     /// # use anyhow::Result;
-    /// # use futures::io;
     /// # use opendal::Operator;
     /// # async fn test(op: Operator) -> Result<()> {
-    /// op.delete("test").await?;
+    /// let report = op.copy_all("path/to/src/", "path/to/dst/").await?;
+    /// assert!(report.failures.is_empty());
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn delete(&self, path: &str) -> Result<()> {
-        self.delete_with(path).await
+    pub fn copy_all(&self, from: &str, to: &str) -> Transfer {
+        self.copy_all_options(from, to, options::CopyAllOptions::default())
     }
 
-    /// Delete the given path with additional options.
-    ///
-    /// # Notes
-    ///
-    /// - Deleting a file that does not exist won't return errors.
+    /// Copy all files under `from` into `to` with additional options.
     ///
     /// # Options
     ///
-    /// Visit [`options::DeleteOptions`] for all available options.
+    /// Check [`options::CopyAllOptions`] for all available options.
     ///
     /// # Examples
     ///
-    /// Delete a specific version of a file:
-    ///
     /// ```
-    /// # use opendal::Result;
+    /// # use anyhow::Result;
     /// # use opendal::Operator;
+    /// use opendal::options;
     ///
-    /// # async fn test(op: Operator, version: &str) -> Result<()> {
-    /// op.delete_with("path/to/file").version(version).await?;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let transfer = op.copy_all_options("path/to/src/", "path/to/dst/", options::CopyAllOptions {
+    ///     concurrent: 8,
+    ///     ..Default::default()
+    /// });
+    /// let progress = transfer.progress();
+    /// tokio::spawn(async move {
+    ///     // Stop the transfer after 10 seconds.
+    ///     tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+    ///     progress.cancel();
+    /// });
+    /// let report = transfer.await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn delete_with(&self, path: &str) -> FutureDelete<impl Future<Output = Result<()>>> {
-        let path = normalize_path(path);
-
-        OperatorFuture::new(
-            self.inner().clone(),
-            path,
-            options::DeleteOptions::default(),
-            Self::delete_inner,
-        )
+    pub fn copy_all_options(
+        &self,
+        from: &str,
+        to: &str,
+        opts: options::CopyAllOptions,
+    ) -> Transfer {
+        let from = normalize_path(from);
+        let to = normalize_path(to);
+        Transfer::copy_all(self.clone(), from, to, opts)
     }
 
-    /// Delete the given path with additional options.
+    /// Copy a file from this operator into another operator.
+    ///
+    /// Returns the metadata of the copied file in the target operator.
     ///
     /// # Notes
     ///
-    /// - Deleting a file that does not exist won't return errors.
+    /// - Data will be streamed from a [`Reader`] of this operator into a [`Writer`] of the
+    ///   target operator, no data will be buffered in memory except the ongoing chunks.
+    /// - `content_type`, `content_disposition`, `content_encoding`, `cache_control` and
+    ///   `user_metadata` will be carried over if the target operator supports them.
+    /// - The source will be read with `if_match` if supported, so that changes during copying
+    ///   will be reported as [`ErrorKind::ConditionNotMatch`].
+    /// - The size of the copied file will be verified after copying. `Content-MD5` or `ETag`
+    ///   will also be verified if both sides carry a content MD5, since different services
+    ///   compute `ETag` differently.
+    /// - The upload will be aborted if the copy failed, use [`Operator::copy_to_resumable`]
+    ///   to keep it for resuming later.
     ///
     /// # Examples
     ///
-    /// Delete a specific version of a file:
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator, target: Operator) -> Result<()> {
+    /// let meta = op.copy_to(&target, "path/to/file", "path/to/file").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy_to(&self, target: &Operator, from: &str, to: &str) -> Result<Metadata> {
+        self.copy_to_options(target, from, to, options::CopyToOptions::default())
+            .await
+    }
+
+    /// Copy a file from this operator into another operator with additional options.
+    ///
+    /// # Options
+    ///
+    /// Check [`options::CopyToOptions`] for all available options.
+    ///
+    /// # Examples
     ///
     /// ```
-    /// # use opendal::Result;
+    /// # use anyhow::Result;
     /// # use opendal::Operator;
     /// use opendal::options;
     ///
-    /// # async fn test(op: Operator, version: &str) -> Result<()> {
-    /// op.delete_options("path/to/file", options::DeleteOptions {
-    ///     version: Some(version.to_string()),
-    ///     ..Default::default()
-    /// })
-    /// .await?;
+    /// # async fn test(op: Operator, target: Operator) -> Result<()> {
+    /// let meta = op
+    ///     .copy_to_options(&target, "path/to/file", "path/to/file", options::CopyToOptions {
+    ///         concurrent: 8,
+    ///         chunk: Some(8 * 1024 * 1024),
+    ///         ..Default::default()
+    ///     })
+    ///     .await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn delete_options(&self, path: &str, opts: options::DeleteOptions) -> Result<()> {
-        let path = normalize_path(path);
-        Self::delete_inner(self.inner().clone(), path, opts).await
-    }
-
-    async fn delete_inner(acc: Accessor, path: String, opts: options::DeleteOptions) -> Result<()> {
-        let (_, mut deleter) = acc.delete_dyn().await?;
-        let args = opts.into();
-        deleter.delete_dyn(&path, args)?;
-        deleter.flush_dyn().await?;
-        Ok(())
+    pub async fn copy_to_options(
+        &self,
+        target: &Operator,
+        from: &str,
+        to: &str,
+        opts: options::CopyToOptions,
+    ) -> Result<Metadata> {
+        self.copy_to_inner(target, from, to, opts, false)
+            .await
+            .map_err(Error::from)
     }
 
-    /// Delete an infallible iterator of paths.
+    /// Copy a file from this operator into another operator, keeping the upload if failed.
     ///
-    /// Also see:
+    /// This is the same as [`Operator::copy_to_options`], except that if the target supports
+    /// [`Capability::write_with_resume`], a failed copy keeps its multipart upload instead of
+    /// aborting it. The state of the kept upload could be got by
+    /// [`CopyToError::upload_state`] and passed to [`options::CopyToOptions::resume`] to
+    /// continue the copy later.
     ///
-    /// - [`Operator::delete_try_iter`]: delete a fallible iterator of paths.
-    /// - [`Operator::delete_stream`]: delete an infallible stream of paths.
-    /// - [`Operator::delete_try_stream`]: delete a fallible stream of paths.
-    pub async fn delete_iter<I, D>(&self, iter: I) -> Result<()>
-    where
-        I: IntoIterator<Item = D>,
-        D: IntoDeleteInput,
-    {
-        let mut deleter = self.deleter().await?;
-        deleter.delete_iter(iter).await?;
-        deleter.close().await?;
-        Ok(())
-    }
-
-    /// Delete a fallible iterator of paths.
+    /// Users should abort the kept upload by [`Operator::abort_uploads`] if they decide not
+    /// to resume it.
     ///
-    /// Also see:
+    /// # Examples
     ///
-    /// - [`Operator::delete_iter`]: delete an infallible iterator of paths.
-    /// - [`Operator::delete_stream`]: delete an infallible stream of paths.
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// use opendal::options;
+    ///
+    /// # async fn test(op: Operator, target: Operator) -> Result<()> {
+    /// let meta = match op
+    ///     .copy_to_resumable(&target, "path/to/file", "path/to/file", options::CopyToOptions::default())
+    ///     .await
+    /// {
+    ///     Ok(meta) => meta,
+    ///     Err(err) => {
+    ///         let (err, state) = err.into_parts();
+    ///         let Some(state) = state else {
+    ///             return Err(err.into());
+    ///         };
+    ///         op.copy_to_options(&target, "path/to/file", "path/to/file", options::CopyToOptions {
+    ///             resume: Some(state),
+    ///             ..Default::default()
+    ///         })
+    ///         .await?
+    ///     }
+    /// };
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy_to_resumable(
+        &self,
+        target: &Operator,
+        from: &str,
+        to: &str,
+        opts: options::CopyToOptions,
+    ) -> std::result::Result<Metadata, CopyToError> {
+        self.copy_to_inner(target, from, to, opts, true).await
+    }
+
+    async fn copy_to_inner(
+        &self,
+        target: &Operator,
+        from: &str,
+        to: &str,
+        opts: options::CopyToOptions,
+        keep_upload: bool,
+    ) -> std::result::Result<Metadata, CopyToError> {
+        let from = normalize_path(from);
+        let to = normalize_path(to);
+
+        if !validate_path(&from, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "from path is a directory")
+                    .with_operation("Operator::copy_to")
+                    .with_context("service", self.info().scheme())
+                    .with_context("from", from)
+                    .into(),
+            );
+        }
+        if !validate_path(&to, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "to path is a directory")
+                    .with_operation("Operator::copy_to")
+                    .with_context("service", target.info().scheme())
+                    .with_context("to", to)
+                    .into(),
+            );
+        }
+
+        let src = self.stat(&from).await?;
+        let src_cap = self.info().full_capability();
+        let dst_cap = target.info().full_capability();
+
+        let offset = opts.resume.as_ref().map(|v| v.offset()).unwrap_or_default();
+        if opts.resume.is_some() && !dst_cap.write_with_resume {
+            return Err(
+                Error::new(ErrorKind::Unsupported, "target doesn't support resume")
+                    .with_operation("Operator::copy_to")
+                    .with_context("service", target.info().scheme())
+                    .with_context("to", to)
+                    .into(),
+            );
+        }
+        if offset > src.content_length() {
+            return Err(Error::new(
+                ErrorKind::ConditionNotMatch,
+                "upload state is larger than the source",
+            )
+            .with_operation("Operator::copy_to")
+            .with_context("from", from)
+            .with_context("to", to)
+            .with_context("offset", offset.to_string())
+            .with_context("source_size", src.content_length().to_string())
+            .into());
+        }
+
+        let reader = self
+            .reader_options(
+                &from,
+                options::ReaderOptions {
+                    if_match: src
+                        .etag()
+                        .filter(|_| src_cap.read_with_if_match)
+                        .map(|v| v.to_string()),
+                    concurrent: opts.concurrent.max(1),
+                    chunk: opts.chunk,
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        let progress = opts.progress;
+        let mut wopts = options::WriteOptions {
+            concurrent: opts.concurrent.max(1),
+            chunk: opts.chunk,
+            resume: opts.resume,
+            ..Default::default()
+        };
+        if dst_cap.write_with_content_type {
+            wopts.content_type = src.content_type().map(|v| v.to_string());
+        }
+        if dst_cap.write_with_content_disposition {
+            wopts.content_disposition = src.content_disposition().map(|v| v.to_string());
+        }
+        if dst_cap.write_with_content_encoding {
+            wopts.content_encoding = src.content_encoding().map(|v| v.to_string());
+        }
+        if dst_cap.write_with_cache_control {
+            wopts.cache_control = src.cache_control().map(|v| v.to_string());
+        }
+        if dst_cap.write_with_user_metadata {
+            wopts.user_metadata = src.user_metadata().cloned();
+        }
+        let mut writer = target.writer_options(&to, wopts).await?;
+
+        let res = async {
+            let mut stream = reader.into_stream(offset..src.content_length()).await?;
+            while let Some(bs) = stream.try_next().await? {
+                if progress.as_ref().is_some_and(|v| v.is_cancelled()) {
+                    return Err(cancelled_error());
+                }
+                let size = bs.len() as u64;
+                writer.write(bs).await?;
+                if let Some(progress) = &progress {
+                    progress.add_bytes(size);
+                }
+            }
+            writer.close().await
+        }
+        .await;
+        let mut dst = match res {
+            Ok(meta) => meta,
+            Err(err) => {
+                let err = err
+                    .with_operation("Operator::copy_to")
+                    .with_context("from", from)
+                    .with_context("to", to);
+                // Keep the upload so that it could be resumed later.
+                if keep_upload && dst_cap.write_with_resume {
+                    if let Some(state) = writer.upload_state() {
+                        return Err(CopyToError::new(err, Some(state)));
+                    }
+                }
+                // Ignore the abort error, the original error is more important.
+                let _ = writer.abort().await;
+                return Err(err.into());
+            }
+        };
+
+        // Not all services return metadata after write, stat it if needed.
+        if dst.content_length() == 0 && src.content_length() != 0 {
+            dst = target.stat(&to).await?;
+        }
+
+        if dst.content_length() != src.content_length() || !content_hash_matches(&src, &dst) {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "copied file doesn't match the source",
+            )
+            .with_operation("Operator::copy_to")
+            .with_context("from", from)
+            .with_context("to", to)
+            .with_context("source_size", src.content_length().to_string())
+            .with_context("target_size", dst.content_length().to_string())
+            .with_context("source_etag", src.etag().unwrap_or("<none>"))
+            .with_context("target_etag", dst.etag().unwrap_or("<none>"))
+            .into());
+        }
+
+        Ok(dst)
+    }
+
+    /// Move a file from this operator into another operator.
+    ///
+    /// The source file will be deleted after it has been copied and verified.
+    ///
+    /// Refer to [`Operator::copy_to`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator, target: Operator) -> Result<()> {
+    /// let meta = op.move_to(&target, "path/to/file", "path/to/file").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn move_to(&self, target: &Operator, from: &str, to: &str) -> Result<Metadata> {
+        self.move_to_options(target, from, to, options::CopyToOptions::default())
+            .await
+    }
+
+    /// Move a file from this operator into another operator with additional options.
+    ///
+    /// # Options
+    ///
+    /// Check [`options::CopyToOptions`] for all available options.
+    pub async fn move_to_options(
+        &self,
+        target: &Operator,
+        from: &str,
+        to: &str,
+        opts: options::CopyToOptions,
+    ) -> Result<Metadata> {
+        let meta = self.copy_to_options(target, from, to, opts).await?;
+        self.delete(from).await?;
+        Ok(meta)
+    }
+
+    async fn copy_inner(
+        acc: Accessor,
+        from: String,
+        (opts, to): (options::CopyOptions, String),
+    ) -> Result<()> {
+        if !validate_path(&from, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "from path is a directory")
+                    .with_operation("Operator::copy")
+                    .with_context("service", acc.info().scheme())
+                    .with_context("from", from),
+            );
+        }
+
+        if !validate_path(&to, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "to path is a directory")
+                    .with_operation("Operator::copy")
+                    .with_context("service", acc.info().scheme())
+                    .with_context("to", to),
+            );
+        }
+
+        if from == to && opts.source_version.is_none() {
+            return Err(
+                Error::new(ErrorKind::IsSameFile, "from and to paths are same")
+                    .with_operation("Operator::copy")
+                    .with_context("service", acc.info().scheme())
+                    .with_context("from", &from)
+                    .with_context("to", &to),
+            );
+        }
+
+        acc.copy(&from, &to, opts.into()).await.map(|_| ())
+    }
+
+    /// Rename a file from `from` to `to`.
+    ///
+    /// # Notes
+    ///
+    /// - `from` and `to` must be a file.
+    /// - `to` will be overwritten if it exists.
+    /// - If `from` and `to` are the same, an `IsSameFile` error will occur.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.rename("path/to/file", "path/to/file2").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rename(&self, from: &str, to: &str) -> Result<()> {
+        let from = normalize_path(from);
+        let to = normalize_path(to);
+
+        Self::rename_inner(
+            self.inner().clone(),
+            from,
+            (options::RenameOptions::default(), to),
+        )
+        .await
+    }
+
+    /// Rename a file from `from` to `to` with additional options.
+    ///
+    /// # Notes
+    ///
+    /// - `from` and `to` must be a file.
+    /// - If `from` and `to` are the same, an `IsSameFile` error will occur.
+    ///
+    /// # Options
+    ///
+    /// Visit [`options::RenameOptions`] for all available options.
+    ///
+    /// # Examples
+    ///
+    /// Rename a file only if the destination doesn't exist:
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.rename_with("path/to/file", "path/to/file2")
+    ///     .if_not_exists(true)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rename_with(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> FutureRename<impl Future<Output = Result<()>>> {
+        let from = normalize_path(from);
+        let to = normalize_path(to);
+
+        OperatorFuture::new(
+            self.inner().clone(),
+            from,
+            (options::RenameOptions::default(), to),
+            Self::rename_inner,
+        )
+    }
+
+    /// Rename a file from `from` to `to` with additional options.
+    ///
+    /// # Notes
+    ///
+    /// - `from` and `to` must be a file.
+    /// - If `from` and `to` are the same, an `IsSameFile` error will occur.
+    ///
+    /// # Options
+    ///
+    /// Check [`options::RenameOptions`] for all available options.
+    ///
+    /// # Examples
+    ///
+    /// Rename a file only if the source hasn't been changed:
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::options::RenameOptions;
+    ///
+    /// # async fn test(op: Operator, etag: &str) -> Result<()> {
+    /// let opts = RenameOptions {
+    ///     source_if_match: Some(etag.to_string()),
+    ///     ..Default::default()
+    /// };
+    /// op.rename_options("path/to/file", "path/to/file2", opts).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rename_options(
+        &self,
+        from: &str,
+        to: &str,
+        opts: impl Into<options::RenameOptions>,
+    ) -> Result<()> {
+        let from = normalize_path(from);
+        let to = normalize_path(to);
+        let opts = opts.into();
+
+        Self::rename_inner(self.inner().clone(), from, (opts, to)).await
+    }
+
+    async fn rename_inner(
+        acc: Accessor,
+        from: String,
+        (opts, to): (options::RenameOptions, String),
+    ) -> Result<()> {
+        if !validate_path(&from, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "from path is a directory")
+                    .with_operation("Operator::move_")
+                    .with_context("service", acc.info().scheme())
+                    .with_context("from", from),
+            );
+        }
+
+        if !validate_path(&to, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "to path is a directory")
+                    .with_operation("Operator::move_")
+                    .with_context("service", acc.info().scheme())
+                    .with_context("to", to),
+            );
+        }
+
+        if from == to {
+            return Err(
+                Error::new(ErrorKind::IsSameFile, "from and to paths are same")
+                    .with_operation("Operator::move_")
+                    .with_context("service", acc.info().scheme())
+                    .with_context("from", &from)
+                    .with_context("to", &to),
+            );
+        }
+
+        acc.rename(&from, &to, opts.into()).await.map(|_| ())
+    }
+
+    /// Delete the given path.
+    ///
+    /// # Notes
+    ///
+    /// - Deleting a file that does not exist won't return errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use futures::io;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.delete("test").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete(&self, path: &str) -> Result<()> {
+        self.delete_with(path).await
+    }
+
+    /// Delete the given path with additional options.
+    ///
+    /// # Notes
+    ///
+    /// - Deleting a file that does not exist won't return errors.
+    ///
+    /// # Options
+    ///
+    /// Visit [`options::DeleteOptions`] for all available options.
+    ///
+    /// # Examples
+    ///
+    /// Delete a specific version of a file:
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # async fn test(op: Operator, version: &str) -> Result<()> {
+    /// op.delete_with("path/to/file").version(version).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn delete_with(&self, path: &str) -> FutureDelete<impl Future<Output = Result<()>>> {
+        let path = normalize_path(path);
+
+        OperatorFuture::new(
+            self.inner().clone(),
+            path,
+            options::DeleteOptions::default(),
+            Self::delete_inner,
+        )
+    }
+
+    /// Delete the given path with additional options.
+    ///
+    /// # Notes
+    ///
+    /// - Deleting a file that does not exist won't return errors.
+    ///
+    /// # Examples
+    ///
+    /// Delete a specific version of a file:
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// use opendal::options;
+    ///
+    /// # async fn test(op: Operator, version: &str) -> Result<()> {
+    /// op.delete_options("path/to/file", options::DeleteOptions {
+    ///     version: Some(version.to_string()),
+    ///     ..Default::default()
+    /// })
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_options(&self, path: &str, opts: options::DeleteOptions) -> Result<()> {
+        let path = normalize_path(path);
+        Self::delete_inner(self.inner().clone(), path, opts).await
+    }
+
+    async fn delete_inner(acc: Accessor, path: String, opts: options::DeleteOptions) -> Result<()> {
+        let (_, mut deleter) = acc.delete_dyn().await?;
+        let args = opts.into();
+        deleter.delete_dyn(&path, args)?;
+        deleter.flush_dyn().await?;
+        Ok(())
+    }
+
+    /// Delete an infallible iterator of paths.
+    ///
+    /// Also see:
+    ///
+    /// - [`Operator::delete_try_iter`]: delete a fallible iterator of paths.
+    /// - [`Operator::delete_stream`]: delete an infallible stream of paths.
+    /// - [`Operator::delete_try_stream`]: delete a fallible stream of paths.
+    pub async fn delete_iter<I, D>(&self, iter: I) -> Result<()>
+    where
+        I: IntoIterator<Item = D>,
+        D: IntoDeleteInput,
+    {
+        let mut deleter = self.deleter().await?;
+        deleter.delete_iter(iter).await?;
+        deleter.close().await?;
+        Ok(())
+    }
+
+    /// Delete a fallible iterator of paths.
+    ///
+    /// Also see:
+    ///
+    /// - [`Operator::delete_iter`]: delete an infallible iterator of paths.
+    /// - [`Operator::delete_stream`]: delete an infallible stream of paths.
     /// - [`Operator::delete_try_stream`]: delete a fallible stream of paths.
     pub async fn delete_try_iter<I, D>(&self, try_iter: I) -> Result<()>
     where
@@ -1424,47 +2218,71 @@ impl Operator {
         Deleter::create(self.inner().clone()).await
     }
 
-    /// Remove the path and all nested dirs and files recursively.
+    /// Remove the path and all nested dirs and files recursively.
+    ///
+    /// # Notes
+    ///
+    /// If underlying services support delete in batch, we will use batch
+    /// delete instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use futures::io;
+    /// # use opendal::Operator;
+    /// #
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.remove_all("path/to/dir").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn remove_all(&self, path: &str) -> Result<()> {
+        let report = self
+            .remove_all_options(path, options::RemoveAllOptions::default())
+            .await?;
+        match report.failures.into_iter().next() {
+            Some((_, err)) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Remove the path and all nested dirs and files recursively with additional options.
+    ///
+    /// Returns a [`Transfer`] handle which could be used to observe the progress, cancel
+    /// the operation and get the final [`TransferReport`].
     ///
     /// # Notes
     ///
-    /// If underlying services support delete in batch, we will use batch
-    /// delete instead.
+    /// - Files will be removed in batches if underlying services support batch delete.
+    /// - Dirs will be removed after all files under them.
+    /// - Failures of single paths will not stop the operation, check
+    ///   [`TransferReport::failures`] for them.
+    ///
+    /// # Options
+    ///
+    /// Check [`options::RemoveAllOptions`] for all available options.
     ///
     /// # Examples
     ///
     /// ```
     /// # use anyhow::Result;
-    /// # use futures::io;
     /// # use opendal::Operator;
-    /// #
+    /// use opendal::options;
+    ///
     /// # async fn test(op: Operator) -> Result<()> {
-    /// op.remove_all("path/to/dir").await?;
+    /// let report = op
+    ///     .remove_all_options("path/to/dir/", options::RemoveAllOptions { concurrent: 8 })
+    ///     .await?;
+    /// for (path, err) in report.failures {
+    ///     println!("failed to remove {path}: {err}");
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn remove_all(&self, path: &str) -> Result<()> {
-        match self.stat(path).await {
-            // If object exists.
-            Ok(metadata) => {
-                // If the object is a file, we can delete it.
-                if metadata.mode() != EntryMode::DIR {
-                    self.delete(path).await?;
-                    // There may still be objects prefixed with the path in some backend, so we can't return here.
-                }
-            }
-
-            // If dir not found, it may be a prefix in object store like S3,
-            // and we still need to delete objects under the prefix.
-            Err(e) if e.kind() == ErrorKind::NotFound => {}
-
-            // Pass on any other error.
-            Err(e) => return Err(e),
-        };
-
-        let lister = self.lister_with(path).recursive(true).await?;
-        self.delete_try_stream(lister).await?;
-        Ok(())
+    pub fn remove_all_options(&self, path: &str, opts: options::RemoveAllOptions) -> Transfer {
+        let path = normalize_path(path);
+        Transfer::remove_all(self.clone(), path, opts)
     }
 
     /// List entries in the parent directory that start with the specified `path`.
@@ -1746,6 +2564,286 @@ impl Operator {
         let lister = Lister::create(acc, &path, args).await?;
         Ok(lister)
     }
+
+    /// List incomplete multipart uploads whose path starts with the given path.
+    ///
+    /// Uploads left by crashed or cancelled writers will keep occupying storage until
+    /// they are aborted. The returned [`Upload`] could be used to resume the upload via
+    /// [`FutureWriter::resume`] or to abort it via [`Operator::abort_uploads`].
+    ///
+    /// # Capability
+    ///
+    /// Check [`Capability::list_uploads`] before using this feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// for upload in op.list_uploads("path/to/dir/").await? {
+    ///     println!("{} started at {:?}", upload.path(), upload.initiated());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_uploads(&self, path: &str) -> Result<Vec<Upload>> {
+        let path = normalize_path(path);
+        let rp = self
+            .inner()
+            .list_uploads(&path, OpListUploads::new())
+            .await?;
+        Ok(rp.into_uploads())
+    }
+
+    /// Abort all incomplete multipart uploads whose path starts with the given path.
+    ///
+    /// Returns the number of aborted uploads.
+    ///
+    /// # Capability
+    ///
+    /// Check [`Capability::list_uploads`] and [`Capability::abort_upload`] before using
+    /// this feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let aborted = op.abort_uploads("path/to/dir/").await?;
+    /// println!("{aborted} uploads have been aborted");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn abort_uploads(&self, path: &str) -> Result<usize> {
+        let uploads = self.list_uploads(path).await?;
+        for upload in &uploads {
+            self.inner()
+                .abort_upload(upload.path(), OpAbortUpload::new(upload.upload_id()))
+                .await?;
+        }
+        Ok(uploads.len())
+    }
+
+    /// Restore the given version of the file as the current version.
+    ///
+    /// If the version is a delete marker, the marker will be removed so that
+    /// the version before it becomes current again. Otherwise, the version
+    /// will be copied onto the path as a new current version.
+    ///
+    /// # Capability
+    ///
+    /// Check [`Capability::copy_with_source_version`] before using this feature.
+    /// [`Capability::list_with_deleted`] and [`Capability::delete_with_version`]
+    /// are required to remove delete markers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator, version: &str) -> Result<()> {
+    /// op.restore_version("path/to/file", version).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn restore_version(&self, path: &str, version: &str) -> Result<()> {
+        let path = normalize_path(path);
+
+        let cap = self.info().full_capability();
+        if cap.list_with_deleted && cap.delete_with_version {
+            let entries = self.list_with(&path).versions(true).deleted(true).await?;
+            let is_delete_marker = entries.iter().any(|e| {
+                e.path() == path
+                    && e.metadata().version() == Some(version)
+                    && e.metadata().is_deleted()
+            });
+            if is_delete_marker {
+                return self.delete_with(&path).version(version).await;
+            }
+        }
+
+        self.copy_with(&path, &path).source_version(version).await
+    }
+
+    /// Purge non-current versions of all files under the given prefix.
+    ///
+    /// The latest `keep_n` non-current versions of each file will be kept, and
+    /// delete markers are treated as versions too. Current versions will never
+    /// be purged.
+    ///
+    /// Versions are deleted while listing, and only the versions of one file are
+    /// kept in memory at a time. This relies on services listing versions of the
+    /// same file together, which holds for all services with versioning.
+    ///
+    /// Returns the number of purged versions.
+    ///
+    /// # Capability
+    ///
+    /// Check [`Capability::list_with_versions`] and [`Capability::delete_with_version`]
+    /// before using this feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// // Keep at most one previous version of each file.
+    /// let purged = op.purge_versions("path/to/dir/", 1).await?;
+    /// println!("{purged} versions have been purged");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn purge_versions(&self, prefix: &str, keep_n: usize) -> Result<usize> {
+        let prefix = normalize_path(prefix);
+
+        let cap = self.info().full_capability();
+        if !(cap.list_with_versions && cap.delete_with_version) {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "purge versions requires list with versions and delete with version",
+            )
+            .with_operation("Operator::purge_versions")
+            .with_context("service", self.info().scheme())
+            .with_context("prefix", &prefix));
+        }
+
+        let mut lister = self
+            .lister_with(&prefix)
+            .recursive(true)
+            .versions(true)
+            .deleted(cap.list_with_deleted)
+            .await?;
+        let mut deleter = self.deleter().await?;
+
+        // Versions of the same file are listed together, so only one file is
+        // buffered at a time.
+        let mut count = 0;
+        let mut group: Vec<Entry> = Vec::new();
+        while let Some(entry) = lister.try_next().await? {
+            let meta = entry.metadata();
+            if meta.is_dir() || meta.version().is_none() || meta.is_current() != Some(false) {
+                continue;
+            }
+            if group.first().is_some_and(|v| v.path() != entry.path()) {
+                count += Self::purge_version_group(&mut deleter, &mut group, keep_n).await?;
+            }
+            group.push(entry);
+        }
+        count += Self::purge_version_group(&mut deleter, &mut group, keep_n).await?;
+
+        deleter.close().await?;
+        Ok(count)
+    }
+
+    /// Delete all but the latest `keep_n` versions in the group.
+    async fn purge_version_group(
+        deleter: &mut Deleter,
+        group: &mut Vec<Entry>,
+        keep_n: usize,
+    ) -> Result<usize> {
+        // Newer versions first, keep the listing order if no last modified.
+        group.sort_by_key(|e| std::cmp::Reverse(e.metadata().last_modified()));
+        let mut count = 0;
+        for entry in group.drain(..).skip(keep_n) {
+            deleter.delete(entry).await?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Acquire a lock of the given path that expires after `ttl`.
+    ///
+    /// Returns a [`Lease`] that can be renewed or released. The lock is
+    /// held by the lease until it's released or expired.
+    ///
+    /// # Capability
+    ///
+    /// Services with [`Capability::lock`] will use their native lock (e.g.
+    /// lease of etcd, `SET NX PX` of redis). Otherwise, this operation
+    /// requires [`Capability::write_with_if_not_exists`],
+    /// [`Capability::write_with_if_match`] and
+    /// [`Capability::delete_with_if_match`] to write a lock record at the
+    /// path, and expired records will be taken over based on their timestamps.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ErrorKind::ConditionNotMatch`] if the lock is held by others.
+    /// - Returns [`ErrorKind::Unsupported`] if the service doesn't have the
+    ///   needed capabilities.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut lease = op.lock("path/to/lock", Duration::from_secs(30)).await?;
+    /// // Do the work while holding the lock.
+    /// lease.renew().await?;
+    /// lease.release().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn lock(&self, path: &str, ttl: Duration) -> Result<Lease> {
+        let path = normalize_path(path);
+        Lease::acquire(self.clone(), &path, ttl).await
+    }
+
+    /// Begin a [`Transaction`] under the given root dir.
+    ///
+    /// Files written in the transaction will be published atomically by
+    /// commit. Readers should resolve files through [`Operator::manifest`].
+    ///
+    /// # Capability
+    ///
+    /// This operation requires [`Capability::write_with_if_not_exists`] or
+    /// [`Capability::rename_with_if_not_exists`] to commit manifests.
+    ///
+    /// # Errors
+    ///
+    /// - `commit` returns [`ErrorKind::ConditionNotMatch`] if another
+    ///   transaction has committed after this transaction began.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut txn = op.transaction("path/to/table/").await?;
+    /// txn.write("data/1.parquet", vec![0; 4096]).await?;
+    /// txn.write("data/2.parquet", vec![0; 4096]).await?;
+    /// let manifest = txn.commit().await?;
+    /// println!("version {} has been committed", manifest.version());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn transaction(&self, root: &str) -> Result<Transaction> {
+        Transaction::begin(self.clone(), root).await
+    }
+
+    /// Load the latest committed [`Manifest`] under the given root dir.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let manifest = op.manifest("path/to/table/").await?;
+    /// if let Some(path) = manifest.resolve("data/1.parquet") {
+    ///     let bs = op.read(path).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn manifest(&self, root: &str) -> Result<Manifest> {
+        Manifest::load(self, root).await
+    }
 }
 
 /// Operator presign API.
@@ -2233,4 +3331,176 @@ impl Operator {
         let rp = acc.presign(&path, op).await?;
         Ok(rp.into_presigned_request())
     }
+
+    /// Presign a browser based POST upload with policy conditions.
+    ///
+    /// Unlike [`Self::presign_write`], the returned form lets the service enforce
+    /// conditions like the accepted size range and content type on the upload.
+    ///
+    /// # Capability
+    ///
+    /// Check [`Capability::presign_post`] before using this feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use anyhow::Result;
+    /// use opendal::options;
+    /// use opendal::Operator;
+    ///
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     let post = op
+    ///         .presign_post(
+    ///             "upload.png",
+    ///             options::PresignPostOptions {
+    ///                 content_length_range: Some((1, 10 * 1024 * 1024)),
+    ///                 content_type_starts_with: Some("image/".to_string()),
+    ///                 ..Default::default()
+    ///             },
+    ///             Duration::from_secs(3600),
+    ///         )
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// - `post.uri()`: `https://examplebucket.s3.amazonaws.com/`
+    /// - `post.fields()`: `[("key", "upload.png"), ("x-amz-algorithm", "AWS4-HMAC-SHA256"), ..., ("policy", "<policy>"), ("x-amz-signature", "<signature>")]`
+    ///
+    /// Clients upload by posting a `multipart/form-data` body that carries all
+    /// fields in order, followed by the `file` field.
+    pub async fn presign_post(
+        &self,
+        path: &str,
+        conditions: options::PresignPostOptions,
+        expire: Duration,
+    ) -> Result<PresignedPost> {
+        let path = normalize_path(path);
+
+        let op = OpPresign::new(OpPresignPost::from(conditions), expire);
+        let rp = self.inner().presign(&path, op).await?;
+        Ok(rp.into_presigned_post())
+    }
+
+    /// Presign the request that initiates a multipart upload.
+    ///
+    /// The response of the presigned request carries the upload id used by
+    /// [`Self::presign_part`] and [`Self::presign_multipart_complete`].
+    ///
+    /// # Capability
+    ///
+    /// Check [`Capability::presign_multipart`] before using this feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use anyhow::Result;
+    /// use opendal::Operator;
+    ///
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     let create = op
+    ///         .presign_multipart_create("large.bin", Duration::from_secs(3600))
+    ///         .await?;
+    ///     // Send `create` to get the upload id, then presign every part.
+    ///     let upload_id = "<upload-id>";
+    ///     let part = op
+    ///         .presign_part("large.bin", upload_id, 1, Duration::from_secs(3600))
+    ///         .await?;
+    ///     let complete = op
+    ///         .presign_multipart_complete("large.bin", upload_id, Duration::from_secs(3600))
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn presign_multipart_create(
+        &self,
+        path: &str,
+        expire: Duration,
+    ) -> Result<PresignedRequest> {
+        self.presign_multipart_create_options(path, expire, options::WriteOptions::default())
+            .await
+    }
+
+    /// Presign the request that initiates a multipart upload with extra options.
+    ///
+    /// Options like `content_type` will be applied to the completed object.
+    pub async fn presign_multipart_create_options(
+        &self,
+        path: &str,
+        expire: Duration,
+        opts: options::WriteOptions,
+    ) -> Result<PresignedRequest> {
+        let path = normalize_path(path);
+
+        let (op_write, _) = opts.into();
+        let op = OpPresign::new(PresignOperation::CreateMultipart(op_write), expire);
+        let rp = self.inner().presign(&path, op).await?;
+        Ok(rp.into_presigned_request())
+    }
+
+    /// Presign the request that uploads one part of a multipart upload.
+    ///
+    /// `part_number` starts from 1. The `ETag` returned by every part must be
+    /// collected by the client to complete the upload.
+    ///
+    /// # Capability
+    ///
+    /// Check [`Capability::presign_multipart`] before using this feature.
+    pub async fn presign_part(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number: usize,
+        expire: Duration,
+    ) -> Result<PresignedRequest> {
+        let path = normalize_path(path);
+
+        let op = OpPresign::new(OpPresignPart::new(upload_id, part_number), expire);
+        let rp = self.inner().presign(&path, op).await?;
+        Ok(rp.into_presigned_request())
+    }
+
+    /// Presign the request that completes a multipart upload.
+    ///
+    /// The client sends the `CompleteMultipartUpload` xml body listing every
+    /// part number and `ETag` with the presigned request.
+    ///
+    /// # Capability
+    ///
+    /// Check [`Capability::presign_multipart`] before using this feature.
+    pub async fn presign_multipart_complete(
+        &self,
+        path: &str,
+        upload_id: &str,
+        expire: Duration,
+    ) -> Result<PresignedRequest> {
+        let path = normalize_path(path);
+
+        let op = OpPresign::new(OpPresignComplete::new(upload_id), expire);
+        let rp = self.inner().presign(&path, op).await?;
+        Ok(rp.into_presigned_request())
+    }
+}
+
+/// Check if the content hash of two files matches.
+///
+/// Returns `true` if the hash can't be compared, for example, `ETag` of multipart uploads
+/// are not the MD5 of content.
+fn content_hash_matches(src: &Metadata, dst: &Metadata) -> bool {
+    if let (Some(a), Some(b)) = (src.content_md5(), dst.content_md5()) {
+        return a == b;
+    }
+
+    let md5_etag = |v: &str| {
+        let v = v.trim_matches('"').to_string();
+        (v.len() == 32 && v.bytes().all(|b| b.is_ascii_hexdigit())).then_some(v)
+    };
+    match (src.etag().and_then(md5_etag), dst.etag().and_then(md5_etag)) {
+        (Some(a), Some(b)) => a.eq_ignore_ascii_case(&b),
+        _ => true,
+    }
 }
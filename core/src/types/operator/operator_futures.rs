@@ -1257,6 +1257,30 @@ impl<F: Future<Output = Result<Writer>>> FutureWriter<F> {
         self.args.user_metadata = Some(HashMap::from_iter(data));
         self
     }
+
+    /// Resume a multipart upload from the given state.
+    ///
+    /// Refer to [`options::WriteOptions::resume`] for more details.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// use opendal::UploadState;
+    ///
+    /// # async fn test(op: Operator, state: UploadState, content: Vec<u8>) -> Result<()> {
+    /// let offset = state.offset() as usize;
+    /// let mut w = op.writer_with("path/to/file").resume(state).await?;
+    /// w.write(content[offset..].to_vec()).await?;
+    /// w.close().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn resume(mut self, state: UploadState) -> Self {
+        self.args.resume = Some(state);
+        self
+    }
 }
 
 /// Future that generated by [`Operator::delete_with`].
@@ -1270,6 +1294,30 @@ impl<F: Future<Output = Result<()>>> FutureDelete<F> {
         self.args.version = Some(v.to_string());
         self
     }
+
+    /// Set the If-Match for this operation.
+    ///
+    /// Refer to [`options::DeleteOptions::if_match`] for more details.
+    pub fn if_match(mut self, v: &str) -> Self {
+        self.args.if_match = Some(v.to_string());
+        self
+    }
+
+    /// Set the If-None-Match for this operation.
+    ///
+    /// Refer to [`options::DeleteOptions::if_none_match`] for more details.
+    pub fn if_none_match(mut self, v: &str) -> Self {
+        self.args.if_none_match = Some(v.to_string());
+        self
+    }
+
+    /// Set the If-Unmodified-Since for this operation.
+    ///
+    /// Refer to [`options::DeleteOptions::if_unmodified_since`] for more details.
+    pub fn if_unmodified_since(mut self, v: DateTime<Utc>) -> Self {
+        self.args.if_unmodified_since = Some(v);
+        self
+    }
 }
 
 /// Future that generated by [`Operator::deleter_with`].
@@ -1336,6 +1384,30 @@ impl<F: Future<Output = Result<Vec<Entry>>>> FutureList<F> {
         self.args.deleted = v;
         self
     }
+
+    /// Set the concurrent for this list operation.
+    ///
+    /// Refer to [`options::ListOptions::concurrent`] for more details.
+    pub fn concurrent(mut self, v: usize) -> Self {
+        self.args.concurrent = v;
+        self
+    }
+
+    /// Set whether concurrent list should keep the order of returned entries.
+    ///
+    /// Refer to [`options::ListOptions::ordered`] for more details.
+    pub fn ordered(mut self, v: bool) -> Self {
+        self.args.ordered = v;
+        self
+    }
+
+    /// Set the metadata fields that must be present in returned entries.
+    ///
+    /// Refer to [`options::ListOptions::metadata`] for more details.
+    pub fn metadata(mut self, v: options::MetadataFields) -> Self {
+        self.args.metadata = v;
+        self
+    }
 }
 
 /// Future that generated by [`Operator::list_with`] or [`Operator::lister_with`].
@@ -1397,6 +1469,30 @@ impl<F: Future<Output = Result<Lister>>> FutureLister<F> {
         self.args.deleted = v;
         self
     }
+
+    /// Set the concurrent for this list operation.
+    ///
+    /// Refer to [`options::ListOptions::concurrent`] for more details.
+    pub fn concurrent(mut self, v: usize) -> Self {
+        self.args.concurrent = v;
+        self
+    }
+
+    /// Set whether concurrent list should keep the order of returned entries.
+    ///
+    /// Refer to [`options::ListOptions::ordered`] for more details.
+    pub fn ordered(mut self, v: bool) -> Self {
+        self.args.ordered = v;
+        self
+    }
+
+    /// Set the metadata fields that must be present in returned entries.
+    ///
+    /// Refer to [`options::ListOptions::metadata`] for more details.
+    pub fn metadata(mut self, v: options::MetadataFields) -> Self {
+        self.args.metadata = v;
+        self
+    }
 }
 
 /// Future that generated by [`Operator::copy_with`].
@@ -1427,4 +1523,109 @@ impl<F: Future<Output = Result<()>>> FutureCopy<F> {
         self.args.0.if_not_exists = v;
         self
     }
+
+    /// Sets the condition that copy operation will succeed only if target's etag matches.
+    ///
+    /// Refer to [`options::CopyOptions::if_match`] for more details.
+    pub fn if_match(mut self, v: &str) -> Self {
+        self.args.0.if_match = Some(v.to_string());
+        self
+    }
+
+    /// Sets the condition that copy operation will succeed only if source's etag matches.
+    ///
+    /// Refer to [`options::CopyOptions::source_if_match`] for more details.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # async fn test(op: Operator, etag: &str) -> Result<()> {
+    /// let _ = op
+    ///     .copy_with("source/path", "target/path")
+    ///     .source_if_match(etag)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn source_if_match(mut self, v: &str) -> Self {
+        self.args.0.source_if_match = Some(v.to_string());
+        self
+    }
+
+    /// Sets the condition that copy operation will succeed only if source's etag doesn't match.
+    ///
+    /// Refer to [`options::CopyOptions::source_if_none_match`] for more details.
+    pub fn source_if_none_match(mut self, v: &str) -> Self {
+        self.args.0.source_if_none_match = Some(v.to_string());
+        self
+    }
+
+    /// Sets the condition that copy operation will succeed only if source hasn't been modified
+    /// since the given time.
+    ///
+    /// Refer to [`options::CopyOptions::source_if_unmodified_since`] for more details.
+    pub fn source_if_unmodified_since(mut self, v: DateTime<Utc>) -> Self {
+        self.args.0.source_if_unmodified_since = Some(v);
+        self
+    }
+
+    /// Sets the version of the source to copy from.
+    ///
+    /// Refer to [`options::CopyOptions::source_version`] for more details.
+    pub fn source_version(mut self, v: &str) -> Self {
+        self.args.0.source_version = Some(v.to_string());
+        self
+    }
+}
+
+/// Future that generated by [`Operator::rename_with`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub type FutureRename<F> = OperatorFuture<(options::RenameOptions, String), (), F>;
+
+impl<F: Future<Output = Result<()>>> FutureRename<F> {
+    /// Sets the condition that rename operation will succeed only if target does not exist.
+    ///
+    /// Refer to [`options::RenameOptions::if_not_exists`] for more details.
+    pub fn if_not_exists(mut self, v: bool) -> Self {
+        self.args.0.if_not_exists = v;
+        self
+    }
+
+    /// Sets the condition that rename operation will succeed only if target's etag matches.
+    ///
+    /// Refer to [`options::RenameOptions::if_match`] for more details.
+    pub fn if_match(mut self, v: &str) -> Self {
+        self.args.0.if_match = Some(v.to_string());
+        self
+    }
+
+    /// Sets the condition that rename operation will succeed only if source's etag matches.
+    ///
+    /// Refer to [`options::RenameOptions::source_if_match`] for more details.
+    pub fn source_if_match(mut self, v: &str) -> Self {
+        self.args.0.source_if_match = Some(v.to_string());
+        self
+    }
+
+    /// Sets the condition that rename operation will succeed only if source's etag doesn't
+    /// match.
+    ///
+    /// Refer to [`options::RenameOptions::source_if_none_match`] for more details.
+    pub fn source_if_none_match(mut self, v: &str) -> Self {
+        self.args.0.source_if_none_match = Some(v.to_string());
+        self
+    }
+
+    /// Sets the condition that rename operation will succeed only if source hasn't been
+    /// modified since the given time.
+    ///
+    /// Refer to [`options::RenameOptions::source_if_unmodified_since`] for more details.
+    pub fn source_if_unmodified_since(mut self, v: DateTime<Utc>) -> Self {
+        self.args.0.source_if_unmodified_since = Some(v);
+        self
+    }
 }
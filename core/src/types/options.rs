@@ -29,6 +29,40 @@ use crate::raw::BytesRange;
 pub struct DeleteOptions {
     /// The version of the file to delete.
     pub version: Option<String>,
+    /// Set `if_match` for this operation.
+    ///
+    /// This option can be used to delete the file only if its `ETag` matches the given `ETag`.
+    ///
+    /// If file exists and it's etag doesn't match, an error with kind [`ErrorKind::ConditionNotMatch`]
+    /// will be returned.
+    ///
+    /// ### Capability
+    ///
+    /// Check [`Capability::delete_with_if_match`] before using this feature.
+    pub if_match: Option<String>,
+    /// Set `if_none_match` for this operation.
+    ///
+    /// This option can be used to delete the file only if its `ETag` doesn't match the given `ETag`.
+    ///
+    /// If file exists and it's etag match, an error with kind [`ErrorKind::ConditionNotMatch`]
+    /// will be returned.
+    ///
+    /// ### Capability
+    ///
+    /// Check [`Capability::delete_with_if_none_match`] before using this feature.
+    pub if_none_match: Option<String>,
+    /// Set `if_unmodified_since` for this operation.
+    ///
+    /// This option can be used to delete the file only if it hasn't been modified since the
+    /// given timestamp.
+    ///
+    /// If file exists and it has been modified since the specified time, an error with kind
+    /// [`ErrorKind::ConditionNotMatch`] will be returned.
+    ///
+    /// ### Capability
+    ///
+    /// Check [`Capability::delete_with_if_unmodified_since`] before using this feature.
+    pub if_unmodified_since: Option<DateTime<Utc>>,
 }
 
 /// Options for list operations.
@@ -66,6 +100,94 @@ pub struct ListOptions {
     ///
     /// Default to `false`
     pub deleted: bool,
+    /// Sets concurrent list for this operation.
+    ///
+    /// ### Behavior
+    ///
+    /// - By default, OpenDAL walks the given path with one sequential page stream
+    /// - When concurrent is set and `recursive` is `true`:
+    ///   - The given path will be listed without `recursive` first to split the keyspace
+    ///   - Every sub dir found will be listed recursively as a partition
+    ///   - At most `concurrent` partitions will be listed at the same time
+    /// - Takes no effect if `recursive` is `false`
+    ///
+    /// This feature significantly improves performance when walking large prefixes with
+    /// many sub dirs on services like s3.
+    pub concurrent: usize,
+    /// Sets whether concurrent list should keep the order of returned entries.
+    ///
+    /// ### Behavior
+    ///
+    /// - If `false`, entries will be returned as soon as any partition yields them
+    /// - If `true`, entries will be returned in the same order as a sequential list, at
+    ///   the cost of buffering entries of partitions that are not consumed yet
+    /// - Takes no effect if `concurrent` is not set
+    ///
+    /// Default to `false`
+    pub ordered: bool,
+    /// Sets the metadata fields that must be present in every returned entry.
+    ///
+    /// ### Behavior
+    ///
+    /// - By default, entries only carry the metadata returned by the list API of the service
+    /// - When any field is selected, OpenDAL will stat file entries that are missing any of the
+    ///   selected fields and fill them in
+    /// - Stats will be batched via [`Capability::stat_many`] if supported, and run with up to
+    ///   `concurrent` requests at the same time
+    /// - Entries that have been removed between list and stat will be returned as is
+    ///
+    /// [`Capability::stat_many`]: crate::Capability::stat_many
+    pub metadata: MetadataFields,
+}
+
+/// The metadata fields that could be requested by list operations.
+///
+/// Every field set to `true` must be present in the returned entries' [`Metadata`](crate::Metadata).
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct MetadataFields {
+    /// Require `content_length` to be present.
+    pub content_length: bool,
+    /// Require `content_md5` to be present.
+    pub content_md5: bool,
+    /// Require `content_type` to be present.
+    pub content_type: bool,
+    /// Require `content_encoding` to be present.
+    pub content_encoding: bool,
+    /// Require `content_disposition` to be present.
+    pub content_disposition: bool,
+    /// Require `cache_control` to be present.
+    pub cache_control: bool,
+    /// Require `etag` to be present.
+    pub etag: bool,
+    /// Require `last_modified` to be present.
+    pub last_modified: bool,
+    /// Require `version` to be present.
+    pub version: bool,
+    /// Require `user_metadata` to be present.
+    pub user_metadata: bool,
+}
+
+impl MetadataFields {
+    /// Check if no field has been selected.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Options for stat_many operations.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct StatManyOptions {
+    /// Sets how many stat requests could be sent at the same time.
+    ///
+    /// - If the service supports [`Capability::stat_many`], every request will carry up to
+    ///   [`Capability::stat_many_max_size`] paths
+    /// - Otherwise, every path will be sent as a separate stat request
+    ///
+    /// Default to `1`.
+    ///
+    /// [`Capability::stat_many`]: crate::Capability::stat_many
+    /// [`Capability::stat_many_max_size`]: crate::Capability::stat_many_max_size
+    pub concurrent: usize,
 }
 
 /// Options for read operations.
@@ -525,6 +647,26 @@ pub struct WriteOptions {
     /// - Lower operation costs
     /// - Better utilize network bandwidth
     pub chunk: Option<usize>,
+
+    /// Sets the upload state to resume a multipart upload from.
+    ///
+    /// ### Capability
+    ///
+    /// Check [`Capability::write_with_resume`] before using this feature.
+    ///
+    /// ### Behavior
+    ///
+    /// - The state could be got by [`Writer::upload_state`] of a previous writer, even in
+    ///   another process
+    /// - Parts in the state will be kept, and new parts will be appended after them
+    /// - Users must continue writing data from [`UploadState::offset`]
+    /// - Chunk size should be the same as the previous writer, since some services require
+    ///   all parts except the last one to have the same size
+    ///
+    /// [`Capability::write_with_resume`]: crate::Capability::write_with_resume
+    /// [`Writer::upload_state`]: crate::Writer::upload_state
+    /// [`UploadState::offset`]: crate::UploadState::offset
+    pub resume: Option<crate::UploadState>,
 }
 
 /// Options for copy operations.
@@ -545,4 +687,201 @@ pub struct CopyOptions {
     /// This operation provides a way to ensure copy operations only create new resources
     /// without overwriting existing ones, useful for implementing "copy if not exists" logic.
     pub if_not_exists: bool,
+    /// Sets the condition that copy operation will succeed only if the target's `ETag` matches
+    /// the given `ETag`.
+    ///
+    /// If target exists and it's etag doesn't match, an error with kind
+    /// [`ErrorKind::ConditionNotMatch`] will be returned.
+    ///
+    /// ### Capability
+    ///
+    /// Check [`Capability::copy_with_if_match`] before using this feature.
+    pub if_match: Option<String>,
+    /// Sets the condition that copy operation will succeed only if the source's `ETag` matches
+    /// the given `ETag`.
+    ///
+    /// If source's etag doesn't match, an error with kind [`ErrorKind::ConditionNotMatch`]
+    /// will be returned.
+    ///
+    /// ### Capability
+    ///
+    /// Check [`Capability::copy_with_source_if_match`] before using this feature.
+    pub source_if_match: Option<String>,
+    /// Sets the condition that copy operation will succeed only if the source's `ETag` doesn't
+    /// match the given `ETag`.
+    ///
+    /// If source's etag matches, an error with kind [`ErrorKind::ConditionNotMatch`]
+    /// will be returned.
+    ///
+    /// ### Capability
+    ///
+    /// Check [`Capability::copy_with_source_if_none_match`] before using this feature.
+    pub source_if_none_match: Option<String>,
+    /// Sets the condition that copy operation will succeed only if the source hasn't been
+    /// modified since the given timestamp.
+    ///
+    /// If source has been modified since the specified time, an error with kind
+    /// [`ErrorKind::ConditionNotMatch`] will be returned.
+    ///
+    /// ### Capability
+    ///
+    /// Check [`Capability::copy_with_source_if_unmodified_since`] before using this feature.
+    pub source_if_unmodified_since: Option<DateTime<Utc>>,
+    /// Specify the version of the source to copy from.
+    ///
+    /// Copy a previous version onto the same path will restore it as the current version.
+    ///
+    /// ### Capability
+    ///
+    /// Check [`Capability::copy_with_source_version`] before using this feature.
+    pub source_version: Option<String>,
+}
+
+/// Options for rename operations.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct RenameOptions {
+    /// Sets the condition that rename operation will succeed only if target does not exist.
+    ///
+    /// If target already exists, an error with kind [`ErrorKind::ConditionNotMatch`] will be
+    /// returned.
+    ///
+    /// ### Capability
+    ///
+    /// Check [`Capability::rename_with_if_not_exists`] before using this feature.
+    pub if_not_exists: bool,
+    /// Sets the condition that rename operation will succeed only if the target's `ETag`
+    /// matches the given `ETag`.
+    ///
+    /// ### Capability
+    ///
+    /// Check [`Capability::rename_with_if_match`] before using this feature.
+    pub if_match: Option<String>,
+    /// Sets the condition that rename operation will succeed only if the source's `ETag`
+    /// matches the given `ETag`.
+    ///
+    /// ### Capability
+    ///
+    /// Check [`Capability::rename_with_source_if_match`] before using this feature.
+    pub source_if_match: Option<String>,
+    /// Sets the condition that rename operation will succeed only if the source's `ETag`
+    /// doesn't match the given `ETag`.
+    ///
+    /// ### Capability
+    ///
+    /// Check [`Capability::rename_with_source_if_none_match`] before using this feature.
+    pub source_if_none_match: Option<String>,
+    /// Sets the condition that rename operation will succeed only if the source hasn't been
+    /// modified since the given timestamp.
+    ///
+    /// ### Capability
+    ///
+    /// Check [`Capability::rename_with_source_if_unmodified_since`] before using this feature.
+    pub source_if_unmodified_since: Option<DateTime<Utc>>,
+}
+
+/// Options for copy_all operations.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct CopyAllOptions {
+    /// Sets how many objects could be copied at the same time.
+    ///
+    /// Default to `1`.
+    pub concurrent: usize,
+    /// Sets the chunk size used while streaming objects that can't be copied server-side.
+    ///
+    /// Default to the service's preferred chunk size.
+    pub chunk: Option<usize>,
+}
+
+/// Options for remove_all operations.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct RemoveAllOptions {
+    /// Sets how many delete requests could be sent at the same time.
+    ///
+    /// Every request will carry up to [`Capability::delete_max_size`] paths if the service
+    /// supports batch delete.
+    ///
+    /// Default to `1`.
+    ///
+    /// [`Capability::delete_max_size`]: crate::Capability::delete_max_size
+    pub concurrent: usize,
+}
+
+/// Options for copy_to and move_to operations.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct CopyToOptions {
+    /// Sets concurrent for both reading from the source and writing into the target.
+    ///
+    /// Refer to [`ReaderOptions::concurrent`] and [`WriteOptions::concurrent`] for more details.
+    pub concurrent: usize,
+    /// Sets chunk for both reading from the source and writing into the target.
+    ///
+    /// Refer to [`ReaderOptions::chunk`] and [`WriteOptions::chunk`] for more details.
+    pub chunk: Option<usize>,
+    /// Sets the upload state to resume a failed copy from.
+    ///
+    /// ### Capability
+    ///
+    /// Check [`Capability::write_with_resume`] of the target before using this feature.
+    ///
+    /// ### Behavior
+    ///
+    /// - If the target supports resume, a failed [`Operator::copy_to_resumable`] keeps its
+    ///   multipart upload instead of aborting it, and the state could be got by
+    ///   [`CopyToError::upload_state`]
+    /// - The resumed copy reads the source from [`UploadState::offset`] and appends the rest
+    ///   to the kept upload
+    /// - The source must not be changed between the copies and `chunk` should be the same,
+    ///   refer to [`WriteOptions::resume`] for more details
+    ///
+    /// [`Capability::write_with_resume`]: crate::Capability::write_with_resume
+    /// [`Operator::copy_to_resumable`]: crate::Operator::copy_to_resumable
+    /// [`CopyToError::upload_state`]: crate::CopyToError::upload_state
+    /// [`UploadState::offset`]: crate::UploadState::offset
+    pub resume: Option<crate::UploadState>,
+    /// Sets the progress to report the copied bytes into.
+    ///
+    /// ### Behavior
+    ///
+    /// - Bytes will be added once they have been written into the target, bytes skipped by
+    ///   `resume` will not be counted
+    /// - The copy will be stopped if the progress has been cancelled
+    pub progress: Option<crate::TransferProgress>,
+}
+
+/// Changes for update_metadata operations.
+///
+/// Fields that are not set will keep their current values.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct UpdateMetadataOptions {
+    /// Set the `Content-Type` of the file.
+    pub content_type: Option<String>,
+    /// Set the `Cache-Control` of the file.
+    pub cache_control: Option<String>,
+    /// Set the `Content-Disposition` of the file.
+    pub content_disposition: Option<String>,
+    /// Changes of the user metadata of the file.
+    ///
+    /// `Some(value)` sets the key to the value while `None` removes the key. Keys that
+    /// are not present here will be kept.
+    pub user_metadata: HashMap<String, Option<String>>,
+    /// Replace all tags of the file with these tags, `None` keeps the current tags.
+    ///
+    /// ### Capability
+    ///
+    /// Check [`Capability::update_metadata_with_tags`] before using this feature.
+    pub tags: Option<HashMap<String, String>>,
+}
+
+/// Conditions for presign_post operations.
+///
+/// All conditions are embedded into the signed policy, uploads that don't match
+/// them will be rejected by the service.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct PresignPostOptions {
+    /// Limit the size of the uploaded content to `(min, max)` bytes, both inclusive.
+    pub content_length_range: Option<(u64, u64)>,
+    /// Require the `Content-Type` of the upload to be exactly this value.
+    pub content_type: Option<String>,
+    /// Require the `Content-Type` of the upload to start with this value.
+    pub content_type_starts_with: Option<String>,
 }
@@ -0,0 +1,477 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::io;
+use std::io::SeekFrom;
+use std::ops::Range;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::BytesMut;
+use futures::AsyncBufRead;
+use futures::AsyncRead;
+use futures::AsyncSeek;
+use futures::FutureExt;
+
+use crate::raw::oio::PooledBuf;
+use crate::raw::*;
+use crate::*;
+
+/// The default minimum size of the read-ahead window: 64KiB.
+const DEFAULT_MIN_READ_AHEAD: usize = 64 * 1024;
+/// The default maximum size of the read-ahead window: 8MiB.
+const DEFAULT_MAX_READ_AHEAD: usize = 8 * 1024 * 1024;
+
+/// Statistics of a [`BufferedReader`].
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct BufferedReaderStats {
+    /// Number of reads served from the buffered window without any request.
+    pub hits: u64,
+    /// Number of reads that required fetching a new window from storage.
+    pub misses: u64,
+    /// Total bytes fetched from storage.
+    pub bytes_fetched: u64,
+}
+
+/// BufferedReader is the adapter of [`AsyncRead`], [`AsyncBufRead`] and
+/// [`AsyncSeek`] with adaptive read-ahead generated by [`Reader::into_buffered_read`].
+///
+/// BufferedReader keeps a window of data fetched ahead of the current position:
+///
+/// - The window starts at the minimum read-ahead size.
+/// - Every sequential read that runs past the window doubles it, up to the maximum
+///   read-ahead size.
+/// - Every seek that lands outside the window shrinks it back to the minimum.
+/// - While reading sequentially, the next window will be fetched in the background
+///   by the executor of the operator, so that it's ready once the current one has
+///   been consumed.
+///
+/// Seeks and reads that fall inside the window are served without sending any
+/// request, which makes it suitable for formats like parquet or zip that jump
+/// around small regions of the file.
+///
+/// Window buffers are taken from and returned to a [`PooledBuf`], which can be shared
+/// between readers via [`BufferedReader::with_pool`].
+///
+/// BufferedReader also implements [`Unpin`], [`Send`] and [`Sync`].
+pub struct BufferedReader {
+    reader: Reader,
+    pool: Arc<PooledBuf>,
+
+    start: u64,
+    end: u64,
+    pos: u64,
+
+    buf: BytesMut,
+    buf_start: u64,
+    fetching: Option<Fetching>,
+    /// Whether the window being fetched is reached by reading sequentially.
+    sequential: bool,
+    executor: Executor,
+
+    min_read_ahead: usize,
+    max_read_ahead: usize,
+    read_ahead: usize,
+    stats: BufferedReaderStats,
+}
+
+/// Safety: BufferedReader only exposes `&mut self` to the outside world,
+unsafe impl Sync for BufferedReader {}
+
+impl BufferedReader {
+    /// NOTE: don't allow users to create BufferedReader directly.
+    pub(super) fn new(reader: Reader, range: Range<u64>) -> Self {
+        BufferedReader {
+            executor: reader.executor(),
+            reader,
+            pool: Arc::new(PooledBuf::new(2)),
+
+            start: range.start,
+            end: range.end,
+            pos: 0,
+
+            buf: BytesMut::new(),
+            buf_start: 0,
+            fetching: None,
+            sequential: false,
+
+            min_read_ahead: DEFAULT_MIN_READ_AHEAD,
+            max_read_ahead: DEFAULT_MAX_READ_AHEAD,
+            read_ahead: DEFAULT_MIN_READ_AHEAD,
+            stats: BufferedReaderStats::default(),
+        }
+    }
+
+    /// Set the minimum and maximum size of the read-ahead window.
+    ///
+    /// The default value is `64KiB..=8MiB`. `max` will be raised to `min` if it's smaller.
+    pub fn with_read_ahead(mut self, min: usize, max: usize) -> Self {
+        let min = min.max(1);
+        self.min_read_ahead = min;
+        self.max_read_ahead = max.max(min);
+        self.read_ahead = min;
+        self
+    }
+
+    /// Set the buffer pool used to allocate windows.
+    ///
+    /// Sharing a pool between readers allows reusing window buffers across them.
+    pub fn with_pool(mut self, pool: Arc<PooledBuf>) -> Self {
+        self.pool = pool;
+        self
+    }
+
+    /// Get the statistics of this reader.
+    pub fn stats(&self) -> BufferedReaderStats {
+        self.stats
+    }
+
+    /// The current size of the read-ahead window.
+    pub fn read_ahead(&self) -> usize {
+        self.read_ahead
+    }
+
+    fn len(&self) -> u64 {
+        self.end - self.start
+    }
+
+    fn buf_end(&self) -> u64 {
+        self.buf_start + self.buf.len() as u64
+    }
+
+    fn buffered(&self) -> bool {
+        (self.buf_start..self.buf_end()).contains(&self.pos)
+    }
+
+    /// Release current window back to pool.
+    fn release(&mut self) {
+        let buf = std::mem::take(&mut self.buf);
+        if buf.capacity() > 0 {
+            self.pool.put(buf);
+        }
+        self.buf_start = self.pos;
+    }
+
+    /// Start fetching the window of given range in the background.
+    fn fetch(&self, range: Range<u64>) -> Fetching {
+        let reader = self.reader.clone();
+        let offset = self.start;
+        let (start, end) = (range.start, range.end);
+        let mut guard = PoolGuard {
+            pool: self.pool.clone(),
+            buf: Some(self.pool.get()),
+        };
+        let task = self.executor.execute(async move {
+            let buf = guard.buf.as_mut().expect("buf must be set");
+            buf.reserve((end - start) as usize);
+            reader.read_into(buf, offset + start..offset + end).await?;
+            Ok(guard.buf.take().expect("buf must be set"))
+        });
+        Fetching { range, task }
+    }
+
+    /// Drop the fetching window if it doesn't cover current position.
+    fn cancel_fetching(&mut self) {
+        if let Some(fetching) = &self.fetching {
+            if !fetching.range.contains(&self.pos) {
+                self.drop_fetching();
+            }
+        }
+    }
+
+    /// Drop the fetching window and return its buffer back to pool.
+    ///
+    /// Unfinished fetching will return the buffer by itself once cancelled.
+    fn drop_fetching(&mut self) {
+        if let Some(fetching) = self.fetching.take() {
+            if let Some(Ok(buf)) = fetching.task.now_or_never() {
+                self.pool.put(buf);
+            }
+        }
+    }
+
+    /// Make sure the window contains current position, returns `false` if
+    /// we reached the end.
+    fn poll_fill(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<bool>> {
+        if self.pos >= self.len() {
+            return Poll::Ready(Ok(false));
+        }
+        if self.buffered() {
+            self.stats.hits += 1;
+            return Poll::Ready(Ok(true));
+        }
+
+        // Leave current window, unless we are waiting for the next one already.
+        if !self.buf.is_empty() || self.fetching.is_none() {
+            // Grow the window if the reader keeps going forward.
+            self.sequential = !self.buf.is_empty() && self.pos == self.buf_end();
+            if self.sequential {
+                self.read_ahead = (self.read_ahead * 2).min(self.max_read_ahead);
+            }
+            self.stats.misses += 1;
+            self.release();
+
+            self.cancel_fetching();
+            if self.fetching.is_none() {
+                let size = (self.read_ahead as u64).min(self.len() - self.pos);
+                self.fetching = Some(self.fetch(self.pos..self.pos + size));
+            }
+        }
+
+        let fetching = self.fetching.as_mut().expect("fetching must be set");
+        let res = ready!(fetching.task.poll_unpin(cx));
+        let range = self.fetching.take().expect("fetching must be set").range;
+        let buf = res.map_err(format_std_io_error)?;
+        if buf.is_empty() {
+            self.pool.put(buf);
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "reader got empty window before reaching the end",
+            )));
+        }
+        self.stats.bytes_fetched += buf.len() as u64;
+        self.buf = buf;
+        self.buf_start = range.start;
+
+        // Fetch the next window while the current one is being consumed.
+        let end = self.buf_end();
+        if self.sequential && end < self.len() {
+            let size = (self.read_ahead as u64).min(self.len() - end);
+            self.fetching = Some(self.fetch(end..end + size));
+        }
+        Poll::Ready(Ok(true))
+    }
+
+    fn chunk(&self) -> &[u8] {
+        &self.buf[(self.pos - self.buf_start) as usize..]
+    }
+}
+
+impl Drop for BufferedReader {
+    fn drop(&mut self) {
+        self.drop_fetching();
+        self.release();
+    }
+}
+
+impl AsyncBufRead for BufferedReader {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+        if !ready!(this.poll_fill(cx))? {
+            return Poll::Ready(Ok(&[]));
+        }
+        Poll::Ready(Ok(this.chunk()))
+    }
+
+    fn consume(mut self: Pin<&mut Self>, amt: usize) {
+        self.pos += amt as u64;
+    }
+}
+
+impl AsyncRead for BufferedReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if !ready!(this.poll_fill(cx))? {
+            return Poll::Ready(Ok(0));
+        }
+
+        let chunk = this.chunk();
+        let size = chunk.len().min(buf.len());
+        buf[..size].copy_from_slice(&chunk[..size]);
+        this.pos += size as u64;
+        Poll::Ready(Ok(size))
+    }
+}
+
+impl AsyncSeek for BufferedReader {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        let new_pos = match pos {
+            SeekFrom::Start(pos) => pos as i64,
+            SeekFrom::End(pos) => self.len() as i64 + pos,
+            SeekFrom::Current(pos) => self.pos as i64 + pos,
+        };
+
+        // Check if new_pos is negative.
+        if new_pos < 0 {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative position",
+            )));
+        }
+
+        let new_pos = new_pos as u64;
+        if new_pos != self.pos {
+            self.pos = new_pos;
+            if !self.buffered() {
+                self.cancel_fetching();
+                // Shrink the window since the access is no longer sequential.
+                if new_pos != self.buf_end() {
+                    self.read_ahead = self.min_read_ahead;
+                }
+            }
+        }
+
+        Poll::Ready(Ok(self.pos))
+    }
+}
+
+/// A window being fetched in the background.
+struct Fetching {
+    /// The range of the window, relative to the start of the reader.
+    range: Range<u64>,
+    task: Task<Result<BytesMut>>,
+}
+
+/// PoolGuard returns the buffer to the pool if the fetching has been dropped
+/// before completion.
+struct PoolGuard {
+    pool: Arc<PooledBuf>,
+    buf: Option<BytesMut>,
+}
+
+impl Drop for PoolGuard {
+    fn drop(&mut self) {
+        if let Some(buf) = self.buf.take() {
+            self.pool.put(buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::AsyncBufReadExt;
+    use futures::AsyncReadExt;
+    use futures::AsyncSeekExt;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    async fn new_reader(content: &[u8]) -> Result<Reader> {
+        let op = Operator::via_iter(Scheme::Memory, [])?;
+        op.write("test", content.to_vec()).await?;
+        op.reader("test").await
+    }
+
+    #[tokio::test]
+    async fn test_trait() -> Result<()> {
+        let r = new_reader(b"Hello, World!").await?;
+        let v = BufferedReader::new(r, 0..13);
+
+        let _: Box<dyn Unpin + MaybeSend + Sync + 'static> = Box::new(v);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_buffered_read_grows_window() -> Result<()> {
+        let content: Vec<u8> = (0..1024u32).map(|v| v as u8).collect();
+        let r = new_reader(&content).await?;
+
+        let mut br = BufferedReader::new(r, 0..1024).with_read_ahead(16, 64);
+        let mut bs = vec![];
+        br.read_to_end(&mut bs).await.unwrap();
+        assert_eq!(bs, content);
+
+        // 16 + 32 + 64 * 15 = 1008, and the last 16 bytes.
+        assert_eq!(br.read_ahead(), 64);
+        assert_eq!(br.stats().misses, 18);
+        assert_eq!(br.stats().bytes_fetched, 1024);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_buffered_read_seek_inside_window() -> Result<()> {
+        let r = new_reader(b"Hello, World!").await?;
+
+        let mut br = BufferedReader::new(r, 0..13).with_read_ahead(8, 8);
+        let mut bs = [0; 5];
+        br.read_exact(&mut bs).await.unwrap();
+        assert_eq!(&bs, b"Hello");
+
+        br.seek(SeekFrom::Start(1)).await.unwrap();
+        let mut bs = [0; 4];
+        br.read_exact(&mut bs).await.unwrap();
+        assert_eq!(&bs, b"ello");
+        assert_eq!(br.stats().misses, 1);
+        assert_eq!(br.stats().hits, 1);
+
+        let chunk = br.fill_buf().await.unwrap();
+        assert_eq!(chunk, b", W");
+        br.consume_unpin(3);
+
+        let mut bs = vec![];
+        br.read_to_end(&mut bs).await.unwrap();
+        assert_eq!(&bs, b"orld!");
+        assert_eq!(br.stats().misses, 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_buffered_read_ahead_returns_buf_after_seek() -> Result<()> {
+        let content: Vec<u8> = (0..1024u32).map(|v| v as u8).collect();
+        let r = new_reader(&content).await?;
+        let pool = Arc::new(PooledBuf::new(2));
+
+        let mut br = BufferedReader::new(r, 0..1024)
+            .with_read_ahead(16, 64)
+            .with_pool(pool.clone());
+        let mut bs = [0; 32];
+        br.read_exact(&mut bs).await.unwrap();
+        assert_eq!(&bs, &content[..32]);
+        // The current window is 16..48, and 48..80 is fetching in background.
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+
+        br.seek(SeekFrom::Start(512)).await.unwrap();
+        let buf = pool.get();
+        assert!(buf.capacity() >= 32, "read-ahead buf must be returned");
+        pool.put(buf);
+
+        let mut bs = [0; 16];
+        br.read_exact(&mut bs).await.unwrap();
+        assert_eq!(&bs, &content[512..528]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_buffered_read_seek_shrinks_window() -> Result<()> {
+        let content = vec![7; 256];
+        let r = new_reader(&content).await?;
+
+        let mut br = BufferedReader::new(r, 32..256).with_read_ahead(8, 64);
+        let mut bs = [0; 24];
+        br.read_exact(&mut bs).await.unwrap();
+        assert_eq!(br.read_ahead(), 16);
+
+        let pos = br.seek(SeekFrom::End(-4)).await.unwrap();
+        assert_eq!(pos, 220);
+        assert_eq!(br.read_ahead(), 8);
+
+        let mut bs = vec![];
+        br.read_to_end(&mut bs).await.unwrap();
+        assert_eq!(bs, vec![7; 4]);
+        Ok(())
+    }
+}
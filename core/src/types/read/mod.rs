@@ -27,3 +27,7 @@ pub use futures_async_reader::FuturesAsyncReader;
 
 mod futures_bytes_stream;
 pub use futures_bytes_stream::FuturesBytesStream;
+
+mod buffered_reader;
+pub use buffered_reader::BufferedReader;
+pub use buffered_reader::BufferedReaderStats;
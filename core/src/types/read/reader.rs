@@ -105,6 +105,11 @@ impl Reader {
         Reader { ctx: Arc::new(ctx) }
     }
 
+    /// The executor to run background tasks of this reader.
+    pub(super) fn executor(&self) -> Executor {
+        self.ctx.accessor().info().executor()
+    }
+
     /// Read give range from reader into [`Buffer`].
     ///
     /// This operation is zero-copy, which means it keeps the [`bytes::Bytes`] returned by underlying
@@ -362,6 +367,53 @@ impl Reader {
         Ok(FuturesAsyncReader::new(self.ctx, range))
     }
 
+    /// Convert reader into [`BufferedReader`] which implements [`futures::AsyncRead`],
+    /// [`futures::AsyncSeek`] and [`futures::AsyncBufRead`] with adaptive read-ahead.
+    ///
+    /// # Notes
+    ///
+    /// Unlike [`Reader::into_futures_async_read`], BufferedReader fetches data in
+    /// windows that grow on sequential access and shrink on seeks. Reads and seeks
+    /// inside the current window won't send any request. Use [`BufferedReader::stats`]
+    /// to check how well the window works for your access pattern.
+    ///
+    /// # Inputs
+    ///
+    /// - `range`: The range of data to read. range like `..` it will read all data from reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use std::io::SeekFrom;
+    ///
+    /// use futures::io::AsyncReadExt;
+    /// use futures::io::AsyncSeekExt;
+    /// use opendal::Operator;
+    ///
+    /// async fn test(op: Operator) -> io::Result<()> {
+    ///     let mut r = op
+    ///         .reader("hello.zip")
+    ///         .await?
+    ///         .into_buffered_read(..)
+    ///         .await?
+    ///         .with_read_ahead(16 * 1024, 4 * 1024 * 1024);
+    ///
+    ///     // Read the trailer first, then jump back to the entries.
+    ///     r.seek(SeekFrom::End(-22)).await?;
+    ///     let mut trailer = [0; 22];
+    ///     r.read_exact(&mut trailer).await?;
+    ///
+    ///     println!("stats: {:?}", r.stats());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[inline]
+    pub async fn into_buffered_read(self, range: impl RangeBounds<u64>) -> Result<BufferedReader> {
+        let range = self.ctx.parse_into_range(range).await?;
+        Ok(BufferedReader::new(self, range))
+    }
+
     /// Convert reader into [`FuturesBytesStream`] which implements [`futures::Stream`].
     ///
     /// # Inputs
@@ -119,6 +119,8 @@ pub enum Scheme {
     Persy,
     /// [redis][crate::services::Redis]: Redis services
     Redis,
+    /// [replay][crate::services::Replay]: Replay recorded calls.
+    Replay,
     /// [postgresql][crate::services::Postgresql]: Postgresql services
     Postgresql,
     /// [mysql][crate::services::Mysql]: Mysql services
@@ -263,6 +265,8 @@ impl Scheme {
             Scheme::Persy,
             #[cfg(feature = "services-redis")]
             Scheme::Redis,
+            #[cfg(feature = "services-replay")]
+            Scheme::Replay,
             #[cfg(feature = "services-rocksdb")]
             Scheme::Rocksdb,
             #[cfg(feature = "services-s3")]
@@ -369,6 +373,7 @@ impl FromStr for Scheme {
             "postgresql" => Ok(Scheme::Postgresql),
             "redb" => Ok(Scheme::Redb),
             "redis" => Ok(Scheme::Redis),
+            "replay" => Ok(Scheme::Replay),
             "rocksdb" => Ok(Scheme::Rocksdb),
             "s3" => Ok(Scheme::S3),
             "seafile" => Ok(Scheme::Seafile),
@@ -437,6 +442,7 @@ impl From<Scheme> for &'static str {
             Scheme::Github => "github",
             Scheme::Dropbox => "dropbox",
             Scheme::Redis => "redis",
+            Scheme::Replay => "replay",
             Scheme::Rocksdb => "rocksdb",
             Scheme::S3 => "s3",
             Scheme::Seafile => "seafile",
@@ -0,0 +1,344 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::raw::*;
+use crate::*;
+
+/// The dir under transaction root to store manifests.
+const MANIFEST_DIR: &str = "_manifests/";
+/// The hint of the latest committed version, it may be stale.
+const MANIFEST_HINT: &str = "_manifests/_latest";
+/// The dir under transaction root to stage writes.
+const STAGING_DIR: &str = "_staging/";
+
+/// Manifest is a committed snapshot of files under a transaction root.
+///
+/// Files are stored at their staged paths and never be moved after commit,
+/// readers should resolve the path of files through the manifest.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    version: u64,
+    files: BTreeMap<String, String>,
+}
+
+impl Manifest {
+    /// Get the version of this manifest, `0` means nothing has been committed.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Resolve the logical path into the path that stores the file.
+    ///
+    /// Returns `None` if the path is not in this manifest.
+    pub fn resolve(&self, path: &str) -> Option<&str> {
+        self.files.get(path).map(|s| s.as_str())
+    }
+
+    /// Iterate all logical paths in this manifest.
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.files.keys().map(|s| s.as_str())
+    }
+
+    /// Load the latest manifest under root.
+    ///
+    /// Start from the version in hint and probe the following versions, since
+    /// the hint is updated after commit and could be stale.
+    pub(crate) async fn load(op: &Operator, root: &str) -> Result<Manifest> {
+        let root = manifest_root(root);
+        let mut version = match op.read(&format!("{root}{MANIFEST_HINT}")).await {
+            Ok(bs) => String::from_utf8_lossy(&bs.to_vec())
+                .trim()
+                .parse::<u64>()
+                .unwrap_or_default(),
+            Err(err) if err.kind() == ErrorKind::NotFound => 0,
+            Err(err) => return Err(err),
+        };
+        while op.exists(&version_path(&root, version + 1)).await? {
+            version += 1;
+        }
+
+        if version == 0 {
+            return Ok(Manifest::default());
+        }
+        let bs = op.read(&version_path(&root, version)).await?;
+        Manifest::decode(&bs.to_vec())
+    }
+
+    fn decode(bs: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bs)
+            .map_err(|err| Error::new(ErrorKind::Unexpected, "manifest is invalid").set_source(err))
+    }
+
+    fn encode(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self).map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "manifest can't be encoded").set_source(err)
+        })
+    }
+}
+
+/// Transaction stages writes under a transaction root and publishes them
+/// atomically by committing a new [`Manifest`].
+///
+/// Created by [`Operator::transaction`].
+///
+/// # Layout
+///
+/// - `<root>/_staging/<id>/<path>`: files written by transaction `<id>`.
+/// - `<root>/_staging/<id>.manifest.json`: the manifest staged by transaction `<id>`.
+/// - `<root>/_manifests/<version>.json`: the manifest of each version.
+/// - `<root>/_manifests/_latest`: the hint of the latest version.
+///
+/// Commit claims the next version by writing its manifest with
+/// `if_not_exists`, so only one of the concurrent commits based on the same
+/// version will succeed. Others will fail with [`ErrorKind::ConditionNotMatch`].
+///
+/// On services that support atomic rename with `if_not_exists` (e.g. fs, hdfs),
+/// the manifest will be written into staging first and renamed into place,
+/// so that readers will never see a partial manifest.
+pub struct Transaction {
+    op: Operator,
+    root: String,
+    id: String,
+    base: Manifest,
+    /// Staged changes, `None` means the path has been deleted.
+    changes: BTreeMap<String, Option<String>>,
+}
+
+impl std::fmt::Debug for Transaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Transaction")
+            .field("root", &self.root)
+            .field("id", &self.id)
+            .field("version", &self.base.version)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Transaction {
+    /// Begin a transaction based on the latest manifest under root.
+    pub(crate) async fn begin(op: Operator, root: &str) -> Result<Transaction> {
+        let cap = op.info().full_capability();
+        if !(cap.read
+            && cap.stat
+            && cap.write
+            && cap.delete
+            && cap.list
+            && (cap.write_with_if_not_exists || cap.rename_with_if_not_exists))
+        {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "transaction requires write with if_not_exists or rename with if_not_exists",
+            )
+            .with_context("service", op.info().scheme())
+            .with_context("root", root));
+        }
+
+        let root = manifest_root(root);
+        let base = Manifest::load(&op, &root).await?;
+        Ok(Transaction {
+            op,
+            root,
+            id: uuid::Uuid::new_v4().to_string(),
+            base,
+            changes: BTreeMap::new(),
+        })
+    }
+
+    /// Get the id of this transaction.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get the manifest this transaction is based on.
+    pub fn base(&self) -> &Manifest {
+        &self.base
+    }
+
+    /// Write a file into this transaction.
+    ///
+    /// The file will be visible to readers after commit.
+    pub async fn write(&mut self, path: &str, bs: impl Into<Buffer>) -> Result<()> {
+        let path = logical_path(path)?;
+        let staged = format!("{}{STAGING_DIR}{}/{path}", self.root, self.id);
+        self.op.write(&staged, bs).await?;
+        self.changes.insert(path, Some(staged));
+        Ok(())
+    }
+
+    /// Delete a file in this transaction.
+    ///
+    /// Only the manifest will be changed, files of previous versions are kept
+    /// for readers that still use them.
+    pub fn delete(&mut self, path: &str) -> Result<()> {
+        let path = logical_path(path)?;
+        self.changes.insert(path, None);
+        Ok(())
+    }
+
+    /// Read a file with the changes of this transaction applied.
+    pub async fn read(&self, path: &str) -> Result<Buffer> {
+        let path = logical_path(path)?;
+        let resolved = match self.changes.get(&path) {
+            Some(staged) => staged.as_deref(),
+            None => self.base.resolve(&path),
+        };
+        match resolved {
+            Some(p) => self.op.read(p).await,
+            None => Err(
+                Error::new(ErrorKind::NotFound, "path is not in the transaction")
+                    .with_context("path", path),
+            ),
+        }
+    }
+
+    /// Commit this transaction and return the new manifest.
+    ///
+    /// Returns [`ErrorKind::ConditionNotMatch`] if another transaction has
+    /// committed after this transaction began. The staged files will be
+    /// removed once the commit is confirmed to have failed, they are kept if
+    /// the state of the commit can't be checked.
+    pub async fn commit(self) -> Result<Manifest> {
+        let mut manifest = Manifest {
+            version: self.base.version + 1,
+            files: self.base.files.clone(),
+        };
+        for (path, staged) in &self.changes {
+            match staged {
+                Some(staged) => manifest.files.insert(path.clone(), staged.clone()),
+                None => manifest.files.remove(path),
+            };
+        }
+
+        if let Err(err) = self.publish(&manifest).await {
+            let err = err
+                .with_context("root", &self.root)
+                .with_context("version", manifest.version.to_string());
+
+            // The publish could have succeeded on the service even if an error is
+            // returned, e.g. timeout or a retried conditional request that conflicts
+            // with itself. Staged files must be kept if they are referenced.
+            match self.is_published(&manifest).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    if let Err(err) = self.op.remove_all(&self.staging_dir()).await {
+                        log::warn!("transaction {} cleanup failed: {err}", self.id);
+                    }
+                    return Err(err);
+                }
+                Err(check_err) => {
+                    log::warn!(
+                        "transaction {} check commit failed, staged files are kept: {check_err}",
+                        self.id
+                    );
+                    return Err(err);
+                }
+            }
+        }
+
+        // The hint is only used to speed up loading, it's fine to be stale.
+        if let Err(err) = self
+            .op
+            .write(&self.hint_path(), manifest.version.to_string())
+            .await
+        {
+            log::warn!("transaction {} update hint failed: {err}", self.id);
+        }
+        Ok(manifest)
+    }
+
+    /// Abort this transaction and remove all staged files.
+    pub async fn abort(self) -> Result<()> {
+        self.op.remove_all(&self.staging_dir()).await
+    }
+
+    async fn publish(&self, manifest: &Manifest) -> Result<()> {
+        let target = version_path(&self.root, manifest.version);
+        let bs = manifest.encode()?;
+
+        let cap = self.op.info().full_capability();
+        if cap.rename && cap.rename_with_if_not_exists {
+            // Keep the staged manifest out of the staging dir, which holds user files.
+            let staged = format!("{}{STAGING_DIR}{}.manifest.json", self.root, self.id);
+            self.op.write(&staged, bs).await?;
+            let res = self
+                .op
+                .rename_with(&staged, &target)
+                .if_not_exists(true)
+                .await;
+            if res.is_err() {
+                if let Err(err) = self.op.delete(&staged).await {
+                    log::warn!("transaction {} cleanup failed: {err}", self.id);
+                }
+            }
+            res
+        } else {
+            self.op
+                .write_with(&target, bs)
+                .if_not_exists(true)
+                .await
+                .map(|_| ())
+        }
+    }
+
+    /// Check whether the target version holds the given manifest.
+    async fn is_published(&self, manifest: &Manifest) -> Result<bool> {
+        let target = version_path(&self.root, manifest.version);
+        match self.op.read(&target).await {
+            Ok(bs) => Ok(&Manifest::decode(&bs.to_vec())? == manifest),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn staging_dir(&self) -> String {
+        format!("{}{STAGING_DIR}{}/", self.root, self.id)
+    }
+
+    fn hint_path(&self) -> String {
+        format!("{}{MANIFEST_HINT}", self.root)
+    }
+}
+
+/// Normalize root into a dir path without the leading `/`.
+fn manifest_root(root: &str) -> String {
+    let root = normalize_path(root);
+    match root.as_str() {
+        "/" => String::new(),
+        _ if root.ends_with('/') => root,
+        _ => format!("{root}/"),
+    }
+}
+
+fn version_path(root: &str, version: u64) -> String {
+    format!("{root}{MANIFEST_DIR}{version:020}.json")
+}
+
+fn logical_path(path: &str) -> Result<String> {
+    let path = normalize_path(path);
+    if path.ends_with('/') {
+        return Err(
+            Error::new(ErrorKind::IsADirectory, "transaction path must be a file")
+                .with_context("path", path),
+        );
+    }
+    Ok(path)
+}
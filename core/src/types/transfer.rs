@@ -0,0 +1,621 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::future::IntoFuture;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use futures::TryStreamExt;
+
+use crate::raw::*;
+use crate::*;
+
+/// Transfer is the handle of a bulk operation like [`Operator::copy_all`] and
+/// [`Operator::remove_all_options`].
+///
+/// - Use [`Transfer::progress`] to get a [`TransferProgress`] which could be used to observe
+///   or cancel the operation from other tasks.
+/// - Await the transfer to run the operation and get a [`TransferReport`].
+///
+/// The operation is lazy: nothing will happen until the transfer is awaited.
+///
+/// # Examples
+///
+/// ```
+/// # use anyhow::Result;
+/// # use opendal::Operator;
+/// # async fn test(op: Operator) -> Result<()> {
+/// let transfer = op.copy_all("from/", "to/");
+/// let progress = transfer.progress();
+///
+/// let report = transfer.await?;
+/// println!(
+///     "copied {} objects, {} bytes, {} failed",
+///     report.objects,
+///     report.bytes,
+///     report.failures.len()
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub struct Transfer {
+    progress: TransferProgress,
+    fut: BoxedStaticFuture<Result<TransferReport>>,
+}
+
+impl Transfer {
+    /// Create a new transfer that copies all objects under `from` into `to`.
+    pub(crate) fn copy_all(
+        op: Operator,
+        from: String,
+        to: String,
+        opts: options::CopyAllOptions,
+    ) -> Self {
+        let progress = TransferProgress::new();
+        let fut = copy_all(op, from, to, opts, progress.clone());
+        Self {
+            progress,
+            fut: Box::pin(fut),
+        }
+    }
+
+    /// Create a new transfer that removes all objects under `path`.
+    pub(crate) fn remove_all(op: Operator, path: String, opts: options::RemoveAllOptions) -> Self {
+        let progress = TransferProgress::new();
+        let fut = remove_all(op, path, opts, progress.clone());
+        Self {
+            progress,
+            fut: Box::pin(fut),
+        }
+    }
+
+    /// Get the progress of this transfer.
+    ///
+    /// The returned progress shares state with this transfer, and could be moved to other
+    /// tasks to observe or cancel it.
+    pub fn progress(&self) -> TransferProgress {
+        self.progress.clone()
+    }
+}
+
+impl IntoFuture for Transfer {
+    type Output = Result<TransferReport>;
+    type IntoFuture = BoxedStaticFuture<Result<TransferReport>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        self.fut
+    }
+}
+
+/// TransferProgress reports the progress of a running [`Transfer`].
+///
+/// It's also the cancellation token of the transfer: call [`TransferProgress::cancel`]
+/// to stop it.
+///
+/// A progress could also be created by [`TransferProgress::default`] and passed to
+/// [`options::CopyToOptions::progress`] to observe a single copy.
+#[derive(Debug, Clone, Default)]
+pub struct TransferProgress {
+    inner: Arc<ProgressState>,
+}
+
+/// Progresses are equal if they share the same state.
+impl PartialEq for TransferProgress {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for TransferProgress {}
+
+#[derive(Debug, Default)]
+struct ProgressState {
+    bytes: AtomicU64,
+    objects: AtomicU64,
+    failed: AtomicU64,
+    cancelled: AtomicBool,
+}
+
+impl TransferProgress {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of bytes that have been transferred.
+    pub fn bytes(&self) -> u64 {
+        self.inner.bytes.load(Ordering::Relaxed)
+    }
+
+    /// The number of objects that have been processed successfully.
+    pub fn objects(&self) -> u64 {
+        self.inner.objects.load(Ordering::Relaxed)
+    }
+
+    /// The number of objects that failed to process.
+    pub fn failed(&self) -> u64 {
+        self.inner.failed.load(Ordering::Relaxed)
+    }
+
+    /// Cancel the transfer.
+    ///
+    /// The transfer will stop scheduling new objects and cancel all ongoing ones as soon as
+    /// possible. Objects that have been processed will not be rolled back.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Check if the transfer has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn add_bytes(&self, n: u64) {
+        self.inner.bytes.fetch_add(n, Ordering::Relaxed);
+    }
+
+    fn add_object(&self) {
+        self.inner.objects.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn add_failed(&self) {
+        self.inner.failed.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// TransferReport is the final report of a [`Transfer`].
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct TransferReport {
+    /// The number of bytes that have been transferred.
+    ///
+    /// For server-side copy, this is the size of copied objects.
+    pub bytes: u64,
+    /// The number of objects that have been processed successfully.
+    pub objects: u64,
+    /// The objects that failed to process with their errors.
+    pub failures: Vec<(String, Error)>,
+    /// Whether the transfer has been cancelled before completion.
+    pub cancelled: bool,
+}
+
+impl TransferReport {
+    fn new(progress: &TransferProgress, failures: Vec<(String, Error)>) -> Self {
+        Self {
+            bytes: progress.bytes(),
+            objects: progress.objects(),
+            failures,
+            cancelled: progress.is_cancelled(),
+        }
+    }
+}
+
+pub(crate) fn cancelled_error() -> Error {
+    Error::new(ErrorKind::Unexpected, "transfer has been cancelled")
+}
+
+/// Record the result of a single object.
+fn record(
+    progress: &TransferProgress,
+    failures: &mut Vec<(String, Error)>,
+    path: String,
+    res: Result<()>,
+) {
+    match res {
+        Ok(()) => progress.add_object(),
+        // Errors caused by cancellation are not failures.
+        Err(_) if progress.is_cancelled() => {}
+        Err(err) => {
+            progress.add_failed();
+            failures.push((path, err));
+        }
+    }
+}
+
+struct CopyInput {
+    op: Operator,
+    progress: TransferProgress,
+    server_side: bool,
+    chunk: Option<usize>,
+
+    from: String,
+    to: String,
+    size: u64,
+}
+
+async fn copy_all(
+    op: Operator,
+    from: String,
+    to: String,
+    opts: options::CopyAllOptions,
+    progress: TransferProgress,
+) -> Result<TransferReport> {
+    let cap = op.info().full_capability();
+    let server_side = cap.copy;
+
+    let new_input = |from: String, to: String, size: u64| CopyInput {
+        op: op.clone(),
+        progress: progress.clone(),
+        server_side,
+        chunk: opts.chunk,
+        from,
+        to,
+        size,
+    };
+
+    let mut failures = vec![];
+
+    // Copy a single file directly.
+    if !from.ends_with('/') {
+        let size = if server_side {
+            op.stat(&from).await?.content_length()
+        } else {
+            0
+        };
+        let input = new_input(from, to, size);
+        let res = copy_one(&input).await;
+        record(&progress, &mut failures, input.from, res);
+        return Ok(TransferReport::new(&progress, failures));
+    }
+
+    if !to.ends_with('/') {
+        return Err(
+            Error::new(ErrorKind::NotADirectory, "to path must be a directory")
+                .with_operation("Operator::copy_all")
+                .with_context("service", op.info().scheme())
+                .with_context("from", from)
+                .with_context("to", to),
+        );
+    }
+    if to.starts_with(&from) || from == "/" {
+        return Err(
+            Error::new(ErrorKind::IsSameFile, "to path is inside from path")
+                .with_operation("Operator::copy_all")
+                .with_context("service", op.info().scheme())
+                .with_context("from", from)
+                .with_context("to", to),
+        );
+    }
+
+    let mut lister = op
+        .lister_with(&from)
+        .recursive(true)
+        .metadata(options::MetadataFields {
+            // Server-side copy doesn't tell us the size, take it from list instead.
+            content_length: server_side,
+            ..Default::default()
+        })
+        .await?;
+
+    let mut tasks = ConcurrentTasks::new(
+        op.inner().info().executor(),
+        opts.concurrent.max(1),
+        0,
+        |input: CopyInput| {
+            Box::pin(async move {
+                let res = copy_one(&input).await;
+                let from = input.from.clone();
+                (input, Ok((from, res)))
+            })
+        },
+    );
+
+    while let Some(entry) = lister.try_next().await? {
+        if progress.is_cancelled() {
+            break;
+        }
+
+        let target = format!("{to}{}", &entry.path()[from.len()..]);
+        if entry.metadata().is_dir() {
+            if cap.create_dir && target != to {
+                let res = op.create_dir(&target).await;
+                if let Err(err) = res {
+                    progress.add_failed();
+                    failures.push((entry.path().to_string(), err));
+                }
+            }
+            continue;
+        }
+
+        if tasks.has_result() {
+            if let Some(res) = tasks.next().await {
+                let (path, res) = res?;
+                record(&progress, &mut failures, path, res);
+            }
+        }
+        let size = entry.metadata().content_length();
+        tasks
+            .execute(new_input(entry.path().to_string(), target, size))
+            .await?;
+    }
+
+    while !progress.is_cancelled() {
+        let Some(res) = tasks.next().await else {
+            break;
+        };
+        let (path, res) = res?;
+        record(&progress, &mut failures, path, res);
+    }
+    // Cancel all ongoing tasks.
+    tasks.clear();
+
+    Ok(TransferReport::new(&progress, failures))
+}
+
+async fn copy_one(input: &CopyInput) -> Result<()> {
+    let CopyInput {
+        op,
+        progress,
+        from,
+        to,
+        ..
+    } = input;
+
+    if progress.is_cancelled() {
+        return Err(cancelled_error());
+    }
+
+    if input.server_side {
+        op.copy(from, to).await?;
+        progress.add_bytes(input.size);
+        return Ok(());
+    }
+
+    let mut reader = op.reader_with(from);
+    let mut writer = op.writer_with(to);
+    if let Some(chunk) = input.chunk {
+        reader = reader.chunk(chunk);
+        writer = writer.chunk(chunk);
+    }
+    let mut stream = reader.await?.into_stream(..).await?;
+    let mut writer = writer.await?;
+
+    let res = async {
+        while let Some(bs) = stream.try_next().await? {
+            if progress.is_cancelled() {
+                return Err(cancelled_error());
+            }
+            let size = bs.len() as u64;
+            writer.write(bs).await?;
+            progress.add_bytes(size);
+        }
+        writer.close().await?;
+        Ok(())
+    }
+    .await;
+
+    if res.is_err() {
+        // Ignore the abort error, the original error is more important.
+        let _ = writer.abort().await;
+    }
+    res
+}
+
+async fn remove_all(
+    op: Operator,
+    path: String,
+    opts: options::RemoveAllOptions,
+    progress: TransferProgress,
+) -> Result<TransferReport> {
+    let mut failures = vec![];
+
+    match op.stat(&path).await {
+        // If object exists.
+        Ok(metadata) => {
+            // If the object is a file, we can delete it.
+            if metadata.mode() != EntryMode::DIR {
+                let res = op.delete(&path).await;
+                record(&progress, &mut failures, path.clone(), res);
+                // There may still be objects prefixed with the path in some backend, so we can't return here.
+            }
+        }
+
+        // If dir not found, it may be a prefix in object store like S3,
+        // and we still need to delete objects under the prefix.
+        Err(e) if e.kind() == ErrorKind::NotFound => {}
+
+        // Pass on any other error.
+        Err(e) => return Err(e),
+    };
+
+    let batch_size = op
+        .info()
+        .full_capability()
+        .delete_max_size
+        .unwrap_or(1)
+        .max(1);
+
+    let mut lister = op.lister_with(&path).recursive(true).await?;
+    let mut tasks = ConcurrentTasks::new(
+        op.inner().info().executor(),
+        opts.concurrent.max(1),
+        0,
+        |(op, paths): (Operator, Vec<String>)| {
+            Box::pin(async move {
+                let res = delete_batch(&op, &paths).await;
+                ((op, paths), Ok(res))
+            })
+        },
+    );
+
+    // Dirs will be removed after all files, since some services refuse to remove
+    // non-empty dirs.
+    let mut dirs = vec![];
+    let mut batch = Vec::with_capacity(batch_size);
+    while !progress.is_cancelled() {
+        let entry = lister.try_next().await?;
+        let done = entry.is_none();
+        match entry {
+            Some(entry) if entry.metadata().is_dir() => dirs.push(entry.path().to_string()),
+            Some(entry) => batch.push(entry.path().to_string()),
+            None => {}
+        }
+        if batch.len() < batch_size && !(done && !batch.is_empty()) {
+            if done {
+                break;
+            }
+            continue;
+        }
+
+        if tasks.has_result() {
+            if let Some(res) = tasks.next().await {
+                for (path, res) in res? {
+                    record(&progress, &mut failures, path, res);
+                }
+            }
+        }
+        let paths = std::mem::replace(&mut batch, Vec::with_capacity(batch_size));
+        tasks.execute((op.clone(), paths)).await?;
+
+        if done {
+            break;
+        }
+    }
+
+    while !progress.is_cancelled() {
+        let Some(res) = tasks.next().await else {
+            break;
+        };
+        for (path, res) in res? {
+            record(&progress, &mut failures, path, res);
+        }
+    }
+    tasks.clear();
+
+    // Remove nested dirs before their parents.
+    dirs.sort_by_key(|p| std::cmp::Reverse(p.matches('/').count()));
+    for dir in dirs {
+        if progress.is_cancelled() {
+            break;
+        }
+        let res = op.delete(&dir).await;
+        record(&progress, &mut failures, dir, res);
+    }
+
+    Ok(TransferReport::new(&progress, failures))
+}
+
+/// Delete given paths in a batch.
+///
+/// If the batch failed, paths will be deleted one by one to find out which paths failed.
+async fn delete_batch(op: &Operator, paths: &[String]) -> Vec<(String, Result<()>)> {
+    let res = async {
+        let mut deleter = op.deleter().await?;
+        deleter.delete_iter(paths.iter().cloned()).await?;
+        deleter.close().await
+    }
+    .await;
+
+    match res {
+        Ok(()) => paths.iter().map(|p| (p.clone(), Ok(()))).collect(),
+        Err(err) if paths.len() == 1 => vec![(paths[0].clone(), Err(err))],
+        Err(_) => {
+            let mut results = Vec::with_capacity(paths.len());
+            for path in paths {
+                results.push((path.clone(), op.delete(path).await));
+            }
+            results
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    async fn prepare(op: &Operator) -> Result<Vec<String>> {
+        let mut paths = vec![];
+        for dir in ["a", "b"] {
+            for file in 0..4 {
+                let path = format!("src/{dir}/{file}");
+                op.write(&path, "hello").await?;
+                paths.push(path);
+            }
+        }
+        Ok(paths)
+    }
+
+    #[tokio::test]
+    async fn test_copy_all() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        let paths = prepare(&op).await?;
+
+        let report = op
+            .copy_all_options(
+                "src/",
+                "dst/",
+                options::CopyAllOptions {
+                    concurrent: 3,
+                    ..Default::default()
+                },
+            )
+            .await?;
+        assert!(report.failures.is_empty());
+        assert!(!report.cancelled);
+        assert_eq!(report.objects, paths.len() as u64);
+        assert_eq!(report.bytes, 5 * paths.len() as u64);
+
+        for path in paths {
+            let target = path.replacen("src/", "dst/", 1);
+            assert_eq!(op.read(&target).await?.to_vec(), b"hello");
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_copy_all_into_itself() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        prepare(&op).await?;
+
+        let err = op.copy_all("src/", "src/a/").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::IsSameFile);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_copy_all_cancelled() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        prepare(&op).await?;
+
+        let transfer = op.copy_all("src/", "dst/");
+        transfer.progress().cancel();
+        let report = transfer.await?;
+        assert!(report.cancelled);
+        assert_eq!(report.objects, 0);
+        assert!(report.failures.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_remove_all_options() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        let paths = prepare(&op).await?;
+
+        let transfer = op.remove_all_options("src/", options::RemoveAllOptions { concurrent: 2 });
+        let progress = transfer.progress();
+        let report = transfer.await?;
+        assert!(report.failures.is_empty());
+        assert_eq!(progress.objects(), report.objects);
+
+        for path in paths {
+            assert!(!op.exists(&path).await?);
+        }
+        Ok(())
+    }
+}
@@ -18,6 +18,12 @@
 mod writer;
 pub use writer::Writer;
 
+mod upload;
+pub use upload::CopyToError;
+pub use upload::Upload;
+pub use upload::UploadPart;
+pub use upload::UploadState;
+
 mod buffer_sink;
 pub use buffer_sink::BufferSink;
 mod futures_async_writer;
@@ -0,0 +1,184 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::Error;
+
+/// UploadState is the exported state of an ongoing multipart upload.
+///
+/// It could be got by [`Writer::upload_state`](crate::Writer::upload_state), persisted by users
+/// (it implements `Serialize` and `Deserialize`), and used to resume the upload later by
+/// [`options::WriteOptions::resume`](crate::options::WriteOptions::resume), even in another process.
+///
+/// # Notes
+///
+/// Only parts that have been uploaded successfully and are contiguous from the beginning will
+/// be included in the state. Users must continue writing from [`UploadState::offset`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UploadState {
+    upload_id: String,
+    parts: Vec<UploadPart>,
+}
+
+impl UploadState {
+    /// Create a new upload state.
+    pub fn new(upload_id: String, parts: Vec<UploadPart>) -> Self {
+        Self { upload_id, parts }
+    }
+
+    /// The upload id of this upload.
+    ///
+    /// For services that don't have upload id like azblob, it will be empty.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+
+    /// The parts that have been uploaded, ordered by part number.
+    pub fn parts(&self) -> &[UploadPart] {
+        &self.parts
+    }
+
+    /// The offset that users should continue writing from.
+    pub fn offset(&self) -> u64 {
+        self.parts.iter().map(|p| p.size).sum()
+    }
+}
+
+/// CopyToError is the error returned by
+/// [`Operator::copy_to_resumable`](crate::Operator::copy_to_resumable).
+///
+/// If the multipart upload of the failed copy has been kept, its state could be got by
+/// [`CopyToError::upload_state`] and passed to
+/// [`options::CopyToOptions::resume`](crate::options::CopyToOptions::resume) to continue
+/// the copy later.
+#[derive(Debug)]
+pub struct CopyToError {
+    error: Error,
+    state: Option<Box<UploadState>>,
+}
+
+impl CopyToError {
+    /// Create a new copy to error with the state of the kept upload.
+    pub(crate) fn new(error: Error, state: Option<UploadState>) -> Self {
+        Self {
+            error,
+            state: state.map(Box::new),
+        }
+    }
+
+    /// The error that fails the copy.
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// The state of the kept upload, `None` if there is nothing to resume.
+    pub fn upload_state(&self) -> Option<&UploadState> {
+        self.state.as_deref()
+    }
+
+    /// Consume self to get the error and the state of the kept upload.
+    pub fn into_parts(self) -> (Error, Option<UploadState>) {
+        (self.error, self.state.map(|v| *v))
+    }
+}
+
+impl From<Error> for CopyToError {
+    fn from(error: Error) -> Self {
+        Self { error, state: None }
+    }
+}
+
+impl From<CopyToError> for Error {
+    fn from(err: CopyToError) -> Self {
+        err.error
+    }
+}
+
+impl Display for CopyToError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+impl std::error::Error for CopyToError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        std::error::Error::source(&self.error)
+    }
+}
+
+/// UploadPart is an uploaded part of [`UploadState`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UploadPart {
+    /// The number of the part, starting from 0.
+    pub part_number: usize,
+    /// The etag of the part.
+    ///
+    /// For services that don't return etag for parts like azblob, it's the block id.
+    pub etag: String,
+    /// The checksum of the part.
+    pub checksum: Option<String>,
+    /// The size of the part.
+    pub size: u64,
+}
+
+/// Upload is an incomplete multipart upload returned by
+/// [`Operator::list_uploads`](crate::Operator::list_uploads).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Upload {
+    path: String,
+    upload_id: String,
+    initiated: Option<DateTime<Utc>>,
+}
+
+impl Upload {
+    /// Create a new upload.
+    pub fn new(path: &str, upload_id: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            upload_id: upload_id.to_string(),
+            initiated: None,
+        }
+    }
+
+    /// Set the time when this upload was initiated.
+    pub fn with_initiated(mut self, v: DateTime<Utc>) -> Self {
+        self.initiated = Some(v);
+        self
+    }
+
+    /// The path of this upload.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The upload id of this upload.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+
+    /// The time when this upload was initiated.
+    pub fn initiated(&self) -> Option<DateTime<Utc>> {
+        self.initiated
+    }
+}
@@ -99,7 +99,7 @@ use crate::*;
 ///   creating writer with `append` enabled.
 pub struct Writer {
     /// Keep a reference to write context in writer.
-    _ctx: Arc<WriteContext>,
+    ctx: Arc<WriteContext>,
     inner: WriteGenerator<oio::Writer>,
 }
 
@@ -109,7 +109,7 @@ impl Writer {
         let ctx = Arc::new(ctx);
         let inner = WriteGenerator::create(ctx.clone()).await?;
 
-        Ok(Self { _ctx: ctx, inner })
+        Ok(Self { ctx, inner })
     }
 
     /// Write [`Buffer`] into writer.
@@ -159,6 +159,33 @@ impl Writer {
         self.write(bs).await
     }
 
+    /// Export the state of the ongoing multipart upload.
+    ///
+    /// The returned [`UploadState`] could be persisted and used to resume the upload later
+    /// via [`FutureWriter::resume`](crate::operator_futures::FutureWriter::resume).
+    ///
+    /// Returns `None` if no multipart upload has been started yet, for example, the data
+    /// written is still buffered or the service doesn't support resumable uploads.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use opendal::Operator;
+    /// use opendal::Result;
+    ///
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     let mut w = op.writer_with("hello.txt").chunk(8 * 1024 * 1024).await?;
+    ///     w.write(vec![0; 16 * 1024 * 1024]).await?;
+    ///
+    ///     // Persist the state somewhere and resume it later.
+    ///     let state = w.upload_state();
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn upload_state(&self) -> Option<UploadState> {
+        self.ctx.args().upload_tracker()?.state()
+    }
+
     /// Abort the writer and clean up all written data.
     ///
     /// ## Notes
@@ -14,31 +14,10 @@
 // KIND, either express or implied.  See the License for the
 // specific language governing permissions and limitations
 // under the License.
-
-// utils that used by tests
-#[macro_use]
-mod utils;
-
-pub use utils::*;
-
-mod async_copy;
-mod async_create_dir;
-mod async_delete;
-mod async_list;
-mod async_presign;
-mod async_read;
-mod async_rename;
-mod async_stat;
-mod async_write;
-
-// External dependencies
 use libtest_mimic::Arguments;
-use libtest_mimic::Trial;
+use opendal::raw::tests::behavior;
 use opendal::raw::tests::init_test_service;
 use opendal::raw::tests::TEST_RUNTIME;
-use opendal::*;
-
-pub static TEST_FIXTURE: Fixture = Fixture::new();
 
 fn main() -> anyhow::Result<()> {
     let args = Arguments::from_args();
@@ -49,17 +28,7 @@ fn main() -> anyhow::Result<()> {
         return Ok(());
     };
 
-    let mut tests = Vec::new();
-
-    async_copy::tests(&op, &mut tests);
-    async_create_dir::tests(&op, &mut tests);
-    async_delete::tests(&op, &mut tests);
-    async_list::tests(&op, &mut tests);
-    async_presign::tests(&op, &mut tests);
-    async_read::tests(&op, &mut tests);
-    async_rename::tests(&op, &mut tests);
-    async_stat::tests(&op, &mut tests);
-    async_write::tests(&op, &mut tests);
+    let tests = behavior::tests(&op);
 
     // Don't init logging while building operator which may break cargo
     // nextest output
@@ -72,7 +41,7 @@ fn main() -> anyhow::Result<()> {
     let conclusion = libtest_mimic::run(&args, tests);
 
     // Cleanup the fixtures.
-    TEST_RUNTIME.block_on(TEST_FIXTURE.cleanup(op));
+    TEST_RUNTIME.block_on(behavior::TEST_FIXTURE.cleanup(op));
 
     conclusion.exit()
 }